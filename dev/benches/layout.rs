@@ -197,6 +197,34 @@ fn bench_layout_a_sentence_ttf(c: &mut Criterion) {
     });
 }
 
+fn bench_glyph_id_sentence(c: &mut Criterion) {
+    c.bench_function("glyph_id_sentence", |b| {
+        let font = FontRef::try_from_slice(OPENS_SANS_ITALIC).unwrap();
+        let mut ids = Vec::with_capacity(SENTENCE.len());
+
+        b.iter(|| {
+            ids.clear();
+            ids.extend(SENTENCE.chars().map(|c| font.glyph_id(c)));
+        });
+
+        assert!(ids.iter().all(|id| id.0 != 0));
+    });
+}
+
+fn bench_glyph_ids_for_str_sentence(c: &mut Criterion) {
+    c.bench_function("glyph_ids_for_str_sentence", |b| {
+        let font = FontRef::try_from_slice(OPENS_SANS_ITALIC).unwrap();
+        let mut ids = Vec::with_capacity(SENTENCE.len());
+
+        b.iter(|| {
+            ids.clear();
+            font.glyph_ids_for_str(SENTENCE, &mut ids);
+        });
+
+        assert!(ids.iter().all(|id| id.0 != 0));
+    });
+}
+
 criterion_group!(
     name = layout_benches;
     config = Criterion::default().sample_size(400);
@@ -205,6 +233,8 @@ criterion_group!(
         bench_layout_a_sentence_arc_slice,
         bench_layout_a_sentence_otf,
         bench_layout_a_sentence_ttf,
+        bench_glyph_id_sentence,
+        bench_glyph_ids_for_str_sentence,
 );
 
 criterion_main!(layout_benches);
@@ -30,7 +30,7 @@ fn bench_layout_and_draw(c: &mut Criterion) {
             glyphs
                 .drain(..)
                 .filter_map(|g| font.outline_glyph(g))
-                .for_each(|outlined| outlined.draw(|_, _, c| coverage_sum += c));
+                .for_each(|outlined| outlined.draw(0, |_, _, c| coverage_sum += c));
         });
 
         // sanity check that work has been done
@@ -56,7 +56,7 @@ fn bench_layout_and_draw(c: &mut Criterion) {
             glyphs
                 .drain(..)
                 .filter_map(|g| font.outline_glyph(g))
-                .for_each(|outlined| outlined.draw(|_, _, c| coverage_sum += c));
+                .for_each(|outlined| outlined.draw(0, |_, _, c| coverage_sum += c));
         });
 
         // sanity check that work has been done
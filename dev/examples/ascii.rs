@@ -52,7 +52,7 @@ fn draw_ascii<F: Font>(font: F) {
     for g in glyphs {
         if let Some(og) = scaled_font.outline_glyph(g) {
             let bounds = og.px_bounds();
-            og.draw(|x, y, v| {
+            og.draw(0, |x, y, v| {
                 let x = x as f32 + bounds.min.x;
                 let y = y as f32 + bounds.min.y;
                 // There's still a possibility that the glyph clips the boundaries of the bitmap
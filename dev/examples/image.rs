@@ -50,7 +50,7 @@ fn draw_image<F: Font>(font: F) {
         if let Some(outlined) = scaled_font.outline_glyph(glyph) {
             let bounds = outlined.px_bounds();
             // Draw the glyph into the image per-pixel by using the draw closure
-            outlined.draw(|x, y, v| {
+            outlined.draw(0, |x, y, v| {
                 // Offset the position by the glyph bounding box
                 let px = image.get_pixel_mut(x + bounds.min.x as u32, y + bounds.min.y as u32);
                 // Turn the coverage into an alpha value (blended with any previous)
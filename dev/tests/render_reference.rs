@@ -126,7 +126,7 @@ fn outline_draw<F: Font>(font: F, c: char, scale: f32) -> image::GrayAlphaImage
 
     let mut glyph_image =
         DynamicImage::new_luma_a8(bounds.width() as _, bounds.height() as _).to_luma_alpha();
-    glyph.draw(|x, y, alpha| {
+    glyph.draw(0, |x, y, alpha| {
         glyph_image.put_pixel(x, y, LumaA([128, (alpha * 255.0).round() as u8]))
     });
     glyph_image
@@ -0,0 +1,448 @@
+//! Packing rasterized glyphs into GPU texture atlas pages, requires the `atlas` feature.
+//!
+//! A [`GlyphAtlas`](struct.GlyphAtlas.html) shelf packs the 8 bit coverage of
+//! [`OutlinedGlyph`](../struct.OutlinedGlyph.html)s into one or more square power-of-two
+//! pages, returning the texture coordinates of each glyph keyed by
+//! [`GlyphKey`](../struct.GlyphKey.html), i.e. glyph id, scale & subpixel position.
+//!
+//! When the maximum number of pages is full the least recently used page, not used in the
+//! current frame, is cleared & reused. Call [`next_frame`](struct.GlyphAtlas.html#method.next_frame)
+//! once per frame and upload each page's [`take_dirty`](struct.AtlasPage.html#method.take_dirty)
+//! region to the GPU after inserting that frame's glyphs.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::{
+//!     atlas::{AtlasConfig, GlyphAtlas},
+//!     point, Font, FontRef,
+//! };
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+//! let mut atlas = GlyphAtlas::new(AtlasConfig { page_size: 256, ..AtlasConfig::default() });
+//!
+//! let glyph = font.glyph_id('a').with_scale_and_position(24.0, point(10.0, 20.0));
+//! let outlined = font.outline_glyph(glyph.clone()).unwrap();
+//! let a = atlas.insert(&outlined)?;
+//!
+//! // draw a quad covering `px_bounds` textured with `uv` of page `page`
+//! assert_eq!(a.px_bounds(&glyph), outlined.px_bounds());
+//! assert!(a.uv.max.x <= 1.0 && a.uv.max.y <= 1.0);
+//!
+//! // coverage is drawn into the page at `a.rect`
+//! let page = &atlas.pages()[a.page];
+//! outlined.draw(0, |x, y, c| {
+//!     let (x, y) = (a.rect.min.0 as u32 + x, a.rect.min.1 as u32 + y);
+//!     let px = page.pixels()[(y * page.size() + x) as usize];
+//!     assert_eq!(px, (c * 255.0).round() as u8);
+//! });
+//!
+//! // glyphs sharing a key, e.g. at another whole pixel position, reuse the entry
+//! let moved = font.glyph_id('a').with_scale_and_position(24.0, point(50.0, 20.0));
+//! assert_eq!(atlas.get(&moved), Some(a));
+//! assert_eq!(atlas.len(), 1);
+//! # Ok(()) }
+//! ```
+use crate::{point, Glyph, GlyphKey, IRect, OutlinedGlyph, Rect, SubpixelGlyph};
+use core::fmt;
+use std::collections::HashMap;
+
+const STEPS: u8 = GlyphKey::DEFAULT_SUBPIXEL_STEPS;
+
+/// [`GlyphAtlas`](struct.GlyphAtlas.html) page size, page limit & glyph spacing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasConfig {
+    /// Width & height of each page in pixels, rounded up to a power of two. Default `1024`.
+    pub page_size: u32,
+    /// Maximum number of pages, at least `1`. Default `4`.
+    pub max_pages: usize,
+    /// Empty pixels between glyphs, avoiding bleeding when sampling with linear filtering.
+    /// Default `1`.
+    pub padding: u32,
+}
+
+impl Default for AtlasConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            page_size: 1024,
+            max_pages: 4,
+            padding: 1,
+        }
+    }
+}
+
+/// Location of a packed glyph, see [`GlyphAtlas`](struct.GlyphAtlas.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasGlyph {
+    /// Index of the page in [`GlyphAtlas::pages`](struct.GlyphAtlas.html#method.pages).
+    pub page: usize,
+    /// Normalized texture coordinates of the coverage in the page, `(0, 0)` top-left to
+    /// `(1, 1)` bottom-right. Empty for glyphs without coverage.
+    pub uv: Rect,
+    /// Pixels of the coverage in the page.
+    pub rect: IRect,
+    /// Pixel bounds relative to the whole pixel of the glyph position.
+    pub bounds: Rect,
+}
+
+impl AtlasGlyph {
+    /// Returns the pixel bounds to draw the coverage for a glyph sharing this entry's key.
+    #[inline]
+    pub fn px_bounds(&self, glyph: &Glyph) -> Rect {
+        let (x, y) = subpixel(glyph).pixel_offset;
+        self.bounds.translate(point(x as f32, y as f32))
+    }
+}
+
+/// A square 8 bit coverage texture of a [`GlyphAtlas`](struct.GlyphAtlas.html).
+#[derive(Clone, Debug)]
+pub struct AtlasPage {
+    size: u32,
+    pixels: Vec<u8>,
+    shelves: Vec<Shelf>,
+    dirty: Option<IRect>,
+    last_used: u64,
+}
+
+/// A row of glyphs packed left to right.
+#[derive(Clone, Copy, Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    x: u32,
+}
+
+impl AtlasPage {
+    #[inline]
+    fn new(size: u32) -> Self {
+        Self {
+            size,
+            pixels: vec![0; size as usize * size as usize],
+            shelves: Vec::new(),
+            dirty: None,
+            last_used: 0,
+        }
+    }
+
+    /// Width & height in pixels.
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Row-major coverage, `size * size` long.
+    #[inline]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the pixels changed since the last call, if any, e.g. to upload to the GPU.
+    #[inline]
+    pub fn take_dirty(&mut self) -> Option<IRect> {
+        let dirty = self.dirty.take();
+        #[cfg(feature = "counters")]
+        if let Some(dirty) = dirty {
+            crate::counters::add_bytes_uploaded(dirty.width() as usize * dirty.height() as usize);
+        }
+        dirty
+    }
+
+    /// Allocates a `width` x `height` region, returning its top-left.
+    ///
+    /// Uses the shortest shelf the region fits in, otherwise starts a new shelf.
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        let size = self.size;
+        let best = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= height && size - shelf.x >= width)
+            .min_by_key(|shelf| shelf.height);
+        if let Some(shelf) = best {
+            let x = shelf.x;
+            shelf.x += width;
+            return Some((x, shelf.y));
+        }
+
+        let y = self
+            .shelves
+            .last()
+            .map_or(0, |shelf| shelf.y + shelf.height);
+        if width > size || height > size - y {
+            return None;
+        }
+        self.shelves.push(Shelf {
+            y,
+            height,
+            x: width,
+        });
+        Some((0, y))
+    }
+
+    /// Removes all glyphs, marking the whole page dirty.
+    fn clear(&mut self) {
+        self.pixels.iter_mut().for_each(|px| *px = 0);
+        self.shelves.clear();
+        let size = self.size as i32;
+        self.dirty = Some(IRect {
+            min: (0, 0),
+            max: (size, size),
+        });
+    }
+
+    #[inline]
+    fn mark_dirty(&mut self, rect: IRect) {
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => dirty.union(&rect),
+            None => rect,
+        });
+    }
+}
+
+/// Why a glyph couldn't be packed into a [`GlyphAtlas`](struct.GlyphAtlas.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AtlasError {
+    /// The glyph, including padding, is larger than a page.
+    TooLarge {
+        /// Glyph pixel width.
+        width: u32,
+        /// Glyph pixel height.
+        height: u32,
+    },
+    /// All pages are full of glyphs used in the current frame.
+    Full,
+}
+
+impl fmt::Display for AtlasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { width, height } => {
+                write!(f, "{}x{} glyph is larger than an atlas page", width, height)
+            }
+            Self::Full => f.write_str("atlas is full of glyphs used in the current frame"),
+        }
+    }
+}
+
+impl std::error::Error for AtlasError {}
+
+/// Texture atlas of rasterized glyph coverage keyed by [`GlyphKey`](../struct.GlyphKey.html).
+///
+/// Glyph positions are quantized into subpixel steps, so glyphs differing only by whole
+/// pixel positions share an entry. Only the first layer of color glyphs is packed.
+///
+/// Entries aren't namespaced by font, so use an atlas per font.
+///
+/// See the [module docs](index.html) for an example.
+#[derive(Clone, Debug)]
+pub struct GlyphAtlas {
+    config: AtlasConfig,
+    pages: Vec<AtlasPage>,
+    glyphs: HashMap<GlyphKey, AtlasGlyph>,
+    frame: u64,
+}
+
+impl Default for GlyphAtlas {
+    #[inline]
+    fn default() -> Self {
+        Self::new(AtlasConfig::default())
+    }
+}
+
+impl GlyphAtlas {
+    /// Creates an empty atlas, pages are added as glyphs are inserted.
+    pub fn new(config: AtlasConfig) -> Self {
+        Self {
+            config: AtlasConfig {
+                page_size: config.page_size.max(1).next_power_of_two(),
+                max_pages: config.max_pages.max(1),
+                ..config
+            },
+            pages: Vec::new(),
+            glyphs: HashMap::new(),
+            frame: 0,
+        }
+    }
+
+    /// Returns the config, with the page size rounded up to a power of two.
+    #[inline]
+    pub fn config(&self) -> AtlasConfig {
+        self.config
+    }
+
+    /// Returns the pages.
+    #[inline]
+    pub fn pages(&self) -> &[AtlasPage] {
+        &self.pages
+    }
+
+    /// Returns the pages, e.g. to [`take_dirty`](struct.AtlasPage.html#method.take_dirty)
+    /// regions.
+    #[inline]
+    pub fn pages_mut(&mut self) -> &mut [AtlasPage] {
+        &mut self.pages
+    }
+
+    /// Starts a new frame. Glyphs used in the current frame are never evicted, glyphs
+    /// of earlier frames may be evicted to make room.
+    #[inline]
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Returns the packed glyph sharing the glyph's key, if any, marking it used in the
+    /// current frame.
+    pub fn get(&mut self, glyph: &Glyph) -> Option<AtlasGlyph> {
+        let packed = *self.glyphs.get(&GlyphKey::from(glyph))?;
+        if let Some(page) = self.pages.get_mut(packed.page) {
+            page.last_used = self.frame;
+        }
+        Some(packed)
+    }
+
+    /// Returns the packed glyph, rasterizing & packing it first if required. The glyph is
+    /// marked used in the current frame.
+    ///
+    /// When all pages are full the least recently used page is cleared to make room.
+    pub fn insert(&mut self, outlined: &OutlinedGlyph) -> Result<AtlasGlyph, AtlasError> {
+        let cached = self.get(outlined.glyph());
+        #[cfg(feature = "counters")]
+        crate::counters::add_cache_lookup(cached.is_some());
+        if let Some(packed) = cached {
+            return Ok(packed);
+        }
+
+        let px_bounds = outlined.px_bounds();
+        let (width, height) = (px_bounds.width() as u32, px_bounds.height() as u32);
+        let (x, y) = subpixel(outlined.glyph()).pixel_offset;
+        let bounds = px_bounds.translate(point(-x as f32, -y as f32));
+
+        let packed = if width == 0 || height == 0 {
+            AtlasGlyph {
+                page: 0,
+                uv: Rect::default(),
+                rect: IRect::default(),
+                bounds,
+            }
+        } else {
+            let (page, (x, y)) = self.allocate(width, height)?;
+            let padding = self.config.padding;
+            let (x, y) = (x + padding, y + padding);
+
+            let page_ref = &mut self.pages[page];
+            let (size, stride) = (page_ref.size as f32, page_ref.size as usize);
+            outlined.draw_into(
+                0,
+                &mut page_ref.pixels,
+                stride,
+                y as usize * stride + x as usize,
+            );
+
+            let rect = IRect {
+                min: (x as i32, y as i32),
+                max: ((x + width) as i32, (y + height) as i32),
+            };
+            page_ref.mark_dirty(rect);
+            page_ref.last_used = self.frame;
+            AtlasGlyph {
+                page,
+                uv: Rect {
+                    min: point(x as f32 / size, y as f32 / size),
+                    max: point((x + width) as f32 / size, (y + height) as f32 / size),
+                },
+                rect,
+                bounds,
+            }
+        };
+        self.glyphs.insert(GlyphKey::from(outlined.glyph()), packed);
+        Ok(packed)
+    }
+
+    /// Packs each of `glyphs`, returning the packed glyphs in the same order.
+    ///
+    /// Glyphs are packed tallest first, which packs shelves more tightly than
+    /// [`insert`](#method.insert) in arbitrary order.
+    pub fn insert_all<I>(&mut self, glyphs: I) -> Result<Vec<AtlasGlyph>, AtlasError>
+    where
+        I: IntoIterator<Item = OutlinedGlyph>,
+    {
+        let glyphs: Vec<_> = glyphs.into_iter().collect();
+        let mut order: Vec<_> = (0..glyphs.len()).collect();
+        order.sort_by(|a, b| {
+            let height = |i: &usize| glyphs[*i].px_bounds().height();
+            height(b)
+                .partial_cmp(&height(a))
+                .unwrap_or(core::cmp::Ordering::Equal)
+        });
+
+        let mut packed = vec![None; glyphs.len()];
+        for idx in order {
+            packed[idx] = Some(self.insert(&glyphs[idx])?);
+        }
+        Ok(packed.into_iter().flatten().collect())
+    }
+
+    /// Allocates a region for a glyph, including padding, returning its page & top-left.
+    ///
+    /// Adds a page or evicts the least recently used page if no page has room.
+    fn allocate(&mut self, width: u32, height: u32) -> Result<(usize, (u32, u32)), AtlasError> {
+        let padding = self.config.padding;
+        let (padded_w, padded_h) = (width + padding, height + padding);
+        if padded_w > self.config.page_size || padded_h > self.config.page_size {
+            return Err(AtlasError::TooLarge { width, height });
+        }
+
+        for (idx, page) in self.pages.iter_mut().enumerate() {
+            if let Some(origin) = page.allocate(padded_w, padded_h) {
+                return Ok((idx, origin));
+            }
+        }
+
+        let idx = if self.pages.len() < self.config.max_pages {
+            self.pages.push(AtlasPage::new(self.config.page_size));
+            self.pages.len() - 1
+        } else {
+            let frame = self.frame;
+            let (idx, page) = self
+                .pages
+                .iter_mut()
+                .enumerate()
+                .filter(|(_, page)| page.last_used < frame)
+                .min_by_key(|(_, page)| page.last_used)
+                .ok_or(AtlasError::Full)?;
+            page.clear();
+            self.glyphs.retain(|_, glyph| glyph.page != idx);
+            idx
+        };
+        let origin = self.pages[idx]
+            .allocate(padded_w, padded_h)
+            .expect("glyph fits an empty page");
+        Ok((idx, origin))
+    }
+
+    /// Returns the number of packed glyphs, including those without coverage.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Returns `true` if no glyphs are packed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    /// Removes all glyphs, clearing the pages.
+    pub fn clear(&mut self) {
+        self.glyphs.clear();
+        self.pages.iter_mut().for_each(AtlasPage::clear);
+    }
+}
+
+/// Splits the glyph position into whole pixels & the key subpixel position.
+#[inline]
+fn subpixel(glyph: &Glyph) -> SubpixelGlyph {
+    glyph
+        .id
+        .with_scale_and_subpixel_position(glyph.scale, glyph.position, STEPS, STEPS)
+}
@@ -0,0 +1,618 @@
+//! BDF & PCF bitmap font support, requires the `bdf` feature.
+use crate::{
+    point, CodepointIdIter, ColorOutline, Font, FontError, GlyphId, Outline, OutlineCurve, Rect,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::{convert::TryFrom, fmt};
+
+/// [BDF](https://en.wikipedia.org/wiki/Glyph_Bitmap_Distribution_Format) or
+/// [PCF](https://en.wikipedia.org/wiki/Portable_Compiled_Format) bitmap font implementing
+/// [`Font`](trait.Font.html) so bitmap & outline fonts can be used interchangeably.
+///
+/// Font units are pixels of the bitmap font. Each glyph's outline traces its set bitmap pixels,
+/// so drawing at the font's native pixel height, [`height_unscaled`](trait.Font.html#method.height_unscaled),
+/// at whole pixel positions reproduces the bitmap exactly. Other scales are drawn antialiased.
+///
+/// Chars are mapped using glyph `ENCODING` values, which are assumed to be unicode.
+/// Chars not in the font map to the `DEFAULT_CHAR` glyph if present, otherwise to an empty
+/// `GlyphId(0)`. Compressed PCF fonts, e.g. `.pcf.gz`, must be decompressed first.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{BdfFont, Font, GlyphId};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = BdfFont::try_from_slice(
+///     b"STARTFONT 2.1
+/// FONT -misc-tiny-medium-r-normal--4-40-75-75-c-40-iso10646-1
+/// SIZE 4 75 75
+/// FONTBOUNDINGBOX 3 4 0 -1
+/// STARTPROPERTIES 2
+/// FONT_ASCENT 3
+/// FONT_DESCENT 1
+/// ENDPROPERTIES
+/// CHARS 1
+/// STARTCHAR uni002B
+/// ENCODING 43
+/// SWIDTH 1000 0
+/// DWIDTH 4 0
+/// BBX 3 3 0 0
+/// BITMAP
+/// 40
+/// E0
+/// 40
+/// ENDCHAR
+/// ENDFONT
+/// ",
+/// )?;
+///
+/// assert_eq!(font.height_unscaled(), 4.0);
+/// let plus = font.glyph_id('+');
+/// assert_eq!(plus, GlyphId(1));
+/// assert_eq!(font.h_advance_unscaled(plus), 4.0);
+/// assert_eq!(font.glyph_id('x'), GlyphId(0));
+///
+/// let outline = font.outline(plus).unwrap();
+/// assert_eq!(outline.bounds.min, ab_glyph::point(0.0, 3.0));
+/// assert_eq!(outline.bounds.max, ab_glyph::point(3.0, 0.0));
+/// # Ok(()) }
+/// ```
+#[derive(Clone)]
+pub struct BdfFont {
+    ascent: f32,
+    descent: f32,
+    /// Index 0 is an empty glyph used for unmapped chars.
+    glyphs: Vec<BdfGlyph>,
+    /// Sorted by char.
+    char_map: Vec<(char, GlyphId)>,
+    default_glyph: GlyphId,
+}
+
+#[derive(Clone, Default)]
+struct BdfGlyph {
+    advance: f32,
+    width: u32,
+    height: u32,
+    x_offset: i32,
+    y_offset: i32,
+    /// Rows, top to bottom, each padded to whole bytes.
+    bitmap: Vec<u8>,
+}
+
+impl BdfGlyph {
+    #[inline]
+    fn row_bytes(&self) -> usize {
+        (self.width as usize + 7) >> 3
+    }
+
+    #[inline]
+    fn is_set(&self, x: u32, y: u32) -> bool {
+        let idx = y as usize * self.row_bytes() + x as usize / 8;
+        self.bitmap.get(idx).copied().unwrap_or(0) & (0x80 >> (x % 8)) != 0
+    }
+}
+
+impl fmt::Debug for BdfFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BdfFont")
+    }
+}
+
+impl BdfFont {
+    /// Parses BDF or PCF font file data.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{BdfFont, FontError};
+    ///
+    /// // PCF data is detected by its magic number
+    /// assert_eq!(BdfFont::try_from_slice(b"\x01fcp").unwrap_err(), FontError::Malformed);
+    ///
+    /// // bitmaps larger than the data are malformed
+    /// let huge = b"STARTFONT 2.1
+    /// FONTBOUNDINGBOX 1 1 0 0
+    /// STARTCHAR a
+    /// BBX 2000000000 2000000000 0 0
+    /// BITMAP
+    /// 00
+    /// ENDCHAR
+    /// ENDFONT
+    /// ";
+    /// assert_eq!(BdfFont::try_from_slice(huge).unwrap_err(), FontError::Malformed);
+    /// ```
+    pub fn try_from_slice(data: &[u8]) -> Result<Self, FontError> {
+        if data.starts_with(PCF_MAGIC) {
+            return Self::try_from_pcf(data).ok_or(FontError::Malformed);
+        }
+        let mut lines = data
+            .split(|b| *b == b'\n')
+            .map(|line| core::str::from_utf8(line).unwrap_or("").trim());
+
+        if !matches!(lines.next(), Some(line) if line.starts_with("STARTFONT")) {
+            return Err(FontError::UnknownMagic);
+        }
+
+        let mut bounding_box = None;
+        let mut ascent = None;
+        let mut descent = None;
+        let mut default_char = None;
+        let mut default_glyph = GlyphId(0);
+        let mut default_advance = 0.0;
+        let mut glyphs = vec![BdfGlyph::default()];
+        let mut char_map = Vec::new();
+
+        while let Some(line) = lines.next() {
+            let mut words = line.split_ascii_whitespace();
+            match words.next() {
+                Some("FONTBOUNDINGBOX") => {
+                    bounding_box = Some([
+                        next_number(&mut words)?,
+                        next_number(&mut words)?,
+                        next_number(&mut words)?,
+                        next_number(&mut words)?,
+                    ]);
+                }
+                Some("FONT_ASCENT") => ascent = Some(next_number(&mut words)?),
+                Some("FONT_DESCENT") => descent = Some(next_number(&mut words)?),
+                Some("DEFAULT_CHAR") => default_char = Some(next_number(&mut words)?),
+                Some("DWIDTH") => default_advance = next_number(&mut words)? as f32,
+                Some("STARTCHAR") => {
+                    if glyphs.len() > usize::from(u16::MAX) {
+                        return Err(FontError::Malformed);
+                    }
+                    let id = GlyphId(glyphs.len() as u16);
+                    let (encoding, glyph) = parse_char(&mut lines, default_advance, data.len())?;
+                    if let Some(encoding) = encoding {
+                        if let Some(c) = core::char::from_u32(encoding as u32) {
+                            char_map.push((c, id));
+                        }
+                        if Some(encoding) == default_char {
+                            default_glyph = id;
+                        }
+                    }
+                    glyphs.push(glyph);
+                }
+                Some("ENDFONT") => break,
+                _ => {}
+            }
+        }
+
+        let [_, bb_height, _, bb_y_offset] = bounding_box.ok_or(FontError::Malformed)?;
+        let ascent = ascent.unwrap_or(bb_height + bb_y_offset) as f32;
+        let descent = descent.map(|d| -d).unwrap_or(bb_y_offset) as f32;
+        char_map.sort_by_key(|(c, _)| *c);
+        char_map.dedup_by_key(|(c, _)| *c);
+
+        Ok(Self {
+            ascent,
+            descent,
+            glyphs,
+            char_map,
+            default_glyph,
+        })
+    }
+
+    /// Parses PCF font file data, reading the metrics, bitmaps, encodings & accelerator
+    /// tables.
+    fn try_from_pcf(data: &[u8]) -> Option<Self> {
+        let accelerators =
+            pcf_table(data, PCF_BDF_ACCELERATORS).or_else(|| pcf_table(data, PCF_ACCELERATORS))?;
+        let metrics = pcf_metrics(pcf_table(data, PCF_METRICS)?)?;
+        if metrics.len() >= usize::from(u16::MAX) {
+            return None;
+        }
+
+        let bitmaps = pcf_table(data, PCF_BITMAPS)?;
+        let count = bitmaps.u32(4)? as usize;
+        if count != metrics.len() {
+            return None;
+        }
+        let bitmap_data = bitmaps.data.get(8 + count * 4 + 16..)?;
+        let format = bitmaps.format;
+        let pad = 1 << (format & 3);
+        let unit = 1 << ((format >> 4) & 3);
+        let msb_bits = format & PCF_BIT_MASK != 0;
+        let swap_units = unit > 1 && bitmaps.big_endian() != msb_bits;
+
+        let mut glyphs = Vec::with_capacity(count + 1);
+        glyphs.push(BdfGlyph::default());
+        // bound the total size so overlapping bitmaps can't multiply it
+        let mut total = 0_usize;
+        for (idx, &[left, right, advance, ascent, descent]) in metrics.iter().enumerate() {
+            let width = u32::try_from(right - left).ok()?;
+            let height = u32::try_from(ascent + descent).ok()?;
+            let mut glyph = BdfGlyph {
+                advance: advance as f32,
+                width,
+                height,
+                x_offset: left,
+                y_offset: -descent,
+                bitmap: Vec::new(),
+            };
+            let row_bytes = glyph.row_bytes();
+            let stride = row_bytes.div_ceil(pad) * pad;
+            let size = stride * height as usize;
+            total += size;
+            if total > bitmap_data.len() {
+                return None;
+            }
+            let offset = bitmaps.u32(8 + idx * 4)? as usize;
+            let mut rows = bitmap_data.get(offset..offset.checked_add(size)?)?.to_vec();
+            if swap_units {
+                rows.chunks_exact_mut(unit).for_each(|unit| unit.reverse());
+            }
+            if !msb_bits {
+                rows.iter_mut().for_each(|b| *b = b.reverse_bits());
+            }
+            glyph.bitmap = rows
+                .chunks_exact(stride.max(1))
+                .flat_map(|row| &row[..row_bytes])
+                .copied()
+                .collect();
+            glyphs.push(glyph);
+        }
+
+        // glyph indices of each 2 byte encoding, rows of the first byte
+        let encodings = pcf_table(data, PCF_BDF_ENCODINGS)?;
+        let (min_col, max_col) = (encodings.u16(4)?, encodings.u16(6)?);
+        let (min_row, max_row) = (encodings.u16(8)?, encodings.u16(10)?);
+        let default_char = u32::from(encodings.u16(12)?);
+        let mut char_map = Vec::new();
+        let mut default_glyph = GlyphId(0);
+        if min_col <= max_col && min_row <= max_row && max_col <= 0xFF && max_row <= 0xFF {
+            let cols = usize::from(max_col - min_col + 1);
+            for row in min_row..=max_row {
+                for col in min_col..=max_col {
+                    let idx = usize::from(row - min_row) * cols + usize::from(col - min_col);
+                    let glyph = encodings.u16(14 + idx * 2)?;
+                    if usize::from(glyph) >= count {
+                        continue;
+                    }
+                    let id = GlyphId(glyph + 1);
+                    let encoding = u32::from(row) << 8 | u32::from(col);
+                    if let Some(c) = core::char::from_u32(encoding) {
+                        char_map.push((c, id));
+                    }
+                    if encoding == default_char {
+                        default_glyph = id;
+                    }
+                }
+            }
+        }
+        char_map.sort_by_key(|(c, _)| *c);
+
+        Some(Self {
+            ascent: accelerators.u32(12)? as i32 as f32,
+            descent: -(accelerators.u32(16)? as i32 as f32),
+            glyphs,
+            char_map,
+            default_glyph,
+        })
+    }
+
+    /// Returns the approximate memory in bytes held by this font.
+    pub fn memory_usage(&self) -> usize {
+        let bitmaps: usize = self.glyphs.iter().map(|g| g.bitmap.capacity()).sum();
+        core::mem::size_of::<Self>()
+            + self.glyphs.capacity() * core::mem::size_of::<BdfGlyph>()
+            + bitmaps
+            + self.char_map.capacity() * core::mem::size_of::<(char, GlyphId)>()
+    }
+
+    #[inline]
+    fn glyph(&self, id: GlyphId) -> &BdfGlyph {
+        self.glyphs
+            .get(usize::from(id.0))
+            .unwrap_or(&self.glyphs[0])
+    }
+}
+
+/// Parses a glyph after `STARTCHAR` up to & including `ENDCHAR`.
+///
+/// `data_len` is the font data length, bounding the bitmap size.
+fn parse_char<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    default_advance: f32,
+    data_len: usize,
+) -> Result<(Option<i32>, BdfGlyph), FontError> {
+    let mut encoding = None;
+    let mut glyph = BdfGlyph {
+        advance: default_advance,
+        ..BdfGlyph::default()
+    };
+
+    while let Some(line) = lines.next() {
+        let mut words = line.split_ascii_whitespace();
+        match words.next() {
+            Some("ENCODING") => {
+                // -1 indicates the glyph is not encoded
+                encoding = Some(next_number(&mut words)?).filter(|e| *e >= 0);
+            }
+            Some("DWIDTH") => glyph.advance = next_number(&mut words)? as f32,
+            Some("BBX") => {
+                let width = next_number(&mut words)?;
+                let height = next_number(&mut words)?;
+                if width < 0 || height < 0 {
+                    return Err(FontError::Malformed);
+                }
+                glyph.width = width as u32;
+                glyph.height = height as u32;
+                glyph.x_offset = next_number(&mut words)?;
+                glyph.y_offset = next_number(&mut words)?;
+            }
+            Some("BITMAP") => {
+                let row_bytes = glyph.row_bytes();
+                // each byte is 2 hex digits of the data, so larger sizes are malformed
+                let size = row_bytes
+                    .checked_mul(glyph.height as usize)
+                    .filter(|size| *size <= data_len / 2)
+                    .ok_or(FontError::Malformed)?;
+                glyph.bitmap.reserve(size);
+                for _ in 0..glyph.height {
+                    let row = lines.next().ok_or(FontError::Malformed)?.as_bytes();
+                    for byte_idx in 0..row_bytes {
+                        let hex = row
+                            .get(byte_idx * 2..byte_idx * 2 + 2)
+                            .ok_or(FontError::Malformed)?;
+                        let hex = core::str::from_utf8(hex).map_err(|_| FontError::Malformed)?;
+                        let byte = u8::from_str_radix(hex, 16).map_err(|_| FontError::Malformed)?;
+                        glyph.bitmap.push(byte);
+                    }
+                }
+            }
+            Some("ENDCHAR") => return Ok((encoding, glyph)),
+            _ => {}
+        }
+    }
+    Err(FontError::Malformed)
+}
+
+/// PCF file magic, `"\1fcp"`.
+const PCF_MAGIC: &[u8] = b"\x01fcp";
+const PCF_ACCELERATORS: u32 = 1 << 1;
+const PCF_METRICS: u32 = 1 << 2;
+const PCF_BITMAPS: u32 = 1 << 3;
+const PCF_BDF_ENCODINGS: u32 = 1 << 5;
+const PCF_BDF_ACCELERATORS: u32 = 1 << 8;
+/// Table format flag, set if multi-byte values are big endian.
+const PCF_BYTE_MASK: u32 = 1 << 2;
+/// Table format flag, set if bitmap bits are most significant first.
+const PCF_BIT_MASK: u32 = 1 << 3;
+const PCF_COMPRESSED_METRICS: u32 = 0x100;
+
+/// A PCF table & its format.
+#[derive(Clone, Copy)]
+struct PcfTable<'a> {
+    data: &'a [u8],
+    format: u32,
+}
+
+impl PcfTable<'_> {
+    #[inline]
+    fn big_endian(self) -> bool {
+        self.format & PCF_BYTE_MASK != 0
+    }
+
+    #[inline]
+    fn u16(self, offset: usize) -> Option<u16> {
+        let bytes = [*self.data.get(offset)?, *self.data.get(offset + 1)?];
+        Some(match self.big_endian() {
+            true => u16::from_be_bytes(bytes),
+            false => u16::from_le_bytes(bytes),
+        })
+    }
+
+    #[inline]
+    fn u32(self, offset: usize) -> Option<u32> {
+        let bytes = self.data.get(offset..offset + 4)?;
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Some(match self.big_endian() {
+            true => u32::from_be_bytes(bytes),
+            false => u32::from_le_bytes(bytes),
+        })
+    }
+}
+
+/// Returns the PCF table of a `kind`, from the little endian table of contents.
+fn pcf_table(data: &[u8], kind: u32) -> Option<PcfTable<'_>> {
+    let toc = PcfTable { data, format: 0 };
+    let count = toc.u32(4)? as usize;
+    (0..count.min(data.len() / 16))
+        .map(|idx| 8 + idx * 16)
+        .find(|&entry| toc.u32(entry) == Some(kind))
+        .and_then(|entry| {
+            let size = toc.u32(entry + 8)? as usize;
+            let offset = toc.u32(entry + 12)? as usize;
+            let table = data.get(offset..offset.checked_add(size)?)?;
+            // tables repeat their format, always little endian
+            let format = PcfTable {
+                data: table,
+                format: 0,
+            }
+            .u32(0)?;
+            Some(PcfTable {
+                data: table,
+                format,
+            })
+        })
+}
+
+/// Returns each glyph's `[left, right, advance, ascent, descent]` metrics.
+fn pcf_metrics(metrics: PcfTable<'_>) -> Option<Vec<[i32; 5]>> {
+    if metrics.format & PCF_COMPRESSED_METRICS != 0 {
+        let count = usize::from(metrics.u16(4)?);
+        let bytes = metrics.data.get(6..6 + count * 5)?;
+        Some(
+            bytes
+                .chunks_exact(5)
+                .map(|m| {
+                    let mut values = [0; 5];
+                    for (value, &byte) in values.iter_mut().zip(m) {
+                        *value = i32::from(byte) - 0x80;
+                    }
+                    values
+                })
+                .collect(),
+        )
+    } else {
+        let count = metrics.u32(4)? as usize;
+        if count > metrics.data.len() / 12 {
+            return None;
+        }
+        (0..count)
+            .map(|idx| {
+                let mut values = [0; 5];
+                for (n, value) in values.iter_mut().enumerate() {
+                    *value = i32::from(metrics.u16(8 + idx * 12 + n * 2)? as i16);
+                }
+                Some(values)
+            })
+            .collect()
+    }
+}
+
+/// Parses the next whitespace separated integer.
+#[inline]
+fn next_number<'a>(words: &mut impl Iterator<Item = &'a str>) -> Result<i32, FontError> {
+    words
+        .next()
+        .and_then(|w| w.parse().ok())
+        .ok_or(FontError::Malformed)
+}
+
+impl Font for BdfFont {
+    /// Returns the font's pixel height.
+    #[inline]
+    fn units_per_em(&self) -> Option<f32> {
+        Some(self.height_unscaled())
+    }
+
+    #[inline]
+    fn ascent_unscaled(&self) -> f32 {
+        self.ascent
+    }
+
+    #[inline]
+    fn descent_unscaled(&self) -> f32 {
+        self.descent
+    }
+
+    #[inline]
+    fn line_gap_unscaled(&self) -> f32 {
+        0.0
+    }
+
+    #[inline]
+    fn glyph_id(&self, c: char) -> GlyphId {
+        match self.char_map.binary_search_by_key(&c, |(c, _)| *c) {
+            Ok(idx) => self.char_map[idx].1,
+            Err(_) => self.default_glyph,
+        }
+    }
+
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        let idx = self.char_map.binary_search_by_key(&c, |(c, _)| *c).ok()?;
+        Some(self.char_map[idx].1)
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        CodepointIdIter::new(self.char_map.iter().map(|&(c, id)| (id, c)))
+    }
+
+    #[inline]
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.glyph(id).advance
+    }
+
+    #[inline]
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.glyph(id).x_offset as f32
+    }
+
+    #[inline]
+    fn v_advance_unscaled(&self, _id: GlyphId) -> f32 {
+        self.height_unscaled()
+    }
+
+    #[inline]
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        let glyph = self.glyph(id);
+        self.ascent - (glyph.y_offset + glyph.height as i32) as f32
+    }
+
+    #[inline]
+    fn kern_unscaled(&self, _first: GlyphId, _second: GlyphId) -> f32 {
+        0.0
+    }
+
+    #[inline]
+    fn has_color(&self, _id: GlyphId) -> bool {
+        false
+    }
+
+    #[inline]
+    fn color_outlines(&self, _id: GlyphId) -> Option<ColorOutline> {
+        None
+    }
+
+    #[inline]
+    fn relative_scale(&self, _id: GlyphId) -> f32 {
+        1.0
+    }
+
+    /// Traces each horizontal run of set pixels as a rectangle.
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        let glyph = self.glyph(id);
+        let mut curves = Vec::new();
+        for y in 0..glyph.height {
+            let top = (glyph.y_offset + (glyph.height - y) as i32) as f32;
+            let bottom = top - 1.0;
+            let mut x = 0;
+            while x < glyph.width {
+                if !glyph.is_set(x, y) {
+                    x += 1;
+                    continue;
+                }
+                let run_start = x;
+                while x < glyph.width && glyph.is_set(x, y) {
+                    x += 1;
+                }
+                let left = (glyph.x_offset + run_start as i32) as f32;
+                let right = (glyph.x_offset + x as i32) as f32;
+                curves.push(OutlineCurve::Line(point(left, top), point(right, top)));
+                curves.push(OutlineCurve::Line(point(right, top), point(right, bottom)));
+                curves.push(OutlineCurve::Line(
+                    point(right, bottom),
+                    point(left, bottom),
+                ));
+                curves.push(OutlineCurve::Line(point(left, bottom), point(left, top)));
+            }
+        }
+
+        if curves.is_empty() {
+            return None;
+        }
+
+        Some(Outline {
+            bounds: Rect {
+                min: point(
+                    glyph.x_offset as f32,
+                    (glyph.y_offset + glyph.height as i32) as f32,
+                ),
+                max: point(
+                    (glyph.x_offset + glyph.width as i32) as f32,
+                    glyph.y_offset as f32,
+                ),
+            },
+            curves,
+        })
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+}
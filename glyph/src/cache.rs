@@ -0,0 +1,531 @@
+use crate::{
+    point, Font, Glyph, GlyphKey, GlyphRasterizer, GlyphRun, OutlineRasterizer, OutlinedGlyph,
+    PxScale, Rect, SubpixelGlyph,
+};
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+#[cfg(feature = "counters")]
+use std::collections::hash_map::Entry;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+const STEPS: u8 = GlyphKey::DEFAULT_SUBPIXEL_STEPS;
+
+/// Cache of rasterized glyph coverage keyed by [`Font::fingerprint`](trait.Font.html#method.fingerprint)
+/// & [`GlyphKey`](struct.GlyphKey.html), requires the `std` feature.
+///
+/// Glyph positions are quantized into subpixel steps, so glyphs differing only by whole
+/// pixel positions share an entry. Glyphs without an outline are also cached.
+///
+/// Entries are namespaced by font, so a single cache can hold glyphs of multiple fonts,
+/// e.g. UI, monospace & emoji fonts, at the same time.
+///
+/// Glyphs are rasterized by the built-in [`OutlineRasterizer`](struct.OutlineRasterizer.html)
+/// unless another [`GlyphRasterizer`](trait.GlyphRasterizer.html) is set with
+/// [`with_rasterizer`](#method.with_rasterizer).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, Font, FontRef, GlyphCache};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let mut cache = GlyphCache::default();
+///
+/// // rasterize ascii up front
+/// cache.warm(&font, 24.0, (' '..='~').chain("\u{2026}".chars()));
+/// assert_eq!(cache.len(), 96);
+///
+/// let glyph = font.glyph_id('q').with_scale_and_position(24.0, point(100.0, 20.0));
+/// let q = cache.get(&font, &glyph).unwrap();
+/// let px_bounds = q.px_bounds(&glyph);
+/// assert_eq!(px_bounds, font.outline_glyph(glyph.clone()).unwrap().px_bounds());
+///
+/// // glyphs of other fonts are cached separately
+/// let mono = FontRef::try_from_slice(include_bytes!("../../dev/fonts/DejaVuSansMono.ttf"))?;
+/// assert!(cache.get(&mono, &glyph).is_none());
+/// cache.get_or_rasterize(&mono, &mono.glyph_id('q').with_scale(24.0));
+/// assert_eq!(cache.len(), 97);
+///
+/// cache.remove_font(&mono);
+/// assert_eq!(cache.len(), 96);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GlyphCache<R = OutlineRasterizer> {
+    glyphs: HashMap<(u64, GlyphKey), Option<CachedGlyph>>,
+    padding: u32,
+    rasterizer: R,
+}
+
+impl Default for GlyphCache {
+    #[inline]
+    fn default() -> Self {
+        Self::with_padding(0)
+    }
+}
+
+/// Rasterized glyph coverage, see [`GlyphCache`](struct.GlyphCache.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedGlyph {
+    /// Pixel bounds, including any padding, relative to the whole pixel of the glyph
+    /// position.
+    pub bounds: Rect,
+    /// 8 bit coverage & color of each layer. Coverage rows are top to bottom with
+    /// `bounds.width()` pixels. Non-color glyphs have a single layer.
+    pub layers: Vec<(Vec<u8>, u32)>,
+}
+
+impl CachedGlyph {
+    /// Rasterizes a glyph at a quantized subpixel position.
+    #[inline]
+    fn rasterize<F, R>(rasterizer: &R, font: &F, glyph: &Glyph, padding: u32) -> Option<Self>
+    where
+        F: Font,
+        R: GlyphRasterizer,
+    {
+        rasterizer.rasterize(font, &subpixel(glyph).glyph, padding)
+    }
+
+    /// Draws the 8 bit coverage of each layer of an outlined glyph.
+    ///
+    /// `bounds` are the outlined glyph's pixel bounds, so should be relative to the whole
+    /// pixel of the glyph position when used by a [`GlyphRasterizer`](trait.GlyphRasterizer.html).
+    pub fn from_outlined(outlined: &OutlinedGlyph) -> Self {
+        let bounds = outlined.px_bounds();
+        let width = bounds.width() as usize;
+
+        let layers = (0..outlined.get_colored_layers().unwrap_or(1))
+            .map(|layer| {
+                let mut coverage = vec![0; width * bounds.height() as usize];
+                outlined.draw(layer, |x, y, c| {
+                    coverage[y as usize * width + x as usize] = (c * 255.0).round() as u8;
+                });
+                (coverage, outlined.get_color(layer))
+            })
+            .collect();
+        Self { bounds, layers }
+    }
+
+    /// Returns the pixel bounds to draw the coverage for a glyph sharing this entry's key.
+    #[inline]
+    pub fn px_bounds(&self, glyph: &Glyph) -> Rect {
+        let sub = subpixel(glyph);
+        let (x, y) = sub.pixel_offset;
+        self.bounds.translate(point(x as f32, y as f32))
+    }
+}
+
+impl GlyphCache {
+    /// Creates an empty cache reserving `padding` whole pixels of empty space around the
+    /// coverage of each glyph, e.g. for outline or drop shadow effects.
+    ///
+    /// See [`OutlinedGlyph::with_padding`](struct.OutlinedGlyph.html#method.with_padding).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontRef, GlyphCache};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let mut cache = GlyphCache::with_padding(2);
+    ///
+    /// let glyph = font.glyph_id('q').with_scale_and_position(24.0, point(100.0, 20.0));
+    /// let q = cache.get_or_rasterize(&font, &glyph).unwrap();
+    /// let outlined = font.outline_glyph(glyph.clone()).unwrap();
+    /// assert_eq!(q.px_bounds(&glyph), outlined.with_padding(2).px_bounds());
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn with_padding(padding: u32) -> Self {
+        Self::with_rasterizer(OutlineRasterizer, padding)
+    }
+}
+
+impl<R: GlyphRasterizer> GlyphCache<R> {
+    /// Creates an empty cache rasterizing glyphs with `rasterizer`, e.g. a GPU or platform
+    /// rasterizer, reserving `padding` whole pixels around each glyph.
+    ///
+    /// See [`GlyphRasterizer`](trait.GlyphRasterizer.html).
+    #[inline]
+    pub fn with_rasterizer(rasterizer: R, padding: u32) -> Self {
+        Self {
+            glyphs: HashMap::new(),
+            padding,
+            rasterizer,
+        }
+    }
+
+    /// Returns the rasterizer drawing glyphs into the cache.
+    #[inline]
+    pub fn rasterizer(&self) -> &R {
+        &self.rasterizer
+    }
+
+    /// Whole pixels of empty space reserved around each glyph, see
+    /// [`with_padding`](#method.with_padding).
+    #[inline]
+    pub fn padding(&self) -> u32 {
+        self.padding
+    }
+
+    /// Returns the cached coverage of a font's glyph, if cached & it has an outline.
+    #[inline]
+    pub fn get<F: Font>(&self, font: &F, glyph: &Glyph) -> Option<&CachedGlyph> {
+        self.glyphs
+            .get(&(font.fingerprint(), GlyphKey::from(glyph)))?
+            .as_ref()
+    }
+
+    /// Returns the cached coverage of a font's glyph, rasterizing & caching if required.
+    ///
+    /// Returns `None` if the glyph has no outline.
+    pub fn get_or_rasterize<F: Font>(&mut self, font: &F, glyph: &Glyph) -> Option<&CachedGlyph> {
+        self.get_or_rasterize_keyed(font.fingerprint(), font, glyph)
+    }
+
+    #[inline]
+    fn get_or_rasterize_keyed<F: Font>(
+        &mut self,
+        fingerprint: u64,
+        font: &F,
+        glyph: &Glyph,
+    ) -> Option<&CachedGlyph> {
+        let (padding, rasterizer) = (self.padding, &self.rasterizer);
+        let entry = self.glyphs.entry((fingerprint, GlyphKey::from(glyph)));
+        #[cfg(feature = "counters")]
+        crate::counters::add_cache_lookup(matches!(entry, Entry::Occupied(_)));
+        entry
+            .or_insert_with(|| CachedGlyph::rasterize(rasterizer, font, glyph, padding))
+            .as_ref()
+    }
+
+    /// Rasterizes & caches the glyphs of `chars` at `scale`, positioned at whole pixels,
+    /// so they're ready for first use.
+    ///
+    /// E.g. warming ascii avoids rasterizing on the first frame of text rendering.
+    pub fn warm<F, S, I>(&mut self, font: &F, scale: S, chars: I)
+    where
+        F: Font,
+        S: Into<PxScale>,
+        I: IntoIterator<Item = char>,
+    {
+        let scale = scale.into();
+        let fingerprint = font.fingerprint();
+        for c in chars {
+            self.get_or_rasterize_keyed(fingerprint, font, &font.glyph_id(c).with_scale(scale));
+        }
+    }
+
+    /// Rasterizes & caches the glyphs of a [`GlyphRun`](struct.GlyphRun.html) at their
+    /// positions, so they're ready to draw.
+    pub fn warm_run<F: Font>(&mut self, run: &GlyphRun<'_, F>) {
+        let fingerprint = run.font.fingerprint();
+        for glyph in &run.glyphs {
+            self.get_or_rasterize_keyed(fingerprint, run.font, glyph);
+        }
+    }
+
+    /// Rasterizes & caches the glyphs of `chars` at `scale`, positioned at whole pixels,
+    /// in parallel using rayon. Requires the `rayon` feature.
+    ///
+    /// See [`warm`](#method.warm).
+    #[cfg(feature = "rayon")]
+    pub fn par_warm<F, S, I>(&mut self, font: &F, scale: S, chars: I)
+    where
+        F: Font + Sync,
+        S: Into<PxScale>,
+        I: IntoIterator<Item = char>,
+        R: Sync,
+    {
+        use rayon2::prelude::*;
+
+        let scale = scale.into();
+        let fingerprint = font.fingerprint();
+        let (padding, rasterizer) = (self.padding, &self.rasterizer);
+        let mut glyphs: Vec<_> = chars
+            .into_iter()
+            .map(|c| font.glyph_id(c).with_scale(scale))
+            .filter(|glyph| {
+                !self
+                    .glyphs
+                    .contains_key(&(fingerprint, GlyphKey::from(glyph)))
+            })
+            .collect();
+        glyphs.sort_by_key(|glyph| glyph.id);
+        glyphs.dedup_by_key(|glyph| glyph.id);
+
+        let rasterized: Vec<_> = glyphs
+            .par_iter()
+            .map(|glyph| {
+                let key = (fingerprint, GlyphKey::from(glyph));
+                (
+                    key,
+                    CachedGlyph::rasterize(rasterizer, font, glyph, padding),
+                )
+            })
+            .collect();
+        self.glyphs.extend(rasterized);
+    }
+
+    /// Rasterizes the uncached glyphs off the calling thread, e.g. so a GUI on an async
+    /// runtime doesn't block its UI thread.
+    ///
+    /// `spawn` is called once with a job rasterizing the glyphs, which it should run, e.g.
+    /// with `std::thread::spawn`, `rayon::spawn` or an async runtime's blocking task pool.
+    /// The returned [`RasterizeTask`](struct.RasterizeTask.html) completes with the
+    /// [`RasterizedGlyphs`](struct.RasterizedGlyphs.html) once the job has run, to be
+    /// added to this cache with [`insert_rasterized`](#method.insert_rasterized). If the
+    /// job is dropped without running, or panics, the task completes with
+    /// [`RasterizeFailed`](struct.RasterizeFailed.html).
+    ///
+    /// Glyphs sharing a cache entry are rasterized once. Glyphs of tasks not yet inserted
+    /// aren't cached, so requesting them again rasterizes them again.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontArc, FontRef, GlyphCache};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontArc::new(FontRef::try_from_slice(include_bytes!(
+    ///     "../../dev/fonts/Exo2-Light.otf"
+    /// ))?);
+    /// let mut cache = GlyphCache::default();
+    ///
+    /// let glyphs: Vec<_> = "hello"
+    ///     .chars()
+    ///     .enumerate()
+    ///     .map(|(n, c)| font.glyph_id(c).with_scale_and_position(24.0, point(n as f32 * 12.0, 24.0)))
+    ///     .collect();
+    /// let mut task = cache.rasterize_async(&font, &glyphs, |job| {
+    ///     std::thread::spawn(job);
+    /// });
+    ///
+    /// // e.g. check each frame, or `.await` the task
+    /// let rasterized = loop {
+    ///     match task.try_take() {
+    ///         Some(rasterized) => break rasterized.expect("job ran"),
+    ///         None => std::thread::yield_now(),
+    ///     }
+    /// };
+    /// assert_eq!(rasterized.len(), 4);
+    ///
+    /// cache.insert_rasterized(rasterized);
+    /// assert!(cache.get(&font, &glyphs[4]).is_some());
+    ///
+    /// // a job that never runs fails the task
+    /// let glyph = font.glyph_id('w').with_scale(24.0);
+    /// let mut task = cache.rasterize_async(&font, &[glyph], drop);
+    /// assert_eq!(task.try_take(), Some(Err(ab_glyph::RasterizeFailed)));
+    /// # Ok(()) }
+    /// ```
+    pub fn rasterize_async<F, S>(&self, font: &F, glyphs: &[Glyph], spawn: S) -> RasterizeTask
+    where
+        F: Font + Clone + Send + 'static,
+        S: FnOnce(Box<dyn FnOnce() + Send>),
+        R: Clone + Send + 'static,
+    {
+        let fingerprint = font.fingerprint();
+        let mut keys = HashSet::new();
+        let glyphs: Vec<_> = glyphs
+            .iter()
+            .filter(|glyph| {
+                let key = GlyphKey::from(*glyph);
+                !self.glyphs.contains_key(&(fingerprint, key)) && keys.insert(key)
+            })
+            .cloned()
+            .collect();
+
+        let task = RasterizeTask::default();
+        let completion = Completion {
+            state: Arc::clone(&task.state),
+            rasterized: None,
+        };
+        let (font, rasterizer, padding) = (font.clone(), self.rasterizer.clone(), self.padding);
+        spawn(Box::new(move || {
+            let glyphs = glyphs
+                .iter()
+                .map(|glyph| {
+                    let cached = CachedGlyph::rasterize(&rasterizer, &font, glyph, padding);
+                    (GlyphKey::from(glyph), cached)
+                })
+                .collect();
+            completion.finish(RasterizedGlyphs {
+                fingerprint,
+                glyphs,
+            });
+        }));
+        task
+    }
+
+    /// Adds glyphs rasterized by [`rasterize_async`](#method.rasterize_async), keeping
+    /// any already cached.
+    ///
+    /// Glyphs should be inserted into the cache that rasterized them, as they're drawn
+    /// with its rasterizer & padding.
+    pub fn insert_rasterized(&mut self, rasterized: RasterizedGlyphs) {
+        let fingerprint = rasterized.fingerprint;
+        for (key, glyph) in rasterized.glyphs {
+            self.glyphs.entry((fingerprint, key)).or_insert(glyph);
+        }
+    }
+
+    /// Returns the number of cached glyphs, including those without an outline.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Returns `true` if no glyphs are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    /// Removes all cached glyphs.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.glyphs.clear();
+    }
+
+    /// Removes all cached glyphs of a font, keeping those of other fonts.
+    pub fn remove_font<F: Font>(&mut self, font: &F) {
+        let fingerprint = font.fingerprint();
+        self.glyphs.retain(|(f, _), _| *f != fingerprint);
+    }
+
+    /// Returns the approximate heap & inline bytes used by the cache & its coverage.
+    pub fn memory_usage(&self) -> usize {
+        let entry = core::mem::size_of::<((u64, GlyphKey), Option<CachedGlyph>)>();
+        let coverage: usize = self
+            .glyphs
+            .values()
+            .flatten()
+            .map(|glyph| {
+                glyph.layers.capacity() * core::mem::size_of::<(Vec<u8>, u32)>()
+                    + glyph.layers.iter().map(|l| l.0.capacity()).sum::<usize>()
+            })
+            .sum();
+        core::mem::size_of::<Self>() + self.glyphs.capacity() * entry + coverage
+    }
+}
+
+/// Glyphs being rasterized off-thread, see
+/// [`GlyphCache::rasterize_async`](struct.GlyphCache.html#method.rasterize_async).
+///
+/// A future completing with the [`RasterizedGlyphs`](struct.RasterizedGlyphs.html), usable
+/// with any async runtime. Event loops without one can instead check
+/// [`try_take`](#method.try_take) each frame.
+#[derive(Debug, Default)]
+pub struct RasterizeTask {
+    state: Arc<Mutex<TaskState>>,
+}
+
+#[derive(Debug, Default)]
+struct TaskState {
+    result: Option<Result<RasterizedGlyphs, RasterizeFailed>>,
+    waker: Option<Waker>,
+}
+
+/// Completes a task when dropped with the job, failing it if the job didn't finish.
+struct Completion {
+    state: Arc<Mutex<TaskState>>,
+    rasterized: Option<RasterizedGlyphs>,
+}
+
+impl Completion {
+    #[inline]
+    fn finish(mut self, rasterized: RasterizedGlyphs) {
+        self.rasterized = Some(rasterized);
+    }
+}
+
+impl Drop for Completion {
+    fn drop(&mut self) {
+        let mut state = lock(&self.state);
+        state.result = Some(self.rasterized.take().ok_or(RasterizeFailed));
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl RasterizeTask {
+    /// Returns the rasterized glyphs if the job has finished, without blocking.
+    ///
+    /// Returns `None` while rasterizing & after the result has been taken.
+    #[inline]
+    pub fn try_take(&mut self) -> Option<Result<RasterizedGlyphs, RasterizeFailed>> {
+        lock(&self.state).result.take()
+    }
+}
+
+impl Future for RasterizeTask {
+    type Output = Result<RasterizedGlyphs, RasterizeFailed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.state);
+        match state.result.take() {
+            Some(rasterized) => Poll::Ready(rasterized),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A [`RasterizeTask`](struct.RasterizeTask.html) job was dropped without running, or
+/// panicked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RasterizeFailed;
+
+impl fmt::Display for RasterizeFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RasterizeFailed")
+    }
+}
+
+impl std::error::Error for RasterizeFailed {}
+
+/// Glyph coverage rasterized by a [`RasterizeTask`](struct.RasterizeTask.html), see
+/// [`GlyphCache::insert_rasterized`](struct.GlyphCache.html#method.insert_rasterized).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RasterizedGlyphs {
+    fingerprint: u64,
+    glyphs: Vec<(GlyphKey, Option<CachedGlyph>)>,
+}
+
+impl RasterizedGlyphs {
+    /// Returns the number of rasterized glyphs, including those without an outline.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Returns `true` if no glyphs were rasterized.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+}
+
+/// Locks task state, ignoring poisoning as the state is always left consistent.
+#[inline]
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Splits the glyph position into whole pixels & the cache key subpixel position.
+#[inline]
+fn subpixel(glyph: &Glyph) -> SubpixelGlyph {
+    glyph
+        .id
+        .with_scale_and_subpixel_position(glyph.scale, glyph.position, STEPS, STEPS)
+}
@@ -0,0 +1,517 @@
+#[cfg(feature = "bumpalo")]
+use crate::ArenaOutline;
+use crate::{
+    CodepointIdIter, ColorGlyphPaint, ColorOutline, ColorPalette, CoverageSet, DecorationMetrics,
+    DeviceVMetrics, Font, FontStyle, FontSubset, GlyphComplexity, GlyphId, GlyphImage, NameId,
+    OpticalBounds, Outline, OutlineCurve, OutlineSink, Rect, Script,
+};
+use core::{fmt, ops::RangeInclusive};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+/// `Font` wrapper memoizing glyph outlines, requires the `std` feature.
+///
+/// Results of [`outline`](trait.Font.html#tymethod.outline) &
+/// [`color_outlines`](trait.Font.html#tymethod.color_outlines) are cached per glyph, up to
+/// `capacity` glyphs each, evicting the least recently used. Implements `Font` itself so it
+/// can replace the wrapped font in existing code, e.g. repeatedly outlining the same glyphs
+/// of text-heavy UIs.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{CachedFont, Font, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let cached = CachedFont::new(font.clone(), 2);
+///
+/// let a = cached.outline(cached.glyph_id('a')).unwrap();
+/// assert_eq!(a.bounds, font.outline(font.glyph_id('a')).unwrap().bounds);
+/// cached.outline(cached.glyph_id('b'));
+/// assert_eq!(cached.len(), 2);
+///
+/// // "a" was used more recently than "b", so "b" is evicted
+/// cached.outline(cached.glyph_id('a'));
+/// cached.outline(cached.glyph_id('c'));
+/// assert_eq!(cached.len(), 2);
+/// assert!(cached.is_cached(cached.glyph_id('a')));
+/// assert!(!cached.is_cached(cached.glyph_id('b')));
+/// # Ok(()) }
+/// ```
+pub struct CachedFont<F> {
+    font: F,
+    capacity: usize,
+    outlines: Mutex<Lru<Option<Outline>>>,
+    color_outlines: Mutex<Lru<Option<ColorOutline>>>,
+}
+
+impl<F> fmt::Debug for CachedFont<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CachedFont")
+    }
+}
+
+impl<F: Font> CachedFont<F> {
+    /// Wraps a font caching the outlines of up to `capacity` glyphs.
+    #[inline]
+    pub fn new(font: F, capacity: usize) -> Self {
+        Self {
+            font,
+            capacity,
+            outlines: Mutex::new(Lru::default()),
+            color_outlines: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Returns the wrapped font.
+    #[inline]
+    pub fn font(&self) -> &F {
+        &self.font
+    }
+
+    /// Unwraps the font, dropping the cache.
+    #[inline]
+    pub fn into_inner(self) -> F {
+        self.font
+    }
+
+    /// Returns the maximum number of glyphs cached.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of glyphs with a cached outline, including those without one.
+    #[inline]
+    pub fn len(&self) -> usize {
+        lock(&self.outlines).entries.len()
+    }
+
+    /// Returns `true` if no outlines are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the glyph's outline is cached.
+    #[inline]
+    pub fn is_cached(&self, id: GlyphId) -> bool {
+        lock(&self.outlines).entries.contains_key(&id)
+    }
+
+    /// Removes all cached outlines & color outlines.
+    pub fn clear(&self) {
+        lock(&self.outlines).entries.clear();
+        lock(&self.color_outlines).entries.clear();
+    }
+}
+
+/// Locks a cache, ignoring poisoning as entries are always complete.
+#[inline]
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Capacity bound glyph cache evicting the least recently used entry.
+struct Lru<T> {
+    /// Entries with the tick of their last use.
+    entries: HashMap<GlyphId, (T, u64)>,
+    tick: u64,
+}
+
+impl<T> Default for Lru<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+}
+
+/// Returns the cached value of a glyph, otherwise computes & caches it.
+///
+/// The cache isn't locked while computing, so other threads can use it meanwhile.
+fn get_or_insert_with<T: Clone>(
+    cache: &Mutex<Lru<T>>,
+    capacity: usize,
+    id: GlyphId,
+    compute: impl FnOnce() -> T,
+) -> T {
+    {
+        let mut lru = lock(cache);
+        lru.tick += 1;
+        let tick = lru.tick;
+        let cached = lru.entries.get_mut(&id);
+        #[cfg(feature = "counters")]
+        crate::counters::add_cache_lookup(cached.is_some());
+        if let Some((value, used)) = cached {
+            *used = tick;
+            return value.clone();
+        }
+    }
+
+    let value = compute();
+    if capacity == 0 {
+        return value;
+    }
+    let mut lru = lock(cache);
+    if lru.entries.len() >= capacity && !lru.entries.contains_key(&id) {
+        let oldest = lru
+            .entries
+            .iter()
+            .min_by_key(|(_, (_, used))| *used)
+            .map(|(id, _)| *id);
+        if let Some(oldest) = oldest {
+            lru.entries.remove(&oldest);
+        }
+    }
+    let tick = lru.tick;
+    lru.entries.insert(id, (value.clone(), tick));
+    value
+}
+
+impl<F: Font> Font for CachedFont<F> {
+    #[inline]
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    #[inline]
+    fn ascent_unscaled(&self) -> f32 {
+        self.font.ascent_unscaled()
+    }
+
+    #[inline]
+    fn descent_unscaled(&self) -> f32 {
+        self.font.descent_unscaled()
+    }
+
+    #[inline]
+    fn line_gap_unscaled(&self) -> f32 {
+        self.font.line_gap_unscaled()
+    }
+
+    #[inline]
+    fn vertical_ascent_unscaled(&self) -> f32 {
+        self.font.vertical_ascent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_descent_unscaled(&self) -> f32 {
+        self.font.vertical_descent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_line_gap_unscaled(&self) -> f32 {
+        self.font.vertical_line_gap_unscaled()
+    }
+
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        self.font.italic_angle()
+    }
+
+    #[inline]
+    fn underline_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.font.underline_metrics_unscaled()
+    }
+
+    #[inline]
+    fn strikeout_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.font.strikeout_metrics_unscaled()
+    }
+
+    #[inline]
+    fn x_height_unscaled(&self) -> f32 {
+        self.font.x_height_unscaled()
+    }
+
+    #[inline]
+    fn cap_height_unscaled(&self) -> f32 {
+        self.font.cap_height_unscaled()
+    }
+
+    #[inline]
+    fn is_monospaced(&self) -> bool {
+        self.font.is_monospaced()
+    }
+
+    #[inline]
+    fn weight(&self) -> u16 {
+        self.font.weight()
+    }
+
+    #[inline]
+    fn stretch(&self) -> u16 {
+        self.font.stretch()
+    }
+
+    #[inline]
+    fn style(&self) -> FontStyle {
+        self.font.style()
+    }
+
+    #[inline]
+    fn is_bold(&self) -> bool {
+        self.font.is_bold()
+    }
+
+    #[inline]
+    fn font_name(&self, id: NameId) -> Option<String> {
+        self.font.font_name(id)
+    }
+
+    #[inline]
+    fn font_name_for_language(&self, id: NameId, language_id: u16) -> Option<String> {
+        self.font.font_name_for_language(id, language_id)
+    }
+
+    #[inline]
+    fn glyph_id(&self, c: char) -> GlyphId {
+        self.font.glyph_id(c)
+    }
+
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        self.font.glyph_id_checked(c)
+    }
+
+    #[inline]
+    fn glyph_id_variation(&self, c: char, selector: char) -> Option<GlyphId> {
+        self.font.glyph_id_variation(c, selector)
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    #[inline]
+    fn coverage_set(&self) -> CoverageSet {
+        self.font.coverage_set()
+    }
+
+    #[inline]
+    fn glyph_ids_for_str(&self, text: &str, ids: &mut Vec<GlyphId>) {
+        self.font.glyph_ids_for_str(text, ids)
+    }
+
+    #[inline]
+    fn substitute(&self, script: Script, features: &[[u8; 4]], glyphs: &mut Vec<GlyphId>) {
+        self.font.substitute(script, features, glyphs)
+    }
+
+    #[inline]
+    fn features(&self, script: Script) -> Vec<[u8; 4]> {
+        self.font.features(script)
+    }
+
+    #[inline]
+    fn ligatures(&self) -> Vec<(GlyphId, Vec<GlyphId>)> {
+        self.font.ligatures()
+    }
+
+    #[inline]
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn try_h_advance_unscaled(&self, id: GlyphId) -> Option<f32> {
+        self.font.try_h_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn h_advances_unscaled(&self, ids: &[GlyphId], out: &mut [f32]) {
+        self.font.h_advances_unscaled(ids, out)
+    }
+
+    #[inline]
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn try_h_side_bearing_unscaled(&self, id: GlyphId) -> Option<f32> {
+        self.font.try_h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_origin_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_origin_unscaled(id)
+    }
+
+    #[inline]
+    fn lsb_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.lsb_unscaled(id)
+    }
+
+    #[inline]
+    fn rsb_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.rsb_unscaled(id)
+    }
+
+    #[inline]
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn v_kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.v_kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn kern_pairs(&self) -> Vec<(GlyphId, GlyphId, f32)> {
+        self.font.kern_pairs()
+    }
+
+    #[inline]
+    fn optical_bounds_unscaled(&self, id: GlyphId) -> Option<OpticalBounds> {
+        self.font.optical_bounds_unscaled(id)
+    }
+
+    #[inline]
+    fn glyph_complexity(&self, id: GlyphId) -> Option<GlyphComplexity> {
+        self.font.glyph_complexity(id)
+    }
+
+    #[inline]
+    fn many_to_one_ranges(&self) -> Vec<(RangeInclusive<char>, GlyphId)> {
+        self.font.many_to_one_ranges()
+    }
+
+    #[inline]
+    fn device_h_advance(&self, id: GlyphId, ppem: u16) -> Option<f32> {
+        self.font.device_h_advance(id, ppem)
+    }
+
+    #[inline]
+    fn device_v_metrics(&self, ppem: u16) -> Option<DeviceVMetrics> {
+        self.font.device_v_metrics(ppem)
+    }
+
+    #[inline]
+    fn relative_scale(&self, glyph: GlyphId) -> f32 {
+        self.font.relative_scale(glyph)
+    }
+
+    #[inline]
+    fn has_color(&self, glyph: GlyphId) -> bool {
+        self.font.has_color(glyph)
+    }
+
+    #[inline]
+    fn color_outlines(&self, glyph: GlyphId) -> Option<ColorOutline> {
+        get_or_insert_with(&self.color_outlines, self.capacity, glyph, || {
+            self.font.color_outlines(glyph)
+        })
+    }
+
+    #[inline]
+    fn color_outlines_with_palette(&self, glyph: GlyphId, palette: usize) -> Option<ColorOutline> {
+        match palette {
+            0 => self.color_outlines(glyph),
+            _ => self.font.color_outlines_with_palette(glyph, palette),
+        }
+    }
+
+    #[inline]
+    fn color_palettes(&self) -> usize {
+        self.font.color_palettes()
+    }
+
+    #[inline]
+    fn color_palette(&self, palette: usize) -> Option<ColorPalette> {
+        self.font.color_palette(palette)
+    }
+
+    #[inline]
+    fn color_paint(&self, glyph: GlyphId) -> Option<ColorGlyphPaint> {
+        self.font.color_paint(glyph)
+    }
+
+    #[inline]
+    fn glyph_raster_image(&self, glyph: GlyphId, pixels_per_em: u16) -> Option<GlyphImage<'_>> {
+        self.font.glyph_raster_image(glyph, pixels_per_em)
+    }
+
+    #[inline]
+    fn outline(&self, glyph: GlyphId) -> Option<Outline> {
+        get_or_insert_with(&self.outlines, self.capacity, glyph, || {
+            self.font.outline(glyph)
+        })
+    }
+
+    #[inline]
+    fn outline_hinted(&self, glyph: GlyphId, ppem: u16) -> Option<Outline> {
+        self.font.outline_hinted(glyph, ppem)
+    }
+
+    #[inline]
+    fn glyph_bounds_unscaled(&self, glyph: GlyphId) -> Option<Rect> {
+        self.font.glyph_bounds_unscaled(glyph)
+    }
+
+    #[inline]
+    fn outline_into(&self, glyph: GlyphId, curves: &mut Vec<OutlineCurve>) -> Option<Rect> {
+        curves.clear();
+        let outline = self.outline(glyph)?;
+        *curves = outline.curves;
+        Some(outline.bounds)
+    }
+
+    #[inline]
+    fn outline_with(&self, glyph: GlyphId, sink: &mut dyn OutlineSink) -> Option<Rect> {
+        let outline = self.outline(glyph)?;
+        outline.for_each_segment(sink);
+        Some(outline.bounds)
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[inline]
+    fn outline_in<'bump>(
+        &self,
+        glyph: GlyphId,
+        arena: &'bump bumpalo2::Bump,
+    ) -> Option<ArenaOutline<'bump>> {
+        let outline = self.outline(glyph)?;
+        Some(ArenaOutline {
+            bounds: outline.bounds,
+            curves: arena.alloc_slice_clone(&outline.curves),
+        })
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    #[inline]
+    fn glyph_name(&self, id: GlyphId) -> Option<&str> {
+        self.font.glyph_name(id)
+    }
+
+    #[inline]
+    fn subset(&self, glyphs: &[GlyphId]) -> Option<FontSubset> {
+        self.font.subset(glyphs)
+    }
+
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        self.font.fingerprint()
+    }
+}
@@ -0,0 +1,42 @@
+use crate::GlyphId;
+use alloc::boxed::Box;
+use core::fmt;
+
+/// Iterator over the `(GlyphId, char)` pairs a font maps, see
+/// [`Font::codepoint_ids`](trait.Font.html#method.codepoint_ids).
+pub struct CodepointIdIter<'a> {
+    inner: Box<dyn Iterator<Item = (GlyphId, char)> + 'a>,
+}
+
+impl<'a> CodepointIdIter<'a> {
+    /// Wraps an iterator of mapped glyphs & chars, e.g. for custom `Font` implementations.
+    #[inline]
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = (GlyphId, char)> + 'a,
+    {
+        Self {
+            inner: Box::new(iter),
+        }
+    }
+}
+
+impl Iterator for CodepointIdIter<'_> {
+    type Item = (GlyphId, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl fmt::Debug for CodepointIdIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CodepointIdIter")
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{point, Outline, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A solid colored layer of a color glyph.
+///
+/// See [`Font::color_outlines`](trait.Font.html#tymethod.color_outlines).
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct ColorLayer {
+    /// Unscaled layer outline.
+    pub outline: Outline,
+    /// Non-premultiplied `[r, g, b, a]` color of the layer.
+    ///
+    /// [`FOREGROUND`](#associatedconstant.FOREGROUND) layers are opaque black, renderers
+    /// may instead use the text color. Colors missing from the palette are transparent.
+    pub rgba: [u8; 4],
+    /// Palette entry index of the color, or [`FOREGROUND`](#associatedconstant.FOREGROUND).
+    pub palette_index: u16,
+    /// Position of the layer in the font's layer list, bottom layer first. Layers without
+    /// an outline are skipped, so this may differ from the index in
+    /// [`ColorOutline::layers`](struct.ColorOutline.html#structfield.layers).
+    pub order: u16,
+}
+
+impl ColorLayer {
+    /// Palette index of layers drawn in the text foreground color.
+    pub const FOREGROUND: u16 = 0xFFFF;
+
+    /// Returns `true` if the layer is drawn in the text foreground color.
+    #[inline]
+    pub fn is_foreground(&self) -> bool {
+        self.palette_index == Self::FOREGROUND
+    }
+
+    /// Returns the color packed as `0xRRGGBBAA`.
+    #[inline]
+    pub fn rgba_u32(&self) -> u32 {
+        u32::from_be_bytes(self.rgba)
+    }
+}
+
+/// The solid colored layers of a color glyph.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{ColorLayer, ColorOutline, Outline, OutlineCurve, point, Rect};
+///
+/// let layer = |x: f32, rgba, palette_index, order| ColorLayer {
+///     outline: Outline {
+///         bounds: Rect {
+///             min: point(x, 10.0),
+///             max: point(x + 5.0, 0.0),
+///         },
+///         curves: vec![OutlineCurve::Line(point(x, 0.0), point(x + 5.0, 10.0))],
+///     },
+///     rgba,
+///     palette_index,
+///     order,
+/// };
+/// let outline = ColorOutline {
+///     layers: vec![
+///         layer(0.0, [255, 0, 0, 255], 3, 0),
+///         layer(10.0, [0, 0, 0, 255], ColorLayer::FOREGROUND, 1),
+///     ],
+/// };
+///
+/// assert!(outline.layers[1].is_foreground());
+/// assert_eq!(outline.layers[0].rgba_u32(), 0xFF0000FF);
+/// assert_eq!(outline.bounds().unwrap().max.x, 15.0);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct ColorOutline {
+    /// Layers, bottom layer first.
+    pub layers: Vec<ColorLayer>,
+}
+
+impl ColorOutline {
+    /// Returns the unscaled union of the layer bounds, or `None` if there are no layers.
+    pub fn bounds(&self) -> Option<Rect> {
+        // note: outline bounds `min.y` is the top, `max.y` the bottom
+        let mut bounds = self.layers.iter().map(|layer| layer.outline.bounds);
+        let init = bounds.next()?;
+        Some(bounds.fold(init, |a, b| Rect {
+            min: point(a.min.x.min(b.min.x), a.min.y.max(b.min.y)),
+            max: point(a.max.x.max(b.max.x), a.max.y.min(b.max.y)),
+        }))
+    }
+
+    /// Returns `true` if there are no layers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{OutlineCurve, Point};
+
+/// Size of a glyph's outline, an estimate of the cost to outline & draw it.
+///
+/// See [`Font::glyph_complexity`](trait.Font.html#method.glyph_complexity).
+///
+/// # Example
+/// Choosing a raster path for small glyphs with complex outlines.
+/// ```
+/// use ab_glyph::{Font, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?;
+///
+/// let o = font.glyph_complexity(font.glyph_id('o')).unwrap();
+/// assert_eq!(o.contours, 2);
+///
+/// let at = font.glyph_complexity(font.glyph_id('@')).unwrap();
+/// assert!(at.points > o.points);
+///
+/// // no outline
+/// assert_eq!(font.glyph_complexity(font.glyph_id(' ')), None);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GlyphComplexity {
+    /// Number of closed contours.
+    pub contours: usize,
+    /// Number of on & off curve points.
+    pub points: usize,
+}
+
+impl GlyphComplexity {
+    /// Counts the contours & points of outline curves.
+    pub(crate) fn of_curves<'a>(curves: impl IntoIterator<Item = &'a OutlineCurve>) -> Self {
+        let mut counter = CurveCounter::default();
+        counter.extend(curves.into_iter().cloned());
+        counter.complexity
+    }
+}
+
+/// Counts curves as they're outlined, without storing them.
+#[derive(Debug, Default)]
+pub(crate) struct CurveCounter {
+    pub(crate) complexity: GlyphComplexity,
+    last_end: Option<Point>,
+}
+
+impl Extend<OutlineCurve> for CurveCounter {
+    fn extend<I: IntoIterator<Item = OutlineCurve>>(&mut self, curves: I) {
+        for curve in curves {
+            let (start, end, points) = match curve {
+                OutlineCurve::Line(p0, p1) => (p0, p1, 1),
+                OutlineCurve::Quad(p0, _, p2) => (p0, p2, 2),
+                OutlineCurve::Cubic(p0, _, _, p3) => (p0, p3, 3),
+            };
+            // a curve not continuing from the last starts a new contour, whose start
+            // point is counted as the end of its closing curve
+            if self.last_end != Some(start) {
+                self.complexity.contours += 1;
+            }
+            self.complexity.points += points;
+            self.last_end = Some(end);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Runtime text rendering cost counters, requires the `counters` feature.
+//!
+//! Counters are global, shared by all fonts & threads, & are never reset automatically.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::{counters, point, Font, FontRef};
+//!
+//! # fn main() -> Result<(), ab_glyph::InvalidFont> {
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+//!
+//! let frame_start = counters::get();
+//!
+//! let glyph = font.glyph_id('q').with_scale_and_position(24.0, point(100.0, 0.0));
+//! if let Some(q) = font.outline_glyph(glyph) {
+//!     q.draw(0, |_, _, _| {});
+//! }
+//!
+//! // counters for the frame
+//! let frame = counters::get().since(frame_start);
+//! assert_eq!(frame.glyphs_outlined, 1);
+//! assert!(frame.pixels_rasterized > 0);
+//! assert_eq!(frame.cache_hit_rate(), None);
+//! # Ok(()) }
+//! ```
+//!
+//! Glyph caches count hits & misses, & atlas pages count the bytes of their dirty regions.
+//! ```
+//! # #[cfg(feature = "atlas")] {
+//! use ab_glyph::{atlas::GlyphAtlas, counters, point, Font, FontRef};
+//!
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+//! let mut atlas = GlyphAtlas::default();
+//! let start = counters::get();
+//!
+//! let glyph = font.glyph_id('a').with_scale_and_position(24.0, point(10.0, 20.0));
+//! let outlined = font.outline_glyph(glyph).unwrap();
+//! for _ in 0..4 {
+//!     atlas.insert(&outlined).unwrap();
+//! }
+//! let dirty = atlas.pages_mut()[0].take_dirty().unwrap();
+//!
+//! let frame = counters::get().since(start);
+//! assert_eq!((frame.cache_hits, frame.cache_misses), (3, 1));
+//! assert_eq!(frame.cache_hit_rate(), Some(0.75));
+//! assert_eq!(frame.bytes_uploaded, (dirty.width() * dirty.height()) as usize);
+//! # }
+//! ```
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static GLYPHS_OUTLINED: AtomicUsize = AtomicUsize::new(0);
+static PIXELS_RASTERIZED: AtomicUsize = AtomicUsize::new(0);
+static CACHE_HITS: AtomicUsize = AtomicUsize::new(0);
+static CACHE_MISSES: AtomicUsize = AtomicUsize::new(0);
+static BYTES_UPLOADED: AtomicUsize = AtomicUsize::new(0);
+
+/// Snapshot of counter values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Counters {
+    /// Number of [`Font::outline_glyph`](../trait.Font.html#method.outline_glyph) calls.
+    pub glyphs_outlined: usize,
+    /// Number of pixels rasterized by [`OutlinedGlyph::draw`](../struct.OutlinedGlyph.html#method.draw).
+    pub pixels_rasterized: usize,
+    /// Number of glyphs found cached by `GlyphCache::get_or_rasterize`, `GlyphAtlas::insert`,
+    /// `CachedFont` outlines & `GlyphMeshCache::get_or_tessellate`.
+    pub cache_hits: usize,
+    /// Number of glyphs computed & cached by the same glyph caches.
+    pub cache_misses: usize,
+    /// Number of bytes in atlas page regions returned by `AtlasPage::take_dirty`, i.e.
+    /// uploaded from the draw cache to the GPU.
+    pub bytes_uploaded: usize,
+}
+
+impl Counters {
+    /// Returns the counts since an `earlier` snapshot.
+    #[inline]
+    pub fn since(self, earlier: Counters) -> Counters {
+        Counters {
+            glyphs_outlined: self.glyphs_outlined.wrapping_sub(earlier.glyphs_outlined),
+            pixels_rasterized: self
+                .pixels_rasterized
+                .wrapping_sub(earlier.pixels_rasterized),
+            cache_hits: self.cache_hits.wrapping_sub(earlier.cache_hits),
+            cache_misses: self.cache_misses.wrapping_sub(earlier.cache_misses),
+            bytes_uploaded: self.bytes_uploaded.wrapping_sub(earlier.bytes_uploaded),
+        }
+    }
+
+    /// Returns the fraction of glyph cache lookups that were hits, `None` without lookups.
+    #[inline]
+    pub fn cache_hit_rate(&self) -> Option<f32> {
+        let lookups = self.cache_hits + self.cache_misses;
+        if lookups == 0 {
+            return None;
+        }
+        Some(self.cache_hits as f32 / lookups as f32)
+    }
+}
+
+/// Returns the current counter values.
+#[inline]
+pub fn get() -> Counters {
+    Counters {
+        glyphs_outlined: GLYPHS_OUTLINED.load(Ordering::Relaxed),
+        pixels_rasterized: PIXELS_RASTERIZED.load(Ordering::Relaxed),
+        cache_hits: CACHE_HITS.load(Ordering::Relaxed),
+        cache_misses: CACHE_MISSES.load(Ordering::Relaxed),
+        bytes_uploaded: BYTES_UPLOADED.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets all counters to zero.
+#[inline]
+pub fn reset() {
+    GLYPHS_OUTLINED.store(0, Ordering::Relaxed);
+    PIXELS_RASTERIZED.store(0, Ordering::Relaxed);
+    CACHE_HITS.store(0, Ordering::Relaxed);
+    CACHE_MISSES.store(0, Ordering::Relaxed);
+    BYTES_UPLOADED.store(0, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn add_glyph_outlined() {
+    GLYPHS_OUTLINED.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn add_pixels_rasterized(pixels: usize) {
+    PIXELS_RASTERIZED.fetch_add(pixels, Ordering::Relaxed);
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn add_cache_lookup(hit: bool) {
+    let counter = if hit { &CACHE_HITS } else { &CACHE_MISSES };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(feature = "atlas")]
+#[inline]
+pub(crate) fn add_bytes_uploaded(bytes: usize) {
+    BYTES_UPLOADED.fetch_add(bytes, Ordering::Relaxed);
+}
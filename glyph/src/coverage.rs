@@ -0,0 +1,153 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{iter::FromIterator, ops::RangeInclusive};
+
+/// Compact set of chars stored as sorted, non-adjacent ranges, e.g. the chars a font
+/// maps, see [`Font::coverage_set`](trait.Font.html#method.coverage_set).
+///
+/// Useful for font fallback itemization over many fonts, avoiding repeated cmap lookups.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{CoverageSet, Font, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let coverage = font.coverage_set();
+///
+/// assert!(coverage.contains('a'));
+/// assert!(!coverage.contains('\u{1F600}'));
+/// assert_eq!(coverage.len(), font.codepoint_ids().count());
+///
+/// // chars missing from the font
+/// let missing: CoverageSet = "abc日本".chars().filter(|&c| !coverage.contains(c)).collect();
+/// assert_eq!(missing.ranges().collect::<Vec<_>>(), vec!['日'..='日', '本'..='本']);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CoverageSet {
+    /// Sorted inclusive ranges, separated by at least one char.
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CoverageSet {
+    /// Returns an empty set.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the set contains `c`.
+    pub fn contains(&self, c: char) -> bool {
+        let c = u32::from(c);
+        self.ranges
+            .binary_search_by(|&(start, end)| {
+                if end < c {
+                    core::cmp::Ordering::Less
+                } else if start > c {
+                    core::cmp::Ordering::Greater
+                } else {
+                    core::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    /// Returns the number of chars in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| {
+                let surrogates = end.min(0xDFFF).saturating_sub(start.max(0xD800) - 1);
+                (end - start + 1 - surrogates) as usize
+            })
+            .sum()
+    }
+
+    /// Returns `true` if the set contains no chars.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns an iterator over the sorted, non-adjacent ranges of the set.
+    #[inline]
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<char>> + '_ {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| to_char(start)..=to_char(end))
+    }
+
+    /// Returns an iterator over the chars of the set in order.
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.ranges
+            .iter()
+            .flat_map(|&(start, end)| start..=end)
+            .filter_map(core::char::from_u32)
+    }
+
+    /// Adds all chars of `other` to the set, e.g. to compute the coverage of a font stack.
+    pub fn union(&mut self, other: &CoverageSet) {
+        let mut ranges = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        ranges.extend_from_slice(&self.ranges);
+        ranges.extend_from_slice(&other.ranges);
+        self.ranges = merge(ranges);
+    }
+}
+
+impl FromIterator<char> for CoverageSet {
+    fn from_iter<I: IntoIterator<Item = char>>(chars: I) -> Self {
+        let ranges = chars
+            .into_iter()
+            .map(|c| (u32::from(c), u32::from(c)))
+            .collect();
+        Self {
+            ranges: merge(ranges),
+        }
+    }
+}
+
+impl FromIterator<RangeInclusive<char>> for CoverageSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<char>>>(ranges: I) -> Self {
+        let ranges = ranges
+            .into_iter()
+            .filter(|range| range.start() <= range.end())
+            .map(|range| (u32::from(*range.start()), u32::from(*range.end())))
+            .collect();
+        Self {
+            ranges: merge(ranges),
+        }
+    }
+}
+
+/// Sorts & merges overlapping or adjacent ranges.
+fn merge(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= after(last.1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged.shrink_to_fit();
+    merged
+}
+
+/// Returns the char value after `c`, skipping surrogates.
+#[inline]
+fn after(c: u32) -> u32 {
+    if c == 0xD7FF {
+        0xE000
+    } else {
+        c + 1
+    }
+}
+
+/// Converts a range bound, which are always chars.
+#[inline]
+fn to_char(c: u32) -> char {
+    core::char::from_u32(c).unwrap_or(core::char::REPLACEMENT_CHARACTER)
+}
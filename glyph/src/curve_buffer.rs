@@ -0,0 +1,175 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, Font, GlyphId, Outline, OutlineCurve, Point, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Glyph outlines converted to quadratic Bézier curves packed into a flat `f32` buffer, for
+/// GPU renderers evaluating outlines per pixel, e.g. Loop-Blinn or Slug style shaders.
+///
+/// Each curve is 6 floats `[x0, y0, x1, y1, x2, y2]` of unscaled outline coordinates, y up.
+/// Lines are stored as quadratics with a midpoint control & cubics are approximated by
+/// quadratics. Each glyph's curves are contiguous, see [`CurveGlyph`](struct.CurveGlyph.html),
+/// so drawing a glyph is a quad covering its bounds with the curve range as instance data.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{CurveBuffer, Font, FontRef, OutlineCurve};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let mut buffer = CurveBuffer::default();
+///
+/// // cubic curves are approximated to within a 1/4 font unit
+/// let o = buffer.push_glyph(&font, font.glyph_id('o'), 0.25).unwrap();
+/// let s = buffer.push_glyph(&font, font.glyph_id('s'), 0.25).unwrap();
+///
+/// assert_eq!(s.start, o.start + o.len);
+/// assert_eq!(buffer.data().len(), 6 * buffer.len());
+/// assert!(buffer.curves().all(|c| matches!(c, OutlineCurve::Quad(..))));
+///
+/// // upload `buffer.data()` & draw each glyph's bounds with its curve range
+/// assert_eq!(s.bounds, font.outline(font.glyph_id('s')).unwrap().bounds);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CurveBuffer {
+    data: Vec<f32>,
+    glyphs: Vec<CurveGlyph>,
+}
+
+/// A glyph's range of curves in a [`CurveBuffer`](struct.CurveBuffer.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CurveGlyph {
+    /// Glyph id.
+    pub id: GlyphId,
+    /// Unscaled outline bounds, like [`Outline::bounds`](struct.Outline.html#structfield.bounds)
+    /// `min.y` is the top & `max.y` the bottom.
+    pub bounds: Rect,
+    /// Index of the first curve.
+    pub start: u32,
+    /// Number of curves.
+    pub len: u32,
+}
+
+impl CurveBuffer {
+    /// Floats per curve.
+    pub const CURVE_LEN: usize = 6;
+
+    /// Maximum quadratics approximating a single cubic.
+    const MAX_CUBIC_SPLITS: usize = 16;
+
+    /// Appends a font glyph's outline, returning its curve range, or `None` if the glyph
+    /// has no outline. See [`push_outline`](#method.push_outline).
+    pub fn push_glyph<F: Font>(
+        &mut self,
+        font: &F,
+        id: GlyphId,
+        tolerance: f32,
+    ) -> Option<CurveGlyph> {
+        Some(self.push_outline(id, &font.outline(id)?, tolerance))
+    }
+
+    /// Appends an unscaled outline, returning its curve range.
+    ///
+    /// Cubics are approximated by quadratics deviating by up to roughly `tolerance` outline
+    /// units.
+    pub fn push_outline(&mut self, id: GlyphId, outline: &Outline, tolerance: f32) -> CurveGlyph {
+        let start = self.len() as u32;
+        for curve in &outline.curves {
+            match *curve {
+                OutlineCurve::Line(p0, p1) => self.push_quad(p0, p0.lerp(p1, 0.5), p1),
+                OutlineCurve::Quad(p0, p1, p2) => self.push_quad(p0, p1, p2),
+                OutlineCurve::Cubic(p0, p1, p2, p3) => self.push_cubic(p0, p1, p2, p3, tolerance),
+            }
+        }
+        let glyph = CurveGlyph {
+            id,
+            bounds: outline.bounds,
+            start,
+            len: self.len() as u32 - start,
+        };
+        self.glyphs.push(glyph);
+        glyph
+    }
+
+    #[inline]
+    fn push_quad(&mut self, p0: Point, p1: Point, p2: Point) {
+        self.data
+            .extend_from_slice(&[p0.x, p0.y, p1.x, p1.y, p2.x, p2.y]);
+    }
+
+    /// Splits a cubic into equal parameter ranges, each approximated by the quadratic
+    /// with the midpoint of the extrapolated control points.
+    fn push_cubic(&mut self, p0: Point, p1: Point, p2: Point, p3: Point, tolerance: f32) {
+        // single quadratic error is sqrt(3)/36 * |p3 - 3p2 + 3p1 - p0|, falling with the
+        // cube of the number of splits
+        let d = p3 - p2 * 3.0 + p1 * 3.0 - p0;
+        let error = (d.x * d.x + d.y * d.y).sqrt() * (3_f32.sqrt() / 36.0);
+        let splits = (error / tolerance.max(f32::EPSILON)).powf(1.0 / 3.0).ceil();
+        let splits = (splits as usize).clamp(1, Self::MAX_CUBIC_SPLITS);
+
+        let at = |t: f32| {
+            let mt = 1.0 - t;
+            p0 * (mt * mt * mt)
+                + p1 * (3.0 * mt * mt * t)
+                + p2 * (3.0 * mt * t * t)
+                + p3 * (t * t * t)
+        };
+        let tangent = |t: f32| {
+            let mt = 1.0 - t;
+            (p1 - p0) * (3.0 * mt * mt) + (p2 - p1) * (6.0 * mt * t) + (p3 - p2) * (3.0 * t * t)
+        };
+
+        let step = 1.0 / splits as f32;
+        let mut q0 = p0;
+        for n in 1..=splits {
+            let (t0, t1) = ((n - 1) as f32 * step, n as f32 * step);
+            let q3 = if n == splits { p3 } else { at(t1) };
+            let q1 = q0 + tangent(t0) * (step / 3.0);
+            let q2 = q3 - tangent(t1) * (step / 3.0);
+            let control = ((q1 + q2) * 3.0 - q0 - q3) / 4.0;
+            self.push_quad(q0, control, q3);
+            q0 = q3;
+        }
+    }
+
+    /// Returns the curves as packed floats, [`CURVE_LEN`](#associatedconstant.CURVE_LEN)
+    /// per curve.
+    #[inline]
+    pub fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    /// Returns the appended glyphs.
+    #[inline]
+    pub fn glyphs(&self) -> &[CurveGlyph] {
+        &self.glyphs
+    }
+
+    /// Iterator of the curves, all `OutlineCurve::Quad`s.
+    pub fn curves(&self) -> impl Iterator<Item = OutlineCurve> + '_ {
+        self.data
+            .chunks_exact(Self::CURVE_LEN)
+            .map(|c| OutlineCurve::Quad(point(c[0], c[1]), point(c[2], c[3]), point(c[4], c[5])))
+    }
+
+    /// Returns the number of curves.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len() / Self::CURVE_LEN
+    }
+
+    /// Returns `true` if there are no curves.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Removes all curves & glyphs, keeping allocated memory for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.glyphs.clear();
+    }
+}
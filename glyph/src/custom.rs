@@ -0,0 +1,310 @@
+use crate::{
+    point, CodepointIdIter, ColorOutline, Font, GlyphId, Outline, OutlineCurve, Point, Rect,
+};
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// Builder for a [`CustomFont`](struct.CustomFont.html), an in-memory `Font` made from
+/// user supplied outlines, advances & char mappings.
+///
+/// Useful for icon fonts generated at build time or procedurally created glyphs.
+///
+/// Unscaled values are in arbitrary "font units", as defined by `units_per_em`, with
+/// y increasing upwards from the baseline.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, Font, FontBuilder, GlyphId, OutlineCurve};
+///
+/// let mut builder = FontBuilder::new(1000.0);
+/// builder.ascent(800.0).descent(-200.0);
+///
+/// // a filled square icon
+/// let square = builder.add_glyph(
+///     1000.0,
+///     vec![
+///         OutlineCurve::Line(point(100.0, 0.0), point(100.0, 800.0)),
+///         OutlineCurve::Line(point(100.0, 800.0), point(900.0, 800.0)),
+///         OutlineCurve::Line(point(900.0, 800.0), point(900.0, 0.0)),
+///         OutlineCurve::Line(point(900.0, 0.0), point(100.0, 0.0)),
+///     ],
+/// );
+/// builder.map_char('■', square);
+///
+/// let font = builder.build();
+/// assert_eq!(font.glyph_id('■'), square);
+/// assert_eq!(font.glyph_id('x'), GlyphId(0));
+/// assert_eq!(font.h_advance_unscaled(square), 1000.0);
+/// assert_eq!(font.h_side_bearing_unscaled(square), 100.0);
+///
+/// let outline = font.outline(square).unwrap();
+/// assert_eq!(outline.bounds.min, point(100.0, 800.0));
+/// assert_eq!(outline.bounds.max, point(900.0, 0.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FontBuilder {
+    font: CustomFont,
+}
+
+impl FontBuilder {
+    /// New builder for a font with the given units per em.
+    ///
+    /// Ascent defaults to `units_per_em`, descent & line gap to `0.0`.
+    /// Contains a single empty `.notdef` glyph, `GlyphId(0)`, see
+    /// [`set_notdef`](#method.set_notdef).
+    pub fn new(units_per_em: f32) -> Self {
+        Self {
+            font: CustomFont {
+                units_per_em,
+                ascent: units_per_em,
+                descent: 0.0,
+                line_gap: 0.0,
+                glyphs: vec![CustomGlyph::new(0.0, Vec::new())],
+                char_map: BTreeMap::new(),
+                kerning: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Sets the unscaled ascent.
+    pub fn ascent(&mut self, ascent: f32) -> &mut Self {
+        self.font.ascent = ascent;
+        self
+    }
+
+    /// Sets the unscaled descent, typically negative.
+    pub fn descent(&mut self, descent: f32) -> &mut Self {
+        self.font.descent = descent;
+        self
+    }
+
+    /// Sets the unscaled line gap.
+    pub fn line_gap(&mut self, line_gap: f32) -> &mut Self {
+        self.font.line_gap = line_gap;
+        self
+    }
+
+    /// Replaces the `.notdef` glyph, `GlyphId(0)`, used for unmapped chars.
+    pub fn set_notdef(&mut self, h_advance: f32, curves: Vec<OutlineCurve>) -> &mut Self {
+        self.font.glyphs[0] = CustomGlyph::new(h_advance, curves);
+        self
+    }
+
+    /// Adds a glyph returning its id. Outline bounds are calculated from the curves.
+    ///
+    /// Glyphs without `curves`, like spaces, will not be outlined.
+    ///
+    /// # Panics
+    /// If more than `u16::MAX` glyphs are added.
+    pub fn add_glyph(&mut self, h_advance: f32, curves: Vec<OutlineCurve>) -> GlyphId {
+        assert!(
+            self.font.glyphs.len() <= usize::from(u16::MAX),
+            "Too many glyphs"
+        );
+        let id = GlyphId(self.font.glyphs.len() as u16);
+        self.font.glyphs.push(CustomGlyph::new(h_advance, curves));
+        id
+    }
+
+    /// Maps a char to a glyph, replacing any previous mapping.
+    pub fn map_char(&mut self, c: char, id: GlyphId) -> &mut Self {
+        self.font.char_map.insert(c, id);
+        self
+    }
+
+    /// Sets additional unscaled kerning for a pair of glyphs.
+    pub fn kern(&mut self, first: GlyphId, second: GlyphId, kern: f32) -> &mut Self {
+        self.font.kerning.insert((first, second), kern);
+        self
+    }
+
+    /// Builds the font from the current glyphs, mappings & metrics.
+    ///
+    /// The builder is left unchanged, so can continue to build variants.
+    pub fn build(&self) -> CustomFont {
+        self.font.clone()
+    }
+}
+
+/// In-memory `Font` constructed with a [`FontBuilder`](struct.FontBuilder.html).
+#[derive(Clone)]
+pub struct CustomFont {
+    units_per_em: f32,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    glyphs: Vec<CustomGlyph>,
+    char_map: BTreeMap<char, GlyphId>,
+    kerning: BTreeMap<(GlyphId, GlyphId), f32>,
+}
+
+impl fmt::Debug for CustomFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CustomFont")
+    }
+}
+
+#[derive(Clone)]
+struct CustomGlyph {
+    h_advance: f32,
+    outline: Option<Outline>,
+}
+
+impl CustomGlyph {
+    fn new(h_advance: f32, curves: Vec<OutlineCurve>) -> Self {
+        let outline = curve_bounds(&curves).map(|bounds| Outline { bounds, curves });
+        Self { h_advance, outline }
+    }
+}
+
+/// Returns the bounds of all curve points, with `min.y` as the top.
+fn curve_bounds(curves: &[OutlineCurve]) -> Option<Rect> {
+    let mut bounds: Option<Rect> = None;
+    let mut include = |p: Point| {
+        let b = bounds.get_or_insert(Rect { min: p, max: p });
+        b.min = point(b.min.x.min(p.x), b.min.y.max(p.y));
+        b.max = point(b.max.x.max(p.x), b.max.y.min(p.y));
+    };
+    for curve in curves {
+        match *curve {
+            OutlineCurve::Line(p0, p1) => {
+                include(p0);
+                include(p1);
+            }
+            OutlineCurve::Quad(p0, p1, p2) => {
+                include(p0);
+                include(p1);
+                include(p2);
+            }
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                include(p0);
+                include(p1);
+                include(p2);
+                include(p3);
+            }
+        }
+    }
+    bounds
+}
+
+impl CustomFont {
+    /// Returns the approximate memory in bytes held by this font.
+    pub fn memory_usage(&self) -> usize {
+        let curves: usize = self
+            .glyphs
+            .iter()
+            .filter_map(|g| g.outline.as_ref())
+            .map(|o| o.curves.capacity() * core::mem::size_of::<OutlineCurve>())
+            .sum();
+        core::mem::size_of::<Self>()
+            + self.glyphs.capacity() * core::mem::size_of::<CustomGlyph>()
+            + curves
+            + self.char_map.len() * core::mem::size_of::<(char, GlyphId)>()
+            + self.kerning.len() * core::mem::size_of::<((GlyphId, GlyphId), f32)>()
+    }
+
+    #[inline]
+    fn glyph(&self, id: GlyphId) -> &CustomGlyph {
+        self.glyphs
+            .get(usize::from(id.0))
+            .unwrap_or(&self.glyphs[0])
+    }
+}
+
+impl Font for CustomFont {
+    #[inline]
+    fn units_per_em(&self) -> Option<f32> {
+        Some(self.units_per_em)
+    }
+
+    #[inline]
+    fn ascent_unscaled(&self) -> f32 {
+        self.ascent
+    }
+
+    #[inline]
+    fn descent_unscaled(&self) -> f32 {
+        self.descent
+    }
+
+    #[inline]
+    fn line_gap_unscaled(&self) -> f32 {
+        self.line_gap
+    }
+
+    #[inline]
+    fn glyph_id(&self, c: char) -> GlyphId {
+        self.char_map.get(&c).copied().unwrap_or(GlyphId(0))
+    }
+
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        self.char_map.get(&c).copied()
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        CodepointIdIter::new(self.char_map.iter().map(|(&c, &id)| (id, c)))
+    }
+
+    #[inline]
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.glyph(id).h_advance
+    }
+
+    #[inline]
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        match &self.glyph(id).outline {
+            Some(outline) => outline.bounds.min.x,
+            None => 0.0,
+        }
+    }
+
+    #[inline]
+    fn v_advance_unscaled(&self, _id: GlyphId) -> f32 {
+        self.height_unscaled()
+    }
+
+    #[inline]
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        match &self.glyph(id).outline {
+            Some(outline) => self.ascent - outline.bounds.min.y,
+            None => 0.0,
+        }
+    }
+
+    #[inline]
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.kerning
+            .get(&(first, second))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn has_color(&self, _id: GlyphId) -> bool {
+        false
+    }
+
+    #[inline]
+    fn color_outlines(&self, _id: GlyphId) -> Option<ColorOutline> {
+        None
+    }
+
+    #[inline]
+    fn relative_scale(&self, _id: GlyphId) -> f32 {
+        1.0
+    }
+
+    #[inline]
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        self.glyph(id).outline.clone()
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+}
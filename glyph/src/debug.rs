@@ -0,0 +1,263 @@
+//! Glyph inspection, e.g. to log what the font holds for a glyph that renders incorrectly.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::{debug, Font, FontRef};
+//!
+//! # fn main() -> Result<(), ab_glyph::InvalidFont> {
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+//!
+//! let report = debug::dump_glyph(&font, font.glyph_id('o'));
+//! assert_eq!(report.chars, vec!['o']);
+//! assert_eq!(report.contours.len(), 2);
+//! // outer & inner contours wind in opposite directions
+//! assert_ne!(report.contours[0].clockwise, report.contours[1].clockwise);
+//! assert!(report.color_layers.is_empty() && report.strikes.is_empty());
+//!
+//! // human readable multi-line report
+//! let text = report.to_string();
+//! assert!(text.starts_with("GlyphId(") && text.contains("contours: 2"));
+//! # Ok(()) }
+//! ```
+use crate::{Font, GlyphComplexity, GlyphId, GlyphImageFormat, OutlineCurve, Point, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Unscaled metrics, outline, color layers & bitmap strikes of a glyph, see
+/// [`dump_glyph`](fn.dump_glyph.html).
+///
+/// Formats with `Display` as a multi-line report.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphReport {
+    /// Glyph id.
+    pub id: GlyphId,
+    /// Chars mapped to the glyph.
+    pub chars: Vec<char>,
+    /// Unscaled horizontal advance.
+    pub h_advance: f32,
+    /// Unscaled horizontal side bearing.
+    pub h_side_bearing: f32,
+    /// Unscaled vertical advance.
+    pub v_advance: f32,
+    /// Unscaled vertical side bearing.
+    pub v_side_bearing: f32,
+    /// Unscaled outline bounds, `min.y` being the top, or `None` if there's no outline.
+    pub bounds: Option<Rect>,
+    /// Outline contours.
+    pub contours: Vec<ContourReport>,
+    /// Color layers, bottom first.
+    pub color_layers: Vec<LayerReport>,
+    /// Bitmap strikes with an image of the glyph, smallest first.
+    pub strikes: Vec<StrikeReport>,
+}
+
+/// A contour of a [`GlyphReport`](struct.GlyphReport.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ContourReport {
+    /// Number of curves.
+    pub curves: usize,
+    /// Number of on & off curve points.
+    pub points: usize,
+    /// Winding direction, clockwise for TrueType outer contours & CFF inner contours.
+    pub clockwise: bool,
+}
+
+/// A color layer of a [`GlyphReport`](struct.GlyphReport.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerReport {
+    /// RGBA color.
+    pub color: u32,
+    /// Palette entry index of the color, see
+    /// [`ColorLayer::palette_index`](../struct.ColorLayer.html#structfield.palette_index).
+    pub palette_index: u16,
+    /// Unscaled layer outline bounds, `min.y` being the top.
+    pub bounds: Rect,
+    /// Number of contours.
+    pub contours: usize,
+}
+
+/// A bitmap strike image of a [`GlyphReport`](struct.GlyphReport.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StrikeReport {
+    /// Pixels per em of the strike.
+    pub pixels_per_em: u16,
+    /// Image data format.
+    pub format: GlyphImageFormat,
+    /// Image pixel width.
+    pub width: u16,
+    /// Image pixel height.
+    pub height: u16,
+    /// Pixel offset from the glyph origin to the top-left of the image.
+    pub bearing: Point,
+}
+
+/// Inspects a glyph, collecting its metrics, outline contours, color layers & bitmap
+/// strikes. Walks the whole character map & each strike, so is intended for debugging.
+pub fn dump_glyph<F: Font>(font: &F, id: GlyphId) -> GlyphReport {
+    let outline = font.outline(id);
+    let contour_reports = outline
+        .as_ref()
+        .map(|outline| contours(&outline.curves))
+        .unwrap_or_default();
+
+    let color_layers = font
+        .color_outlines(id)
+        .unwrap_or_default()
+        .layers
+        .into_iter()
+        .map(|layer| LayerReport {
+            color: layer.rgba_u32(),
+            palette_index: layer.palette_index,
+            bounds: layer.outline.bounds,
+            contours: contours(&layer.outline.curves).len(),
+        })
+        .collect();
+
+    // each strike is found by asking for one larger than the last
+    let mut strikes = Vec::new();
+    let mut ppem = 1;
+    while let Some(image) = font.glyph_raster_image(id, ppem) {
+        if image.pixels_per_em < ppem {
+            break;
+        }
+        strikes.push(StrikeReport {
+            pixels_per_em: image.pixels_per_em,
+            format: image.format,
+            width: image.width,
+            height: image.height,
+            bearing: image.bearing,
+        });
+        match image.pixels_per_em.checked_add(1) {
+            Some(next) => ppem = next,
+            None => break,
+        }
+    }
+
+    GlyphReport {
+        id,
+        chars: font
+            .codepoint_ids()
+            .filter(|&(mapped, _)| mapped == id)
+            .map(|(_, c)| c)
+            .collect(),
+        h_advance: font.h_advance_unscaled(id),
+        h_side_bearing: font.h_side_bearing_unscaled(id),
+        v_advance: font.v_advance_unscaled(id),
+        v_side_bearing: font.v_side_bearing_unscaled(id),
+        bounds: outline.map(|outline| outline.bounds),
+        contours: contour_reports,
+        color_layers,
+        strikes,
+    }
+}
+
+/// Splits curves into contours, a curve not continuing from the last starting a new one.
+fn contours(curves: &[OutlineCurve]) -> Vec<ContourReport> {
+    let mut contours = Vec::new();
+    let mut start = 0;
+    for i in 1..=curves.len() {
+        let continues = curves
+            .get(i)
+            .is_some_and(|curve| start_point(curve) == end_point(&curves[i - 1]));
+        if !continues {
+            contours.push(contour(&curves[start..i]));
+            start = i;
+        }
+    }
+    contours
+}
+
+fn contour(curves: &[OutlineCurve]) -> ContourReport {
+    // shoelace area of the polygon through all points, negative if clockwise in y up units
+    let mut points = Vec::with_capacity(curves.len() * 2);
+    for curve in curves {
+        match *curve {
+            OutlineCurve::Line(p0, _) => points.push(p0),
+            OutlineCurve::Quad(p0, p1, _) => points.extend_from_slice(&[p0, p1]),
+            OutlineCurve::Cubic(p0, p1, p2, _) => points.extend_from_slice(&[p0, p1, p2]),
+        }
+    }
+    let mut area = 0.0;
+    for (i, p0) in points.iter().enumerate() {
+        let p1 = points[(i + 1) % points.len()];
+        area += p0.x * p1.y - p1.x * p0.y;
+    }
+
+    ContourReport {
+        curves: curves.len(),
+        points: GlyphComplexity::of_curves(curves).points,
+        clockwise: area < 0.0,
+    }
+}
+
+#[inline]
+fn start_point(curve: &OutlineCurve) -> Point {
+    match *curve {
+        OutlineCurve::Line(p, _) | OutlineCurve::Quad(p, ..) | OutlineCurve::Cubic(p, ..) => p,
+    }
+}
+
+#[inline]
+fn end_point(curve: &OutlineCurve) -> Point {
+    match *curve {
+        OutlineCurve::Line(_, p) | OutlineCurve::Quad(_, _, p) | OutlineCurve::Cubic(.., p) => p,
+    }
+}
+
+impl fmt::Display for GlyphReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?} chars: {:?}", self.id, self.chars)?;
+        writeln!(
+            f,
+            "  h_advance: {}, h_side_bearing: {}, v_advance: {}, v_side_bearing: {}",
+            self.h_advance, self.h_side_bearing, self.v_advance, self.v_side_bearing
+        )?;
+        match self.bounds {
+            Some(b) => writeln!(
+                f,
+                "  bounds: x {}..{}, y {}..{}",
+                b.min.x, b.max.x, b.max.y, b.min.y
+            )?,
+            None => writeln!(f, "  bounds: None")?,
+        }
+        writeln!(f, "  contours: {}", self.contours.len())?;
+        for (n, c) in self.contours.iter().enumerate() {
+            let direction = if c.clockwise {
+                "clockwise"
+            } else {
+                "anti-clockwise"
+            };
+            writeln!(
+                f,
+                "    {}: {} curves, {} points, {}",
+                n, c.curves, c.points, direction
+            )?;
+        }
+        writeln!(f, "  color layers: {}", self.color_layers.len())?;
+        for (n, layer) in self.color_layers.iter().enumerate() {
+            let b = layer.bounds;
+            writeln!(
+                f,
+                "    {}: color #{:08x}, palette index {}, {} contours, x {}..{}, y {}..{}",
+                n,
+                layer.color,
+                layer.palette_index,
+                layer.contours,
+                b.min.x,
+                b.max.x,
+                b.max.y,
+                b.min.y
+            )?;
+        }
+        writeln!(f, "  strikes: {}", self.strikes.len())?;
+        for s in &self.strikes {
+            writeln!(
+                f,
+                "    {} ppem: {:?} {}x{}, bearing ({}, {})",
+                s.pixels_per_em, s.format, s.width, s.height, s.bearing.x, s.bearing.y
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{point, Font, Glyph, OutlineCurve, Point, Rect, ScaleFont};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Line segments each curve is flattened into when intersecting outlines.
+const CURVE_SEGMENTS: u8 = 8;
+
+/// Unscaled position & thickness of a text decoration line, e.g. an underline.
+///
+/// See [`Font::underline_metrics_unscaled`](trait.Font.html#method.underline_metrics_unscaled).
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct DecorationMetrics {
+    /// Distance from the baseline to the top of the line, negative below the baseline.
+    pub position: f32,
+    /// Line thickness.
+    pub thickness: f32,
+}
+
+/// Text decoration line kinds.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Decoration, Font, FontRef, Layout, point};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let scaled_font = font.as_scaled(24.0);
+///
+/// let mut glyphs = Vec::new();
+/// let lines = Layout::default().layout(&scaled_font, point(10.0, 0.0), "Hello", &mut glyphs);
+///
+/// let underline = Decoration::Underline.rect(&scaled_font, &glyphs).unwrap();
+/// assert_eq!(underline.min.x, 10.0);
+/// assert!(underline.min.y > lines[0].baseline);
+///
+/// let strike = Decoration::Strikethrough.rect(&scaled_font, &glyphs).unwrap();
+/// assert!(strike.max.y < lines[0].baseline);
+/// # Ok(()) }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Decoration {
+    /// Line below the baseline.
+    Underline,
+    /// Line through the middle of lowercase glyphs.
+    Strikethrough,
+    /// Line at the ascent.
+    Overline,
+}
+
+impl Decoration {
+    /// Returns unscaled metrics of the decoration for a font.
+    ///
+    /// Uses the font's underline & strikeout metrics, falling back to estimates from the
+    /// ascent, descent & units per em for fonts without them. Overlines use the underline
+    /// thickness with the top of the line at the ascent.
+    pub fn metrics_unscaled<F: Font>(self, font: &F) -> DecorationMetrics {
+        let em = font
+            .units_per_em()
+            .unwrap_or_else(|| font.height_unscaled());
+        let underline = || {
+            font.underline_metrics_unscaled()
+                .filter(|m| m.thickness > 0.0)
+                .unwrap_or(DecorationMetrics {
+                    position: font.descent_unscaled() / 2.0,
+                    thickness: em / 14.0,
+                })
+        };
+        match self {
+            Self::Underline => underline(),
+            Self::Strikethrough => font
+                .strikeout_metrics_unscaled()
+                .filter(|m| m.thickness > 0.0)
+                .unwrap_or_else(|| {
+                    let thickness = underline().thickness;
+                    DecorationMetrics {
+                        // roughly half the x-height
+                        position: font.ascent_unscaled() / 4.0 + thickness / 2.0,
+                        thickness,
+                    }
+                }),
+            Self::Overline => DecorationMetrics {
+                position: font.ascent_unscaled(),
+                thickness: underline().thickness,
+            },
+        }
+    }
+
+    /// Returns the pixel rect of the decoration for a run of glyphs laid out on the same
+    /// baseline, spanning from the leftmost glyph position to the furthest advance.
+    ///
+    /// Returns `None` for an empty run.
+    pub fn rect<F, SF>(self, font: &SF, glyphs: &[Glyph]) -> Option<Rect>
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        let baseline = glyphs.first()?.position.y;
+        let min_x = glyphs
+            .iter()
+            .map(|g| g.position.x)
+            .fold(f32::INFINITY, f32::min);
+        let max_x = glyphs
+            .iter()
+            .map(|g| g.position.x + font.h_advance(g.id))
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        let metrics = self.metrics_unscaled(font.font());
+        let top = baseline - font.v_units_to_px(metrics.position);
+        let thickness = font.v_units_to_px(metrics.thickness);
+        Some(Rect {
+            min: point(min_x, top),
+            max: point(max_x, top + thickness),
+        })
+    }
+
+    /// Returns the pixel rects of the decoration `rect` broken around where glyph outlines
+    /// intersect it, plus `padding` pixels either side, so e.g. underlines skip the
+    /// descenders of "g", "y" & "p".
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Decoration, Font, FontRef, Layout, point};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let scaled_font = font.as_scaled(48.0);
+    ///
+    /// let mut glyphs = Vec::new();
+    /// Layout::default().layout(&scaled_font, point(0.0, 0.0), "type", &mut glyphs);
+    ///
+    /// let underline = Decoration::Underline.rect(&scaled_font, &glyphs).unwrap();
+    /// let parts = Decoration::skip_ink(&scaled_font, &glyphs, underline, 1.0);
+    /// // broken around the "y" & "p" descenders
+    /// assert!(parts.len() > 1);
+    /// assert!(parts
+    ///     .iter()
+    ///     .all(|part| part.min.x >= underline.min.x && part.max.x <= underline.max.x));
+    /// # Ok(()) }
+    /// ```
+    pub fn skip_ink<F, SF>(font: &SF, glyphs: &[Glyph], rect: Rect, padding: f32) -> Vec<Rect>
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        let mut gaps: Vec<(f32, f32)> = glyphs
+            .iter()
+            .filter_map(|glyph| {
+                let (min_x, max_x) = ink_x_range(font.font(), glyph, rect.min.y, rect.max.y)?;
+                Some((min_x - padding, max_x + padding))
+            })
+            .collect();
+        gaps.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+
+        let mut parts = Vec::new();
+        let mut x = rect.min.x;
+        for (gap_min, gap_max) in gaps {
+            if gap_min > x {
+                parts.push(Rect {
+                    min: point(x, rect.min.y),
+                    max: point(gap_min.min(rect.max.x), rect.max.y),
+                });
+            }
+            x = x.max(gap_max);
+            if x >= rect.max.x {
+                break;
+            }
+        }
+        if x < rect.max.x {
+            parts.push(Rect {
+                min: point(x, rect.min.y),
+                max: rect.max,
+            });
+        }
+        parts.retain(|r| r.width() > 0.0);
+        parts
+    }
+}
+
+/// Returns the pixel x range of a glyph's outline between pixel y `top` & `bottom`.
+fn ink_x_range<F: Font>(font: &F, glyph: &Glyph, top: f32, bottom: f32) -> Option<(f32, f32)> {
+    let outline = font.outline(glyph.id)?;
+    let scaled = font.as_scaled(glyph.scale);
+    let (h_factor, v_factor) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+    // pixel band in unscaled, y up, units
+    let (lo, hi) = (
+        (glyph.position.y - bottom) / v_factor,
+        (glyph.position.y - top) / v_factor,
+    );
+    // outline bounds are y up, min.y is the top
+    if outline.bounds.min.y < lo || outline.bounds.max.y > hi {
+        return None;
+    }
+
+    let mut range: Option<(f32, f32)> = None;
+    let mut add = |x: f32| {
+        range = Some(match range {
+            Some((min, max)) => (min.min(x), max.max(x)),
+            None => (x, x),
+        });
+    };
+    for curve in &outline.curves {
+        let mut prev = curve_point(curve, 0.0);
+        let segments = match curve {
+            OutlineCurve::Line(..) => 1,
+            _ => CURVE_SEGMENTS,
+        };
+        for n in 1..=segments {
+            let next = curve_point(curve, f32::from(n) / f32::from(segments));
+            // clip the segment to the band
+            let (a, b) = if prev.y <= next.y {
+                (prev, next)
+            } else {
+                (next, prev)
+            };
+            if b.y >= lo && a.y <= hi {
+                let x_at = |y: f32| {
+                    if b.y > a.y {
+                        a.x + (b.x - a.x) * (y - a.y) / (b.y - a.y)
+                    } else {
+                        a.x
+                    }
+                };
+                add(x_at(a.y.max(lo)));
+                add(x_at(b.y.min(hi)));
+            }
+            prev = next;
+        }
+    }
+
+    let (min, max) = range?;
+    Some((
+        glyph.position.x + min * h_factor,
+        glyph.position.x + max * h_factor,
+    ))
+}
+
+/// Point at `t` along a curve.
+fn curve_point(curve: &OutlineCurve, t: f32) -> Point {
+    match *curve {
+        OutlineCurve::Line(p0, p1) => p0.lerp(p1, t),
+        OutlineCurve::Quad(p0, p1, p2) => p0.lerp(p1, t).lerp(p1.lerp(p2, t), t),
+        OutlineCurve::Cubic(p0, p1, p2, p3) => {
+            let (a, b, c) = (p0.lerp(p1, t), p1.lerp(p2, t), p2.lerp(p3, t));
+            a.lerp(b, t).lerp(b.lerp(c, t), t)
+        }
+    }
+}
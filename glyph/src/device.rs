@@ -0,0 +1,24 @@
+/// Pixel vertical metrics for a specific pixels-per-em size, as used by legacy
+/// rasterizers to avoid clipping. TrueType/OpenType fonts read these from the `VDMX` table.
+///
+/// See [`Font::device_v_metrics`](trait.Font.html#method.device_v_metrics).
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct DeviceVMetrics {
+    /// Maximum pixel height above the baseline.
+    pub y_max: f32,
+    /// Minimum pixel height, typically negative below the baseline.
+    pub y_min: f32,
+}
+
+impl DeviceVMetrics {
+    /// Pixel line height `y_max - y_min`.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.y_max - self.y_min
+    }
+}
@@ -0,0 +1,95 @@
+//! [embedded-graphics](https://docs.rs/embedded-graphics) `DrawTarget` rendering,
+//! requires the `embedded-graphics` feature.
+//!
+//! Works in no_std environments, enabling .ttf/.otf text on embedded displays.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::{embedded_graphics::draw_text, point, FontRef, PxScale};
+//! use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+//! # use embedded_graphics_core::pixelcolor::BinaryColor as C;
+//! # struct Display(Vec<Pixel<C>>);
+//! # impl OriginDimensions for Display {
+//! #     fn size(&self) -> Size { Size::new(128, 64) }
+//! # }
+//! # impl DrawTarget for Display {
+//! #     type Color = C;
+//! #     type Error = core::convert::Infallible;
+//! #     fn draw_iter<I: IntoIterator<Item = Pixel<C>>>(&mut self, pixels: I) -> Result<(), Self::Error> {
+//! #         self.0.extend(pixels);
+//! #         Ok(())
+//! #     }
+//! # }
+//! # let mut display = Display(vec![]);
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+//!
+//! // draw "Hello" with a 1-bit threshold, baseline at y=20
+//! let color = |coverage: f32| Some(BinaryColor::On).filter(|_| coverage >= 0.5);
+//! draw_text(&mut display, &font, PxScale::from(16.0), point(0.0, 20.0), "Hello", color)?;
+//! # assert!(!display.0.is_empty());
+//! # Ok::<(), core::convert::Infallible>(())
+//! ```
+use crate::{Font, OutlinedGlyph, Point, PxScale, ScaleFont};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Point as EgPoint, Pixel};
+
+/// Draws an outlined glyph onto an embedded-graphics `DrawTarget`.
+///
+/// `color` maps pixel coverage, in the range `[0.0, 1.0]`, to the color to draw
+/// or `None` to leave the pixel untouched.
+///
+/// Colored glyphs draw all layers using the same `color` function.
+pub fn draw_glyph<D, C>(target: &mut D, glyph: &OutlinedGlyph, mut color: C) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    C: FnMut(f32) -> Option<D::Color>,
+{
+    let bounds = glyph.px_bounds();
+    let (min_x, min_y) = (bounds.min.x as i32, bounds.min.y as i32);
+    let layers = glyph.get_colored_layers().unwrap_or(1);
+
+    let mut pixels = Vec::new();
+    for layer in 0..layers {
+        glyph.draw(layer, |x, y, c| {
+            if let Some(px_color) = color(c) {
+                let px = EgPoint::new(min_x + x as i32, min_y + y as i32);
+                pixels.push(Pixel(px, px_color));
+            }
+        });
+    }
+    target.draw_iter(pixels)
+}
+
+/// Draws a single line of `text` onto an embedded-graphics `DrawTarget` applying glyph
+/// advances & kerning. `position` is the start of the text baseline.
+///
+/// See [`draw_glyph`](fn.draw_glyph.html) for `color` usage.
+pub fn draw_text<D, F, C>(
+    target: &mut D,
+    font: F,
+    scale: PxScale,
+    position: Point,
+    text: &str,
+    mut color: C,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    F: Font,
+    C: FnMut(f32) -> Option<D::Color>,
+{
+    let font = font.into_scaled(scale);
+    let mut caret = position;
+    let mut last = None;
+    for c in text.chars().filter(|c| !c.is_control()) {
+        let mut glyph = font.scaled_glyph(c);
+        caret.x += font.pair_advance(last, glyph.id);
+        glyph.position = caret;
+        last = Some(glyph.id);
+
+        if let Some(outlined) = font.outline_glyph(glyph) {
+            draw_glyph(target, &outlined, &mut color)?;
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
+use crate::PxScale;
 use core::fmt;
 
 /// Invalid font data error.
+///
+/// Loading functions return the more detailed [`FontError`](enum.FontError.html), which
+/// converts into this, so `?` still works in functions returning `InvalidFont`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct InvalidFont;
 
@@ -12,3 +16,72 @@ impl fmt::Display for InvalidFont {
 
 #[cfg(feature = "std")]
 impl std::error::Error for InvalidFont {}
+
+impl From<FontError> for InvalidFont {
+    #[inline]
+    fn from(_: FontError) -> Self {
+        InvalidFont
+    }
+}
+
+/// Why font data failed to load.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{FontError, FontRef, InvalidFont};
+///
+/// let err = FontRef::try_from_slice(b"not a font").unwrap_err();
+/// assert_eq!(err, FontError::UnknownMagic);
+///
+/// // converts into the detail-less error
+/// assert_eq!(InvalidFont::from(err), InvalidFont);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FontError {
+    /// Data doesn't start with a known font format magic number, e.g. it isn't a font.
+    UnknownMagic,
+    /// Font collection face index is out of range.
+    IndexOutOfBounds,
+    /// A required table is missing, e.g. `*b"head"`.
+    MissingTable([u8; 4]),
+    /// Data is truncated or otherwise malformed.
+    Malformed,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMagic => write!(f, "unknown font format magic number"),
+            Self::IndexOutOfBounds => write!(f, "font collection index out of bounds"),
+            Self::MissingTable(tag) => {
+                write!(f, "missing required ")?;
+                for &b in tag {
+                    write!(f, "{}", char::from(b))?;
+                }
+                write!(f, " table")
+            }
+            Self::Malformed => write!(f, "malformed font data"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FontError {}
+
+/// Zero, negative or non-finite pixel scale error, see
+/// [`PxScale::validate`](struct.PxScale.html#method.validate).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InvalidScale(pub PxScale);
+
+impl fmt::Display for InvalidScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid pixel scale {}x{}, must be finite & positive",
+            self.0.x, self.0.y
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidScale {}
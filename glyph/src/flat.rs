@@ -0,0 +1,164 @@
+use crate::{point, Outline, OutlineCurve, Point, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Curve command of a [`FlatOutline`](struct.FlatOutline.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FlatVerb {
+    /// Straight line using the next 2 points.
+    Line,
+    /// Quadratic Bézier curve using the next 3 points.
+    Quad,
+    /// Cubic Bézier curve using the next 4 points.
+    Cubic,
+}
+
+impl FlatVerb {
+    /// Number of points used by this verb.
+    #[inline]
+    pub fn point_count(self) -> usize {
+        match self {
+            Self::Line => 2,
+            Self::Quad => 3,
+            Self::Cubic => 4,
+        }
+    }
+}
+
+/// Unscaled & unpositioned glyph outline stored as a structure of arrays.
+///
+/// Point coordinates are held in flat contiguous `xs` & `ys` arrays, in the same
+/// order as an [`Outline`](struct.Outline.html)'s curve points, with a separate
+/// array of verbs describing how many points each curve uses.
+/// This can be more cache friendly than an `OutlineCurve` vector for passes over
+/// every point, like transforming, flattening or tessellation.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{FlatOutline, Font, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let outline = font.outline(font.glyph_id('s')).unwrap();
+///
+/// let mut flat = FlatOutline::from(&outline);
+/// assert_eq!(flat.verbs().len(), outline.curves.len());
+/// assert_eq!(flat.xs().len(), flat.ys().len());
+///
+/// // scale every x coordinate in a single pass
+/// flat.xs_mut().iter_mut().for_each(|x| *x *= 0.5);
+///
+/// // convert back to curves
+/// let narrow = ab_glyph::Outline::from(&flat);
+/// assert_eq!(narrow.curves.len(), outline.curves.len());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FlatOutline {
+    /// Unscaled bounding box.
+    pub bounds: Rect,
+    verbs: Vec<FlatVerb>,
+    xs: Vec<f32>,
+    ys: Vec<f32>,
+}
+
+impl FlatOutline {
+    /// Curve commands.
+    #[inline]
+    pub fn verbs(&self) -> &[FlatVerb] {
+        &self.verbs
+    }
+
+    /// Point x coordinates.
+    #[inline]
+    pub fn xs(&self) -> &[f32] {
+        &self.xs
+    }
+
+    /// Point y coordinates.
+    #[inline]
+    pub fn ys(&self) -> &[f32] {
+        &self.ys
+    }
+
+    /// Mutable point x coordinates.
+    #[inline]
+    pub fn xs_mut(&mut self) -> &mut [f32] {
+        &mut self.xs
+    }
+
+    /// Mutable point y coordinates.
+    #[inline]
+    pub fn ys_mut(&mut self) -> &mut [f32] {
+        &mut self.ys
+    }
+
+    /// Appends a curve.
+    pub fn push(&mut self, curve: &OutlineCurve) {
+        let mut add = |verb, points: &[Point]| {
+            self.verbs.push(verb);
+            self.xs.extend(points.iter().map(|p| p.x));
+            self.ys.extend(points.iter().map(|p| p.y));
+        };
+        match *curve {
+            OutlineCurve::Line(p0, p1) => add(FlatVerb::Line, &[p0, p1]),
+            OutlineCurve::Quad(p0, p1, p2) => add(FlatVerb::Quad, &[p0, p1, p2]),
+            OutlineCurve::Cubic(p0, p1, p2, p3) => add(FlatVerb::Cubic, &[p0, p1, p2, p3]),
+        }
+    }
+
+    /// Iterator of the curves as `OutlineCurve`s.
+    pub fn curves(&self) -> impl Iterator<Item = OutlineCurve> + '_ {
+        let mut idx = 0;
+        self.verbs.iter().map(move |verb| {
+            let p = |n: usize| point(self.xs[idx + n], self.ys[idx + n]);
+            let curve = match verb {
+                FlatVerb::Line => OutlineCurve::Line(p(0), p(1)),
+                FlatVerb::Quad => OutlineCurve::Quad(p(0), p(1), p(2)),
+                FlatVerb::Cubic => OutlineCurve::Cubic(p(0), p(1), p(2), p(3)),
+            };
+            idx += verb.point_count();
+            curve
+        })
+    }
+}
+
+impl From<&Outline> for FlatOutline {
+    fn from(outline: &Outline) -> Self {
+        let points: usize = outline
+            .curves
+            .iter()
+            .map(|curve| match curve {
+                OutlineCurve::Line(..) => 2,
+                OutlineCurve::Quad(..) => 3,
+                OutlineCurve::Cubic(..) => 4,
+            })
+            .sum();
+        let mut flat = Self {
+            bounds: outline.bounds,
+            verbs: Vec::with_capacity(outline.curves.len()),
+            xs: Vec::with_capacity(points),
+            ys: Vec::with_capacity(points),
+        };
+        for curve in &outline.curves {
+            flat.push(curve);
+        }
+        flat
+    }
+}
+
+impl From<Outline> for FlatOutline {
+    #[inline]
+    fn from(outline: Outline) -> Self {
+        Self::from(&outline)
+    }
+}
+
+impl From<&FlatOutline> for Outline {
+    fn from(flat: &FlatOutline) -> Self {
+        Self {
+            bounds: flat.bounds,
+            curves: flat.curves().collect(),
+        }
+    }
+}
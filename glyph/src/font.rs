@@ -1,4 +1,17 @@
-use crate::{point, Glyph, GlyphId, Outline, OutlinedGlyph, PxScale, PxScaleFont, Rect, ScaleFont, outlined::OutlineGroup};
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+#[cfg(feature = "bumpalo")]
+use crate::ArenaOutline;
+use crate::{
+    outlined::OutlineGroup, point, CodepointIdIter, ColorGlyphPaint, ColorOutline, ColorPalette,
+    CoverageSet, DecorationMetrics, DeviceVMetrics, FontStyle, FontSubset, Glyph, GlyphComplexity,
+    GlyphId, GlyphImage, GlyphImageFormat, GlyphRender, Hinting, NameId, OpticalBounds, Outline,
+    OutlineCurve, OutlineSink, OutlinedGlyph, PxScale, PxScaleFactor, PxScaleFont, Rect, ScaleFont,
+    Script, Transform,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+use core::ops::RangeInclusive;
 
 /// Functionality required from font data.
 ///
@@ -68,48 +81,1146 @@ pub trait Font {
     /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
     fn line_gap_unscaled(&self) -> f32;
 
+    /// Unscaled vertical layout ascent, the horizontal distance from a column's center
+    /// line to its right edge.
+    ///
+    /// Returns half the em by default, fonts without vertical metrics are centered on
+    /// the column's center line.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// // Exo2 has no vertical metrics, so columns are an em wide
+    /// assert_eq!(font.vertical_ascent_unscaled(), 500.0);
+    /// assert_eq!(font.vertical_descent_unscaled(), -500.0);
+    /// assert_eq!(font.vertical_line_gap_unscaled(), 0.0);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn vertical_ascent_unscaled(&self) -> f32 {
+        self.units_per_em().map_or(0.0, |em| em / 2.0)
+    }
+
+    /// Unscaled vertical layout descent, the horizontal distance from a column's center
+    /// line to its left edge. Usually negative.
+    ///
+    /// Returns minus half the em by default.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    #[inline]
+    fn vertical_descent_unscaled(&self) -> f32 {
+        -self.vertical_ascent_unscaled()
+    }
+
+    /// Unscaled vertical layout line gap, the horizontal space between columns.
+    ///
+    /// Returns `0.0` by default.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    #[inline]
+    fn vertical_line_gap_unscaled(&self) -> f32 {
+        0.0
+    }
+
+    /// Italic angle in counter-clockwise degrees from the vertical, `0.0` for upright fonts.
+    ///
+    /// Returns `0.0` by default.
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        0.0
+    }
+
+    /// Unscaled underline position & thickness, if the font defines them.
+    ///
+    /// Returns `None` by default. See also [`Decoration`](enum.Decoration.html).
+    #[inline]
+    fn underline_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        None
+    }
+
+    /// Unscaled strikeout position & thickness, if the font defines them.
+    ///
+    /// Returns `None` by default. See also [`Decoration`](enum.Decoration.html).
+    #[inline]
+    fn strikeout_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        None
+    }
+
+    /// Unscaled height of lowercase letters, e.g. to size a text cursor or to align mixed
+    /// font text.
+    ///
+    /// Returns the top of the `'x'` outline by default, or half the ascent if the font
+    /// has no `'x'`. TrueType/OpenType fonts read this from the OS/2 table when defined.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let x_height = font.x_height_unscaled();
+    /// let cap_height = font.cap_height_unscaled();
+    /// assert!(x_height > 0.0 && x_height < cap_height);
+    /// assert!(cap_height <= font.ascent_unscaled());
+    /// ```
+    #[inline]
+    fn x_height_unscaled(&self) -> f32 {
+        glyph_top_unscaled(self, 'x').unwrap_or_else(|| self.ascent_unscaled() / 2.0)
+    }
+
+    /// Unscaled height of capital letters.
+    ///
+    /// Returns the top of the `'H'` outline by default, or the ascent if the font has
+    /// no `'H'`.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    #[inline]
+    fn cap_height_unscaled(&self) -> f32 {
+        glyph_top_unscaled(self, 'H').unwrap_or_else(|| self.ascent_unscaled())
+    }
+
+    /// Returns `true` if the font is marked as monospaced, i.e. all glyphs share
+    /// the same horizontal advance.
+    ///
+    /// Returns `false` by default.
+    #[inline]
+    fn is_monospaced(&self) -> bool {
+        false
+    }
+
+    /// Weight class, from `100` thin to `900` black, `400` being regular & `700` bold.
+    ///
+    /// Returns `400` by default. TrueType/OpenType fonts read this from the OS/2 table.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// assert_eq!(font.weight(), 300);
+    /// assert_eq!(font.stretch(), 5);
+    /// assert_eq!(font.style(), FontStyle::Normal);
+    /// ```
+    #[inline]
+    fn weight(&self) -> u16 {
+        400
+    }
+
+    /// Width class, from `1` ultra-condensed to `9` ultra-expanded, `5` being normal.
+    ///
+    /// Returns `5` by default. TrueType/OpenType fonts read this from the OS/2 table.
+    #[inline]
+    fn stretch(&self) -> u16 {
+        5
+    }
+
+    /// Slope of the glyphs.
+    ///
+    /// Returns [`Italic`](enum.FontStyle.html#variant.Italic) by default if the
+    /// [`italic_angle`](#method.italic_angle) isn't zero, otherwise `Normal`.
+    /// TrueType/OpenType fonts read this from the OS/2 table.
+    ///
+    /// See [`FontQuery`](struct.FontQuery.html) to match fonts by family, weight, stretch
+    /// & style.
+    #[inline]
+    fn style(&self) -> FontStyle {
+        if self.italic_angle() == 0.0 {
+            FontStyle::Normal
+        } else {
+            FontStyle::Italic
+        }
+    }
+
+    /// Returns `true` if the font is flagged as bold, e.g. the bold face of a family with
+    /// regular, italic, bold & bold italic.
+    ///
+    /// Returns `true` by default if the [`weight`](#method.weight) is at least `700`.
+    /// TrueType/OpenType fonts read this from the OS/2 table.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// assert_eq!(font.weight(), 400);
+    /// assert_eq!(font.style(), FontStyle::Italic);
+    /// assert!(!font.is_bold());
+    ///
+    /// let bold = SyntheticFont::new(&font, SyntheticStyle::bold(&font));
+    /// assert!(bold.is_bold());
+    /// ```
+    #[inline]
+    fn is_bold(&self) -> bool {
+        self.weight() >= 700
+    }
+
+    /// Returns a unicode name table string, e.g. the family or PostScript name, preferring
+    /// US English.
+    ///
+    /// Returns `None` if the font has no such unicode name.
+    /// See [`font_name_for_language`](#method.font_name_for_language).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, NameId};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// assert_eq!(font.font_name(NameId::FAMILY).as_deref(), Some("Exo 2 Light"));
+    /// assert_eq!(font.font_name(NameId::SUBFAMILY).as_deref(), Some("Regular"));
+    /// assert_eq!(font.font_name(NameId::TYPOGRAPHIC_FAMILY).as_deref(), Some("Exo 2"));
+    /// assert_eq!(font.font_name(NameId::POST_SCRIPT_NAME).as_deref(), Some("Exo2-Light"));
+    ///
+    /// // names in other languages fall back to English
+    /// let japanese = font.font_name_for_language(NameId::FAMILY, 0x0411);
+    /// assert_eq!(japanese, font.font_name(NameId::FAMILY));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn font_name(&self, id: NameId) -> Option<String> {
+        self.font_name_for_language(id, 0x0409)
+    }
+
+    /// Returns a unicode name table string in a language, given as a Windows language id
+    /// e.g. `0x0409` US English or `0x0411` Japanese. Falls back to US English, then any
+    /// unicode name.
+    ///
+    /// Returns `None` by default.
+    #[inline]
+    fn font_name_for_language(&self, _id: NameId, _language_id: u16) -> Option<String> {
+        None
+    }
+
     /// Lookup a `GlyphId` matching a given `char`.
     ///
-    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
-    fn glyph_id(&self, c: char) -> GlyphId;
-
-    /// Unscaled horizontal advance for a given glyph id.
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    fn glyph_id(&self, c: char) -> GlyphId;
+
+    /// Get the glyph id mapped to a char, `None` if the font doesn't map the char.
+    ///
+    /// Unlike [`glyph_id`](#tymethod.glyph_id) this distinguishes unmapped chars from chars
+    /// mapped to the .notdef glyph, e.g. to select a fallback font.
+    ///
+    /// Default implementation treats glyph `0`, .notdef, as unmapped.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// assert_eq!(font.glyph_id_checked('a'), Some(font.glyph_id('a')));
+    /// assert_eq!(font.glyph_id_checked('\u{10FFFD}'), None);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        Some(self.glyph_id(c)).filter(|id| id.0 != 0)
+    }
+
+    /// Get the glyph id of a unicode variation sequence, a char followed by a variation
+    /// selector, e.g. U+2764 U+FE0F selecting the emoji presentation of a heart.
+    ///
+    /// Returns `None` if the font doesn't support the sequence, in which case the default
+    /// glyph, [`glyph_id`](#tymethod.glyph_id), should be used. TrueType/OpenType fonts
+    /// read these from cmap format 14 subtables.
+    ///
+    /// [`Layout`](struct.Layout.html) applies variation selectors following a char.
+    ///
+    /// Default implementation returns `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// // no variation sequences in this font
+    /// let text_heart = font.glyph_id_variation('\u{2764}', '\u{FE0E}');
+    /// let heart = text_heart.unwrap_or_else(|| font.glyph_id('\u{2764}'));
+    /// assert_eq!(heart, font.glyph_id('\u{2764}'));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn glyph_id_variation(&self, _c: char, _selector: char) -> Option<GlyphId> {
+        None
+    }
+
+    /// Returns an iterator over the glyph id & char of each char mapped by the font, e.g.
+    /// to build font fallback chains or glyph atlases ahead of time.
+    ///
+    /// Default implementation returns an empty iterator.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let mapped: Vec<_> = font.codepoint_ids().collect();
+    /// assert!(mapped.contains(&(font.glyph_id('a'), 'a')));
+    /// assert!(mapped.iter().all(|&(id, c)| font.glyph_id(c) == id));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        CodepointIdIter::new(core::iter::empty())
+    }
+
+    /// Returns the set of chars mapped by the font, stored compactly as ranges.
+    ///
+    /// Collects the [`codepoint_ids`](#method.codepoint_ids) chars by default. See
+    /// [`CoverageSet`](struct.CoverageSet.html).
+    #[inline]
+    fn coverage_set(&self) -> CoverageSet {
+        self.codepoint_ids().map(|(_, c)| c).collect()
+    }
+
+    /// Lookup the `GlyphId` of each `char` in `text`, appending them to `ids`.
+    ///
+    /// Equivalent to calling [`glyph_id`](#method.glyph_id) for each char but
+    /// implementations may amortize char map access over the whole string.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let mut ids = Vec::new();
+    /// font.glyph_ids_for_str("sense", &mut ids);
+    /// assert_eq!(ids.len(), 5);
+    /// assert_eq!(ids[0], font.glyph_id('s'));
+    /// assert_eq!(ids[1], font.glyph_id('e'));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn glyph_ids_for_str(&self, text: &str, ids: &mut Vec<GlyphId>) {
+        ids.extend(text.chars().map(|c| self.glyph_id(c)));
+    }
+
+    /// Applies the glyph substitutions of `features`, plus the required feature of the
+    /// script's language system, to `glyphs` in place. Substitutions may change the
+    /// number of glyphs, e.g. ligatures.
+    ///
+    /// [`Script::REQUIRED_FEATURES`](struct.Script.html#associatedconstant.REQUIRED_FEATURES)
+    /// are the features that should be applied to all text.
+    ///
+    /// Default implementation does nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, Script};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let mut glyphs = vec![font.glyph_id('i')];
+    /// font.substitute(Script::new(b"latn"), &Script::REQUIRED_FEATURES, &mut glyphs);
+    /// assert_eq!(glyphs, [font.glyph_id('i')]);
+    ///
+    /// // turkish localized dotted i
+    /// let turkish = Script::new(b"latn").with_language(b"TRK ");
+    /// font.substitute(turkish, &Script::REQUIRED_FEATURES, &mut glyphs);
+    /// assert_ne!(glyphs, [font.glyph_id('i')]);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn substitute(&self, _script: Script, _features: &[[u8; 4]], _glyphs: &mut Vec<GlyphId>) {}
+
+    /// Returns the sorted OpenType feature tags the font supports for a script & language
+    /// system, e.g. `*b"tnum"`, usable with [`substitute`](#method.substitute).
+    ///
+    /// Default implementation returns no features.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, Script};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let features = font.features(Script::new(b"latn"));
+    /// assert!(features.contains(b"liga"));
+    /// assert!(features.contains(b"tnum"));
+    /// assert!(!features.contains(b"smcp"));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn features(&self, _script: Script) -> Vec<[u8; 4]> {
+        Vec::new()
+    }
+
+    /// Returns each ligature glyph of the font's glyph substitutions with the component
+    /// glyphs it replaces, in any script or feature, e.g. to recover the text of ligated
+    /// glyphs. See [`GlyphTextMap`](struct.GlyphTextMap.html).
+    ///
+    /// Default implementation returns no ligatures.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let (f, i) = (font.glyph_id('f'), font.glyph_id('i'));
+    /// let ligatures = font.ligatures();
+    /// assert!(ligatures.iter().any(|(_, components)| components == &[f, f, i]));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn ligatures(&self) -> Vec<(GlyphId, Vec<GlyphId>)> {
+        Vec::new()
+    }
+
+    /// Unscaled horizontal advance for a given glyph id.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    ///
+    /// Doesn't panic for invalid glyphs or malformed fonts, falling back to a default
+    /// advance. See [`try_h_advance_unscaled`](#method.try_h_advance_unscaled).
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32;
+
+    /// Unscaled horizontal advance for a given glyph id, `None` if the font has no advance
+    /// for the glyph, e.g. for an out of range id or malformed font data.
+    ///
+    /// Default implementation returns [`h_advance_unscaled`](#method.h_advance_unscaled)
+    /// for ids less than the [`glyph_count`](#tymethod.glyph_count).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, GlyphId};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let a = font.glyph_id('a');
+    /// assert_eq!(font.try_h_advance_unscaled(a), Some(font.h_advance_unscaled(a)));
+    ///
+    /// let invalid = GlyphId(u16::MAX);
+    /// assert_eq!(font.try_h_advance_unscaled(invalid), None);
+    /// // falls back to the .notdef advance
+    /// assert_eq!(font.h_advance_unscaled(invalid), font.h_advance_unscaled(GlyphId(0)));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn try_h_advance_unscaled(&self, id: GlyphId) -> Option<f32> {
+        if usize::from(id.0) < self.glyph_count() {
+            Some(self.h_advance_unscaled(id))
+        } else {
+            None
+        }
+    }
+
+    /// Writes the unscaled horizontal advance of each glyph of `ids` into the matching
+    /// element of `out`, as [`h_advance_unscaled`](#method.h_advance_unscaled) would return.
+    /// Only the first `ids.len().min(out.len())` advances are written.
+    ///
+    /// Implementations may read the advances of all glyphs in a single pass over the
+    /// font's metrics, e.g. for measuring long strings.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let mut ids = Vec::new();
+    /// font.glyph_ids_for_str("Hello", &mut ids);
+    /// let mut advances = vec![0.0; ids.len()];
+    /// font.h_advances_unscaled(&ids, &mut advances);
+    ///
+    /// for (id, advance) in ids.iter().zip(&advances) {
+    ///     assert_eq!(*advance, font.h_advance_unscaled(*id));
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn h_advances_unscaled(&self, ids: &[GlyphId], out: &mut [f32]) {
+        for (id, advance) in ids.iter().zip(out) {
+            *advance = self.h_advance_unscaled(*id);
+        }
+    }
+
+    /// Unscaled horizontal side bearing for a given glyph id.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    ///
+    /// Doesn't panic for invalid glyphs or malformed fonts, falling back to `0.0`. See
+    /// [`try_h_side_bearing_unscaled`](#method.try_h_side_bearing_unscaled).
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32;
+
+    /// Unscaled horizontal side bearing for a given glyph id, `None` if the font has no
+    /// side bearing for the glyph, e.g. for an out of range id or malformed font data.
+    ///
+    /// Default implementation returns
+    /// [`h_side_bearing_unscaled`](#tymethod.h_side_bearing_unscaled) for ids less than the
+    /// [`glyph_count`](#tymethod.glyph_count).
+    #[inline]
+    fn try_h_side_bearing_unscaled(&self, id: GlyphId) -> Option<f32> {
+        if usize::from(id.0) < self.glyph_count() {
+            Some(self.h_side_bearing_unscaled(id))
+        } else {
+            None
+        }
+    }
+
+    /// Unscaled vertical advance for a given glyph id.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32;
+
+    /// Unscaled vertical side bearing for a given glyph id.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32;
+
+    /// Unscaled vertical distance from the top of a glyph's vertical advance to its
+    /// baseline origin, for vertical layout.
+    ///
+    /// By default the top side bearing plus the top of the glyph's outline, or the ascent
+    /// for glyphs without an outline.
+    #[inline]
+    fn v_origin_unscaled(&self, id: GlyphId) -> f32 {
+        match self.outline_bounds_unscaled(id) {
+            // unscaled outline bounds `min.y` is the glyph's top
+            Some(bounds) => self.v_side_bearing_unscaled(id) + bounds.min.y,
+            None => self.ascent_unscaled(),
+        }
+    }
+
+    /// Unscaled left side bearing, the horizontal distance from the glyph origin to the
+    /// left edge of the outline bounds.
+    ///
+    /// Unlike [`h_side_bearing_unscaled`](#tymethod.h_side_bearing_unscaled), read from the
+    /// font's metrics, this is derived from the outline bounds so is always consistent with
+    /// [`rsb_unscaled`](#method.rsb_unscaled):
+    /// `lsb + (bounds.max.x - bounds.min.x) + rsb == h_advance`.
+    /// Glyphs without an outline, e.g. space, use the metrics side bearing.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let id = font.glyph_id('f');
+    /// let bounds = font.outline(id).unwrap().bounds;
+    ///
+    /// let ink_width = bounds.max.x - bounds.min.x;
+    /// assert_eq!(
+    ///     font.lsb_unscaled(id) + ink_width + font.rsb_unscaled(id),
+    ///     font.h_advance_unscaled(id)
+    /// );
+    ///
+    /// // italic 'f' extends past its advance
+    /// assert!(font.rsb_unscaled(id) < 0.0);
+    /// ```
+    #[inline]
+    fn lsb_unscaled(&self, id: GlyphId) -> f32 {
+        match self.outline(id) {
+            Some(outline) => outline.bounds.min.x,
+            None => self.h_side_bearing_unscaled(id),
+        }
+    }
+
+    /// Unscaled right side bearing, the horizontal distance from the right edge of the
+    /// outline bounds to the advance. Negative when the outline extends past the advance.
+    ///
+    /// See [`lsb_unscaled`](#method.lsb_unscaled).
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    #[inline]
+    fn rsb_unscaled(&self, id: GlyphId) -> f32 {
+        let right = match self.outline(id) {
+            Some(outline) => outline.bounds.max.x,
+            None => self.h_side_bearing_unscaled(id),
+        };
+        self.h_advance_unscaled(id) - right
+    }
+
+    /// Returns additional unscaled kerning to apply for a particular pair of glyph ids.
+    ///
+    /// TrueType/OpenType fonts read the kern table or, if it has no horizontal subtables,
+    /// GPOS pair adjustment lookups of "kern" features.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// // Exo2 kerning is only in GPOS
+    /// let (a, v) = (font.glyph_id('A'), font.glyph_id('V'));
+    /// assert_eq!(font.kern_unscaled(a, v), -34.0);
+    /// ```
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32;
+
+    /// Returns additional unscaled kerning to apply along the vertical advance, for
+    /// vertical layout, for a particular pair of glyph ids.
+    ///
+    /// Default implementation returns `0.0`.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    #[inline]
+    fn v_kern_unscaled(&self, _first: GlyphId, _second: GlyphId) -> f32 {
+        0.0
+    }
+
+    /// Returns all unscaled kerning pairs `(first, second, kerning)` with non-zero kerning,
+    /// sorted by glyph ids.
+    ///
+    /// Pairs are read from the kern table or, if it has none, GPOS pair adjustment lookups
+    /// of "kern" features. Default implementation returns an empty `Vec`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let pairs = font.kern_pairs();
+    /// assert!(!pairs.is_empty());
+    ///
+    /// for (first, second, kerning) in pairs {
+    ///     assert_eq!(font.kern_unscaled(first, second), kerning);
+    /// }
+    /// ```
+    #[inline]
+    fn kern_pairs(&self) -> Vec<(GlyphId, GlyphId, f32)> {
+        Vec::new()
+    }
+
+    /// Returns the unscaled optical bounds of a glyph, if the font has them. TrueType/OpenType
+    /// fonts read these from the AAT `opbd` table.
+    ///
+    /// Default implementation returns `None`.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    #[inline]
+    fn optical_bounds_unscaled(&self, _id: GlyphId) -> Option<OpticalBounds> {
+        None
+    }
+
+    /// Returns char ranges that are each mapped onto a single glyph, like the cmap
+    /// format 13 "many-to-one" subtables of "last resort" fonts.
+    ///
+    /// [`glyph_id`](#tymethod.glyph_id) maps chars in these ranges to placeholder glyphs,
+    /// so fallback logic may prefer to treat them as not covered by this font.
+    ///
+    /// Default implementation returns no ranges.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let is_placeholder = |c: char| {
+    ///     font.many_to_one_ranges()
+    ///         .iter()
+    ///         .any(|(range, _)| range.contains(&c))
+    /// };
+    /// assert!(!is_placeholder('a'));
+    /// # Ok(()) }
+    /// ```
+    fn many_to_one_ranges(&self) -> Vec<(RangeInclusive<char>, GlyphId)> {
+        Vec::new()
+    }
+
+    /// Returns the pre-computed whole pixel horizontal advance of a glyph at a specific
+    /// pixels-per-em size, if the font has one. TrueType/OpenType fonts read these from
+    /// the `hdmx` table.
+    ///
+    /// Default implementation returns `None`.
+    ///
+    /// Used by [`ScaleFont::h_advance`](trait.ScaleFont.html#method.h_advance) at whole
+    /// number pixels-per-em.
+    #[inline]
+    fn device_h_advance(&self, _id: GlyphId, _ppem: u16) -> Option<f32> {
+        None
+    }
+
+    /// Returns pixel vertical metrics at a specific pixels-per-em size, if the font has them.
+    ///
+    /// Default implementation returns `None`.
+    ///
+    /// Used by [`ScaleFont::ascent`](trait.ScaleFont.html#method.ascent), `descent` &
+    /// `height` at whole number pixels-per-em.
+    #[inline]
+    fn device_v_metrics(&self, _ppem: u16) -> Option<DeviceVMetrics> {
+        None
+    }
+
+    fn has_color(&self, id: GlyphId) -> bool;
+
+    /// Returns the unscaled outline, color & palette index of each layer of a color glyph,
+    /// bottom layer first, using the default palette `0`. Returns `None` for non-color glyphs.
+    ///
+    /// Layers without an outline, e.g. space-like glyphs, are skipped. Each layer outline
+    /// has its own bounds, see [`ColorOutline::bounds`](struct.ColorOutline.html#method.bounds)
+    /// for the bounds of the whole glyph.
+    fn color_outlines(&self, id: GlyphId) -> Option<ColorOutline>;
+
+    /// Returns the color layers of a glyph, like [`color_outlines`](#tymethod.color_outlines),
+    /// using the colors of the given palette rather than the default palette `0`.
+    ///
+    /// Returns `None` for non-color glyphs. Layers with colors missing from the palette
+    /// are transparent, layers using the text foreground color are opaque black, see
+    /// [`ColorLayer::rgba`](struct.ColorLayer.html#structfield.rgba).
+    ///
+    /// Default implementation ignores the palette, returning
+    /// [`color_outlines`](#tymethod.color_outlines).
+    #[inline]
+    fn color_outlines_with_palette(&self, id: GlyphId, _palette: usize) -> Option<ColorOutline> {
+        self.color_outlines(id)
+    }
+
+    /// Returns the unscaled bounds of a glyph's outline, the union of all layers for color
+    /// glyphs, e.g. to reserve space for emoji in layouts working in font units.
+    ///
+    /// Returns `None` if the glyph has no outline.
+    ///
+    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let a = font.glyph_id('a');
+    ///
+    /// // non-color glyphs have the bounds of their outline
+    /// assert_eq!(font.outline_bounds_unscaled(a), font.outline(a).map(|o| o.bounds));
+    /// assert_eq!(font.outline_bounds_unscaled(font.glyph_id(' ')), None);
+    /// # Ok(()) }
+    /// ```
+    fn outline_bounds_unscaled(&self, id: GlyphId) -> Option<Rect> {
+        match self.color_outlines(id) {
+            Some(outline) => outline.bounds(),
+            None => self.outline(id).map(|outline| outline.bounds),
+        }
+    }
+
+    /// Returns the contour & point counts of a glyph's outline, an estimate of its cost to
+    /// outline & draw, e.g. to choose between vector & raster rendering per glyph.
+    ///
+    /// Returns `None` if the glyph has no outline. TrueType fonts read these from glyf
+    /// headers without outlining, otherwise the outline curves are counted.
+    ///
+    /// See [`GlyphComplexity`](struct.GlyphComplexity.html).
+    #[inline]
+    fn glyph_complexity(&self, id: GlyphId) -> Option<GlyphComplexity> {
+        self.outline(id)
+            .map(|outline| GlyphComplexity::of_curves(&outline.curves))
+    }
+
+    /// Returns the number of color palettes, `0` for fonts without a CPAL table.
+    ///
+    /// Default implementation returns `0`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// // select the first dark background palette, if any
+    /// let dark = (0..font.color_palettes())
+    ///     .find(|&p| font.color_palette(p).map_or(false, |info| info.dark_background))
+    ///     .unwrap_or(0);
+    /// assert_eq!(dark, 0);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn color_palettes(&self) -> usize {
+        0
+    }
+
+    /// Returns the name & intended background of a color palette, or `None` if the palette
+    /// doesn't exist.
+    ///
+    /// Default implementation returns `None`.
+    #[inline]
+    fn color_palette(&self, _palette: usize) -> Option<ColorPalette> {
+        None
+    }
+
+    /// Returns the color paint graph of a glyph, supporting gradients, transforms &
+    /// compositing of COLR version 1 fonts. Version 0 color layers are returned as
+    /// solid filled glyphs.
+    ///
+    /// Unlike [`color_outlines`](#tymethod.color_outlines) this does not flatten paints
+    /// into solid layers, renderers walk the graph themselves.
+    ///
+    /// Default implementation returns `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// // not a color font
+    /// assert_eq!(font.color_paint(font.glyph_id('a')), None);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn color_paint(&self, _id: GlyphId) -> Option<ColorGlyphPaint> {
+        None
+    }
+
+    /// Returns the embedded raster image of a glyph, e.g. a color emoji png, from the
+    /// bitmap strike best matching `pixels_per_em`. That is the smallest strike at least
+    /// `pixels_per_em`, otherwise the largest.
+    ///
+    /// The image's [`pixels_per_em`](struct.GlyphImage.html#structfield.pixels_per_em) is
+    /// that of the strike, use [`GlyphImage::px_bounds`](struct.GlyphImage.html#method.px_bounds)
+    /// to scale it to the drawn size. Fonts may have both images & outlines for a glyph.
+    ///
+    /// Default implementation returns `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// // no bitmap strikes in this font
+    /// assert_eq!(font.glyph_raster_image(font.glyph_id('a'), 24), None);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn glyph_raster_image(&self, _id: GlyphId, _pixels_per_em: u16) -> Option<GlyphImage<'_>> {
+        None
+    }
+
+    fn relative_scale(&self, id: GlyphId) -> f32;
+
+    /// Compute unscaled glyph outline curves & bounding box.
+    fn outline(&self, id: GlyphId) -> Option<Outline>;
+
+    /// Compute glyph outline curves & bounding box in pixels at `ppem` pixels per em, grid
+    /// fitted for sharper small text.
+    ///
+    /// Uses simple vertical autohinting, font hinting instructions are not run. Horizontal
+    /// edges, like the baseline, x-height & stem tops, are snapped to whole pixels and
+    /// other points interpolated between them. Horizontal positions are only scaled.
+    ///
+    /// Useful for consumers that tessellate rather than rasterize. Like unscaled outlines
+    /// y points up, so may be drawn as an [`OutlinedGlyph`](struct.OutlinedGlyph.html) with
+    /// a scale factor of `1.0`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let hinted = font.outline_hinted(font.glyph_id('x'), 11).unwrap();
+    ///
+    /// // flat baseline & x-height aligned to the pixel grid
+    /// assert_eq!(hinted.bounds.min.y.fract(), 0.0);
+    /// assert_eq!(hinted.bounds.max.y, 0.0);
+    ///
+    /// let scale_factor = PxScaleFactor {
+    ///     horizontal: 1.0,
+    ///     vertical: 1.0,
+    /// };
+    /// let glyph = font.glyph_id('x').with_scale_and_position(11.0, point(0.0, 10.0));
+    /// let outlined = OutlinedGlyph::new(glyph, OutlineGroup::from_outline(hinted), scale_factor);
+    /// assert_eq!(outlined.px_bounds().max.y, 10.0);
+    /// ```
+    fn outline_hinted(&self, id: GlyphId, ppem: u16) -> Option<Outline> {
+        let outline = self.outline(id)?;
+        let units_per_em = self
+            .units_per_em()
+            .unwrap_or_else(|| self.height_unscaled());
+        let scale = f32::from(ppem) / units_per_em;
+        let scale = PxScaleFactor {
+            horizontal: scale,
+            vertical: scale,
+        };
+        Some(crate::hint::hint(&outline, scale))
+    }
+
+    /// Unscaled glyph bounding box, like the
+    /// [`Outline::bounds`](struct.Outline.html#structfield.bounds) of the glyph but without
+    /// building its curves, e.g. for atlas size estimation or culling.
+    /// Returns `None` if the glyph has no outline.
+    ///
+    /// The default implementation delegates to [`outline`](#method.outline).
+    /// TrueType/OpenType fonts read the `glyf` bounding box, or compute CFF bounds without
+    /// allocating curves.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let a = font.glyph_id('a');
+    /// assert_eq!(font.glyph_bounds_unscaled(a), font.outline(a).map(|o| o.bounds));
+    /// assert_eq!(font.glyph_bounds_unscaled(font.glyph_id(' ')), None);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn glyph_bounds_unscaled(&self, id: GlyphId) -> Option<Rect> {
+        self.outline(id).map(|outline| outline.bounds)
+    }
+
+    /// Compute unscaled glyph outline curves into `curves`, returning the bounding box.
+    ///
+    /// `curves` is cleared first & its allocation reused, so recycling the same `Vec`, or the
+    /// `curves` of a previous [`Outline`](struct.Outline.html), avoids allocating per glyph.
+    /// Returns `None`, leaving `curves` empty, if the glyph has no outline.
+    ///
+    /// The default implementation delegates to [`outline`](#method.outline).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let mut curves = Vec::new();
+    /// for c in "Hello world".chars() {
+    ///     if let Some(bounds) = font.outline_into(font.glyph_id(c), &mut curves) {
+    ///         assert!(!curves.is_empty());
+    ///         assert!(bounds.width() > 0.0);
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn outline_into(&self, id: GlyphId, curves: &mut Vec<OutlineCurve>) -> Option<Rect> {
+        curves.clear();
+        let outline = self.outline(id)?;
+        *curves = outline.curves;
+        Some(outline.bounds)
+    }
+
+    /// Compute unscaled glyph outline curves allocated in `arena`, requires the `bumpalo` feature.
+    ///
+    /// Batch pipelines can free all outlines at once by resetting the arena instead of
+    /// dropping each `Vec`.
+    ///
+    /// The default implementation copies the curves of [`outline`](#method.outline).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    /// # extern crate bumpalo2 as bumpalo;
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let mut arena = bumpalo::Bump::new();
+    ///
+    /// for _ in 0..3 {
+    ///     let outlines: Vec<_> = "Hello"
+    ///         .chars()
+    ///         .filter_map(|c| font.outline_in(font.glyph_id(c), &arena))
+    ///         .collect();
+    ///     assert_eq!(outlines.len(), 5);
+    ///     // ...
+    ///
+    ///     // free all outline curves
+    ///     drop(outlines);
+    ///     arena.reset();
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "bumpalo")]
+    #[inline]
+    fn outline_in<'bump>(
+        &self,
+        id: GlyphId,
+        arena: &'bump bumpalo2::Bump,
+    ) -> Option<ArenaOutline<'bump>> {
+        let outline = self.outline(id)?;
+        Some(ArenaOutline {
+            bounds: outline.bounds,
+            curves: arena.alloc_slice_clone(&outline.curves),
+        })
+    }
+
+    /// Streams unscaled glyph outline path commands to `sink`, returning the bounding box.
+    ///
+    /// Fonts implemented by this crate read the outline straight from the font data into
+    /// the sink without allocating, useful for embedded & high frequency use. Contours are
+    /// the same as [`Outline::for_each_segment`](struct.Outline.html#method.for_each_segment)
+    /// of the [`outline`](#method.outline), which the default implementation visits, though
+    /// lines back to the start of a contour may be left implied by `close`.
+    ///
+    /// Returns `None` if the glyph has no outline, the sink may have been sent some
+    /// segments of a malformed glyph.
+    ///
+    /// See [`draw_glyph`](#method.draw_glyph) to rasterize streamed outlines.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, OutlineSink, Point};
+    ///
+    /// /// Counts contours.
+    /// #[derive(Default)]
+    /// struct Contours(usize);
+    ///
+    /// impl OutlineSink for Contours {
+    ///     fn move_to(&mut self, _: Point) {
+    ///         self.0 += 1;
+    ///     }
+    ///     fn line_to(&mut self, _: Point) {}
+    ///     fn quad_to(&mut self, _: Point, _: Point) {}
+    ///     fn curve_to(&mut self, _: Point, _: Point, _: Point) {}
+    ///     fn close(&mut self) {}
+    /// }
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let o = font.glyph_id('o');
+    ///
+    /// let mut contours = Contours::default();
+    /// let bounds = font.outline_with(o, &mut contours).unwrap();
+    /// assert_eq!(bounds, font.outline(o).unwrap().bounds);
+    /// assert_eq!(contours.0, 2);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn outline_with(&self, id: GlyphId, sink: &mut dyn OutlineSink) -> Option<Rect> {
+        let outline = self.outline(id)?;
+        outline.for_each_segment(sink);
+        Some(outline.bounds)
+    }
+
+    /// The number of glyphs present in this font. Glyph identifiers for this
+    /// font will always be in the range `0..self.glyph_count()`
+    fn glyph_count(&self) -> usize;
+
+    /// Returns the PostScript name of a glyph, from the post table or CFF charset, e.g.
+    /// for PDF generation & font debugging.
+    ///
+    /// Returns `None` by default & for glyphs without names.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// assert_eq!(font.glyph_name(font.glyph_id('s')), Some("s"));
+    /// assert_eq!(font.glyph_id_by_name("s"), Some(font.glyph_id('s')));
+    ///
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?;
+    /// assert_eq!(font.glyph_name(font.glyph_id('&')), Some("ampersand"));
+    /// assert_eq!(font.glyph_id_by_name("not a glyph"), None);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn glyph_name(&self, id: GlyphId) -> Option<&str> {
+        let _ = id;
+        None
+    }
+
+    /// Returns the glyph with a PostScript name, the inverse of
+    /// [`glyph_name`](#method.glyph_name). Searches every glyph, so cache results for
+    /// repeated lookups.
+    fn glyph_id_by_name(&self, name: &str) -> Option<GlyphId> {
+        (0..self.glyph_count())
+            .map(|id| GlyphId(id as u16))
+            .find(|&id| self.glyph_name(id) == Some(name))
+    }
+
+    /// Writes a new font containing only the given glyphs, for embedding in documents
+    /// or serving smaller web fonts.
+    ///
+    /// `.notdef` & the components of composite glyphs are always included. Glyphs are
+    /// renumbered, see [`FontSubset::new_id`](struct.FontSubset.html#method.new_id).
+    /// The glyf, loca, hmtx & cmap tables are rebuilt, layout & variation tables are dropped.
+    ///
+    /// Returns `None` by default & for fonts without TrueType (glyf) outlines.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.ttf"))?;
+    /// let glyphs: Vec<_> = "Hello".chars().map(|c| font.glyph_id(c)).collect();
+    ///
+    /// let subset = font.subset(&glyphs).expect("subset");
+    /// assert_eq!(subset.glyphs().len(), 5); // .notdef, H, e, l, o
     ///
-    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
-    fn h_advance_unscaled(&self, id: GlyphId) -> f32;
+    /// let subset_font = FontRef::try_from_slice(subset.data())?;
+    /// assert_eq!(subset_font.glyph_count(), 5);
+    /// assert_eq!(Some(subset_font.glyph_id('e')), subset.new_id(font.glyph_id('e')));
+    /// assert_eq!(
+    ///     subset_font.h_advance_unscaled(subset_font.glyph_id('H')),
+    ///     font.h_advance_unscaled(font.glyph_id('H')),
+    /// );
+    /// assert!(subset_font.outline(subset_font.glyph_id('o')).is_some());
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    fn subset(&self, glyphs: &[GlyphId]) -> Option<FontSubset> {
+        let _ = glyphs;
+        None
+    }
 
-    /// Unscaled horizontal side bearing for a given glyph id.
+    /// Returns a stable identifier of this font, used to namespace caches holding glyphs
+    /// of multiple fonts, e.g. [`GlyphCache`](struct.GlyphCache.html).
     ///
-    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
-    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32;
-
-    /// Unscaled vertical advance for a given glyph id.
+    /// Like [`Outline::content_hash`](struct.Outline.html#method.content_hash) the value
+    /// is the same across runs. Different fonts are very likely, though not guaranteed, to
+    /// have different fingerprints.
     ///
-    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
-    fn v_advance_unscaled(&self, id: GlyphId) -> f32;
-
-    /// Unscaled vertical side bearing for a given glyph id.
+    /// The default implementation hashes the metrics, glyph count & first few glyph outlines.
     ///
-    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
-    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32;
-
-    /// Returns additional unscaled kerning to apply for a particular pair of glyph ids.
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, FontVec};
     ///
-    /// Scaling can be done with [as_scaled](trait.Font.html#method.as_scaled).
-    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32;
-
-    fn has_color(&self, id: GlyphId) -> bool;
-
-    fn color_outlines(&self, id: GlyphId) -> Option<Vec<(Outline,u32)>>;
-
-    fn relative_scale(&self, id: GlyphId) -> f32;
-
-    /// Compute unscaled glyph outline curves & bounding box.
-    fn outline(&self, id: GlyphId) -> Option<Outline>;
-
-    /// The number of glyphs present in this font. Glyph identifiers for this
-    /// font will always be in the range `0..self.glyph_count()`
-    fn glyph_count(&self) -> usize;
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let data = include_bytes!("../../dev/fonts/Exo2-Light.otf");
+    /// let font = FontRef::try_from_slice(data)?;
+    /// let font_vec = FontVec::try_from_vec(data.to_vec())?;
+    /// assert_eq!(font.fingerprint(), font_vec.fingerprint());
+    ///
+    /// let other = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.ttf"))?;
+    /// assert_ne!(font.fingerprint(), other.fingerprint());
+    /// # Ok(()) }
+    /// ```
+    fn fingerprint(&self) -> u64 {
+        let mut hash = crate::outlined::Fnv1a::default();
+        for v in &[
+            self.units_per_em().unwrap_or(0.0),
+            self.ascent_unscaled(),
+            self.descent_unscaled(),
+            self.line_gap_unscaled(),
+        ] {
+            hash.bytes(&v.to_bits().to_le_bytes());
+        }
+        hash.bytes(&(self.glyph_count() as u64).to_le_bytes());
+        for id in 0..self.glyph_count().min(8) {
+            let id = GlyphId(id as _);
+            hash.bytes(&self.h_advance_unscaled(id).to_bits().to_le_bytes());
+            let outline = self.outline(id).map_or(0, |o| o.content_hash());
+            hash.bytes(&outline.to_le_bytes());
+        }
+        hash.0
+    }
 
     /// Returns the layout bounds of this glyph. These are different to the outline `px_bounds()`.
     ///
@@ -134,16 +1245,213 @@ pub trait Font {
     where
         Self: Sized,
     {
+        #[cfg(feature = "counters")]
+        crate::counters::add_glyph_outlined();
+
         let scale_factor = self.as_scaled(glyph.scale).scale_factor();
 
-        if let Some(outlines) = self.color_outlines(glyph.id) {
-            return Some(OutlinedGlyph::new(glyph, OutlineGroup::new(outlines), scale_factor));
+        match self.color_outlines(glyph.id) {
+            Some(outlines) if !outlines.is_empty() => {
+                return Some(OutlinedGlyph::new(
+                    glyph,
+                    OutlineGroup::new(outlines),
+                    scale_factor,
+                ));
+            }
+            _ => {}
         }
 
         let outline = self.outline(glyph.id)?;
         Some(OutlinedGlyph::new(glyph, OutlineGroup::from_outline(outline), scale_factor))
     }
 
+    /// Compute glyph outline ready for drawing like [`outline_glyph`](#method.outline_glyph),
+    /// vertically autohinted when [`Hinting::applies`](struct.Hinting.html#method.applies) to
+    /// the glyph's scale. Color glyphs are never hinted.
+    ///
+    /// Edges are snapped relative to the glyph's position, so glyphs should be positioned
+    /// at whole pixel y, e.g. on whole pixel baselines. See
+    /// [`outline_hinted`](#method.outline_hinted).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let x = font.glyph_id('x').with_scale_and_position(11.0, point(0.0, 10.0));
+    /// let hinted = font.outline_glyph_hinted(x.clone(), Hinting::default()).unwrap();
+    /// let unhinted = font.outline_glyph(x).unwrap();
+    ///
+    /// // x-height snapped to the pixel grid, so the top row is more solidly covered
+    /// let top_row = |outlined: &OutlinedGlyph| {
+    ///     let mut coverage = 0.0;
+    ///     outlined.draw(0, |_, y, c| if y == 0 { coverage += c });
+    ///     coverage
+    /// };
+    /// assert!(top_row(&hinted) > top_row(&unhinted));
+    ///
+    /// // large glyphs are not hinted
+    /// let big = font.glyph_id('x').with_scale_and_position(48.0, point(0.0, 50.0));
+    /// let hinted = font.outline_glyph_hinted(big.clone(), Hinting::default()).unwrap();
+    /// assert_eq!(hinted.px_bounds(), font.outline_glyph(big).unwrap().px_bounds());
+    /// ```
+    fn outline_glyph_hinted(&self, glyph: Glyph, hinting: Hinting) -> Option<OutlinedGlyph>
+    where
+        Self: Sized,
+    {
+        if !hinting.applies(glyph.scale) || self.has_color(glyph.id) {
+            return self.outline_glyph(glyph);
+        }
+
+        #[cfg(feature = "counters")]
+        crate::counters::add_glyph_outlined();
+
+        let scale_factor = self.as_scaled(glyph.scale).scale_factor();
+        let outline = crate::hint::hint(&self.outline(glyph.id)?, scale_factor);
+        // hinted outlines are already in pixels
+        let scale_factor = PxScaleFactor {
+            horizontal: 1.0,
+            vertical: 1.0,
+        };
+        Some(OutlinedGlyph::new(
+            glyph,
+            OutlineGroup::from_outline(outline),
+            scale_factor,
+        ))
+    }
+
+    /// Compute glyph outline ready for drawing like [`outline_glyph`](#method.outline_glyph),
+    /// with an affine pixel `transform`, e.g. rotation, skew or mirroring, applied about
+    /// the glyph's position. Pixel bounds fit the transformed outline.
+    ///
+    /// Allows drawing rotated text, e.g. map & chart labels, without transforming
+    /// rasterized coverage.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let glyph = font.glyph_id('l').with_scale_and_position(40.0, point(20.0, 40.0));
+    /// let upright = font.outline_glyph(glyph.clone()).unwrap();
+    /// let quarter_turn = Transform::rotate(std::f32::consts::FRAC_PI_2);
+    /// let rotated = font.outline_glyph_transformed(glyph, quarter_turn).unwrap();
+    ///
+    /// assert!(upright.px_bounds().height() > upright.px_bounds().width());
+    /// assert!(rotated.px_bounds().width() > rotated.px_bounds().height());
+    /// // rotated clockwise about the position, so the ascender points right
+    /// assert!(rotated.px_bounds().min.x >= 19.0);
+    ///
+    /// // the same area is covered
+    /// let area = |outlined: &OutlinedGlyph| {
+    ///     let mut area = 0.0;
+    ///     outlined.draw(0, |_, _, c| area += c);
+    ///     area
+    /// };
+    /// assert!((area(&rotated) - area(&upright)).abs() < area(&upright) * 0.05);
+    /// ```
+    fn outline_glyph_transformed(&self, glyph: Glyph, transform: Transform) -> Option<OutlinedGlyph>
+    where
+        Self: Sized,
+    {
+        if transform.is_identity() {
+            return self.outline_glyph(glyph);
+        }
+
+        #[cfg(feature = "counters")]
+        crate::counters::add_glyph_outlined();
+
+        let scale_factor = self.as_scaled(glyph.scale).scale_factor();
+        let group = match self.color_outlines(glyph.id) {
+            Some(mut outline) if !outline.is_empty() => {
+                for layer in &mut outline.layers {
+                    layer.outline = transform.px_outline(&layer.outline, scale_factor);
+                }
+                OutlineGroup::new(outline)
+            }
+            _ => OutlineGroup::from_outline(
+                transform.px_outline(&self.outline(glyph.id)?, scale_factor),
+            ),
+        };
+        // transformed outlines are already in pixels
+        let scale_factor = PxScaleFactor {
+            horizontal: 1.0,
+            vertical: 1.0,
+        };
+        Some(OutlinedGlyph::new(glyph, group, scale_factor))
+    }
+
+    /// Draws a glyph's outline, streamed from [`outline_with`](#method.outline_with)
+    /// straight into the rasterizer without collecting curves, returning the pixel bounds.
+    ///
+    /// Draws the same coverage as [`outline_glyph`](#method.outline_glyph) then
+    /// [`OutlinedGlyph::draw`](struct.OutlinedGlyph.html#method.draw) for non-colored
+    /// glyphs, with `(x, y)` relative to the returned bounds. Color layers aren't drawn.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let glyph = font.glyph_id('g').with_scale_and_position(30.0, point(5.3, 30.0));
+    ///
+    /// let mut streamed = vec![];
+    /// let bounds = font.draw_glyph(&glyph, |x, y, c| streamed.push((x, y, c))).unwrap();
+    ///
+    /// let outlined = font.outline_glyph(glyph).unwrap();
+    /// assert_eq!(bounds, outlined.px_bounds());
+    /// let mut drawn = vec![];
+    /// outlined.draw(0, |x, y, c| drawn.push((x, y, c)));
+    /// assert_eq!(streamed.len(), drawn.len());
+    /// for (a, b) in streamed.iter().zip(&drawn) {
+    ///     assert_eq!((a.0, a.1), (b.0, b.1));
+    ///     assert!((a.2 - b.2).abs() < 1e-5);
+    /// }
+    /// ```
+    fn draw_glyph<O: FnMut(u32, u32, f32)>(&self, glyph: &Glyph, o: O) -> Option<Rect>
+    where
+        Self: Sized,
+    {
+        crate::outlined::draw_streamed(self, glyph, o)
+    }
+
+    /// Prepares a glyph for drawing, choosing between color layers, embedded raster images
+    /// & the outline for the glyph's size. Returns `None` if the glyph has nothing to draw.
+    ///
+    /// Color outline layers are preferred, as they scale to any size. Color images, e.g.
+    /// emoji pngs, are preferred to monochrome outlines, while monochrome images are only
+    /// used at their strike's exact size or if the glyph has no outline.
+    ///
+    /// See [`GlyphRender`](enum.GlyphRender.html).
+    fn render_glyph(&self, glyph: Glyph) -> Option<GlyphRender<'_>>
+    where
+        Self: Sized,
+    {
+        if self.has_color(glyph.id) {
+            if let Some(outlined) = self.outline_glyph(glyph.clone()) {
+                if outlined.get_colored_layers().is_some() {
+                    return Some(GlyphRender::Color(outlined));
+                }
+            }
+        }
+
+        // fonts without units per em have no bitmap strikes
+        let em_px = self
+            .units_per_em()
+            .map(|em| self.as_scaled(glyph.scale).v_scale_factor() * em);
+        let ppem = em_px.map(|px| px.round().max(1.0).min(f32::from(u16::MAX)) as u16);
+        let image = ppem.and_then(|ppem| self.glyph_raster_image(glyph.id, ppem));
+        let prefer_image = image.as_ref().is_some_and(|image| {
+            image.format != GlyphImageFormat::Mask || Some(image.pixels_per_em) == ppem
+        });
+
+        if !prefer_image {
+            if let Some(outlined) = self.outline_glyph(glyph.clone()) {
+                return Some(GlyphRender::Outline(outlined));
+            }
+        }
+        let image = image?;
+        let px_bounds = image.px_bounds(em_px.unwrap_or_default(), glyph.position);
+        Some(GlyphRender::Image { image, px_bounds })
+    }
+
     /// Construct a [`PxScaleFontRef`](struct.PxScaleFontRef.html) by associating with the
     /// given pixel `scale`.
     ///
@@ -165,7 +1473,7 @@ pub trait Font {
         Self: Sized,
     {
         PxScaleFont {
-            font: &self,
+            font: self,
             scale: scale.into(),
         }
     }
@@ -205,21 +1513,151 @@ impl<F: Font> Font for &F {
         (*self).line_gap_unscaled()
     }
 
+    #[inline]
+    fn vertical_ascent_unscaled(&self) -> f32 {
+        (*self).vertical_ascent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_descent_unscaled(&self) -> f32 {
+        (*self).vertical_descent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_line_gap_unscaled(&self) -> f32 {
+        (*self).vertical_line_gap_unscaled()
+    }
+
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        (*self).italic_angle()
+    }
+
+    #[inline]
+    fn underline_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        (*self).underline_metrics_unscaled()
+    }
+
+    #[inline]
+    fn strikeout_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        (*self).strikeout_metrics_unscaled()
+    }
+
+    #[inline]
+    fn x_height_unscaled(&self) -> f32 {
+        (*self).x_height_unscaled()
+    }
+
+    #[inline]
+    fn cap_height_unscaled(&self) -> f32 {
+        (*self).cap_height_unscaled()
+    }
+
+    #[inline]
+    fn is_monospaced(&self) -> bool {
+        (*self).is_monospaced()
+    }
+
+    #[inline]
+    fn weight(&self) -> u16 {
+        (*self).weight()
+    }
+
+    #[inline]
+    fn stretch(&self) -> u16 {
+        (*self).stretch()
+    }
+
+    #[inline]
+    fn style(&self) -> FontStyle {
+        (*self).style()
+    }
+
+    #[inline]
+    fn is_bold(&self) -> bool {
+        (*self).is_bold()
+    }
+
+    #[inline]
+    fn font_name(&self, id: NameId) -> Option<String> {
+        (*self).font_name(id)
+    }
+
+    #[inline]
+    fn font_name_for_language(&self, id: NameId, language_id: u16) -> Option<String> {
+        (*self).font_name_for_language(id, language_id)
+    }
+
     #[inline]
     fn glyph_id(&self, c: char) -> GlyphId {
         (*self).glyph_id(c)
     }
 
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        (*self).glyph_id_checked(c)
+    }
+
+    #[inline]
+    fn glyph_id_variation(&self, c: char, selector: char) -> Option<GlyphId> {
+        (*self).glyph_id_variation(c, selector)
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        (*self).codepoint_ids()
+    }
+
+    #[inline]
+    fn coverage_set(&self) -> CoverageSet {
+        (*self).coverage_set()
+    }
+
+    #[inline]
+    fn glyph_ids_for_str(&self, text: &str, ids: &mut Vec<GlyphId>) {
+        (*self).glyph_ids_for_str(text, ids)
+    }
+
+    #[inline]
+    fn substitute(&self, script: Script, features: &[[u8; 4]], glyphs: &mut Vec<GlyphId>) {
+        (*self).substitute(script, features, glyphs)
+    }
+
+    #[inline]
+    fn features(&self, script: Script) -> Vec<[u8; 4]> {
+        (*self).features(script)
+    }
+
+    #[inline]
+    fn ligatures(&self) -> Vec<(GlyphId, Vec<GlyphId>)> {
+        (*self).ligatures()
+    }
+
     #[inline]
     fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
         (*self).h_advance_unscaled(id)
     }
 
+    #[inline]
+    fn try_h_advance_unscaled(&self, id: GlyphId) -> Option<f32> {
+        (*self).try_h_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn h_advances_unscaled(&self, ids: &[GlyphId], out: &mut [f32]) {
+        (*self).h_advances_unscaled(ids, out)
+    }
+
     #[inline]
     fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
         (*self).h_side_bearing_unscaled(id)
     }
 
+    #[inline]
+    fn try_h_side_bearing_unscaled(&self, id: GlyphId) -> Option<f32> {
+        (*self).try_h_side_bearing_unscaled(id)
+    }
+
     #[inline]
     fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
         (*self).v_advance_unscaled(id)
@@ -230,11 +1668,56 @@ impl<F: Font> Font for &F {
         (*self).v_side_bearing_unscaled(id)
     }
 
+    #[inline]
+    fn v_origin_unscaled(&self, id: GlyphId) -> f32 {
+        (*self).v_origin_unscaled(id)
+    }
+
+    #[inline]
+    fn lsb_unscaled(&self, id: GlyphId) -> f32 {
+        (*self).lsb_unscaled(id)
+    }
+
+    #[inline]
+    fn rsb_unscaled(&self, id: GlyphId) -> f32 {
+        (*self).rsb_unscaled(id)
+    }
+
     #[inline]
     fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
         (*self).kern_unscaled(first, second)
     }
 
+    #[inline]
+    fn v_kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        (*self).v_kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn kern_pairs(&self) -> Vec<(GlyphId, GlyphId, f32)> {
+        (*self).kern_pairs()
+    }
+
+    #[inline]
+    fn optical_bounds_unscaled(&self, id: GlyphId) -> Option<OpticalBounds> {
+        (*self).optical_bounds_unscaled(id)
+    }
+
+    #[inline]
+    fn many_to_one_ranges(&self) -> Vec<(RangeInclusive<char>, GlyphId)> {
+        (*self).many_to_one_ranges()
+    }
+
+    #[inline]
+    fn device_h_advance(&self, id: GlyphId, ppem: u16) -> Option<f32> {
+        (*self).device_h_advance(id, ppem)
+    }
+
+    #[inline]
+    fn device_v_metrics(&self, ppem: u16) -> Option<DeviceVMetrics> {
+        (*self).device_v_metrics(ppem)
+    }
+
     #[inline]
     fn relative_scale(&self, glyph: GlyphId) -> f32 {
         (*self).relative_scale(glyph)
@@ -246,17 +1729,105 @@ impl<F: Font> Font for &F {
     }
 
     #[inline]
-    fn color_outlines(&self, glyph: GlyphId) -> Option<Vec<(Outline,u32)>> {
+    fn color_outlines(&self, glyph: GlyphId) -> Option<ColorOutline> {
         (*self).color_outlines(glyph)
     }
 
+    #[inline]
+    fn color_outlines_with_palette(&self, glyph: GlyphId, palette: usize) -> Option<ColorOutline> {
+        (*self).color_outlines_with_palette(glyph, palette)
+    }
+
+    #[inline]
+    fn outline_bounds_unscaled(&self, id: GlyphId) -> Option<Rect> {
+        (*self).outline_bounds_unscaled(id)
+    }
+
+    #[inline]
+    fn glyph_complexity(&self, id: GlyphId) -> Option<GlyphComplexity> {
+        (*self).glyph_complexity(id)
+    }
+
+    #[inline]
+    fn color_palettes(&self) -> usize {
+        (*self).color_palettes()
+    }
+
+    #[inline]
+    fn color_palette(&self, palette: usize) -> Option<ColorPalette> {
+        (*self).color_palette(palette)
+    }
+
+    #[inline]
+    fn color_paint(&self, glyph: GlyphId) -> Option<ColorGlyphPaint> {
+        (*self).color_paint(glyph)
+    }
+
+    #[inline]
+    fn glyph_raster_image(&self, glyph: GlyphId, pixels_per_em: u16) -> Option<GlyphImage<'_>> {
+        (*self).glyph_raster_image(glyph, pixels_per_em)
+    }
+
     #[inline]
     fn outline(&self, glyph: GlyphId) -> Option<Outline> {
         (*self).outline(glyph)
     }
 
+    #[inline]
+    fn outline_hinted(&self, glyph: GlyphId, ppem: u16) -> Option<Outline> {
+        (*self).outline_hinted(glyph, ppem)
+    }
+
+    #[inline]
+    fn glyph_bounds_unscaled(&self, glyph: GlyphId) -> Option<Rect> {
+        (*self).glyph_bounds_unscaled(glyph)
+    }
+
+    #[inline]
+    fn outline_into(&self, glyph: GlyphId, curves: &mut Vec<OutlineCurve>) -> Option<Rect> {
+        (*self).outline_into(glyph, curves)
+    }
+
+    #[inline]
+    fn outline_with(&self, glyph: GlyphId, sink: &mut dyn OutlineSink) -> Option<Rect> {
+        (*self).outline_with(glyph, sink)
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[inline]
+    fn outline_in<'bump>(
+        &self,
+        glyph: GlyphId,
+        arena: &'bump bumpalo2::Bump,
+    ) -> Option<ArenaOutline<'bump>> {
+        (*self).outline_in(glyph, arena)
+    }
+
     #[inline]
     fn glyph_count(&self) -> usize {
         (*self).glyph_count()
     }
+
+    #[inline]
+    fn glyph_name(&self, id: GlyphId) -> Option<&str> {
+        (*self).glyph_name(id)
+    }
+
+    #[inline]
+    fn subset(&self, glyphs: &[GlyphId]) -> Option<FontSubset> {
+        (*self).subset(glyphs)
+    }
+
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        (*self).fingerprint()
+    }
+}
+
+/// Returns the unscaled top of the outline of `c`, if the font has the glyph.
+pub(crate) fn glyph_top_unscaled<F: Font + ?Sized>(font: &F, c: char) -> Option<f32> {
+    match font.glyph_id(c) {
+        GlyphId(0) => None,
+        id => font.outline(id).map(|o| o.bounds.min.y),
+    }
 }
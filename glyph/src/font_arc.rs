@@ -1,6 +1,12 @@
-use crate::{Font, FontRef, FontVec, GlyphId, InvalidFont, Outline};
+#[cfg(feature = "bumpalo")]
+use crate::ArenaOutline;
+use crate::{
+    CodepointIdIter, ColorGlyphPaint, ColorOutline, ColorPalette, CoverageSet, DecorationMetrics,
+    DeviceVMetrics, Font, FontError, FontRef, FontStyle, FontSubset, FontVec, GlyphComplexity,
+    GlyphId, GlyphImage, NameId, OpticalBounds, Outline, OutlineCurve, OutlineSink, Rect, Script,
+};
 use alloc::sync::Arc;
-use core::fmt;
+use core::{fmt, ops::RangeInclusive};
 
 /// `Font` implementor that wraps another concrete `Font + 'static` type storing in an `Arc`.
 ///
@@ -45,7 +51,7 @@ impl FontArc {
     /// # Ok(()) }
     /// ```
     #[inline]
-    pub fn try_from_vec(data: Vec<u8>) -> Result<Self, InvalidFont> {
+    pub fn try_from_vec(data: Vec<u8>) -> Result<Self, FontError> {
         Ok(FontVec::try_from_vec(data)?.into())
     }
 
@@ -59,7 +65,7 @@ impl FontArc {
     /// # Ok(()) }
     /// ```
     #[inline]
-    pub fn try_from_slice(data: &'static [u8]) -> Result<Self, InvalidFont> {
+    pub fn try_from_slice(data: &'static [u8]) -> Result<Self, FontError> {
         Ok(FontRef::try_from_slice(data)?.into())
     }
 }
@@ -91,21 +97,151 @@ impl Font for FontArc {
         self.0.line_gap_unscaled()
     }
 
+    #[inline]
+    fn vertical_ascent_unscaled(&self) -> f32 {
+        self.0.vertical_ascent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_descent_unscaled(&self) -> f32 {
+        self.0.vertical_descent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_line_gap_unscaled(&self) -> f32 {
+        self.0.vertical_line_gap_unscaled()
+    }
+
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        self.0.italic_angle()
+    }
+
+    #[inline]
+    fn underline_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.0.underline_metrics_unscaled()
+    }
+
+    #[inline]
+    fn strikeout_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.0.strikeout_metrics_unscaled()
+    }
+
+    #[inline]
+    fn x_height_unscaled(&self) -> f32 {
+        self.0.x_height_unscaled()
+    }
+
+    #[inline]
+    fn cap_height_unscaled(&self) -> f32 {
+        self.0.cap_height_unscaled()
+    }
+
+    #[inline]
+    fn is_monospaced(&self) -> bool {
+        self.0.is_monospaced()
+    }
+
+    #[inline]
+    fn weight(&self) -> u16 {
+        self.0.weight()
+    }
+
+    #[inline]
+    fn stretch(&self) -> u16 {
+        self.0.stretch()
+    }
+
+    #[inline]
+    fn style(&self) -> FontStyle {
+        self.0.style()
+    }
+
+    #[inline]
+    fn is_bold(&self) -> bool {
+        self.0.is_bold()
+    }
+
+    #[inline]
+    fn font_name(&self, id: NameId) -> Option<String> {
+        self.0.font_name(id)
+    }
+
+    #[inline]
+    fn font_name_for_language(&self, id: NameId, language_id: u16) -> Option<String> {
+        self.0.font_name_for_language(id, language_id)
+    }
+
     #[inline]
     fn glyph_id(&self, c: char) -> GlyphId {
         self.0.glyph_id(c)
     }
 
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        self.0.glyph_id_checked(c)
+    }
+
+    #[inline]
+    fn glyph_id_variation(&self, c: char, selector: char) -> Option<GlyphId> {
+        self.0.glyph_id_variation(c, selector)
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.0.codepoint_ids()
+    }
+
+    #[inline]
+    fn coverage_set(&self) -> CoverageSet {
+        self.0.coverage_set()
+    }
+
+    #[inline]
+    fn glyph_ids_for_str(&self, text: &str, ids: &mut Vec<GlyphId>) {
+        self.0.glyph_ids_for_str(text, ids)
+    }
+
+    #[inline]
+    fn substitute(&self, script: Script, features: &[[u8; 4]], glyphs: &mut Vec<GlyphId>) {
+        self.0.substitute(script, features, glyphs)
+    }
+
+    #[inline]
+    fn features(&self, script: Script) -> Vec<[u8; 4]> {
+        self.0.features(script)
+    }
+
+    #[inline]
+    fn ligatures(&self) -> Vec<(GlyphId, Vec<GlyphId>)> {
+        self.0.ligatures()
+    }
+
     #[inline]
     fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
         self.0.h_advance_unscaled(id)
     }
 
+    #[inline]
+    fn try_h_advance_unscaled(&self, id: GlyphId) -> Option<f32> {
+        self.0.try_h_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn h_advances_unscaled(&self, ids: &[GlyphId], out: &mut [f32]) {
+        self.0.h_advances_unscaled(ids, out)
+    }
+
     #[inline]
     fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
         self.0.h_side_bearing_unscaled(id)
     }
 
+    #[inline]
+    fn try_h_side_bearing_unscaled(&self, id: GlyphId) -> Option<f32> {
+        self.0.try_h_side_bearing_unscaled(id)
+    }
+
     #[inline]
     fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
         self.0.v_advance_unscaled(id)
@@ -116,11 +252,61 @@ impl Font for FontArc {
         self.0.v_side_bearing_unscaled(id)
     }
 
+    #[inline]
+    fn v_origin_unscaled(&self, id: GlyphId) -> f32 {
+        self.0.v_origin_unscaled(id)
+    }
+
+    #[inline]
+    fn lsb_unscaled(&self, id: GlyphId) -> f32 {
+        self.0.lsb_unscaled(id)
+    }
+
+    #[inline]
+    fn rsb_unscaled(&self, id: GlyphId) -> f32 {
+        self.0.rsb_unscaled(id)
+    }
+
     #[inline]
     fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
         self.0.kern_unscaled(first, second)
     }
 
+    #[inline]
+    fn v_kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.0.v_kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn kern_pairs(&self) -> Vec<(GlyphId, GlyphId, f32)> {
+        self.0.kern_pairs()
+    }
+
+    #[inline]
+    fn optical_bounds_unscaled(&self, id: GlyphId) -> Option<OpticalBounds> {
+        self.0.optical_bounds_unscaled(id)
+    }
+
+    #[inline]
+    fn glyph_complexity(&self, id: GlyphId) -> Option<GlyphComplexity> {
+        self.0.glyph_complexity(id)
+    }
+
+    #[inline]
+    fn many_to_one_ranges(&self) -> Vec<(RangeInclusive<char>, GlyphId)> {
+        self.0.many_to_one_ranges()
+    }
+
+    #[inline]
+    fn device_h_advance(&self, id: GlyphId, ppem: u16) -> Option<f32> {
+        self.0.device_h_advance(id, ppem)
+    }
+
+    #[inline]
+    fn device_v_metrics(&self, ppem: u16) -> Option<DeviceVMetrics> {
+        self.0.device_v_metrics(ppem)
+    }
+
     #[inline]
     fn relative_scale(&self, glyph: GlyphId) -> f32 {
         self.0.relative_scale(glyph)
@@ -132,19 +318,94 @@ impl Font for FontArc {
     }
 
     #[inline]
-    fn color_outlines(&self, glyph: GlyphId) -> Option<Vec<(Outline,u32)>> {
+    fn color_outlines(&self, glyph: GlyphId) -> Option<ColorOutline> {
         self.0.color_outlines(glyph)
     }
 
+    #[inline]
+    fn color_outlines_with_palette(&self, glyph: GlyphId, palette: usize) -> Option<ColorOutline> {
+        self.0.color_outlines_with_palette(glyph, palette)
+    }
+
+    #[inline]
+    fn outline_bounds_unscaled(&self, id: GlyphId) -> Option<Rect> {
+        self.0.outline_bounds_unscaled(id)
+    }
+
+    #[inline]
+    fn color_palettes(&self) -> usize {
+        self.0.color_palettes()
+    }
+
+    #[inline]
+    fn color_palette(&self, palette: usize) -> Option<ColorPalette> {
+        self.0.color_palette(palette)
+    }
+
+    #[inline]
+    fn color_paint(&self, glyph: GlyphId) -> Option<ColorGlyphPaint> {
+        self.0.color_paint(glyph)
+    }
+
+    #[inline]
+    fn glyph_raster_image(&self, glyph: GlyphId, pixels_per_em: u16) -> Option<GlyphImage<'_>> {
+        self.0.glyph_raster_image(glyph, pixels_per_em)
+    }
+
     #[inline]
     fn outline(&self, glyph: GlyphId) -> Option<Outline> {
         self.0.outline(glyph)
     }
 
+    #[inline]
+    fn outline_hinted(&self, glyph: GlyphId, ppem: u16) -> Option<Outline> {
+        self.0.outline_hinted(glyph, ppem)
+    }
+
+    #[inline]
+    fn glyph_bounds_unscaled(&self, glyph: GlyphId) -> Option<Rect> {
+        self.0.glyph_bounds_unscaled(glyph)
+    }
+
+    #[inline]
+    fn outline_into(&self, glyph: GlyphId, curves: &mut Vec<OutlineCurve>) -> Option<Rect> {
+        self.0.outline_into(glyph, curves)
+    }
+
+    #[inline]
+    fn outline_with(&self, glyph: GlyphId, sink: &mut dyn OutlineSink) -> Option<Rect> {
+        self.0.outline_with(glyph, sink)
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[inline]
+    fn outline_in<'bump>(
+        &self,
+        glyph: GlyphId,
+        arena: &'bump bumpalo2::Bump,
+    ) -> Option<ArenaOutline<'bump>> {
+        self.0.outline_in(glyph, arena)
+    }
+
     #[inline]
     fn glyph_count(&self) -> usize {
         self.0.glyph_count()
     }
+
+    #[inline]
+    fn glyph_name(&self, id: GlyphId) -> Option<&str> {
+        self.0.glyph_name(id)
+    }
+
+    #[inline]
+    fn subset(&self, glyphs: &[GlyphId]) -> Option<FontSubset> {
+        self.0.subset(glyphs)
+    }
+
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        self.0.fingerprint()
+    }
 }
 
 impl From<FontVec> for FontArc {
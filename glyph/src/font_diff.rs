@@ -0,0 +1,148 @@
+use crate::{CoverageSet, Font, GlyphId};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Differences between two versions of a font, e.g. for font pipeline regression checks.
+///
+/// Glyphs are matched by the chars they're mapped to, as glyph ids may change between
+/// versions. Outlines are compared by [`Outline::content_hash`](struct.Outline.html#method.content_hash).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{FontDiff, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let otf = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let ttf = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.ttf"))?;
+///
+/// assert!(FontDiff::new(&otf, &otf).is_empty());
+///
+/// // the TrueType version has quadratic rather than cubic outlines
+/// let diff = FontDiff::new(&otf, &ttf);
+/// assert!(diff.glyphs.iter().any(|g| g.c == 'a' && g.outline_changed));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FontDiff {
+    /// Chars mapped by the new font but not the old.
+    pub added_chars: CoverageSet,
+    /// Chars mapped by the old font but not the new.
+    pub removed_chars: CoverageSet,
+    /// Chars mapped by both fonts whose glyph outline or advance changed, in char order.
+    pub glyphs: Vec<GlyphChange>,
+    /// Changed font wide metrics.
+    pub metrics: Vec<MetricChange>,
+    /// Old & new [`glyph_count`](trait.Font.html#tymethod.glyph_count).
+    pub glyph_count: (usize, usize),
+}
+
+/// A char's changed glyph, see [`FontDiff`](struct.FontDiff.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphChange {
+    /// The mapped char.
+    pub c: char,
+    /// Old & new glyph ids.
+    pub id: (GlyphId, GlyphId),
+    /// `true` if the outline changed, including being added or removed.
+    pub outline_changed: bool,
+    /// Old & new unscaled horizontal advances.
+    pub h_advance: (f32, f32),
+}
+
+/// A changed font wide metric, see [`FontDiff`](struct.FontDiff.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetricChange {
+    /// Metric name, the `Font` method without any `_unscaled` suffix, e.g. `"ascent"`.
+    pub name: &'static str,
+    /// Old value.
+    pub old: f32,
+    /// New value.
+    pub new: f32,
+}
+
+impl MetricChange {
+    /// Returns `new - old`.
+    #[inline]
+    pub fn delta(&self) -> f32 {
+        self.new - self.old
+    }
+}
+
+impl FontDiff {
+    /// Compares an `old` & `new` version of a font.
+    ///
+    /// Outlines of every char mapped by both fonts are compared, so this is relatively
+    /// slow for large fonts.
+    pub fn new<A: Font, B: Font>(old: &A, new: &B) -> Self {
+        let metric = |name, f: fn(&dyn Font) -> f32| MetricChange {
+            name,
+            old: f(old),
+            new: f(new),
+        };
+        let metrics = [
+            metric("units_per_em", |f| f.units_per_em().unwrap_or(0.0)),
+            metric("ascent", |f| f.ascent_unscaled()),
+            metric("descent", |f| f.descent_unscaled()),
+            metric("line_gap", |f| f.line_gap_unscaled()),
+            metric("x_height", |f| f.x_height_unscaled()),
+            metric("cap_height", |f| f.cap_height_unscaled()),
+            metric("italic_angle", |f| f.italic_angle()),
+            metric("weight", |f| f32::from(f.weight())),
+            metric("stretch", |f| f32::from(f.stretch())),
+        ]
+        .iter()
+        .copied()
+        .filter(|m| m.old.to_bits() != m.new.to_bits())
+        .collect();
+
+        let old_chars = old.coverage_set();
+        let new_chars = new.coverage_set();
+        let added_chars = new_chars
+            .chars()
+            .filter(|&c| !old_chars.contains(c))
+            .collect();
+        let removed_chars = old_chars
+            .chars()
+            .filter(|&c| !new_chars.contains(c))
+            .collect();
+
+        let glyphs = old_chars
+            .chars()
+            .filter(|&c| new_chars.contains(c))
+            .filter_map(|c| {
+                let id = (old.glyph_id(c), new.glyph_id(c));
+                let outline_changed = old.outline(id.0).map(|o| o.content_hash())
+                    != new.outline(id.1).map(|o| o.content_hash());
+                let h_advance = (old.h_advance_unscaled(id.0), new.h_advance_unscaled(id.1));
+                if outline_changed || h_advance.0.to_bits() != h_advance.1.to_bits() {
+                    Some(GlyphChange {
+                        c,
+                        id,
+                        outline_changed,
+                        h_advance,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            added_chars,
+            removed_chars,
+            glyphs,
+            metrics,
+            glyph_count: (old.glyph_count(), new.glyph_count()),
+        }
+    }
+
+    /// Returns `true` if no differences were found.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added_chars.is_empty()
+            && self.removed_chars.is_empty()
+            && self.glyphs.is_empty()
+            && self.metrics.is_empty()
+            && self.glyph_count.0 == self.glyph_count.1
+    }
+}
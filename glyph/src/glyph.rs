@@ -1,4 +1,7 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
 use crate::PxScale;
+use core::f32::consts::PI;
 
 /// An (x, y) coordinate. `Point { x: f32, y: f32 }`
 pub type Point = ab_glyph_rasterizer::Point;
@@ -16,6 +19,11 @@ pub use ab_glyph_rasterizer::point;
 /// # Ok(()) }
 /// ```
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
 pub struct GlyphId(pub u16);
 
 impl GlyphId {
@@ -53,10 +61,221 @@ impl GlyphId {
     pub fn with_scale<S: Into<PxScale>>(self, scale: S) -> Glyph {
         self.with_scale_and_position(scale, Point::default())
     }
+
+    /// Construct a `SubpixelGlyph` by splitting `position` into a whole pixel offset &
+    /// a subpixel offset quantized to `h_steps` horizontal & `v_steps` vertical positions
+    /// per pixel.
+    ///
+    /// The resultant glyph is positioned at the subpixel offset only, so it can be
+    /// rasterized & cached once per subpixel step then drawn at the pixel offset.
+    /// Steps of `0` or `1` snap to whole pixels.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// // 4 horizontal subpixel positions, whole pixel vertical positions
+    /// let sub = font
+    ///     .glyph_id('a')
+    ///     .with_scale_and_subpixel_position(24.0, point(100.3, 20.6), 4, 1);
+    ///
+    /// assert_eq!(sub.pixel_offset, (100, 21));
+    /// assert_eq!(sub.glyph.position, point(0.25, 0.0));
+    /// assert_eq!(sub.subpixel, (1, 0));
+    /// ```
+    pub fn with_scale_and_subpixel_position<S: Into<PxScale>, P: Into<Point>>(
+        self,
+        scale: S,
+        position: P,
+        h_steps: u8,
+        v_steps: u8,
+    ) -> SubpixelGlyph {
+        let position = position.into();
+        let (x, sub_x) = quantize(position.x, h_steps);
+        let (y, sub_y) = quantize(position.y, v_steps);
+        SubpixelGlyph {
+            glyph: self.with_scale_and_position(
+                scale,
+                point(
+                    f32::from(sub_x) / f32::from(h_steps.max(1)),
+                    f32::from(sub_y) / f32::from(v_steps.max(1)),
+                ),
+            ),
+            pixel_offset: (x, y),
+            subpixel: (sub_x, sub_y),
+        }
+    }
+}
+
+/// Hashable glyph cache key of glyph id, scale, subpixel position & optional transform.
+///
+/// Scale is stored as raw `f32` bits & position is quantized into subpixel steps
+/// ignoring whole pixels, so glyphs that rasterize identically, other than a whole pixel
+/// offset, have the same key.
+///
+/// # Example
+/// ```
+/// # use ab_glyph::*;
+/// # use std::collections::HashMap;
+/// let a = GlyphId(12).with_scale_and_position(24.0, point(10.26, 5.0));
+/// let b = GlyphId(12).with_scale_and_position(24.0, point(34.24, 8.0));
+///
+/// // 4 horizontal & vertical subpixel steps
+/// assert_eq!(GlyphKey::new(&a, 4, 4), GlyphKey::new(&b, 4, 4));
+///
+/// let mut cache = HashMap::new();
+/// cache.insert(GlyphKey::from(&a), "rasterized a");
+/// assert_eq!(cache.get(&GlyphKey::from(&b)), Some(&"rasterized a"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlyphKey {
+    /// Glyph id.
+    pub id: GlyphId,
+    /// Horizontal & vertical `PxScale` as `f32::to_bits`.
+    pub scale: (u32, u32),
+    /// Horizontal & vertical subpixel step indices.
+    pub subpixel: (u8, u8),
+    /// Rotation & horizontal skew step indices, `(0, 0)` if untransformed.
+    ///
+    /// See [`with_transform`](#method.with_transform).
+    pub transform: (i16, i16),
+}
+
+impl GlyphKey {
+    /// Subpixel steps per pixel used by the `From<&Glyph>` conversion.
+    pub const DEFAULT_SUBPIXEL_STEPS: u8 = 4;
+
+    /// Construct a key quantizing the glyph position to `h_steps` horizontal &
+    /// `v_steps` vertical subpixel positions, see
+    /// [`GlyphId::with_scale_and_subpixel_position`](struct.GlyphId.html#method.with_scale_and_subpixel_position).
+    #[inline]
+    pub fn new(glyph: &Glyph, h_steps: u8, v_steps: u8) -> Self {
+        Self {
+            id: glyph.id,
+            scale: scale_bits(glyph.scale),
+            subpixel: (
+                quantize(glyph.position.x, h_steps).1,
+                quantize(glyph.position.y, v_steps).1,
+            ),
+            transform: (0, 0),
+        }
+    }
+
+    /// Returns this key for the glyph drawn rotated by `rotation` & horizontally skewed by
+    /// `skew` radians, each quantized to the nearest of `steps` per full turn.
+    ///
+    /// Nearby transforms share a key, so rotated text, e.g. map & chart labels, can still
+    /// be cached when drawn using the quantized angles of
+    /// [`transform_angles`](#method.transform_angles).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// let glyph = GlyphId(12).with_scale(24.0);
+    ///
+    /// // 1 degree steps
+    /// let a = GlyphKey::from(&glyph).with_transform(0.3, 0.0, 360);
+    /// let b = GlyphKey::from(&glyph).with_transform(0.302, 0.0, 360);
+    /// assert_eq!(a, b);
+    /// assert_ne!(a, GlyphKey::from(&glyph));
+    ///
+    /// // draw using the quantized angles
+    /// let (rotation, skew) = a.transform_angles(360);
+    /// assert!((rotation - 17_f32.to_radians()).abs() < 1e-6);
+    /// assert_eq!(skew, 0.0);
+    /// ```
+    #[inline]
+    pub fn with_transform(mut self, rotation: f32, skew: f32, steps: u16) -> Self {
+        let steps = i32::from(steps.max(1));
+        let step_index = |angle: f32| (angle / (2.0 * PI) * steps as f32).round() as i32;
+
+        // wrap rotation into (-half turn, half turn]
+        let mut rotation = step_index(rotation).rem_euclid(steps);
+        if rotation > steps / 2 {
+            rotation -= steps;
+        }
+        let skew = step_index(skew).clamp(i16::MIN.into(), i16::MAX.into());
+        self.transform = (rotation as i16, skew as i16);
+        self
+    }
+
+    /// Returns the quantized rotation & horizontal skew angles in radians, using the same
+    /// `steps` per full turn passed to [`with_transform`](#method.with_transform).
+    #[inline]
+    pub fn transform_angles(&self, steps: u16) -> (f32, f32) {
+        let step = 2.0 * PI / f32::from(steps.max(1));
+        (
+            f32::from(self.transform.0) * step,
+            f32::from(self.transform.1) * step,
+        )
+    }
+}
+
+impl From<&Glyph> for GlyphKey {
+    /// Key using [`GlyphKey::DEFAULT_SUBPIXEL_STEPS`](#associatedconstant.DEFAULT_SUBPIXEL_STEPS).
+    #[inline]
+    fn from(glyph: &Glyph) -> Self {
+        Self::new(
+            glyph,
+            Self::DEFAULT_SUBPIXEL_STEPS,
+            Self::DEFAULT_SUBPIXEL_STEPS,
+        )
+    }
+}
+
+impl From<&SubpixelGlyph> for GlyphKey {
+    #[inline]
+    fn from(sub: &SubpixelGlyph) -> Self {
+        Self {
+            id: sub.glyph.id,
+            scale: scale_bits(sub.glyph.scale),
+            subpixel: sub.subpixel,
+            transform: (0, 0),
+        }
+    }
+}
+
+/// Returns `f32::to_bits` of the scale, normalizing `-0.0` to `0.0`.
+#[inline]
+fn scale_bits(scale: PxScale) -> (u32, u32) {
+    ((scale.x + 0.0).to_bits(), (scale.y + 0.0).to_bits())
+}
+
+/// Splits a coordinate into whole pixels & the nearest of `steps` subpixel steps,
+/// rounding up to the next whole pixel if nearer.
+#[inline]
+fn quantize(v: f32, steps: u8) -> (i32, u8) {
+    let steps = steps.max(1);
+    let whole = v.floor();
+    let step = ((v - whole) * f32::from(steps)).round() as u8;
+    if step >= steps {
+        (whole as i32 + 1, 0)
+    } else {
+        (whole as i32, step)
+    }
+}
+
+/// A [`Glyph`](struct.Glyph.html) positioned at a quantized subpixel offset, with the
+/// remaining whole pixel offset stored separately.
+///
+/// See [`GlyphId::with_scale_and_subpixel_position`](struct.GlyphId.html#method.with_scale_and_subpixel_position).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct SubpixelGlyph {
+    /// Glyph positioned at the subpixel offset, each coordinate in `[0, 1)`.
+    pub glyph: Glyph,
+    /// Whole pixel offset to add to the glyph's pixel bounds when drawing.
+    pub pixel_offset: (i32, i32),
+    /// Horizontal & vertical subpixel step indices, useful in cache keys.
+    pub subpixel: (u8, u8),
 }
 
 /// A glyph with pixel scale & position.
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
 pub struct Glyph {
     /// Glyph id.
     pub id: GlyphId,
@@ -0,0 +1,94 @@
+use crate::{point, Point, Rect};
+use alloc::borrow::Cow;
+
+/// Pixel data format of a [`GlyphImage`](struct.GlyphImage.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GlyphImageFormat {
+    /// PNG encoded image.
+    Png,
+    /// Uncompressed premultiplied BGRA, 4 bytes per pixel, rows top to bottom.
+    Bgra,
+    /// Uncompressed coverage, 1 byte per pixel, rows top to bottom.
+    Mask,
+}
+
+/// An embedded raster image of a glyph.
+///
+/// Bitmap glyph sources, e.g. sbix, CBDT & EBDT tables, are all converted into this type so
+/// they can be consumed uniformly. Metrics are in pixels of the image's strike, see
+/// [`pixels_per_em`](#structfield.pixels_per_em).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, GlyphImage, GlyphImageFormat, Rect};
+///
+/// let image = GlyphImage {
+///     data: vec![255; 20 * 16].into(),
+///     format: GlyphImageFormat::Mask,
+///     width: 20,
+///     height: 16,
+///     bearing: point(1.0, -14.0),
+///     pixels_per_em: 20,
+/// };
+///
+/// // draw at 40px per em, twice the strike size
+/// assert_eq!(
+///     image.px_bounds(40.0, point(100.0, 50.0)),
+///     Rect {
+///         min: point(102.0, 22.0),
+///         max: point(142.0, 54.0),
+///     }
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlyphImage<'a> {
+    /// Image data, in `format`.
+    pub data: Cow<'a, [u8]>,
+    /// Image data format.
+    pub format: GlyphImageFormat,
+    /// Image pixel width.
+    ///
+    /// Encoded formats like png may not match the actual image data.
+    pub width: u16,
+    /// Image pixel height.
+    ///
+    /// Encoded formats like png may not match the actual image data.
+    pub height: u16,
+    /// Pixel offset from the glyph origin, on the baseline, to the top-left of the image.
+    /// Positive y is down, so images above the baseline have a negative y bearing.
+    pub bearing: Point,
+    /// Pixels per em of the image's strike.
+    pub pixels_per_em: u16,
+}
+
+impl GlyphImage<'_> {
+    /// Returns the factor to scale image pixels to draw at the given pixels per em.
+    #[inline]
+    pub fn scale_factor(&self, pixels_per_em: f32) -> f32 {
+        pixels_per_em / f32::from(self.pixels_per_em.max(1))
+    }
+
+    /// Returns the pixel bounds of the image drawn scaled to the given pixels per em, for a
+    /// glyph at `position`.
+    pub fn px_bounds(&self, pixels_per_em: f32, position: Point) -> Rect {
+        let factor = self.scale_factor(pixels_per_em);
+        let min = position + self.bearing * factor;
+        Rect {
+            min,
+            max: min + point(f32::from(self.width), f32::from(self.height)) * factor,
+        }
+    }
+
+    /// Converts into an image owning its data.
+    #[inline]
+    pub fn into_owned(self) -> GlyphImage<'static> {
+        GlyphImage {
+            data: Cow::Owned(self.data.into_owned()),
+            format: self.format,
+            width: self.width,
+            height: self.height,
+            bearing: self.bearing,
+            pixels_per_em: self.pixels_per_em,
+        }
+    }
+}
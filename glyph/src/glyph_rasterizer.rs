@@ -0,0 +1,56 @@
+use crate::{CachedGlyph, Font, Glyph};
+
+/// Rasterizes glyphs for a [`GlyphCache`](struct.GlyphCache.html), requires the `std` feature.
+///
+/// Implement to draw glyphs with another rasterizer, e.g. GPU compute or a platform
+/// rasterizer like CoreText or DirectWrite, while reusing the cache, warming & layout of
+/// this crate. The built-in [`OutlineRasterizer`](struct.OutlineRasterizer.html) is the
+/// default.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, CachedGlyph, Font, FontRef, Glyph, GlyphCache, GlyphRasterizer};
+///
+/// /// Rasterizes glyph bounding boxes, e.g. a placeholder while fonts load.
+/// struct BoxRasterizer;
+///
+/// impl GlyphRasterizer for BoxRasterizer {
+///     fn rasterize<F: Font>(&self, font: &F, glyph: &Glyph, padding: u32) -> Option<CachedGlyph> {
+///         let outlined = font.outline_glyph(glyph.clone())?.with_padding(padding);
+///         let bounds = outlined.px_bounds();
+///         let pixels = (bounds.width() * bounds.height()) as usize;
+///         Some(CachedGlyph { bounds, layers: vec![(vec![255; pixels], 0xFFFFFFFF)] })
+///     }
+/// }
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let mut cache = GlyphCache::with_rasterizer(BoxRasterizer, 0);
+///
+/// let glyph = font.glyph_id('o').with_scale_and_position(24.0, point(10.0, 20.0));
+/// let o = cache.get_or_rasterize(&font, &glyph).unwrap();
+/// assert!(o.layers[0].0.iter().all(|&c| c == 255));
+/// # Ok(()) }
+/// ```
+pub trait GlyphRasterizer {
+    /// Rasterizes a glyph into 8 bit coverage, returning `None` if it has nothing to draw.
+    ///
+    /// `glyph` is positioned within the first pixel at its quantized subpixel offset, so
+    /// the returned [`bounds`](struct.CachedGlyph.html#structfield.bounds) are relative to
+    /// the whole pixel of the cached glyph's position. Bounds should be expanded by
+    /// `padding` whole pixels of empty space on all sides.
+    fn rasterize<F: Font>(&self, font: &F, glyph: &Glyph, padding: u32) -> Option<CachedGlyph>;
+}
+
+/// The built-in [`GlyphRasterizer`](trait.GlyphRasterizer.html) drawing glyph outlines,
+/// including color layers, with [`OutlinedGlyph::draw`](struct.OutlinedGlyph.html#method.draw).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OutlineRasterizer;
+
+impl GlyphRasterizer for OutlineRasterizer {
+    #[inline]
+    fn rasterize<F: Font>(&self, font: &F, glyph: &Glyph, padding: u32) -> Option<CachedGlyph> {
+        let outlined = font.outline_glyph(glyph.clone())?.with_padding(padding);
+        Some(CachedGlyph::from_outlined(&outlined))
+    }
+}
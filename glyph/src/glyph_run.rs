@@ -0,0 +1,107 @@
+use crate::{Font, Glyph, OutlinedGlyph, PxScale, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+/// Horizontal text direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub enum Direction {
+    /// Left to right, e.g. Latin.
+    LeftToRight,
+    /// Right to left, e.g. Arabic & Hebrew.
+    RightToLeft,
+}
+
+impl Default for Direction {
+    #[inline]
+    fn default() -> Self {
+        Self::LeftToRight
+    }
+}
+
+/// Positioned glyphs of a single font & scale along with the source text they were laid
+/// out from.
+///
+/// A layout engine agnostic interchange type, output by
+/// [`Layout::layout_runs`](struct.Layout.html#method.layout_runs) & accepted by
+/// rasterization APIs like [`GlyphCache::warm_run`](struct.GlyphCache.html#method.warm_run).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, Direction, Font, FontRef, Layout};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+///
+/// let text = "Hello\nworld";
+/// let runs = Layout::default().layout_runs(&font, 24.0, point(0.0, 0.0), text);
+/// assert_eq!(runs.len(), 2);
+/// assert_eq!(&text[runs[1].text.clone()], "world");
+/// assert_eq!(runs[1].glyphs.len(), 5);
+/// assert_eq!(runs[1].direction, Direction::LeftToRight);
+///
+/// let bounds = runs[0].px_bounds().unwrap();
+/// assert!(bounds.max.y < runs[1].px_bounds().unwrap().max.y);
+/// for outlined in runs[0].outline_glyphs() {
+///     outlined.draw(0, |x, y, c| { /* draw pixel `(x, y)` with coverage: `c` */ });
+/// }
+/// # Ok(()) }
+/// ```
+pub struct GlyphRun<'a, F> {
+    /// Font of all glyphs in the run.
+    pub font: &'a F,
+    /// Pixel scale of the run.
+    pub scale: PxScale,
+    /// Positioned glyphs in visual order.
+    pub glyphs: Vec<Glyph>,
+    /// Byte range of the source text.
+    pub text: Range<usize>,
+    /// Direction of the source text.
+    pub direction: Direction,
+}
+
+impl<F> Clone for GlyphRun<'_, F> {
+    fn clone(&self) -> Self {
+        Self {
+            font: self.font,
+            scale: self.scale,
+            glyphs: self.glyphs.clone(),
+            text: self.text.clone(),
+            direction: self.direction,
+        }
+    }
+}
+
+impl<F> fmt::Debug for GlyphRun<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlyphRun")
+            .field("scale", &self.scale)
+            .field("glyphs", &self.glyphs)
+            .field("text", &self.text)
+            .field("direction", &self.direction)
+            .finish()
+    }
+}
+
+impl<F: Font> GlyphRun<'_, F> {
+    /// Returns the outlined glyphs of the run, skipping glyphs without an outline.
+    #[inline]
+    pub fn outline_glyphs(&self) -> impl Iterator<Item = OutlinedGlyph> + '_ {
+        self.glyphs
+            .iter()
+            .filter_map(move |glyph| self.font.outline_glyph(glyph.clone()))
+    }
+
+    /// Returns the union of the pixel bounds of all glyph outlines, `None` if no glyphs
+    /// have an outline.
+    pub fn px_bounds(&self) -> Option<Rect> {
+        let mut bounds = self.outline_glyphs().map(|outlined| outlined.px_bounds());
+        let init = bounds.next()?;
+        Some(bounds.fold(init, |a, b| a.union(&b)))
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{Font, GlyphId};
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+use alloc::{collections::BTreeMap, string::String};
+
+/// Maximum rounds of ligature decomposition, resolving ligatures of ligatures.
+const MAX_DEPTH: u8 = 8;
+
+/// Best Unicode text of each glyph of a font, e.g. for PDF `/ToUnicode` maps or
+/// accessibility, so extracted text copies & pastes correctly.
+///
+/// Glyphs take their text from the char map, preferring regular chars over private use
+/// chars & ligature presentation forms, e.g. `'ﬁ'`. Ligature glyphs without a regular char
+/// take the text of their components, see [`Font::ligatures`](trait.Font.html#method.ligatures).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontRef, GlyphTextMap, Script};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let texts = GlyphTextMap::new(&font);
+///
+/// assert_eq!(texts.get(font.glyph_id('a')), Some("a"));
+/// assert_eq!(texts.get(font.glyph_id('\u{FB01}')), Some("fi"));
+///
+/// // unmapped ligature glyph
+/// let mut glyphs = Vec::new();
+/// font.glyph_ids_for_str("ffi", &mut glyphs);
+/// font.substitute(Script::new(b"latn"), &[*b"liga"], &mut glyphs);
+/// assert_eq!(glyphs.len(), 1);
+/// assert_eq!(texts.get(glyphs[0]), Some("ffi"));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlyphTextMap {
+    texts: BTreeMap<GlyphId, String>,
+}
+
+impl GlyphTextMap {
+    /// Resolves the text of each glyph of `font` from its char map & ligatures.
+    pub fn new<F: Font>(font: &F) -> Self {
+        let mut chars = BTreeMap::new();
+        for (id, c) in font.codepoint_ids() {
+            if id == GlyphId(0) {
+                continue;
+            }
+            chars
+                .entry(id)
+                .and_modify(|best: &mut char| {
+                    if (is_fallback(c), c) < (is_fallback(*best), *best) {
+                        *best = c;
+                    }
+                })
+                .or_insert(c);
+        }
+        let mut texts: BTreeMap<_, _> = chars
+            .into_iter()
+            .map(|(id, c)| (id, c.to_string()))
+            .collect();
+
+        // components may themselves be ligatures, so resolve over a few rounds
+        let mut ligatures = font.ligatures();
+        for _ in 0..MAX_DEPTH {
+            let unresolved = ligatures.len();
+            ligatures.retain(|(id, components)| {
+                if texts.get(id).is_some_and(|text| !is_fallback_text(text)) {
+                    return false;
+                }
+                let text = components
+                    .iter()
+                    .map(|component| texts.get(component).map(String::as_str))
+                    .collect::<Option<String>>();
+                match text {
+                    Some(text) => {
+                        texts.insert(*id, text);
+                        false
+                    }
+                    None => true,
+                }
+            });
+            if ligatures.len() == unresolved {
+                break;
+            }
+        }
+
+        Self { texts }
+    }
+
+    /// Returns the text of a glyph, or `None` if it is unknown.
+    #[inline]
+    pub fn get(&self, id: GlyphId) -> Option<&str> {
+        self.texts.get(&id).map(String::as_str)
+    }
+
+    /// Returns an iterator over the glyphs with known text, in glyph id order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (GlyphId, &str)> + '_ {
+        self.texts.iter().map(|(id, text)| (*id, text.as_str()))
+    }
+
+    /// Returns the number of glyphs with known text.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Returns `true` if no glyphs have known text.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+}
+
+/// Returns `true` for chars that poorly represent a glyph's text, i.e. private use chars
+/// & alphabetic presentation forms, which are usually ligatures.
+#[inline]
+fn is_fallback(c: char) -> bool {
+    matches!(
+        c,
+        '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{10FFFF}' | '\u{FB00}'..='\u{FB4F}'
+    )
+}
+
+/// Returns `true` if `text` is a single fallback char.
+#[inline]
+fn is_fallback_text(text: &str) -> bool {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => is_fallback(c),
+        _ => false,
+    }
+}
@@ -0,0 +1,183 @@
+//! Simple vertical grid fitting, similar to "light" autohinting.
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, Outline, OutlineCurve, Point, PxScale, PxScaleFactor, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Edges nearer than this many pixels, in the same direction, are snapped together.
+/// Suppresses overshoots of round glyph tops & bottoms at small sizes.
+const MERGE_PX: f32 = 0.5;
+/// Opposing edges, e.g. the top & bottom of a stem, at least this many pixels apart are
+/// kept at least a pixel apart.
+const MIN_STEM_PX: f32 = 0.25;
+
+/// Horizontal outline edge in pixels.
+#[derive(Clone, Copy)]
+struct Edge {
+    y: f32,
+    /// Direction the outline travels along the edge, true if rightwards.
+    rightwards: bool,
+    /// Whether the edge is a straight line, rather than a curve extremum.
+    flat: bool,
+}
+
+/// Group of edges snapped to the same pixel boundary.
+#[derive(Clone, Copy)]
+struct Zone {
+    min: f32,
+    max: f32,
+    rightwards: bool,
+    snapped: f32,
+}
+
+/// Vertical autohinting configuration, snapping horizontal edges like the baseline,
+/// x-height & stem tops to the pixel grid for sharper small text.
+///
+/// See [`Font::outline_glyph_hinted`](trait.Font.html#method.outline_glyph_hinted).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hinting {
+    /// Glyphs with a vertical pixel scale at or below this are hinted. Larger text is
+    /// sharp enough unhinted, where hinting would only distort its shapes.
+    /// Default `24.0`.
+    pub max_px_scale: f32,
+}
+
+impl Default for Hinting {
+    #[inline]
+    fn default() -> Self {
+        Self { max_px_scale: 24.0 }
+    }
+}
+
+impl Hinting {
+    /// Returns `true` if glyphs at `scale` are hinted.
+    #[inline]
+    pub fn applies(&self, scale: PxScale) -> bool {
+        scale.y <= self.max_px_scale
+    }
+}
+
+/// Returns the outline scaled into pixels with horizontal edges snapped to whole pixels &
+/// other points interpolated between them.
+pub(crate) fn hint(outline: &Outline, scale: PxScaleFactor) -> Outline {
+    let (h_scale, v_scale) = (scale.horizontal, scale.vertical);
+    let zones = zones(outline, v_scale);
+    let fit = |p: &Point| point(p.x * h_scale, fit_y(&zones, p.y * v_scale));
+
+    let curves = outline
+        .curves
+        .iter()
+        .map(|curve| match curve {
+            OutlineCurve::Line(p0, p1) => OutlineCurve::Line(fit(p0), fit(p1)),
+            OutlineCurve::Quad(p0, p1, p2) => OutlineCurve::Quad(fit(p0), fit(p1), fit(p2)),
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                OutlineCurve::Cubic(fit(p0), fit(p1), fit(p2), fit(p3))
+            }
+        })
+        .collect();
+
+    Outline {
+        bounds: Rect {
+            min: fit(&outline.bounds.min),
+            max: fit(&outline.bounds.max),
+        },
+        curves,
+    }
+}
+
+/// Finds horizontal edges, at straight lines & curve extrema, grouping them into zones
+/// sorted by y.
+fn zones(outline: &Outline, scale: f32) -> Vec<Zone> {
+    let mut edges = Vec::new();
+    let mut edge = |p: &Point, tangent: &Point, flat: bool| {
+        // horizontal to within a font unit
+        if (tangent.y - p.y).abs() < 1.0 && tangent.x != p.x {
+            edges.push(Edge {
+                y: p.y * scale,
+                rightwards: tangent.x > p.x,
+                flat,
+            });
+        }
+    };
+    for curve in &outline.curves {
+        match curve {
+            OutlineCurve::Line(p0, p1) => edge(p0, p1, true),
+            OutlineCurve::Quad(p0, p1, p2) => {
+                edge(p0, p1, false);
+                edge(p2, &(*p2 * 2.0 - *p1), false);
+            }
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                edge(p0, p1, false);
+                edge(p3, &(*p3 * 2.0 - *p2), false);
+            }
+        }
+    }
+    edges.sort_by(|a, b| a.y.partial_cmp(&b.y).unwrap_or(core::cmp::Ordering::Equal));
+
+    let mut zones: Vec<Zone> = Vec::new();
+    // y of the edge the last zone is snapped by & whether it's straight, preferring
+    // straight edges
+    let mut anchor = (0.0, false);
+    for edge in edges {
+        match zones.last_mut() {
+            Some(zone) if zone.rightwards == edge.rightwards && edge.y - zone.min < MERGE_PX => {
+                zone.max = edge.y;
+                if edge.flat && !anchor.1 {
+                    anchor = (edge.y, true);
+                }
+            }
+            _ => {
+                snap(&mut zones, anchor.0);
+                zones.push(Zone {
+                    min: edge.y,
+                    max: edge.y,
+                    rightwards: edge.rightwards,
+                    snapped: edge.y,
+                });
+                anchor = (edge.y, edge.flat);
+            }
+        }
+    }
+    snap(&mut zones, anchor.0);
+    zones
+}
+
+/// Snaps the last zone, rounding its `anchor` y, to a whole pixel at or above the previous
+/// zone, keeping stems at least a pixel tall.
+fn snap(zones: &mut [Zone], anchor: f32) {
+    let (last, previous) = match zones {
+        [.., previous, last] => (last, Some(*previous)),
+        [last] => (last, None),
+        [] => return,
+    };
+    last.snapped = anchor.round();
+    if let Some(previous) = previous {
+        let stem = previous.rightwards != last.rightwards && last.min - previous.max >= MIN_STEM_PX;
+        let min_snapped = previous.snapped + if stem { 1.0 } else { 0.0 };
+        last.snapped = last.snapped.max(min_snapped);
+    }
+}
+
+/// Maps a pixel y coordinate, snapping zones & linearly interpolating between them.
+fn fit_y(zones: &[Zone], y: f32) -> f32 {
+    let (first, last) = match (zones.first(), zones.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return y,
+    };
+    if y < first.min {
+        return y - first.min + first.snapped;
+    }
+    if y > last.max {
+        return y - last.max + last.snapped;
+    }
+
+    let idx = zones.iter().position(|zone| y <= zone.max).unwrap_or(0);
+    let zone = &zones[idx];
+    if y >= zone.min || idx == 0 {
+        return zone.snapped;
+    }
+    let below = &zones[idx - 1];
+    let t = (y - below.max) / (zone.min - below.max);
+    below.snapped + t * (zone.snapped - below.snapped)
+}
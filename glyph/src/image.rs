@@ -0,0 +1,91 @@
+//! [image](https://docs.rs/image) crate output, requires the `image` feature.
+use crate::OutlinedGlyph;
+use image2::{GrayImage, Luma, Rgba, RgbaImage};
+
+impl OutlinedGlyph {
+    /// Draws layer `0` into a `Luma8` image sized to [`px_bounds`](#method.px_bounds),
+    /// mapping coverage to `0..=255`. Requires the `image` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let outlined = font.outline_glyph(font.glyph_id('q').with_scale(40.0)).unwrap();
+    ///
+    /// let image = outlined.to_alpha_image();
+    /// let px = outlined.px_irect();
+    /// assert_eq!(image.dimensions(), (px.width(), px.height()));
+    /// assert!(image.pixels().any(|p| p.0[0] == 255));
+    /// // image.save("q.png")
+    /// # Ok(()) }
+    /// ```
+    pub fn to_alpha_image(&self) -> GrayImage {
+        let px = self.px_irect();
+        let mut image = GrayImage::new(px.width(), px.height());
+        self.draw(0, |x, y, c| {
+            image.put_pixel(x, y, Luma([(c * 255.0).round() as u8]));
+        });
+        image
+    }
+
+    /// Draws all layers into an `Rgba8` image sized to [`px_bounds`](#method.px_bounds),
+    /// compositing color layers bottom first with their colors. Requires the `image` feature.
+    ///
+    /// Non-color glyphs are drawn with `foreground`, as `0xRRGGBBAA`. Pixels outside the
+    /// glyph are transparent.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let outlined = font.outline_glyph(font.glyph_id('q').with_scale(40.0)).unwrap();
+    ///
+    /// let image = outlined.to_rgba_image(0xFF00_00FF);
+    /// assert!(image.pixels().any(|p| p.0 == [255, 0, 0, 255]));
+    /// assert!(image.pixels().any(|p| p.0[3] == 0));
+    /// # Ok(()) }
+    /// ```
+    pub fn to_rgba_image(&self, foreground: u32) -> RgbaImage {
+        let px = self.px_irect();
+        let width = px.width() as usize;
+        // non-premultiplied rgba
+        let mut pixels = vec![[0.0_f32; 4]; width * px.height() as usize];
+
+        let layers = self.get_colored_layers();
+        for layer in 0..layers.unwrap_or(1) {
+            let color = match layers {
+                Some(_) => self.get_color(layer),
+                None => foreground,
+            };
+            let [r, g, b, a] = color.to_be_bytes();
+            let rgb = [f32::from(r), f32::from(g), f32::from(b)];
+            let alpha = f32::from(a) / 255.0;
+
+            self.draw(layer, |x, y, c| {
+                let src_a = alpha * c;
+                if src_a <= 0.0 {
+                    return;
+                }
+                // source-over
+                let dst = &mut pixels[y as usize * width + x as usize];
+                let dst_a = dst[3] * (1.0 - src_a);
+                let out_a = src_a + dst_a;
+                for (d, s) in dst[..3].iter_mut().zip(&rgb) {
+                    *d = (s * src_a + *d * dst_a) / out_a;
+                }
+                dst[3] = out_a;
+            });
+        }
+
+        let mut image = RgbaImage::new(px.width(), px.height());
+        for (p, [r, g, b, a]) in image.pixels_mut().zip(pixels) {
+            let to_u8 = |v: f32| v.round().clamp(0.0, 255.0) as u8;
+            *p = Rgba([to_u8(r), to_u8(g), to_u8(b), to_u8(a * 255.0)]);
+        }
+        image
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, OutlinedGlyph, Rect};
+
+/// Whole pixel rectangle, with top-left pixel at `min` & `max` one past the bottom-right
+/// pixel, so `max - min` is the size.
+///
+/// Converting from a [`Rect`](struct.Rect.html) rounds outwards, flooring `min` & ceiling
+/// `max`, so every pixel touched by the rect is included. Use this rather than rounding
+/// `Rect` values, which can crop the edge pixels of a glyph.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, IRect, Rect};
+///
+/// let rect = Rect { min: point(-0.5, 1.2), max: point(10.1, 20.0) };
+/// let px = IRect::from(rect);
+/// assert_eq!(px, IRect { min: (-1, 1), max: (11, 20) });
+/// assert_eq!((px.width(), px.height()), (12, 19));
+///
+/// // iterate rows & columns of pixels
+/// for y in px.min.1..px.max.1 {
+///     for x in px.min.0..px.max.0 { /* ... */ }
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IRect {
+    /// Top-left pixel `(x, y)`.
+    pub min: (i32, i32),
+    /// Pixel `(x, y)` one past the bottom-right.
+    pub max: (i32, i32),
+}
+
+impl IRect {
+    /// Pixel width, `0` if `max.0 <= min.0`.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        (i64::from(self.max.0) - i64::from(self.min.0)).max(0) as u32
+    }
+
+    /// Pixel height, `0` if `max.1 <= min.1`.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        (i64::from(self.max.1) - i64::from(self.min.1)).max(0) as u32
+    }
+
+    /// Returns `true` if the rect contains no pixels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.max.0 <= self.min.0 || self.max.1 <= self.min.1
+    }
+
+    /// Returns `true` if pixel `(x, y)` is inside the rect.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// let px = IRect { min: (0, 0), max: (4, 4) };
+    /// assert!(px.contains(0, 3));
+    /// assert!(!px.contains(4, 2));
+    /// ```
+    #[inline]
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        self.min.0 <= x && x < self.max.0 && self.min.1 <= y && y < self.max.1
+    }
+
+    /// Returns the pixels in both rects, `None` if they don't overlap.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// let a = IRect { min: (0, 0), max: (4, 4) };
+    /// let b = IRect { min: (2, 1), max: (6, 3) };
+    /// assert_eq!(a.intersect(&b), Some(IRect { min: (2, 1), max: (4, 3) }));
+    /// assert_eq!(a.intersect(&IRect { min: (4, 0), max: (6, 4) }), None);
+    /// ```
+    #[inline]
+    pub fn intersect(&self, other: &IRect) -> Option<IRect> {
+        let rect = IRect {
+            min: (self.min.0.max(other.min.0), self.min.1.max(other.min.1)),
+            max: (self.max.0.min(other.max.0), self.max.1.min(other.max.1)),
+        };
+        if rect.is_empty() {
+            None
+        } else {
+            Some(rect)
+        }
+    }
+
+    /// Returns the smallest rect containing both rects.
+    #[inline]
+    pub fn union(&self, other: &IRect) -> IRect {
+        IRect {
+            min: (self.min.0.min(other.min.0), self.min.1.min(other.min.1)),
+            max: (self.max.0.max(other.max.0), self.max.1.max(other.max.1)),
+        }
+    }
+}
+
+impl From<Rect> for IRect {
+    /// Rounds outwards, flooring `min` & ceiling `max`.
+    #[inline]
+    fn from(rect: Rect) -> Self {
+        IRect {
+            min: (rect.min.x.floor() as i32, rect.min.y.floor() as i32),
+            max: (rect.max.x.ceil() as i32, rect.max.y.ceil() as i32),
+        }
+    }
+}
+
+impl From<IRect> for Rect {
+    #[inline]
+    fn from(rect: IRect) -> Self {
+        Rect {
+            min: point(rect.min.0 as f32, rect.min.1 as f32),
+            max: point(rect.max.0 as f32, rect.max.1 as f32),
+        }
+    }
+}
+
+impl OutlinedGlyph {
+    /// Conservative whole pixel bounding box for this glyph, as an [`IRect`](struct.IRect.html).
+    ///
+    /// Equivalent to [`px_bounds`](#method.px_bounds) converted to integers, so pixel
+    /// `(x, y)` from [`draw`](#method.draw) is at `(min.0 + x, min.1 + y)`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let glyph = font.glyph_id('q').with_scale_and_position(24.0, point(10.5, 20.5));
+    /// let outlined = font.outline_glyph(glyph).unwrap();
+    ///
+    /// let px = outlined.px_irect();
+    /// let mut drawn = (0, 0);
+    /// outlined.draw(0, |x, y, _| drawn = (drawn.0.max(x + 1), drawn.1.max(y + 1)));
+    /// assert_eq!(drawn, (px.width(), px.height()));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn px_irect(&self) -> IRect {
+        self.px_bounds().into()
+    }
+}
@@ -0,0 +1,1302 @@
+//! Simple text layout.
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{
+    point, Direction, Font, Glyph, GlyphId, GlyphRun, NumericStyle, OpticalBounds, Point, PxScale,
+    ScaleFont, Script,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::{marker::PhantomData, ops::Range, str::Chars};
+
+/// How a character advances the layout caret.
+///
+/// Fonts inconsistently map whitespace & invisible characters, so these are handled
+/// explicitly rather than relying on the font's glyph, or notdef, advance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CharAdvance {
+    /// Draw the font's glyph & advance by its advance.
+    Glyph,
+    /// Space character, using the font's glyph if mapped, otherwise advancing by the
+    /// given fraction of an em without drawing a glyph.
+    Space(f32),
+    /// Space character defined by the width of another character, using the font's glyph
+    /// if mapped, otherwise advancing by the given character's advance without drawing a
+    /// glyph.
+    SpaceAs(char),
+    /// Advance to the next tab stop without drawing a glyph.
+    Tab,
+    /// Start a new line.
+    Newline,
+    /// Invisible with zero advance, e.g. zero width space, joiners & control characters.
+    Zero,
+}
+
+impl CharAdvance {
+    /// Classifies a character.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::CharAdvance;
+    /// assert_eq!(CharAdvance::of('a'), CharAdvance::Glyph);
+    /// assert_eq!(CharAdvance::of('\u{2009}'), CharAdvance::Space(0.2)); // thin space
+    /// assert_eq!(CharAdvance::of('\u{2008}'), CharAdvance::SpaceAs('.')); // punctuation space
+    /// assert_eq!(CharAdvance::of('\u{200B}'), CharAdvance::Zero); // zero width space
+    /// assert_eq!(CharAdvance::of('\t'), CharAdvance::Tab);
+    /// assert_eq!(CharAdvance::of('\u{FE0F}'), CharAdvance::Zero); // emoji presentation
+    /// assert_eq!(CharAdvance::of('\u{200F}'), CharAdvance::Zero); // right-to-left mark
+    /// ```
+    pub fn of(c: char) -> Self {
+        match c {
+            '\t' => Self::Tab,
+            '\n' | '\u{2028}' | '\u{2029}' => Self::Newline,
+            ' ' | '\u{A0}' => Self::Space(0.25),
+            // en quad, em quad, en space, em space
+            '\u{2000}' | '\u{2002}' => Self::Space(0.5),
+            '\u{2001}' | '\u{2003}' => Self::Space(1.0),
+            // three-per-em, four-per-em & six-per-em spaces
+            '\u{2004}' => Self::Space(1.0 / 3.0),
+            '\u{2005}' => Self::Space(0.25),
+            '\u{2006}' => Self::Space(1.0 / 6.0),
+            // figure & punctuation spaces, the widths of a digit & a period
+            '\u{2007}' => Self::SpaceAs('0'),
+            '\u{2008}' => Self::SpaceAs('.'),
+            // thin, hair & narrow no-break spaces
+            '\u{2009}' | '\u{202F}' => Self::Space(0.2),
+            '\u{200A}' => Self::Space(0.1),
+            // medium mathematical space
+            '\u{205F}' => Self::Space(4.0 / 18.0),
+            // ideographic space
+            '\u{3000}' => Self::Space(1.0),
+            // zero width space, non-joiner, joiner, word joiner, BOM & soft hyphen
+            '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{AD}' => Self::Zero,
+            // bidi marks, embeddings, overrides & isolates
+            '\u{200E}' | '\u{200F}' | '\u{61C}' => Self::Zero,
+            '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => Self::Zero,
+            // variation selectors, selecting the glyph of the preceding char
+            c if is_variation_selector(c) => Self::Zero,
+            c if c.is_control() => Self::Zero,
+            _ => Self::Glyph,
+        }
+    }
+}
+
+/// Returns `true` if lines may wrap after `c`, i.e. breaking spaces, zero width space &
+/// hyphens.
+#[inline]
+fn is_break_after(c: char) -> bool {
+    match c {
+        // no-break, figure & narrow no-break spaces
+        '\u{A0}' | '\u{2007}' | '\u{202F}' => false,
+        '\u{200B}' | '-' | '\u{2010}' => true,
+        c => c.is_whitespace() && CharAdvance::of(c) != CharAdvance::Newline,
+    }
+}
+
+/// Returns `true` for unicode variation selectors VS1-VS256.
+#[inline]
+fn is_variation_selector(c: char) -> bool {
+    matches!(c, '\u{FE00}'..='\u{FE0F}' | '\u{E0100}'..='\u{E01EF}')
+}
+
+/// Returns the glyph of the variation sequence of the char at `idx` & the following
+/// variation selector, if any.
+#[inline]
+fn variation_glyph<F: Font>(font: &F, text: &str, idx: usize, c: char) -> Option<GlyphId> {
+    let selector = text[idx + c.len_utf8()..]
+        .chars()
+        .next()
+        .filter(|&s| is_variation_selector(s))?;
+    font.glyph_id_variation(c, selector)
+}
+
+/// Whitespace & invisible character handling configuration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WhitespacePolicy {
+    /// Tab stop interval as a multiple of the space advance. Default `4.0`.
+    pub tab_size: f32,
+    /// Use the nominal em fraction advance for spaces the font doesn't map, rather than
+    /// the font's notdef advance. Default `true`.
+    pub synthesize_spaces: bool,
+    /// Draw the font's glyphs of invisible characters, like joiners, variation selectors,
+    /// bidi & other control characters, with their advance, e.g. to debug text content.
+    /// Otherwise these are never drawn with zero advance, even if the font maps them to
+    /// visible glyphs. Default `false`.
+    pub show_invisible: bool,
+}
+
+impl Default for WhitespacePolicy {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            tab_size: 4.0,
+            synthesize_spaces: true,
+            show_invisible: false,
+        }
+    }
+}
+
+impl WhitespacePolicy {
+    /// Returns the glyph to draw, if any, & the caret advance of a non-newline character
+    /// `line_x` pixels from the line start.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let font = font.as_scaled(24.0);
+    /// let policy = WhitespacePolicy::default();
+    ///
+    /// // zero width space
+    /// assert_eq!(policy.advance(&font, '\u{200B}', 5.0), (None, 0.0));
+    ///
+    /// // zero width joiner drawn for debugging
+    /// let debug = WhitespacePolicy {
+    ///     show_invisible: true,
+    ///     ..policy
+    /// };
+    /// let zwj = font.glyph_id('\u{200D}');
+    /// assert_eq!(debug.advance(&font, '\u{200D}', 5.0), (Some(zwj), font.h_advance(zwj)));
+    ///
+    /// // tab advances to the next tab stop
+    /// let tab_stop = 4.0 * font.h_advance(font.glyph_id(' '));
+    /// let (glyph, advance) = policy.advance(&font, '\t', 5.0);
+    /// assert_eq!(glyph, None);
+    /// assert!((5.0 + advance - tab_stop).abs() < 1e-4);
+    /// ```
+    pub fn advance<F, SF>(&self, font: &SF, c: char, line_x: f32) -> (Option<GlyphId>, f32)
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        match CharAdvance::of(c) {
+            CharAdvance::Glyph => {
+                let id = font.glyph_id(c);
+                (Some(id), font.h_advance(id))
+            }
+            CharAdvance::Zero if self.show_invisible => {
+                let id = font.glyph_id(c);
+                (Some(id), font.h_advance(id))
+            }
+            CharAdvance::Space(em) => {
+                let mut id = font.glyph_id(c);
+                if id.0 == 0 && c == '\u{A0}' {
+                    // fallback to a regular space
+                    id = font.glyph_id(' ');
+                }
+                if id.0 == 0 && self.synthesize_spaces {
+                    let units_per_em = font
+                        .font()
+                        .units_per_em()
+                        .unwrap_or_else(|| font.font().height_unscaled());
+                    (None, font.h_units_to_px(units_per_em * em))
+                } else {
+                    (Some(id), font.h_advance(id))
+                }
+            }
+            CharAdvance::SpaceAs(width_of) => {
+                let id = font.glyph_id(c);
+                if id.0 == 0 && self.synthesize_spaces {
+                    (None, font.h_advance(font.glyph_id(width_of)))
+                } else {
+                    (Some(id), font.h_advance(id))
+                }
+            }
+            CharAdvance::Tab => {
+                let stop = self.tab_size * self.advance(font, ' ', 0.0).1;
+                if stop > 0.0 {
+                    (None, ((line_x / stop).floor() + 1.0) * stop - line_x)
+                } else {
+                    (None, 0.0)
+                }
+            }
+            CharAdvance::Newline | CharAdvance::Zero => (None, 0.0),
+        }
+    }
+}
+
+/// Rounding of caret advances, including kerning, during layout.
+///
+/// Whole or half pixel advances place glyphs consistently on the pixel grid for crisper
+/// text on low-DPI screens, subpixel advances keep the font's exact widths for print &
+/// export. Text measured with one should also be drawn with it, as rounding drifts from
+/// subpixel positions along a line.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, AdvanceRounding, Font, FontRef, Layout};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let layout = Layout {
+///     rounding: AdvanceRounding::Pixel,
+///     ..Layout::default()
+/// };
+///
+/// let mut glyphs = Vec::new();
+/// layout.layout(font.as_scaled(13.0), point(0.0, 0.0), "Hello world", &mut glyphs);
+/// assert!(glyphs.iter().all(|g| g.position.x.fract() == 0.0));
+///
+/// assert_eq!(AdvanceRounding::HalfPixel.round(6.3), 6.5);
+/// assert_eq!(AdvanceRounding::Subpixel.round(6.3), 6.3);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AdvanceRounding {
+    /// Exact fractional advances.
+    Subpixel,
+    /// Advances rounded to the nearest half pixel.
+    HalfPixel,
+    /// Advances rounded to the nearest whole pixel.
+    Pixel,
+}
+
+impl Default for AdvanceRounding {
+    #[inline]
+    fn default() -> Self {
+        Self::Subpixel
+    }
+}
+
+impl AdvanceRounding {
+    /// Rounds a pixel advance.
+    #[inline]
+    pub fn round(self, advance: f32) -> f32 {
+        match self {
+            Self::Subpixel => advance,
+            Self::HalfPixel => (advance * 2.0).round() / 2.0,
+            Self::Pixel => advance.round(),
+        }
+    }
+}
+
+/// How [`Layout::measure`](struct.Layout.html#method.measure) measures the width of text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WidthMode {
+    /// Width from the line start to the end caret position, the sum of advances & kerning
+    /// including side bearings & trailing whitespace. See
+    /// [`LineMetrics::width`](struct.LineMetrics.html#structfield.width).
+    Advance,
+    /// Width of the ink, from the leftmost glyph's left outline edge to the rightmost
+    /// glyph's right outline edge, excluding side bearings & whitespace. Useful for
+    /// visually tight boxes, e.g. badges & buttons.
+    Ink,
+}
+
+impl Default for WidthMode {
+    #[inline]
+    fn default() -> Self {
+        Self::Advance
+    }
+}
+
+/// Override of the advance of a class of characters, see
+/// [`AdvanceOverrides`](struct.AdvanceOverrides.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AdvanceOverride {
+    /// Fixed advance as a fraction of an em, with each glyph centered within it.
+    Fixed(f32),
+    /// Advance of a reference char's glyph, with each glyph centered within it, e.g.
+    /// `Match('0')` for tabular digits in a proportional font.
+    Match(char),
+    /// Font advance multiplied by a factor, e.g. `0.9` to tighten.
+    Scale(f32),
+    /// Font advance plus a fraction of an em, e.g. `-0.05` to tighten.
+    Adjust(f32),
+}
+
+impl AdvanceOverride {
+    /// Returns `true` for fixed width cells, `Fixed` & `Match`, which aren't kerned.
+    #[inline]
+    pub fn is_cell(self) -> bool {
+        match self {
+            Self::Fixed(_) | Self::Match(_) => true,
+            Self::Scale(_) | Self::Adjust(_) => false,
+        }
+    }
+
+    /// Returns the overridden advance of a glyph with the pixel `advance` & the glyph's
+    /// horizontal offset within it.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let font = font.as_scaled(24.0);
+    /// let (one, zero) = (font.h_advance(font.glyph_id('1')), font.h_advance(font.glyph_id('0')));
+    ///
+    /// let (advance, offset) = AdvanceOverride::Match('0').apply(&font, one);
+    /// assert_eq!(advance, zero);
+    /// assert_eq!(offset, (zero - one) / 2.0);
+    ///
+    /// assert_eq!(AdvanceOverride::Scale(0.5).apply(&font, one), (one / 2.0, 0.0));
+    /// ```
+    pub fn apply<F, SF>(self, font: &SF, advance: f32) -> (f32, f32)
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        let em = || {
+            let font = font.font();
+            font.units_per_em()
+                .unwrap_or_else(|| font.height_unscaled())
+        };
+        let overridden = match self {
+            Self::Fixed(ems) => font.h_units_to_px(em() * ems),
+            Self::Match(c) => font.h_advance(font.glyph_id(c)),
+            Self::Scale(factor) => return (advance * factor, 0.0),
+            Self::Adjust(ems) => return (advance + font.h_units_to_px(em() * ems), 0.0),
+        };
+        (overridden, (overridden - advance) / 2.0)
+    }
+}
+
+/// Per-class advance overrides, adjusting glyph spacing without modifying the font, e.g.
+/// tabular digits for counters or tighter letter fit for headings.
+///
+/// Overrides apply to visible glyphs classified by their source char, before
+/// [rounding](enum.AdvanceRounding.html).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, AdvanceOverride, AdvanceOverrides, Font, FontRef, Layout};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let counter = Layout {
+///     advances: AdvanceOverrides {
+///         digits: Some(AdvanceOverride::Match('0')),
+///         ..AdvanceOverrides::default()
+///     },
+///     ..Layout::default()
+/// };
+///
+/// // digits of changing values line up
+/// let (mut a, mut b) = (Vec::new(), Vec::new());
+/// let a_lines = counter.layout(font.as_scaled(24.0), point(0.0, 0.0), "1111", &mut a);
+/// let b_lines = counter.layout(font.as_scaled(24.0), point(0.0, 0.0), "8080", &mut b);
+/// assert_eq!(a_lines[0].width, b_lines[0].width);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AdvanceOverrides {
+    /// Override of decimal digits. Default `None`.
+    pub digits: Option<AdvanceOverride>,
+    /// Override of letters. Default `None`.
+    pub letters: Option<AdvanceOverride>,
+    /// Override of ASCII punctuation. Default `None`.
+    pub punctuation: Option<AdvanceOverride>,
+    /// Override of all other visible glyphs, e.g. symbols. Default `None`.
+    pub other: Option<AdvanceOverride>,
+}
+
+impl AdvanceOverrides {
+    /// Returns the override of a char's class.
+    #[inline]
+    pub fn get(&self, c: char) -> Option<AdvanceOverride> {
+        if c.is_ascii_digit() {
+            self.digits
+        } else if c.is_alphabetic() {
+            self.letters
+        } else if c.is_ascii_punctuation() {
+            self.punctuation
+        } else {
+            self.other
+        }
+    }
+}
+
+/// Small capitals rendering of letters.
+///
+/// Uses the font's small capital glyphs, "smcp" & "c2sc" substitutions, when available,
+/// otherwise synthesizes them from uppercase glyphs scaled by
+/// [`SYNTHETIC_SCALE`](#associatedconstant.SYNTHETIC_SCALE), giving consistent small caps
+/// across fonts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SmallCaps {
+    /// Lowercase letters as small capitals.
+    Lowercase,
+    /// Lowercase & uppercase letters as small capitals.
+    All,
+}
+
+impl SmallCaps {
+    /// Scale of synthesized small capitals relative to the font's scale.
+    pub const SYNTHETIC_SCALE: f32 = 0.7;
+
+    /// Returns the small capital glyph of a char & its scale relative to the font's scale,
+    /// or the regular glyph & `1.0` for chars that aren't small capitals.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, Script, SmallCaps};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let script = Script::new(b"latn");
+    ///
+    /// // Exo2 has no small capital glyphs, so they're synthesized
+    /// let (id, scale) = SmallCaps::Lowercase.glyph(&font, script, 'a');
+    /// assert_eq!((id, scale), (font.glyph_id('A'), SmallCaps::SYNTHETIC_SCALE));
+    ///
+    /// let (id, scale) = SmallCaps::Lowercase.glyph(&font, script, 'B');
+    /// assert_eq!((id, scale), (font.glyph_id('B'), 1.0));
+    /// let (id, scale) = SmallCaps::All.glyph(&font, script, 'B');
+    /// assert_eq!((id, scale), (font.glyph_id('B'), SmallCaps::SYNTHETIC_SCALE));
+    /// # Ok(()) }
+    /// ```
+    pub fn glyph<F: Font>(self, font: &F, script: Script, c: char) -> (GlyphId, f32) {
+        let id = font.glyph_id(c);
+        let feature = if c.is_lowercase() {
+            *b"smcp"
+        } else if c.is_uppercase() && self == Self::All {
+            *b"c2sc"
+        } else {
+            return (id, 1.0);
+        };
+
+        let mut ids = vec![id];
+        font.substitute(script, &[feature], &mut ids);
+        match ids[..] {
+            [small_cap] if small_cap != id => (small_cap, 1.0),
+            _ => {
+                // synthesize from the uppercase glyph, unless it's multiple chars like "SS"
+                let mut upper = c.to_uppercase();
+                match (upper.next(), upper.next()) {
+                    (Some(upper), None) => (font.glyph_id(upper), Self::SYNTHETIC_SCALE),
+                    _ => (id, 1.0),
+                }
+            }
+        }
+    }
+}
+
+/// Simple left-aligned paragraph layout.
+///
+/// # Example
+/// ```
+/// # use ab_glyph::*;
+/// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+/// let layout = Layout {
+///     max_width: 200.0,
+///     ..Layout::default()
+/// };
+///
+/// let mut glyphs = Vec::new();
+/// let lines = layout.layout(
+///     font.as_scaled(24.0),
+///     point(0.0, 0.0),
+///     "Hello\u{200B}\tworld\nline 2",
+///     &mut glyphs,
+/// );
+///
+/// // zero width space, tab & newline are not drawn
+/// assert_eq!(glyphs.len(), 16);
+///
+/// assert_eq!(lines.len(), 2);
+/// assert_eq!(lines[0].glyphs, 0..10);
+/// assert_eq!(lines[1].glyphs, 10..16);
+/// assert!(lines[1].baseline > lines[0].baseline);
+///
+/// // lines wrap after spaces & hyphens, keeping words whole
+/// let text = "wrap these well-kept words";
+/// let narrow = Layout {
+///     max_width: 80.0,
+///     ..layout
+/// };
+/// let mut glyphs = Vec::new();
+/// let lines = narrow.layout(font.as_scaled(24.0), point(0.0, 0.0), text, &mut glyphs);
+/// assert!(lines.iter().all(|line| glyphs[line.glyphs.start].position.x == 0.0));
+/// let lines: Vec<_> = lines.into_iter().map(|line| &text[line.text]).collect();
+/// assert_eq!(lines, ["wrap ", "these ", "well-", "kept ", "words"]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    /// Maximum line width in pixels. Lines wrap after the last breaking space, zero width
+    /// space or hyphen before exceeding this, or before the exceeding glyph if the line
+    /// has no such break. Default `f32::INFINITY`.
+    pub max_width: f32,
+    /// Whitespace & invisible character handling.
+    pub whitespace: WhitespacePolicy,
+    /// Script & language of the text. If set, the script's
+    /// [`REQUIRED_FEATURES`](struct.Script.html#associatedconstant.REQUIRED_FEATURES)
+    /// substitutions are applied to each run of visible glyphs. Default `None`.
+    pub script: Option<Script>,
+    /// Small capitals rendering of letters. Default `None`.
+    pub small_caps: Option<SmallCaps>,
+    /// Figure spacing, case & position substitutions. Default no change.
+    pub numeric: NumericStyle,
+    /// Apply standard ligatures, e.g. "fi" & "ffi", to each run of visible glyphs. See
+    /// [`Script::LIGATURE_FEATURES`](struct.Script.html#associatedconstant.LIGATURE_FEATURES).
+    /// Default `false`.
+    pub ligatures: bool,
+    /// Direction of the text. Right to left lines are wrapped in logical order, then
+    /// positioned in visual order from the right edge of each line, kerning visually
+    /// adjacent pairs. Default [`LeftToRight`](enum.Direction.html#variant.LeftToRight).
+    pub direction: Direction,
+    /// Rounding of advances & kerning. Default
+    /// [`Subpixel`](enum.AdvanceRounding.html#variant.Subpixel).
+    pub rounding: AdvanceRounding,
+    /// Per-class advance overrides. Default none.
+    pub advances: AdvanceOverrides,
+    /// Hang glyphs, typically punctuation, into the margins by their
+    /// [`optical_bounds`](trait.ScaleFont.html#method.optical_bounds), so the optical
+    /// edges of left to right lines align with the margins. The first glyph of each
+    /// line is shifted left by its optical left delta & lines wrap once a glyph's
+    /// optical right edge exceeds `max_width`, so line widths may exceed it by the
+    /// hang. Default `false`.
+    pub hang_punctuation: bool,
+}
+
+impl Default for Layout {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_width: f32::INFINITY,
+            whitespace: WhitespacePolicy::default(),
+            script: None,
+            small_caps: None,
+            numeric: NumericStyle::default(),
+            ligatures: false,
+            direction: Direction::default(),
+            rounding: AdvanceRounding::default(),
+            advances: AdvanceOverrides::default(),
+            hang_punctuation: false,
+        }
+    }
+}
+
+impl Layout {
+    /// Lays out `text` glyphs into `target` applying kerning & wrapping, returning the
+    /// metrics of each line. `position` is the top-left of the paragraph.
+    ///
+    /// Glyphs & lines are always in logical, i.e. text, order. Right to left glyphs are
+    /// positioned with the first glyph of each line on the right.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Direction, Font, FontRef, Layout, ScaleFont};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/KernTest-Hebrew.ttf"))?;
+    /// // 1000 units per em, so 100px is 0.1px per unit
+    /// let scaled_font = font.as_scaled(100.0);
+    /// let rtl = Layout {
+    ///     direction: Direction::RightToLeft,
+    ///     ..Layout::default()
+    /// };
+    ///
+    /// let mut glyphs = Vec::new();
+    /// let lines = rtl.layout(&scaled_font, point(0.0, 0.0), "בו", &mut glyphs);
+    /// let (bet, vav) = (&glyphs[0], &glyphs[1]);
+    ///
+    /// // vav is drawn left of bet, kerned as the visual pair "וב" not the logical "בו"
+    /// let kern = scaled_font.kern(vav.id, bet.id);
+    /// assert!((kern - -10.0).abs() < 1e-3);
+    /// assert_ne!(kern, scaled_font.kern(bet.id, vav.id));
+    /// assert_eq!(vav.position.x, 0.0);
+    /// assert!((bet.position.x - (scaled_font.h_advance(vav.id) + kern)).abs() < 1e-3);
+    /// assert!((bet.position.x + scaled_font.h_advance(bet.id) - lines[0].width).abs() < 1e-3);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// Wrapped right to left lines each start at the right edge, keeping their visual pair
+    /// kerning & the offsets of [advance overrides](struct.AdvanceOverrides.html).
+    /// ```
+    /// use ab_glyph::{point, AdvanceOverride, AdvanceOverrides, Direction, Font, FontRef, Layout};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/KernTest-Hebrew.ttf"))?;
+    /// let scaled_font = font.as_scaled(100.0);
+    /// let rtl = Layout {
+    ///     direction: Direction::RightToLeft,
+    ///     max_width: 150.0,
+    ///     ..Layout::default()
+    /// };
+    /// // glyph x positions & width of each line, in logical order
+    /// let layout = |layout: Layout, text| {
+    ///     let mut glyphs = Vec::new();
+    ///     let lines = layout.layout(&scaled_font, point(10.0, 0.0), text, &mut glyphs);
+    ///     lines
+    ///         .into_iter()
+    ///         .map(|line| {
+    ///             let glyphs = &glyphs[line.glyphs];
+    ///             let xs: Vec<_> = glyphs.iter().map(|g| g.position.x.round()).collect();
+    ///             (xs, line.width.round())
+    ///         })
+    ///         .collect::<Vec<_>>()
+    /// };
+    ///
+    /// // alef 60, bet 55, gimel 40, dalet 50, vav 25 & space 30px advances, with
+    /// // visual pairs "דא" -6, "גד" -4 & "וב" -10px kerning
+    /// assert_eq!(
+    ///     layout(rtl, "אד דג בו"),
+    ///     [
+    ///         (vec![84.0, 40.0, 10.0], 134.0),
+    ///         (vec![76.0, 40.0, 10.0], 116.0),
+    ///         (vec![25.0, 10.0], 70.0),
+    ///     ]
+    /// );
+    ///
+    /// // letters 5px wider & kerned, digits centered in unkerned 60px cells
+    /// let overridden = Layout {
+    ///     max_width: 200.0,
+    ///     advances: AdvanceOverrides {
+    ///         letters: Some(AdvanceOverride::Adjust(0.05)),
+    ///         digits: Some(AdvanceOverride::Fixed(0.6)),
+    ///         ..AdvanceOverrides::default()
+    ///     },
+    ///     ..rtl
+    /// };
+    /// assert_eq!(
+    ///     layout(overridden, "בו0 אד"),
+    ///     [
+    ///         (vec![120.0, 100.0, 45.0, 10.0], 170.0),
+    ///         (vec![59.0, 10.0], 114.0),
+    ///     ]
+    /// );
+    /// # Ok(()) }
+    /// ```
+    pub fn layout<F, SF>(
+        &self,
+        font: SF,
+        position: Point,
+        text: &str,
+        target: &mut Vec<Glyph>,
+    ) -> Vec<LineMetrics>
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        let v_advance = font.height() + font.line_gap();
+        let mut caret = position + point(0.0, font.ascent());
+        let mut last_glyph: Option<GlyphId> = None;
+        let mut lines = Vec::new();
+        let mut line_start = target.len();
+        let mut text_start = 0;
+        let rtl = self.direction == Direction::RightToLeft;
+        let glyphs_start = target.len();
+        let mut advances = Vec::new();
+        let hang = self.hang_punctuation && !rtl;
+        let mut line_first = true;
+        // whether the line has any non-whitespace glyphs
+        let mut line_ink = false;
+        let mut breakpoint: Option<LineBreak> = None;
+        let mut end_line = |width: f32, baseline: f32, glyphs_end: usize, text: Range<usize>| {
+            lines.push(LineMetrics {
+                ascent: font.ascent(),
+                descent: font.descent(),
+                width,
+                baseline,
+                glyphs: line_start..glyphs_end,
+                text,
+            });
+            line_start = glyphs_end;
+        };
+
+        let mut chars = text.char_indices().map(|(idx, c)| {
+            let variation = variation_glyph(font.font(), text, idx, c);
+            variation.map_or(Token::Char(idx, c), |id| Token::Glyph(idx, id, 1.0))
+        });
+        let mut substituted;
+        let mut features = Vec::new();
+        if self.script.is_some() {
+            features.extend_from_slice(&Script::REQUIRED_FEATURES);
+        }
+        if self.ligatures {
+            features.extend_from_slice(&Script::LIGATURE_FEATURES);
+        }
+        features.extend(self.numeric.run_features());
+        let digit_feature = self.numeric.digit_feature();
+        let tokens: &mut dyn Iterator<Item = Token> = match self.small_caps {
+            None if features.is_empty() && digit_feature.is_none() => &mut chars,
+            small_caps => {
+                let script = self.script.unwrap_or_default();
+                substituted = substitute_runs(
+                    font.font(),
+                    script,
+                    &features,
+                    small_caps,
+                    digit_feature,
+                    text,
+                )
+                .into_iter();
+                &mut substituted
+            }
+        };
+
+        for token in tokens {
+            let (id, advance, scale, c) = match token {
+                Token::Glyph(idx, id, relative) => (
+                    id,
+                    font.h_advance(id) * relative,
+                    font.scale() * relative,
+                    text[idx..].chars().next(),
+                ),
+                Token::Char(idx, c) if CharAdvance::of(c) == CharAdvance::Newline => {
+                    end_line(caret.x - position.x, caret.y, target.len(), text_start..idx);
+                    text_start = idx + c.len_utf8();
+                    caret = point(position.x, caret.y + v_advance);
+                    last_glyph = None;
+                    line_first = true;
+                    line_ink = false;
+                    breakpoint = None;
+                    continue;
+                }
+                Token::Char(idx, c) => {
+                    let (id, advance) = self.whitespace.advance(&font, c, caret.x - position.x);
+                    match id {
+                        Some(id) => (id, advance, font.scale(), Some(c)),
+                        None => {
+                            caret.x += self.rounding.round(advance);
+                            if advance != 0.0 {
+                                last_glyph = None;
+                            }
+                            if line_ink && is_break_after(c) {
+                                let text_idx = idx + c.len_utf8();
+                                breakpoint = Some(LineBreak::new(target.len(), text_idx, caret.x));
+                            }
+                            continue;
+                        }
+                    }
+                }
+            };
+            let is_whitespace = c.is_some_and(char::is_whitespace);
+            let advance_override = c
+                .filter(|c| !c.is_whitespace())
+                .and_then(|c| self.advances.get(c));
+            let (advance, offset) = match advance_override {
+                Some(advance_override) => advance_override.apply(&font, advance),
+                None => (advance, 0.0),
+            };
+            let advance = self.rounding.round(advance);
+            // fixed width cells aren't kerned
+            let kerned = !advance_override.is_some_and(AdvanceOverride::is_cell);
+
+            if let Some(previous) = last_glyph.take().filter(|_| kerned) {
+                // kern pairs in visual order
+                let kern = self.rounding.round(if rtl {
+                    font.kern(id, previous)
+                } else {
+                    font.kern(previous, id)
+                });
+                caret.x += kern;
+                if let Some(line_break) = &mut breakpoint {
+                    if line_break.glyph == target.len() {
+                        // kerning across the break is dropped if the line wraps there
+                        line_break.next_x += kern;
+                    }
+                }
+            }
+            let optical = match hang {
+                true => font.optical_bounds(id).unwrap_or_default(),
+                false => OpticalBounds::default(),
+            };
+            if line_first {
+                caret.x -= optical.left;
+            }
+            let mut glyph = id.with_scale_and_position(scale, caret + point(offset, 0.0));
+            last_glyph = Some(id).filter(|_| kerned);
+            caret.x += advance;
+
+            let overflows = |caret: Point| {
+                !is_whitespace && caret.x + optical.right > position.x + self.max_width
+            };
+            if overflows(caret) {
+                if let Some(line_break) = breakpoint.take() {
+                    // move the glyphs after the last break opportunity to a new line
+                    let LineBreak {
+                        glyph: glyph_idx,
+                        text: text_idx,
+                        ..
+                    } = line_break;
+                    end_line(
+                        line_break.x - position.x,
+                        caret.y,
+                        glyph_idx,
+                        text_start..text_idx,
+                    );
+                    text_start = text_idx;
+                    let first = target.get(glyph_idx).map_or(id, |glyph| glyph.id);
+                    let left = match hang {
+                        true => font.optical_bounds(first).unwrap_or_default().left,
+                        false => 0.0,
+                    };
+                    let shift = point(position.x - left - line_break.next_x, v_advance);
+                    for moved in &mut target[glyph_idx..] {
+                        moved.position += shift;
+                    }
+                    glyph.position += shift;
+                    caret += shift;
+                    line_ink = glyph_idx < target.len();
+                }
+            }
+            // otherwise wrap before the glyph, e.g. within a word wider than the line
+            if overflows(caret) && line_ink {
+                let idx = token.source_index().max(text_start);
+                end_line(
+                    glyph.position.x - position.x,
+                    caret.y,
+                    target.len(),
+                    text_start..idx,
+                );
+                text_start = idx;
+                caret = point(position.x - optical.left, caret.y + v_advance);
+                glyph.position = caret + point(offset, 0.0);
+                caret.x += advance;
+                last_glyph = Some(id).filter(|_| kerned);
+                breakpoint = None;
+            }
+
+            let after_ink = line_ink;
+            target.push(glyph);
+            line_first = false;
+            line_ink |= !is_whitespace;
+            if let Some(c) = c.filter(|&c| after_ink && is_break_after(c)) {
+                let text_idx = token.source_index() + c.len_utf8();
+                breakpoint = Some(LineBreak::new(target.len(), text_idx, caret.x));
+            }
+            if rtl {
+                advances.push((advance, offset));
+            }
+        }
+        end_line(
+            caret.x - position.x,
+            caret.y,
+            target.len(),
+            text_start..text.len(),
+        );
+
+        if rtl {
+            // mirror each line so the first glyph is on the right
+            for line in &lines {
+                for idx in line.glyphs.clone() {
+                    let glyph = &mut target[idx];
+                    let (advance, offset) = advances[idx - glyphs_start];
+                    let end = glyph.position.x - offset - position.x + advance;
+                    glyph.position.x = position.x + line.width - end + offset;
+                }
+            }
+        }
+
+        lines
+    }
+
+    /// Lays out `text` into a [`GlyphRun`](struct.GlyphRun.html) per line at `scale`.
+    ///
+    /// See [`layout`](#method.layout).
+    pub fn layout_runs<'a, F, S>(
+        &self,
+        font: &'a F,
+        scale: S,
+        position: Point,
+        text: &str,
+    ) -> Vec<GlyphRun<'a, F>>
+    where
+        F: Font,
+        S: Into<PxScale>,
+    {
+        let scale = scale.into();
+        let mut glyphs = Vec::new();
+        let lines = self.layout(font.as_scaled(scale), position, text, &mut glyphs);
+        lines
+            .into_iter()
+            .map(|line| {
+                let mut glyphs = glyphs[line.glyphs].to_vec();
+                if self.direction == Direction::RightToLeft {
+                    glyphs.reverse();
+                }
+                GlyphRun {
+                    font,
+                    scale,
+                    glyphs,
+                    text: line.text,
+                    direction: self.direction,
+                }
+            })
+            .collect()
+    }
+
+    /// Lays out `text` into a [`LayoutResult`](struct.LayoutResult.html) tied to the
+    /// font's fingerprint, e.g. to serialize & draw elsewhere.
+    ///
+    /// See [`layout`](#method.layout).
+    pub fn layout_result<F, SF>(&self, font: SF, position: Point, text: &str) -> LayoutResult
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        let font_fingerprint = font.font().fingerprint();
+        let mut glyphs = Vec::new();
+        let lines = self.layout(font, position, text, &mut glyphs);
+        LayoutResult {
+            font_fingerprint,
+            glyphs,
+            lines,
+        }
+    }
+
+    /// Returns the pixel width of the widest line of `text` laid out with `font`, measured
+    /// according to `mode`. Text without ink has an ink width of `0.0`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, Layout, ScaleFont, WidthMode};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let scaled_font = font.as_scaled(24.0);
+    /// let layout = Layout::default();
+    ///
+    /// let advance = layout.measure(&scaled_font, "Hi", WidthMode::Advance);
+    /// let ink = layout.measure(&scaled_font, "Hi", WidthMode::Ink);
+    ///
+    /// // the ink excludes the side bearings of 'H' & 'i'
+    /// let (h, i) = (font.glyph_id('H'), font.glyph_id('i'));
+    /// let bearings = scaled_font.lsb(h) + scaled_font.rsb(i);
+    /// assert!((advance - bearings - ink).abs() < 1e-3);
+    ///
+    /// // & surrounding whitespace
+    /// let padded = layout.measure(&scaled_font, " Hi ", WidthMode::Ink);
+    /// assert!((padded - ink).abs() < 1e-3);
+    /// assert_eq!(layout.measure(&scaled_font, "  ", WidthMode::Ink), 0.0);
+    /// # Ok(()) }
+    /// ```
+    pub fn measure<F, SF>(&self, font: SF, text: &str, mode: WidthMode) -> f32
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        let mut glyphs = Vec::new();
+        let lines = self.layout(&font, point(0.0, 0.0), text, &mut glyphs);
+        lines
+            .iter()
+            .map(|line| match mode {
+                WidthMode::Advance => line.width,
+                WidthMode::Ink => ink_width(font.font(), &glyphs[line.glyphs.clone()]),
+            })
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Returns the width from the leftmost to the rightmost ink edge of the glyphs.
+fn ink_width<F: Font>(font: &F, glyphs: &[Glyph]) -> f32 {
+    let mut ink: Option<(f32, f32)> = None;
+    for glyph in glyphs {
+        let scaled_font = font.as_scaled(glyph.scale);
+        let left = glyph.position.x + scaled_font.lsb(glyph.id);
+        let right = glyph.position.x + scaled_font.h_advance(glyph.id) - scaled_font.rsb(glyph.id);
+        // whitespace has no ink
+        if right <= left {
+            continue;
+        }
+        ink = Some(match ink {
+            Some((min, max)) => (min.min(left), max.max(right)),
+            None => (left, right),
+        });
+    }
+    ink.map_or(0.0, |(left, right)| right - left)
+}
+
+/// A line break opportunity, the last of a line is where it wraps.
+#[derive(Clone, Copy)]
+struct LineBreak {
+    /// Index of the first glyph after the break.
+    glyph: usize,
+    /// Byte index of the text after the break.
+    text: usize,
+    /// Caret x at the break, the end of the line if it wraps here.
+    x: f32,
+    /// Caret x of the first glyph after the break, including kerning.
+    next_x: f32,
+}
+
+impl LineBreak {
+    #[inline]
+    fn new(glyph: usize, text: usize, x: f32) -> Self {
+        Self {
+            glyph,
+            text,
+            x,
+            next_x: x,
+        }
+    }
+}
+
+/// Layout input with the byte index of its source char, either a char or a glyph resulting
+/// from substitution with its scale relative to the font's scale.
+#[derive(Clone, Copy)]
+enum Token {
+    Char(usize, char),
+    Glyph(usize, GlyphId, f32),
+}
+
+impl Token {
+    #[inline]
+    fn source_index(self) -> usize {
+        match self {
+            Self::Char(idx, _) | Self::Glyph(idx, ..) => idx,
+        }
+    }
+}
+
+/// Tokenizes `text` into runs of visible glyphs, leaving whitespace & invisible chars as is.
+///
+/// Applies small caps & `digit_feature` to each char, then the substitutions of `features`
+/// to each run.
+fn substitute_runs<F: Font>(
+    font: &F,
+    script: Script,
+    features: &[[u8; 4]],
+    small_caps: Option<SmallCaps>,
+    digit_feature: Option<[u8; 4]>,
+    text: &str,
+) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    // glyphs of a run & the byte indices of their source chars
+    let mut run = (Vec::new(), Vec::new());
+    let mut run_scale = 1.0;
+    let flush = |(run, indices): &mut (Vec<GlyphId>, Vec<usize>), scale, tokens: &mut Vec<_>| {
+        if !features.is_empty() && !run.is_empty() {
+            font.substitute(script, features, run);
+        }
+        // substituted glyphs map to the run start if substitution changed the glyph count
+        let start = indices.first().copied().unwrap_or_default();
+        let same_len = run.len() == indices.len();
+        tokens.extend(run.drain(..).enumerate().map(|(n, id)| {
+            let idx = if same_len { indices[n] } else { start };
+            Token::Glyph(idx, id, scale)
+        }));
+        indices.clear();
+    };
+
+    for (idx, c) in text.char_indices() {
+        if CharAdvance::of(c) != CharAdvance::Glyph || c.is_whitespace() {
+            flush(&mut run, run_scale, &mut tokens);
+            tokens.push(Token::Char(idx, c));
+            continue;
+        }
+        let (id, scale) = match small_caps {
+            Some(small_caps) => small_caps.glyph(font, script, c),
+            None => {
+                let id = variation_glyph(font, text, idx, c).unwrap_or_else(|| font.glyph_id(c));
+                (id, 1.0)
+            }
+        };
+        let id = match digit_feature {
+            Some(feature) if c.is_ascii_digit() => {
+                let mut ids = vec![id];
+                font.substitute(script, &[feature], &mut ids);
+                match ids[..] {
+                    [digit] => digit,
+                    _ => id,
+                }
+            }
+            _ => id,
+        };
+        // substitute runs of a single scale
+        if scale != run_scale {
+            flush(&mut run, run_scale, &mut tokens);
+            run_scale = scale;
+        }
+        run.0.push(id);
+        run.1.push(idx);
+    }
+    flush(&mut run, run_scale, &mut tokens);
+    tokens
+}
+
+/// Metrics of a line of laid out glyphs.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct LineMetrics {
+    /// Pixel ascent above the baseline.
+    pub ascent: f32,
+    /// Pixel descent below the baseline, usually negative.
+    pub descent: f32,
+    /// Pixel width from the line start to the end caret position, including trailing
+    /// whitespace.
+    pub width: f32,
+    /// Baseline y position.
+    pub baseline: f32,
+    /// Index range of the line's glyphs in the layout target.
+    pub glyphs: Range<usize>,
+    /// Byte range of the line's source text, excluding the newline ending the line.
+    pub text: Range<usize>,
+}
+
+impl LineMetrics {
+    /// Top y position of the line, `baseline - ascent`.
+    #[inline]
+    pub fn top(&self) -> f32 {
+        self.baseline - self.ascent
+    }
+
+    /// Bottom y position of the line, `baseline - descent`.
+    #[inline]
+    pub fn bottom(&self) -> f32 {
+        self.baseline - self.descent
+    }
+}
+
+/// Positioned glyphs & line metrics of a [`Layout`](struct.Layout.html), along with the
+/// [`Font::fingerprint`](trait.Font.html#method.fingerprint) of the font used.
+///
+/// With the `serde` feature this is serializable, so layout can be computed once, e.g. on
+/// a server, & sent to thin clients that only draw. Clients should check the result
+/// against their own font before drawing.
+///
+/// # Example
+/// ```
+/// # use ab_glyph::*;
+/// # fn main() -> Result<(), InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let result = Layout::default().layout_result(font.as_scaled(24.0), point(0.0, 0.0), "Hello");
+///
+/// // elsewhere, with the same font data
+/// let data = include_bytes!("../../dev/fonts/Exo2-Light.otf").to_vec();
+/// let client_font = FontVec::try_from_vec(data)?;
+/// let glyphs = result.glyphs_for(&client_font).expect("same font");
+/// assert_eq!(glyphs.len(), 5);
+///
+/// let other = FontRef::try_from_slice(include_bytes!("../../dev/fonts/DejaVuSansMono.ttf"))?;
+/// assert!(result.glyphs_for(&other).is_none());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct LayoutResult {
+    /// Fingerprint of the font the glyphs were laid out with.
+    pub font_fingerprint: u64,
+    /// Positioned glyphs.
+    pub glyphs: Vec<Glyph>,
+    /// Metrics of each line, with index ranges into `glyphs`.
+    pub lines: Vec<LineMetrics>,
+}
+
+impl LayoutResult {
+    /// Returns `true` if `font` has the fingerprint of the font used for layout.
+    #[inline]
+    pub fn matches<F: Font>(&self, font: &F) -> bool {
+        font.fingerprint() == self.font_fingerprint
+    }
+
+    /// Returns the positioned glyphs if `font` matches the font used for layout,
+    /// otherwise `None` as the glyph ids would be meaningless.
+    #[inline]
+    pub fn glyphs_for<F: Font>(&self, font: &F) -> Option<&[Glyph]> {
+        if self.matches(font) {
+            Some(&self.glyphs)
+        } else {
+            None
+        }
+    }
+}
+
+/// Positions the glyphs of `text` from `start` on the baseline, applying advances &
+/// kerning. Each newline, see [`CharAdvance::Newline`](enum.CharAdvance.html#variant.Newline),
+/// resets the caret to the start of the next line.
+///
+/// This is the minimal glyph positioning loop, without the wrapping, alignment & whitespace
+/// handling of [`Layout`](struct.Layout.html). After iterating,
+/// [`caret`](struct.LineGlyphs.html#method.caret) is the end position, e.g. to continue
+/// with more text.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{layout_line, point, Font, FontRef, ScaleFont};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let scaled_font = font.as_scaled(24.0);
+///
+/// let mut line = layout_line(&scaled_font, "AV\nA", point(10.0, 30.0));
+/// let glyphs: Vec<_> = line.by_ref().collect();
+/// assert_eq!(glyphs.len(), 3);
+/// assert_eq!(glyphs[0].position, point(10.0, 30.0));
+///
+/// // kerned
+/// let kern = scaled_font.kern(glyphs[0].id, glyphs[1].id);
+/// let a_advance = scaled_font.h_advance(glyphs[0].id);
+/// assert_eq!(glyphs[1].position.x, 10.0 + a_advance + kern);
+///
+/// // newline resets to the start of the next line
+/// let next_baseline = 30.0 + scaled_font.height() + scaled_font.line_gap();
+/// assert_eq!(glyphs[2].position, point(10.0, next_baseline));
+/// assert_eq!(line.caret(), point(10.0 + a_advance, next_baseline));
+/// # Ok(()) }
+/// ```
+#[inline]
+pub fn layout_line<F, SF>(font: SF, text: &str, start: Point) -> LineGlyphs<'_, F, SF>
+where
+    F: Font,
+    SF: ScaleFont<F>,
+{
+    LineGlyphs {
+        font,
+        chars: text.chars(),
+        start_x: start.x,
+        caret: start,
+        last_glyph: None,
+        _font: PhantomData,
+    }
+}
+
+/// Iterator of positioned glyphs, see [`layout_line`](fn.layout_line.html).
+#[derive(Clone, Debug)]
+pub struct LineGlyphs<'a, F, SF> {
+    font: SF,
+    chars: Chars<'a>,
+    start_x: f32,
+    caret: Point,
+    last_glyph: Option<GlyphId>,
+    _font: PhantomData<F>,
+}
+
+impl<F, SF> LineGlyphs<'_, F, SF> {
+    /// Returns the caret position after the glyphs iterated so far, on the baseline.
+    #[inline]
+    pub fn caret(&self) -> Point {
+        self.caret
+    }
+}
+
+impl<F: Font, SF: ScaleFont<F>> Iterator for LineGlyphs<'_, F, SF> {
+    type Item = Glyph;
+
+    fn next(&mut self) -> Option<Glyph> {
+        loop {
+            let c = self.chars.next()?;
+            if CharAdvance::of(c) == CharAdvance::Newline {
+                self.caret = point(
+                    self.start_x,
+                    self.caret.y + self.font.height() + self.font.line_gap(),
+                );
+                self.last_glyph = None;
+                continue;
+            }
+
+            let mut glyph = self.font.scaled_glyph(c);
+            if let Some(last) = self.last_glyph {
+                self.caret.x += self.font.kern(last, glyph.id);
+            }
+            glyph.position = self.caret;
+            self.caret.x += self.font.h_advance(glyph.id);
+            self.last_glyph = Some(glyph.id);
+            return Some(glyph);
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.chars.size_hint().1)
+    }
+}
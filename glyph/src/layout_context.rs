@@ -0,0 +1,212 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{
+    point, scale::whole_ppem, Font, Glyph, GlyphId, Layout, LineMetrics, Point, PxScale, ScaleFont,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Display scaling & pixel snapping applied when laying out text, so hi-DPI decisions are
+/// made in one place rather than at each call site.
+///
+/// Inputs, i.e. font scale, position & [`Layout::max_width`](struct.Layout.html#structfield.max_width),
+/// are in logical pixels. Outputs, glyph positions & scales & line metrics, are in physical
+/// pixels ready to rasterize.
+///
+/// See [`Layout::layout_in_context`](struct.Layout.html#method.layout_in_context).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayoutContext {
+    /// Physical pixels per logical pixel, e.g. `2.0` for a 200% scaled display.
+    /// Default `1.0`.
+    pub dpi_factor: f32,
+    /// Snap glyph positions & baselines to whole physical pixels, for crisper text at
+    /// the cost of less precise spacing. Default `false`.
+    pub snap_to_pixels: bool,
+    /// Source of vertical metrics & advances. Default
+    /// [`Font`](enum.MetricsSource.html#variant.Font).
+    pub metrics_source: MetricsSource,
+}
+
+impl Default for LayoutContext {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            dpi_factor: 1.0,
+            snap_to_pixels: false,
+            metrics_source: MetricsSource::default(),
+        }
+    }
+}
+
+impl LayoutContext {
+    /// Returns a logical pixel scale in physical pixels.
+    #[inline]
+    pub fn physical_scale<S: Into<PxScale>>(&self, scale: S) -> PxScale {
+        scale.into() * self.dpi_factor
+    }
+
+    /// Returns a logical pixel position in physical pixels.
+    #[inline]
+    pub fn physical_point(&self, p: Point) -> Point {
+        p * self.dpi_factor
+    }
+
+    /// Returns a physical pixel position in logical pixels, e.g. to map glyph positions
+    /// back to UI coordinates.
+    #[inline]
+    pub fn logical_point(&self, p: Point) -> Point {
+        p / self.dpi_factor
+    }
+}
+
+/// Source of the vertical metrics & advances used for layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MetricsSource {
+    /// Scaled outline metrics, ignoring any device metrics.
+    Font,
+    /// Device metrics at whole number physical pixels-per-em, matching legacy rasterizers,
+    /// otherwise the same as `Font`. See
+    /// [`ScaleFont::h_advance`](trait.ScaleFont.html#method.h_advance) &
+    /// [`ScaleFont::ascent`](trait.ScaleFont.html#method.ascent).
+    Device,
+}
+
+impl Default for MetricsSource {
+    #[inline]
+    fn default() -> Self {
+        Self::Font
+    }
+}
+
+impl Layout {
+    /// Lays out `text` glyphs into `target` like [`layout`](#method.layout), applying the
+    /// `context` display scaling, pixel snapping & metrics source.
+    ///
+    /// `font` scale, `position` & `max_width` are logical pixels, resulting glyphs & line
+    /// metrics are physical pixels.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontRef, Layout, LayoutContext};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let hidpi = LayoutContext {
+    ///     dpi_factor: 2.0,
+    ///     snap_to_pixels: true,
+    ///     ..LayoutContext::default()
+    /// };
+    ///
+    /// let (mut logical, mut physical) = (Vec::new(), Vec::new());
+    /// let layout = Layout::default();
+    /// layout.layout(font.as_scaled(12.0), point(5.0, 5.0), "Hello", &mut logical);
+    /// let lines = layout.layout_in_context(
+    ///     &hidpi,
+    ///     font.as_scaled(12.0),
+    ///     point(5.0, 5.0),
+    ///     "Hello",
+    ///     &mut physical,
+    /// );
+    ///
+    /// assert_eq!(physical[0].scale.y, 24.0);
+    /// assert_eq!(physical[0].position.x, 10.0);
+    /// assert_eq!(lines[0].baseline, lines[0].baseline.round());
+    /// for (logical, physical) in logical.iter().zip(&physical) {
+    ///     assert_eq!(physical.position.x, physical.position.x.round());
+    ///     assert!((logical.position.x * 2.0 - physical.position.x).abs() <= 0.5);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn layout_in_context<F, SF>(
+        &self,
+        context: &LayoutContext,
+        font: SF,
+        position: Point,
+        text: &str,
+        target: &mut Vec<Glyph>,
+    ) -> Vec<LineMetrics>
+    where
+        F: Font,
+        SF: ScaleFont<F>,
+    {
+        let font = ContextFont {
+            font: font.font(),
+            scale: context.physical_scale(font.scale()),
+            device: context.metrics_source == MetricsSource::Device,
+        };
+        let layout = Layout {
+            max_width: self.max_width * context.dpi_factor,
+            ..*self
+        };
+        let mut lines = layout.layout(font, context.physical_point(position), text, target);
+
+        if context.snap_to_pixels {
+            for line in &mut lines {
+                line.baseline = line.baseline.round();
+                for glyph in &mut target[line.glyphs.clone()] {
+                    glyph.position = point(glyph.position.x.round(), glyph.position.y.round());
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// Font scaled to physical pixels using the layout context's metrics source.
+struct ContextFont<'a, F> {
+    font: &'a F,
+    scale: PxScale,
+    device: bool,
+}
+
+impl<F: Font> ScaleFont<F> for ContextFont<'_, F> {
+    #[inline]
+    fn scale(&self) -> PxScale {
+        self.scale
+    }
+
+    #[inline]
+    fn font(&self) -> &F {
+        self.font
+    }
+
+    #[inline]
+    fn ascent(&self) -> f32 {
+        match self.device_v_metrics() {
+            Some((ascent, _)) => ascent,
+            None => self.v_scale_factor() * self.font.ascent_unscaled(),
+        }
+    }
+
+    #[inline]
+    fn descent(&self) -> f32 {
+        match self.device_v_metrics() {
+            Some((_, descent)) => descent,
+            None => self.v_scale_factor() * self.font.descent_unscaled(),
+        }
+    }
+
+    #[inline]
+    fn h_advance(&self, id: GlyphId) -> f32 {
+        let advance =
+            self.h_scale_factor() * self.font.h_advance_unscaled(id) * self.font.relative_scale(id);
+        if !self.device {
+            return advance;
+        }
+        whole_ppem(self)
+            .and_then(|ppem| self.font.device_h_advance(id, ppem))
+            .unwrap_or(advance)
+    }
+}
+
+impl<F: Font> ContextFont<'_, F> {
+    /// Returns the device `(ascent, descent)` if using device metrics at a whole ppem.
+    #[inline]
+    fn device_v_metrics(&self) -> Option<(f32, f32)> {
+        if !self.device {
+            return None;
+        }
+        let metrics = self.font.device_v_metrics(whole_ppem(self)?)?;
+        Some((metrics.y_max, metrics.y_min))
+    }
+}
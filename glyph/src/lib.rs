@@ -12,7 +12,7 @@
 //!
 //! // Draw it.
 //! if let Some(q) = font.outline_glyph(q_glyph) {
-//!     q.draw(|x, y, c| { /* draw pixel `(x, y)` with coverage: `c` */ });
+//!     q.draw(0, |x, y, c| { /* draw pixel `(x, y)` with coverage: `c` */ });
 //! }
 //! # Ok(()) }
 //! ```
@@ -21,24 +21,143 @@
 
 extern crate alloc;
 
+#[cfg(feature = "atlas")]
+pub mod atlas;
+#[cfg(feature = "bdf")]
+mod bdf;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+mod cached_font;
+mod codepoint_ids;
+mod color;
+mod complexity;
+#[cfg(feature = "counters")]
+pub mod counters;
+mod coverage;
+mod curve_buffer;
+mod custom;
+pub mod debug;
+mod decoration;
+mod device;
+#[cfg(feature = "embedded-graphics")]
+pub mod embedded_graphics;
 mod err;
+mod flat;
 mod font;
 #[cfg(feature = "std")]
 mod font_arc;
+mod font_diff;
 mod glyph;
+mod glyph_image;
+#[cfg(feature = "std")]
+mod glyph_rasterizer;
+mod glyph_run;
+mod glyph_text;
+mod hint;
+#[cfg(feature = "image")]
+mod image;
+mod irect;
+mod layout;
+mod layout_context;
+mod matching;
+mod mesh;
+#[cfg(feature = "std")]
+mod mesh_cache;
+mod name;
 #[cfg(all(feature = "libm", not(feature = "std")))]
 mod nostd_float;
+mod numeric;
+mod optical;
 mod outlined;
+mod overlap;
+mod paint;
+#[cfg(feature = "rayon")]
+mod par;
+mod path;
+pub mod pdf;
+mod polyline;
+pub mod prelude;
+mod render;
 mod scale;
+mod script;
+#[cfg(feature = "shaping")]
+pub mod shaping;
+mod stroke;
+mod subpixel;
+mod subset;
+mod synthetic;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod text;
+mod transform;
 mod ttfp;
+mod variation;
+mod vertical;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+#[cfg(feature = "bdf")]
+pub use crate::bdf::BdfFont;
+#[cfg(feature = "std")]
+pub use crate::cache::{CachedGlyph, GlyphCache, RasterizeFailed, RasterizeTask, RasterizedGlyphs};
+#[cfg(feature = "std")]
+pub use crate::cached_font::CachedFont;
 #[cfg(feature = "std")]
 pub use crate::font_arc::*;
+#[cfg(feature = "std")]
+pub use crate::glyph_rasterizer::{GlyphRasterizer, OutlineRasterizer};
+#[cfg(feature = "std")]
+pub use crate::mesh_cache::{GlyphMeshCache, MeshInstance, MeshRange};
+#[cfg(feature = "rayon")]
+pub use crate::par::{rasterize_all, AtlasWriter, ParFont};
+#[cfg(feature = "memmap2")]
+pub use crate::ttfp::FontMmap;
+#[cfg(feature = "std")]
+pub use crate::ttfp::IncrementalFont;
 pub use crate::{
+    codepoint_ids::CodepointIdIter,
+    color::{ColorLayer, ColorOutline},
+    complexity::GlyphComplexity,
+    coverage::CoverageSet,
+    curve_buffer::{CurveBuffer, CurveGlyph},
+    custom::{CustomFont, FontBuilder},
+    decoration::{Decoration, DecorationMetrics},
+    device::DeviceVMetrics,
     err::*,
+    flat::{FlatOutline, FlatVerb},
     font::*,
+    font_diff::{FontDiff, GlyphChange, MetricChange},
     glyph::*,
+    glyph_image::{GlyphImage, GlyphImageFormat},
+    glyph_run::{Direction, GlyphRun},
+    glyph_text::GlyphTextMap,
+    hint::Hinting,
+    irect::IRect,
+    layout::{
+        layout_line, AdvanceOverride, AdvanceOverrides, AdvanceRounding, CharAdvance, Layout,
+        LayoutResult, LineGlyphs, LineMetrics, SmallCaps, WhitespacePolicy, WidthMode,
+    },
+    layout_context::{LayoutContext, MetricsSource},
+    matching::{FontQuery, FontStyle},
+    mesh::{ExtrudedMesh, GlyphMesh},
+    name::NameId,
+    numeric::{FigureCase, FigurePosition, FigureSpacing, NumericStyle},
+    optical::OpticalBounds,
     outlined::*,
+    paint::{ColorGlyphPaint, ColorLine, ColorPalette, ColorStop, CompositeMode, GradientExtend},
+    path::OutlineSink,
+    polyline::Winding,
+    render::GlyphRender,
     scale::*,
-    ttfp::{FontRef, FontVec},
+    script::Script,
+    stroke::{LineCap, LineJoin},
+    subpixel::LcdFilter,
+    subset::FontSubset,
+    synthetic::{SyntheticFont, SyntheticStyle},
+    text::Text,
+    transform::Transform,
+    ttfp::{FontCollectionBuilder, FontRef, FontVec, FontVecCollection},
+    variation::{NamedInstance, VariationAxis},
+    vertical::{layout_vertical, VerticalGlyph, VerticalOrientation},
 };
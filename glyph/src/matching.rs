@@ -0,0 +1,159 @@
+use crate::{Font, NameId};
+
+/// Slope of a font's glyphs, see [`Font::style`](trait.Font.html#method.style).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    /// Upright.
+    Normal,
+    /// Cursive slanted glyphs.
+    Italic,
+    /// Slanted upright glyphs.
+    Oblique,
+}
+
+impl Default for FontStyle {
+    #[inline]
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// A requested family, weight, stretch & style to score fonts against, e.g. to pick the
+/// closest face of a fallback stack.
+///
+/// Fonts are ranked like CSS font matching, by family, then stretch, style & weight. Ties
+/// are resolved by order, so matching is deterministic.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{FontQuery, FontRef, FontStyle};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let fonts = [
+///     FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?,
+///     FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?,
+///     FontRef::try_from_slice(include_bytes!("../../dev/fonts/DejaVuSansMono.ttf"))?,
+/// ];
+///
+/// // family matches first, compared with the typographic family "Exo 2"
+/// let bold_exo = FontQuery {
+///     weight: 700,
+///     ..FontQuery::new("exo 2")
+/// };
+/// assert_eq!(bold_exo.best_match(&fonts), Some(0));
+/// assert_eq!(FontQuery::new("Open Sans").best_match(&fonts), Some(1));
+///
+/// // otherwise the closest style & weight
+/// let italic = FontQuery {
+///     style: FontStyle::Italic,
+///     ..FontQuery::new("Helvetica")
+/// };
+/// assert_eq!(italic.best_match(&fonts), Some(1));
+/// assert_eq!(FontQuery::new("Helvetica").best_match(&fonts), Some(2));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FontQuery<'a> {
+    /// Family name, compared ASCII case-insensitively with the font's typographic &
+    /// legacy family names.
+    pub family: &'a str,
+    /// Weight class, from `100` thin to `900` black, `400` being regular & `700` bold.
+    pub weight: u16,
+    /// Width class, from `1` ultra-condensed to `9` ultra-expanded, `5` being normal.
+    pub stretch: u16,
+    /// Slope of the glyphs.
+    pub style: FontStyle,
+}
+
+impl<'a> FontQuery<'a> {
+    /// Query for the regular, normal width & upright face of a family.
+    #[inline]
+    pub fn new(family: &'a str) -> Self {
+        Self {
+            family,
+            weight: 400,
+            stretch: 5,
+            style: FontStyle::Normal,
+        }
+    }
+
+    /// Returns how well a font matches the query, higher is better.
+    pub fn score<F: Font>(&self, font: &F) -> u32 {
+        let family = [NameId::TYPOGRAPHIC_FAMILY, NameId::FAMILY]
+            .iter()
+            .filter_map(|&id| font.font_name(id))
+            .any(|name| name.eq_ignore_ascii_case(self.family));
+        let stretch = 31 - stretch_penalty(self.stretch, font.stretch()).min(31);
+        let style = style_score(self.style, font.style());
+        let weight = 4095 - weight_penalty(self.weight, font.weight()).min(4095);
+        (u32::from(family) << 19) | (stretch << 14) | (style << 12) | weight
+    }
+
+    /// Returns the index of the best matching font, the first of equal scores, or `None`
+    /// if there are no fonts.
+    pub fn best_match<'f, F, I>(&self, fonts: I) -> Option<usize>
+    where
+        F: Font + 'f,
+        I: IntoIterator<Item = &'f F>,
+    {
+        let mut scores = fonts.into_iter().map(|font| self.score(font)).enumerate();
+        let init = scores.next()?;
+        let (index, _) = scores.fold(init, |best, next| if next.1 > best.1 { next } else { best });
+        Some(index)
+    }
+}
+
+/// Returns the stretch distance, narrower widths first for normal or narrower queries,
+/// otherwise wider widths first.
+#[inline]
+fn stretch_penalty(query: u16, stretch: u16) -> u32 {
+    let distance = u32::from(query.abs_diff(stretch));
+    let preferred = if query <= 5 {
+        stretch <= query
+    } else {
+        stretch >= query
+    };
+    if preferred {
+        distance
+    } else {
+        9 + distance
+    }
+}
+
+/// Returns `3` for the requested style, then `2` & `1` for the fallbacks.
+#[inline]
+fn style_score(query: FontStyle, style: FontStyle) -> u32 {
+    use FontStyle::*;
+    match (query, style) {
+        (Normal, Normal) | (Italic, Italic) | (Oblique, Oblique) => 3,
+        (Normal, Oblique) | (Italic, Oblique) | (Oblique, Italic) => 2,
+        (Normal, Italic) | (Italic, Normal) | (Oblique, Normal) => 1,
+    }
+}
+
+/// Returns the weight distance, preferring weights up to `500` for queries from `400` to
+/// `500`, then lighter weights for queries up to `500`, otherwise heavier weights.
+#[inline]
+fn weight_penalty(query: u16, weight: u16) -> u32 {
+    let distance = u32::from(query.abs_diff(weight));
+    let band = if (400..=500).contains(&query) {
+        if (query..=500).contains(&weight) {
+            0
+        } else if weight < query {
+            1
+        } else {
+            2
+        }
+    } else if query < 400 {
+        if weight <= query {
+            0
+        } else {
+            1
+        }
+    } else if weight >= query {
+        0
+    } else {
+        1
+    };
+    band * 1000 + distance
+}
@@ -0,0 +1,236 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{
+    overlap::{fill_trapezoids, flatten_contours},
+    point, Outline, Point,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Indexed triangle mesh filling a glyph outline, see
+/// [`Outline::tessellate`](struct.Outline.html#method.tessellate).
+///
+/// Vertices are unscaled outline coordinates, y up. Triangles are counter-clockwise in
+/// y up coordinates & don't overlap.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct GlyphMesh {
+    /// Unscaled vertex positions.
+    pub vertices: Vec<Point>,
+    /// Vertex indices, 3 per triangle.
+    pub indices: Vec<u32>,
+}
+
+impl GlyphMesh {
+    /// Returns the number of triangles.
+    #[inline]
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Returns `true` if there are no triangles.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the total unscaled area of the triangles.
+    pub fn area(&self) -> f32 {
+        self.indices
+            .chunks_exact(3)
+            .map(|tri| {
+                let vertex = |n: usize| self.vertices[tri[n] as usize];
+                let (a, b, c) = (vertex(0), vertex(1), vertex(2));
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2.0
+            })
+            .sum()
+    }
+}
+
+/// Closed 3D triangle mesh of an extruded glyph outline, see
+/// [`Outline::extrude`](struct.Outline.html#method.extrude).
+///
+/// Coordinates are unscaled, x right, y up & z towards the viewer. Triangles are
+/// counter-clockwise seen from outside the mesh.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct ExtrudedMesh {
+    /// Vertex `[x, y, z]` positions.
+    pub positions: Vec<[f32; 3]>,
+    /// Unit length vertex `[x, y, z]` normals, one per position.
+    pub normals: Vec<[f32; 3]>,
+    /// Vertex indices, 3 per triangle.
+    pub indices: Vec<u32>,
+}
+
+impl ExtrudedMesh {
+    /// Returns the number of triangles.
+    #[inline]
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Appends a vertex returning its index.
+    #[inline]
+    fn vertex(&mut self, position: [f32; 3], normal: [f32; 3]) -> u32 {
+        self.positions.push(position);
+        self.normals.push(normal);
+        self.positions.len() as u32 - 1
+    }
+}
+
+impl Outline {
+    /// Returns a triangle mesh filling the outline with the non-zero fill rule, e.g. for
+    /// 3D or GPU rendering without rasterization.
+    ///
+    /// Curves are flattened to lines within `tolerance` unscaled units. Overlapping
+    /// contours are merged, so no point is covered by more than one triangle.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let outline = font.outline(font.glyph_id('o')).unwrap();
+    ///
+    /// let mesh = outline.tessellate(1.0);
+    /// assert!(mesh.triangle_count() > 0);
+    /// assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.vertices.len()));
+    ///
+    /// // finer tolerances use more triangles
+    /// assert!(outline.tessellate(0.1).triangle_count() > mesh.triangle_count());
+    /// # Ok(()) }
+    /// ```
+    pub fn tessellate(&self, tolerance: f32) -> GlyphMesh {
+        let mut mesh = GlyphMesh::default();
+        fill_trapezoids(self, tolerance.max(1e-3), |y0, y1, (l0, l1), (r0, r1)| {
+            let base = mesh.vertices.len() as u32;
+            // counter-clockwise from bottom left
+            mesh.vertices.extend_from_slice(&[
+                point(l0, y0),
+                point(r0, y0),
+                point(r1, y1),
+                point(l1, y1),
+            ]);
+            if l0 != r0 {
+                mesh.indices.extend_from_slice(&[base, base + 1, base + 2]);
+            }
+            if l1 != r1 {
+                mesh.indices.extend_from_slice(&[base, base + 2, base + 3]);
+            }
+        });
+        mesh
+    }
+
+    /// Returns a closed 3D mesh of the outline extruded `depth` units, e.g. for 3D titles.
+    ///
+    /// The front cap is at `z = 0` facing `+z` & the back cap at `z = -depth` facing `-z`,
+    /// both filled like [`tessellate`](#method.tessellate). Side walls follow the
+    /// contours, each wall quad having flat normals facing out of the glyph. Curves are
+    /// flattened to lines within `tolerance` unscaled units, the same for caps & walls, so
+    /// their edges coincide.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let outline = font.outline(font.glyph_id('o')).unwrap();
+    ///
+    /// let mesh = outline.extrude(100.0, 1.0);
+    /// assert_eq!(mesh.positions.len(), mesh.normals.len());
+    ///
+    /// // the enclosed volume is the filled area times the depth
+    /// let volume: f32 = mesh
+    ///     .indices
+    ///     .chunks_exact(3)
+    ///     .map(|tri| {
+    ///         let (a, b, c) = (
+    ///             mesh.positions[tri[0] as usize],
+    ///             mesh.positions[tri[1] as usize],
+    ///             mesh.positions[tri[2] as usize],
+    ///         );
+    ///         (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+    ///             + a[2] * (b[0] * c[1] - b[1] * c[0]))
+    ///             / 6.0
+    ///     })
+    ///     .sum();
+    /// let area = outline.tessellate(1.0).area();
+    /// assert!((volume - area * 100.0).abs() / volume < 1e-3);
+    /// # Ok(()) }
+    /// ```
+    pub fn extrude(&self, depth: f32, tolerance: f32) -> ExtrudedMesh {
+        let tolerance = tolerance.max(1e-3);
+        let mut mesh = ExtrudedMesh::default();
+
+        // front & back caps
+        let cap = self.tessellate(tolerance);
+        for (z, normal) in [(0.0, 1.0), (-depth, -1.0)].iter().copied() {
+            let base = mesh.positions.len() as u32;
+            for v in &cap.vertices {
+                mesh.vertex([v.x, v.y, z], [0.0, 0.0, normal]);
+            }
+            for tri in cap.indices.chunks_exact(3) {
+                if normal > 0.0 {
+                    mesh.indices
+                        .extend_from_slice(&[base + tri[0], base + tri[1], base + tri[2]]);
+                } else {
+                    mesh.indices
+                        .extend_from_slice(&[base + tri[0], base + tri[2], base + tri[1]]);
+                }
+            }
+        }
+
+        // side walls of each closed contour
+        let contours = flatten_contours(self, tolerance);
+        let signed_area: f32 = contours
+            .iter()
+            .flat_map(|contour| edges(contour))
+            .map(|(p0, p1)| p0.x * p1.y - p1.x * p0.y)
+            .sum();
+        // non-zero filled contours wind clockwise (TrueType) or counter-clockwise (CFF),
+        // walk clockwise so the outside is on the left
+        let clockwise = signed_area < 0.0;
+
+        for contour in &contours {
+            for (p0, p1) in edges(contour) {
+                let (p0, p1) = if clockwise { (p0, p1) } else { (p1, p0) };
+                let dir = p1 - p0;
+                let len = (dir.x * dir.x + dir.y * dir.y).sqrt();
+                if len == 0.0 {
+                    continue;
+                }
+                let normal = [-dir.y / len, dir.x / len, 0.0];
+                let a = mesh.vertex([p0.x, p0.y, 0.0], normal);
+                let b = mesh.vertex([p1.x, p1.y, 0.0], normal);
+                let c = mesh.vertex([p1.x, p1.y, -depth], normal);
+                let d = mesh.vertex([p0.x, p0.y, -depth], normal);
+                mesh.indices.extend_from_slice(&[a, b, c, a, c, d]);
+            }
+        }
+        mesh
+    }
+}
+
+/// Returns the edges of a contour, including the closing edge from the last to the first
+/// point if they differ.
+fn edges(contour: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    let closing = match (contour.first(), contour.last()) {
+        (Some(&first), Some(&last)) if first != last => Some((last, first)),
+        _ => None,
+    };
+    contour
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .chain(closing)
+}
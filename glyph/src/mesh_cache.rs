@@ -0,0 +1,178 @@
+use crate::{Font, Glyph, GlyphId, Point, PxScaleFactor, ScaleFont};
+#[cfg(feature = "counters")]
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Cache of glyph triangle meshes sharing a single vertex & index buffer, keyed by
+/// [`Font::fingerprint`](trait.Font.html#method.fingerprint), glyph id & tessellation
+/// tolerance, requires the `std` feature.
+///
+/// Each glyph is tessellated once with [`Outline::tessellate`](struct.Outline.html#method.tessellate),
+/// so repeated characters of 3D or GPU text are drawn by instancing the same
+/// [`MeshRange`](struct.MeshRange.html) with per-glyph [`MeshInstance`](struct.MeshInstance.html)
+/// transforms. Glyphs without an outline are also cached.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, Font, FontRef, GlyphMeshCache};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let mut cache = GlyphMeshCache::default();
+///
+/// let glyphs: Vec<_> = "llama"
+///     .chars()
+///     .enumerate()
+///     .map(|(n, c)| font.glyph_id(c).with_scale_and_position(24.0, point(n as f32 * 12.0, 24.0)))
+///     .collect();
+/// let instances = cache.instances(&font, &glyphs, 1.0);
+///
+/// // each distinct glyph is tessellated once
+/// assert_eq!(cache.len(), 3);
+/// assert_eq!(instances.len(), 5);
+///
+/// // instances are grouped by mesh, so each run is one instanced draw call
+/// assert_eq!(instances[0].range, instances[1].range);
+///
+/// // upload `cache.vertices()` & `cache.indices()` once, then draw each range
+/// let range = instances[0].range;
+/// let end = (range.first_index + range.index_count) as usize;
+/// assert!(end <= cache.indices().len());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GlyphMeshCache {
+    vertices: Vec<Point>,
+    indices: Vec<u32>,
+    meshes: HashMap<(u64, GlyphId, u32), Option<MeshRange>>,
+}
+
+/// A glyph mesh in the [`GlyphMeshCache`](struct.GlyphMeshCache.html) buffers.
+///
+/// Indices are relative to `base_vertex`, like `glDrawElementsBaseVertex` or the
+/// `base_vertex` of indexed draws in Vulkan, Metal & wgpu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MeshRange {
+    /// Position of the first index in [`indices`](struct.GlyphMeshCache.html#method.indices).
+    pub first_index: u32,
+    /// Number of indices, 3 per triangle.
+    pub index_count: u32,
+    /// Position of the first vertex in [`vertices`](struct.GlyphMeshCache.html#method.vertices).
+    pub base_vertex: u32,
+}
+
+/// A positioned glyph drawing a cached mesh, see
+/// [`GlyphMeshCache::instances`](struct.GlyphMeshCache.html#method.instances).
+///
+/// Unscaled y up vertices `v` are drawn at pixel
+/// `(position.x + v.x * scale.horizontal, position.y - v.y * scale.vertical)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshInstance {
+    /// The glyph mesh.
+    pub range: MeshRange,
+    /// Glyph position, see [`Glyph::position`](struct.Glyph.html#structfield.position).
+    pub position: Point,
+    /// Scale factor of unscaled vertices into pixels.
+    pub scale: PxScaleFactor,
+}
+
+impl GlyphMeshCache {
+    /// Returns the cached mesh of a font's glyph, if cached & it has an outline.
+    #[inline]
+    pub fn get<F: Font>(&self, font: &F, id: GlyphId, tolerance: f32) -> Option<MeshRange> {
+        *self.meshes.get(&key(font, id, tolerance))?
+    }
+
+    /// Returns the cached mesh of a font's glyph, tessellating with `tolerance` & caching
+    /// if required.
+    ///
+    /// Returns `None` if the glyph has no outline.
+    pub fn get_or_tessellate<F: Font>(
+        &mut self,
+        font: &F,
+        id: GlyphId,
+        tolerance: f32,
+    ) -> Option<MeshRange> {
+        let (vertices, indices) = (&mut self.vertices, &mut self.indices);
+        let entry = self.meshes.entry(key(font, id, tolerance));
+        #[cfg(feature = "counters")]
+        crate::counters::add_cache_lookup(matches!(entry, Entry::Occupied(_)));
+        *entry.or_insert_with(|| {
+            let mesh = font.outline(id)?.tessellate(tolerance);
+            let range = MeshRange {
+                first_index: indices.len() as u32,
+                index_count: mesh.indices.len() as u32,
+                base_vertex: vertices.len() as u32,
+            };
+            vertices.extend(mesh.vertices);
+            indices.extend(mesh.indices);
+            Some(range)
+        })
+    }
+
+    /// Returns an instance of each of the glyphs with an outline, tessellating & caching
+    /// meshes if required.
+    ///
+    /// Instances are sorted by mesh, keeping the glyph order within each mesh, so each run
+    /// of instances sharing a range can be drawn with a single instanced draw call.
+    pub fn instances<F: Font>(
+        &mut self,
+        font: &F,
+        glyphs: &[Glyph],
+        tolerance: f32,
+    ) -> Vec<MeshInstance> {
+        let mut instances: Vec<_> = glyphs
+            .iter()
+            .filter_map(|glyph| {
+                Some(MeshInstance {
+                    range: self.get_or_tessellate(font, glyph.id, tolerance)?,
+                    position: glyph.position,
+                    scale: font.as_scaled(glyph.scale).scale_factor(),
+                })
+            })
+            .collect();
+        instances.sort_by_key(|instance| instance.range.first_index);
+        instances
+    }
+
+    /// Returns the unscaled y up vertices of all cached meshes.
+    #[inline]
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// Returns the triangle indices of all cached meshes, relative to each mesh's
+    /// [`base_vertex`](struct.MeshRange.html#structfield.base_vertex).
+    #[inline]
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Returns the number of cached glyphs, including those without an outline.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    /// Returns `true` if no glyphs are cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// Removes all cached meshes.
+    ///
+    /// Vertices & indices are only freed when clearing, as removing a single mesh would
+    /// move the others.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.meshes.clear();
+    }
+}
+
+#[inline]
+fn key<F: Font>(font: &F, id: GlyphId, tolerance: f32) -> (u64, GlyphId, u32) {
+    (font.fingerprint(), id, (tolerance + 0.0).to_bits())
+}
@@ -0,0 +1,26 @@
+/// Font name table record id, see
+/// [`Font::font_name`](trait.Font.html#method.font_name).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NameId(pub u16);
+
+impl NameId {
+    /// Copyright notice.
+    pub const COPYRIGHT: Self = Self(0);
+    /// Family name, e.g. "Exo 2 Light".
+    pub const FAMILY: Self = Self(1);
+    /// Subfamily name, e.g. "Regular" or "Bold Italic".
+    pub const SUBFAMILY: Self = Self(2);
+    /// Unique font identifier.
+    pub const UNIQUE_ID: Self = Self(3);
+    /// Full name, usually the family & subfamily names.
+    pub const FULL_NAME: Self = Self(4);
+    /// Version string.
+    pub const VERSION: Self = Self(5);
+    /// PostScript name, e.g. "Exo2-Light".
+    pub const POST_SCRIPT_NAME: Self = Self(6);
+    /// Typographic family name, grouping more than the 4 regular, bold & italic styles
+    /// of `FAMILY`.
+    pub const TYPOGRAPHIC_FAMILY: Self = Self(16);
+    /// Typographic subfamily name, e.g. "Semibold Condensed".
+    pub const TYPOGRAPHIC_SUBFAMILY: Self = Self(17);
+}
@@ -9,6 +9,11 @@ pub(crate) trait FloatExt {
     fn abs(self) -> Self;
     fn trunc(self) -> Self;
     fn fract(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn atan(self) -> Self;
 }
 
 impl FloatExt for f32 {
@@ -40,4 +45,24 @@ impl FloatExt for f32 {
     fn fract(self) -> Self {
         self - self.trunc()
     }
+    #[inline]
+    fn powf(self, n: Self) -> Self {
+        libm::powf(self, n)
+    }
+    #[inline]
+    fn sin(self) -> Self {
+        libm::sinf(self)
+    }
+    #[inline]
+    fn cos(self) -> Self {
+        libm::cosf(self)
+    }
+    #[inline]
+    fn tan(self) -> Self {
+        libm::tanf(self)
+    }
+    #[inline]
+    fn atan(self) -> Self {
+        libm::atanf(self)
+    }
 }
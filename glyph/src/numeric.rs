@@ -0,0 +1,196 @@
+use crate::{Font, Script};
+
+/// Figure, i.e. digit, widths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FigureSpacing {
+    /// Figures with individual widths, "pnum".
+    Proportional,
+    /// Figures of equal width, "tnum", aligning columns of numbers.
+    Tabular,
+}
+
+impl FigureSpacing {
+    /// Returns the OpenType feature tag.
+    #[inline]
+    pub fn feature(self) -> [u8; 4] {
+        match self {
+            Self::Proportional => *b"pnum",
+            Self::Tabular => *b"tnum",
+        }
+    }
+
+    /// Returns `true` if the font supports this spacing for the script.
+    #[inline]
+    pub fn is_supported<F: Font>(self, font: &F, script: Script) -> bool {
+        font.features(script).contains(&self.feature())
+    }
+}
+
+/// Figure, i.e. digit, heights.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FigureCase {
+    /// Figures of cap height, "lnum".
+    Lining,
+    /// Figures with ascenders & descenders like lowercase letters, "onum".
+    OldStyle,
+}
+
+impl FigureCase {
+    /// Returns the OpenType feature tag.
+    #[inline]
+    pub fn feature(self) -> [u8; 4] {
+        match self {
+            Self::Lining => *b"lnum",
+            Self::OldStyle => *b"onum",
+        }
+    }
+
+    /// Returns `true` if the font supports this case for the script.
+    #[inline]
+    pub fn is_supported<F: Font>(self, font: &F, script: Script) -> bool {
+        font.features(script).contains(&self.feature())
+    }
+}
+
+/// Figure, i.e. digit, positions & fractions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FigurePosition {
+    /// Superscript figures, "sups".
+    Superscript,
+    /// Subscript figures, "subs", e.g. for chemical formulas like "H2O".
+    Subscript,
+    /// Fraction numerator figures, "numr".
+    Numerator,
+    /// Fraction denominator figures, "dnom".
+    Denominator,
+    /// Fractions, "frac", replacing e.g. "1/2" with a numerator, fraction slash &
+    /// denominator.
+    Fraction,
+}
+
+impl FigurePosition {
+    /// Returns the OpenType feature tag.
+    #[inline]
+    pub fn feature(self) -> [u8; 4] {
+        match self {
+            Self::Superscript => *b"sups",
+            Self::Subscript => *b"subs",
+            Self::Numerator => *b"numr",
+            Self::Denominator => *b"dnom",
+            Self::Fraction => *b"frac",
+        }
+    }
+
+    /// Returns `true` if the font supports this position for the script.
+    #[inline]
+    pub fn is_supported<F: Font>(self, font: &F, script: Script) -> bool {
+        font.features(script).contains(&self.feature())
+    }
+}
+
+/// Figure spacing, case & position substitutions, `None` using the font's default figures.
+///
+/// See [`Layout::numeric`](struct.Layout.html#structfield.numeric).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{
+///     point, FigurePosition, FigureSpacing, Font, FontRef, Layout, NumericStyle, ScaleFont, Script,
+/// };
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let scaled_font = font.as_scaled(24.0);
+///
+/// let tabular = NumericStyle {
+///     spacing: Some(FigureSpacing::Tabular),
+///     ..NumericStyle::default()
+/// };
+/// assert!(tabular.is_supported(&font, Script::default()));
+///
+/// let layout = Layout {
+///     numeric: tabular,
+///     ..Layout::default()
+/// };
+/// let mut glyphs = Vec::new();
+/// layout.layout(&scaled_font, point(0.0, 0.0), "18", &mut glyphs);
+///
+/// // "1" is usually narrower than "8", but not tabular figures
+/// assert_eq!(
+///     scaled_font.h_advance(glyphs[0].id),
+///     scaled_font.h_advance(glyphs[1].id)
+/// );
+///
+/// // fractions & chemical formulas
+/// let fraction = Layout {
+///     numeric: NumericStyle {
+///         position: Some(FigurePosition::Fraction),
+///         ..NumericStyle::default()
+///     },
+///     ..Layout::default()
+/// };
+/// glyphs.clear();
+/// fraction.layout(&scaled_font, point(0.0, 0.0), "1/2", &mut glyphs);
+/// let mut numerator = vec![font.glyph_id('1')];
+/// font.substitute(Script::default(), &[*b"numr"], &mut numerator);
+/// let mut denominator = vec![font.glyph_id('2')];
+/// font.substitute(Script::default(), &[*b"dnom"], &mut denominator);
+/// assert_eq!(glyphs[0].id, numerator[0]);
+/// assert_eq!(glyphs[2].id, denominator[0]);
+///
+/// let subscript = Layout {
+///     numeric: NumericStyle {
+///         position: Some(FigurePosition::Subscript),
+///         ..NumericStyle::default()
+///     },
+///     ..Layout::default()
+/// };
+/// glyphs.clear();
+/// subscript.layout(&scaled_font, point(0.0, 0.0), "H2O", &mut glyphs);
+/// assert_eq!(glyphs[0].id, font.glyph_id('H'));
+/// assert_ne!(glyphs[1].id, font.glyph_id('2'));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NumericStyle {
+    /// Figure widths.
+    pub spacing: Option<FigureSpacing>,
+    /// Figure heights.
+    pub case: Option<FigureCase>,
+    /// Figure positions. Fractions apply to whole runs, other positions only substitute
+    /// digits so e.g. subscript "H2O" keeps a regular "H" & "O".
+    pub position: Option<FigurePosition>,
+}
+
+impl NumericStyle {
+    /// Returns the OpenType feature tags of the set spacing, case & position.
+    #[inline]
+    pub fn features(self) -> impl Iterator<Item = [u8; 4]> {
+        self.spacing
+            .map(FigureSpacing::feature)
+            .into_iter()
+            .chain(self.case.map(FigureCase::feature))
+            .chain(self.position.map(FigurePosition::feature))
+    }
+
+    /// Returns `true` if the font supports the set spacing, case & position for the script.
+    pub fn is_supported<F: Font>(self, font: &F, script: Script) -> bool {
+        let supported = font.features(script);
+        self.features().all(|feature| supported.contains(&feature))
+    }
+
+    /// Returns the feature tags applied to whole runs of glyphs.
+    pub(crate) fn run_features(self) -> impl Iterator<Item = [u8; 4]> {
+        let digit = self.digit_feature();
+        self.features()
+            .filter(move |&feature| Some(feature) != digit)
+    }
+
+    /// Returns the position feature tag applied only to digits.
+    pub(crate) fn digit_feature(self) -> Option<[u8; 4]> {
+        match self.position? {
+            FigurePosition::Fraction => None,
+            position => Some(position.feature()),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+/// Optical bounds of a glyph, used for optical margin alignment.
+///
+/// Each value is a delta applied to the corresponding edge of the glyph's advance box to
+/// give its optical edge. For example punctuation like a hyphen may have an optical right
+/// edge inside its advance, allowing it to hang slightly into the margin at the end of a
+/// justified line so the column edge looks straight.
+///
+/// See [`Font::optical_bounds_unscaled`](trait.Font.html#method.optical_bounds_unscaled)
+/// & [`ScaleFont::optical_bounds`](trait.ScaleFont.html#method.optical_bounds).
+///
+/// # Example
+/// Hanging the first glyph of a line into the left margin.
+/// ```
+/// use ab_glyph::{point, Font, FontRef, ScaleFont};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let scaled_font = font.as_scaled(24.0);
+/// let margin = 10.0;
+///
+/// let mut glyph = scaled_font.scaled_glyph('"');
+/// let hang = scaled_font
+///     .optical_bounds(glyph.id)
+///     .map(|bounds| bounds.left)
+///     .unwrap_or_default();
+/// glyph.position = point(margin - hang, 20.0);
+///
+/// // no opbd table in this font
+/// assert_eq!(glyph.position.x, margin);
+/// # Ok(()) }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
+pub struct OpticalBounds {
+    /// Delta of the left edge, along the x-axis.
+    pub left: f32,
+    /// Delta of the top edge, along the y-axis.
+    pub top: f32,
+    /// Delta of the right edge, along the x-axis.
+    pub right: f32,
+    /// Delta of the bottom edge, along the y-axis.
+    pub bottom: f32,
+}
@@ -1,11 +1,21 @@
 #[cfg(all(feature = "libm", not(feature = "std")))]
 use crate::nostd_float::FloatExt;
-use crate::{point, Glyph, Point, PxScaleFactor};
+use crate::{
+    point, ColorLayer, ColorOutline, Font, Glyph, OutlineSink, Point, PxScaleFactor, ScaleFont,
+};
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
 /// A "raw" collection of outline curves for a glyph, unscaled & unpositioned.
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
 pub struct Outline {
     /// Unscaled bounding box.
     pub bounds: Rect,
@@ -15,57 +25,562 @@ pub struct Outline {
 
 impl Outline {
     /// Convert unscaled bounds into pixel bounds at a given scale & position.
+    #[inline]
     pub fn px_bounds(&self, scale_factor: PxScaleFactor, position: Point) -> Rect {
-        let Rect { min, max } = self.bounds;
+        unscaled_px_bounds(self.bounds, scale_factor, position)
+    }
 
-        // Use subpixel fraction in floor/ceil rounding to elimate rounding error
-        // from identical subpixel positions
+    /// Exact whole number pixel bounds at a given scale & position, calculated by
+    /// walking the curves including their extremities.
+    ///
+    /// Slower than [`px_bounds`](#method.px_bounds), which only transforms the stored
+    /// bounding box, but may be smaller as the stored box can include off-curve control points.
+    ///
+    /// Returns `None` if there are no curves.
+    pub fn tight_px_bounds(&self, scale_factor: PxScaleFactor, position: Point) -> Option<Rect> {
         let (x_trunc, x_fract) = (position.x.trunc(), position.x.fract());
         let (y_trunc, y_fract) = (position.y.trunc(), position.y.fract());
+        let scale = |p: &Point| {
+            point(
+                p.x * scale_factor.horizontal + x_fract,
+                p.y * -scale_factor.vertical + y_fract,
+            )
+        };
 
-        Rect {
-            min: point(
-                (min.x * scale_factor.horizontal + x_fract).floor() + x_trunc,
-                (min.y * -scale_factor.vertical + y_fract).floor() + y_trunc,
-            ),
-            max: point(
-                (max.x * scale_factor.horizontal + x_fract).ceil() + x_trunc,
-                (max.y * -scale_factor.vertical + y_fract).ceil() + y_trunc,
-            ),
+        let mut extents = self.curves.iter().map(|curve| curve_extent(curve, scale));
+        let first = extents.next()?;
+        let Rect { min, max } = extents.fold(first, |a, b| a.union(&b));
+
+        Some(Rect {
+            min: point(min.x.floor() + x_trunc, min.y.floor() + y_trunc),
+            max: point(max.x.ceil() + x_trunc, max.y.ceil() + y_trunc),
+        })
+    }
+
+    /// Returns an iterator over the outline curves.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, OutlineCurve> {
+        self.curves.iter()
+    }
+
+    /// Returns the number of curves.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.curves.len()
+    }
+
+    /// Returns `true` if there are no curves.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty()
+    }
+
+    /// Returns a stable hash of the bounds & curves, so identical shapes, e.g. from
+    /// different glyph ids or fonts, can be detected & share cache entries.
+    ///
+    /// Unlike `std::hash::Hash` with a `RandomState` hasher the value is the same across
+    /// runs, platforms & crate versions so may be persisted. Outlines with equal hashes
+    /// are very likely, though not guaranteed, to be identical.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outline = |c| font.outline(font.glyph_id(c)).unwrap();
+    ///
+    /// // 'A' & 'Α' (greek alpha) share a shape
+    /// assert_eq!(outline('A').content_hash(), outline('\u{391}').content_hash());
+    /// assert_ne!(outline('A').content_hash(), outline('B').content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = Fnv1a::default();
+        hash.points(&[self.bounds.min, self.bounds.max]);
+        for curve in &self.curves {
+            match curve {
+                OutlineCurve::Line(p0, p1) => {
+                    hash.write(1);
+                    hash.points(&[*p0, *p1]);
+                }
+                OutlineCurve::Quad(p0, p1, p2) => {
+                    hash.write(2);
+                    hash.points(&[*p0, *p1, *p2]);
+                }
+                OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                    hash.write(3);
+                    hash.points(&[*p0, *p1, *p2, *p3]);
+                }
+            }
+        }
+        hash.0
+    }
+
+    /// Interpolates between this outline, at `t = 0.0`, & `other`, at `t = 1.0`, e.g. to
+    /// animate between two weights or variation instances without re-extracting outlines
+    /// each frame.
+    ///
+    /// Returns `None` unless the outlines have matching structure, the same number & kinds
+    /// of curves, as do the instances of a variable font glyph.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let upright = font.outline(font.glyph_id('l')).unwrap();
+    /// let oblique = SyntheticStyle::oblique().transform(&upright);
+    ///
+    /// let half = upright.lerp(&oblique, 0.5).unwrap();
+    /// let half_slant = SyntheticStyle {
+    ///     slant: SyntheticStyle::OBLIQUE_SLANT / 2.0,
+    ///     ..SyntheticStyle::default()
+    /// };
+    /// let expected = half_slant.transform(&upright).bounds;
+    /// assert!((half.bounds.max.x - expected.max.x).abs() < 1e-2);
+    /// assert!((half.bounds.min.x - expected.min.x).abs() < 1e-2);
+    ///
+    /// let other = font.outline(font.glyph_id('o')).unwrap();
+    /// assert!(upright.lerp(&other, 0.5).is_none());
+    /// ```
+    pub fn lerp(&self, other: &Outline, t: f32) -> Option<Outline> {
+        if self.curves.len() != other.curves.len() {
+            return None;
+        }
+        let mut curves = Vec::with_capacity(self.curves.len());
+        for pair in self.curves.iter().zip(&other.curves) {
+            curves.push(match pair {
+                (OutlineCurve::Line(a0, a1), OutlineCurve::Line(b0, b1)) => {
+                    OutlineCurve::Line(a0.lerp(*b0, t), a1.lerp(*b1, t))
+                }
+                (OutlineCurve::Quad(a0, a1, a2), OutlineCurve::Quad(b0, b1, b2)) => {
+                    OutlineCurve::Quad(a0.lerp(*b0, t), a1.lerp(*b1, t), a2.lerp(*b2, t))
+                }
+                (OutlineCurve::Cubic(a0, a1, a2, a3), OutlineCurve::Cubic(b0, b1, b2, b3)) => {
+                    OutlineCurve::Cubic(
+                        a0.lerp(*b0, t),
+                        a1.lerp(*b1, t),
+                        a2.lerp(*b2, t),
+                        a3.lerp(*b3, t),
+                    )
+                }
+                _ => return None,
+            });
+        }
+
+        let bounds = crate::synthetic::curves_bounds(&curves);
+        Some(Outline { bounds, curves })
+    }
+}
+
+/// 64 bit FNV-1a hasher, used for stable content hashes.
+pub(crate) struct Fnv1a(pub(crate) u64);
+
+impl Default for Fnv1a {
+    #[inline]
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    #[inline]
+    pub(crate) fn write(&mut self, byte: u8) {
+        self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+    }
+
+    #[inline]
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write(byte);
+        }
+    }
+
+    /// Writes the little endian bits of each coordinate, normalizing `-0.0` to `0.0`.
+    #[inline]
+    pub(crate) fn points(&mut self, points: &[Point]) {
+        for p in points {
+            for v in &[p.x, p.y] {
+                self.bytes(&(v + 0.0).to_bits().to_le_bytes());
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Outline {
+    type Item = &'a OutlineCurve;
+    type IntoIter = core::slice::Iter<'a, OutlineCurve>;
+
+    /// Iterates over the outline curves.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let outline = font.outline(font.glyph_id('q')).unwrap();
+    ///
+    /// let mut curves = 0;
+    /// for curve in &outline {
+    ///     let bounds = curve.bounds();
+    ///     assert!(bounds.min.x >= outline.bounds.min.x && bounds.max.x <= outline.bounds.max.x);
+    ///     curves += 1;
+    /// }
+    /// assert_eq!(curves, outline.len());
+    /// assert_eq!(outline.iter().len(), outline.len());
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.curves.iter()
+    }
+}
+
+/// Returns the exact extent of a curve, including any turning points, after transforming
+/// each point.
+fn curve_extent(curve: &OutlineCurve, transform: impl Fn(&Point) -> Point) -> Rect {
+    let (mut min_x, mut max_x) = (f32::INFINITY, f32::NEG_INFINITY);
+    let (mut min_y, mut max_y) = (f32::INFINITY, f32::NEG_INFINITY);
+    let mut include_x = |x: f32| {
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+    };
+    let mut include_y = |y: f32| {
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    };
+
+    match curve {
+        OutlineCurve::Line(p0, p1) => {
+            let (p0, p1) = (transform(p0), transform(p1));
+            include_x(p0.x);
+            include_x(p1.x);
+            include_y(p0.y);
+            include_y(p1.y);
+        }
+        OutlineCurve::Quad(p0, p1, p2) => {
+            let (p0, p1, p2) = (transform(p0), transform(p1), transform(p2));
+            include_x(p0.x);
+            include_x(p2.x);
+            include_y(p0.y);
+            include_y(p2.y);
+            quad_extrema(p0.x, p1.x, p2.x, &mut include_x);
+            quad_extrema(p0.y, p1.y, p2.y, &mut include_y);
+        }
+        OutlineCurve::Cubic(p0, p1, p2, p3) => {
+            let (p0, p1, p2, p3) = (transform(p0), transform(p1), transform(p2), transform(p3));
+            include_x(p0.x);
+            include_x(p3.x);
+            include_y(p0.y);
+            include_y(p3.y);
+            cubic_extrema(p0.x, p1.x, p2.x, p3.x, &mut include_x);
+            cubic_extrema(p0.y, p1.y, p2.y, p3.y, &mut include_y);
+        }
+    }
+
+    Rect {
+        min: point(min_x, min_y),
+        max: point(max_x, max_y),
+    }
+}
+
+/// Calls `f` with the value of any turning point of a single axis of a quadratic
+/// Bézier curve in `0 < t < 1`.
+fn quad_extrema(p0: f32, p1: f32, p2: f32, f: &mut impl FnMut(f32)) {
+    let denom = p0 - 2.0 * p1 + p2;
+    if denom != 0.0 {
+        let t = (p0 - p1) / denom;
+        if t > 0.0 && t < 1.0 {
+            let mt = 1.0 - t;
+            f(mt * mt * p0 + 2.0 * mt * t * p1 + t * t * p2);
+        }
+    }
+}
+
+/// Calls `f` with the value of any turning points of a single axis of a cubic
+/// Bézier curve in `0 < t < 1`.
+fn cubic_extrema(p0: f32, p1: f32, p2: f32, p3: f32, f: &mut impl FnMut(f32)) {
+    let mut eval = |t: f32| {
+        if t > 0.0 && t < 1.0 {
+            let mt = 1.0 - t;
+            f(mt * mt * mt * p0 + 3.0 * mt * mt * t * p1 + 3.0 * mt * t * t * p2 + t * t * t * p3);
+        }
+    };
+
+    // derivative / 3 = a*t^2 + 2*b*t + c
+    let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+    let b = p0 - 2.0 * p1 + p2;
+    let c = p1 - p0;
+
+    if a.abs() < 1e-6 {
+        if b != 0.0 {
+            eval(-c / (2.0 * b));
+        }
+    } else {
+        let discriminant = b * b - a * c;
+        if discriminant >= 0.0 {
+            let root = discriminant.sqrt();
+            eval((-b + root) / a);
+            eval((-b - root) / a);
+        }
+    }
+}
+
+/// Float type used for outline pixel transforms, `f64` with the `f64` feature.
+///
+/// Only scaling & positioning use it, the rasterizer draws f32 points relative to the
+/// glyph's pixel bounds origin which are small enough not to lose precision.
+#[cfg(not(feature = "f64"))]
+type Float = f32;
+#[cfg(feature = "f64")]
+type Float = f64;
+
+#[cfg(not(feature = "f64"))]
+#[inline]
+fn to_f32(f: Float) -> f32 {
+    f
+}
+#[cfg(feature = "f64")]
+#[inline]
+fn to_f32(f: Float) -> f32 {
+    f as f32
+}
+
+/// Converts unscaled outline bounds into whole pixel bounds at a given scale & position.
+pub(crate) fn unscaled_px_bounds(
+    bounds: Rect,
+    scale_factor: PxScaleFactor,
+    position: Point,
+) -> Rect {
+    let Rect { min, max } = bounds;
+    let (h_factor, v_factor) = (
+        Float::from(scale_factor.horizontal),
+        Float::from(-scale_factor.vertical),
+    );
+
+    // Use subpixel fraction in floor/ceil rounding to elimate rounding error
+    // from identical subpixel positions
+    let (x_trunc, x_fract) = (position.x.trunc(), Float::from(position.x.fract()));
+    let (y_trunc, y_fract) = (position.y.trunc(), Float::from(position.y.fract()));
+
+    Rect {
+        min: point(
+            to_f32((Float::from(min.x) * h_factor + x_fract).floor()) + x_trunc,
+            to_f32((Float::from(min.y) * v_factor + y_fract).floor()) + y_trunc,
+        ),
+        max: point(
+            to_f32((Float::from(max.x) * h_factor + x_fract).ceil()) + x_trunc,
+            to_f32((Float::from(max.y) * v_factor + y_fract).ceil()) + y_trunc,
+        ),
+    }
+}
+
+/// Transforms unscaled outline points into pixel coordinates relative to an origin.
+///
+/// The offset is calculated in `Float` precision, avoiding loss of subpixel
+/// accuracy when subtracting large positions.
+#[derive(Clone, Copy)]
+pub(crate) struct PxTransform {
+    h_factor: Float,
+    v_factor: Float,
+    offset_x: Float,
+    offset_y: Float,
+}
+
+impl PxTransform {
+    #[inline]
+    pub(crate) fn new(scale_factor: PxScaleFactor, position: Point, origin: Point) -> Self {
+        Self {
+            h_factor: Float::from(scale_factor.horizontal),
+            v_factor: Float::from(-scale_factor.vertical),
+            offset_x: Float::from(position.x) - Float::from(origin.x),
+            offset_y: Float::from(position.y) - Float::from(origin.y),
+        }
+    }
+
+    #[inline]
+    fn apply(&self, p: &Point) -> Point {
+        point(
+            to_f32(Float::from(p.x) * self.h_factor + self.offset_x),
+            to_f32(Float::from(p.y) * self.v_factor + self.offset_y),
+        )
+    }
+}
+
+/// Rasterizes a glyph's outline streamed from
+/// [`Font::outline_with`](trait.Font.html#method.outline_with) without collecting curves,
+/// returning the pixel bounds drawn.
+pub(crate) fn draw_streamed<F: Font>(
+    font: &F,
+    glyph: &Glyph,
+    o: impl FnMut(u32, u32, f32),
+) -> Option<Rect> {
+    let scale_factor = font.as_scaled(glyph.scale).scale_factor();
+    let bounds = font.glyph_bounds_unscaled(glyph.id)?;
+    let px_bounds = unscaled_px_bounds(bounds, scale_factor, glyph.position);
+    let (w, h) = (px_bounds.width() as usize, px_bounds.height() as usize);
+    #[cfg(feature = "counters")]
+    crate::counters::add_pixels_rasterized(w * h);
+
+    let mut sink = RasterizerSink {
+        rasterizer: ab_glyph_rasterizer::Rasterizer::new(w, h),
+        transform: PxTransform::new(scale_factor, glyph.position, px_bounds.min),
+        start: Point::default(),
+        last: Point::default(),
+    };
+    font.outline_with(glyph.id, &mut sink)?;
+    sink.rasterizer.for_each_pixel_2d(o);
+    Some(px_bounds)
+}
+
+/// Draws outline segments, transformed into pixels, into a rasterizer.
+struct RasterizerSink {
+    rasterizer: ab_glyph_rasterizer::Rasterizer,
+    transform: PxTransform,
+    start: Point,
+    last: Point,
+}
+
+impl OutlineSink for RasterizerSink {
+    #[inline]
+    fn move_to(&mut self, p: Point) {
+        self.start = self.transform.apply(&p);
+        self.last = self.start;
+    }
+
+    #[inline]
+    fn line_to(&mut self, p: Point) {
+        let p = self.transform.apply(&p);
+        self.rasterizer.draw_line(self.last, p);
+        self.last = p;
+    }
+
+    #[inline]
+    fn quad_to(&mut self, control: Point, p: Point) {
+        let (control, p) = (self.transform.apply(&control), self.transform.apply(&p));
+        self.rasterizer.draw_quad(self.last, control, p);
+        self.last = p;
+    }
+
+    #[inline]
+    fn curve_to(&mut self, control0: Point, control1: Point, p: Point) {
+        let control0 = self.transform.apply(&control0);
+        let control1 = self.transform.apply(&control1);
+        let p = self.transform.apply(&p);
+        self.rasterizer.draw_cubic(self.last, control0, control1, p);
+        self.last = p;
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        if self.last != self.start {
+            self.rasterizer.draw_line(self.last, self.start);
+            self.last = self.start;
         }
     }
 }
 
+/// Glyph outline with curves allocated in a bumpalo arena, requires the `bumpalo` feature.
+///
+/// See [`Font::outline_in`](trait.Font.html#method.outline_in).
+#[cfg(feature = "bumpalo")]
+#[derive(Clone, Copy, Debug)]
+pub struct ArenaOutline<'bump> {
+    /// Unscaled bounding box.
+    pub bounds: Rect,
+    /// Unscaled & unpositioned outline curves.
+    pub curves: &'bump [OutlineCurve],
+}
+
 #[derive(Clone, Debug)]
 pub struct OutlineGroup {
-    group: Vec<(Outline,u32)>,
-    is_colored: bool
+    group: Layers,
+    is_colored: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Layers {
+    Owned(Vec<ColorLayer>),
+    #[cfg(feature = "std")]
+    Shared(Arc<Outline>),
 }
 
 impl OutlineGroup {
     pub fn from_outline(outline: Outline) -> Self {
-        OutlineGroup{
-            group: vec!((outline,0xFFFFFFFF)),
-            is_colored: false
+        OutlineGroup {
+            group: Layers::Owned(vec![uncolored_layer(outline)]),
+            is_colored: false,
+        }
+    }
+
+    /// Color glyph group of the layers of `outline`, bottom layer first.
+    pub fn new(outline: ColorOutline) -> Self {
+        OutlineGroup {
+            group: Layers::Owned(outline.layers),
+            is_colored: true,
+        }
+    }
+
+    /// Single layer, non-colored group sharing an already extracted outline.
+    ///
+    /// Cloning the group, or glyphs outlined with it, does not clone the curves.
+    #[cfg(feature = "std")]
+    pub fn from_shared(outline: Arc<Outline>) -> Self {
+        OutlineGroup {
+            group: Layers::Shared(outline),
+            is_colored: false,
+        }
+    }
+
+    /// Replaces each layer's outline with `f` of it, sharing no outlines afterwards.
+    fn map_outlines(self, f: impl Fn(&Outline) -> Outline) -> Self {
+        let group = match self.group {
+            Layers::Owned(group) => group
+                .into_iter()
+                .map(|layer| ColorLayer {
+                    outline: f(&layer.outline),
+                    ..layer
+                })
+                .collect(),
+            #[cfg(feature = "std")]
+            Layers::Shared(outline) => vec![uncolored_layer(f(&outline))],
+        };
+        Self {
+            group: Layers::Owned(group),
+            is_colored: self.is_colored,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.group {
+            Layers::Owned(group) => group.len(),
+            #[cfg(feature = "std")]
+            Layers::Shared(_) => 1,
         }
     }
 
-    pub fn new(group: Vec<(Outline,u32)>) -> Self {
-        OutlineGroup{
-            group,
-            is_colored: true
+    fn layer(&self, layer: usize) -> (&Outline, u32) {
+        match &self.group {
+            Layers::Owned(group) => (&group[layer].outline, group[layer].rgba_u32()),
+            #[cfg(feature = "std")]
+            Layers::Shared(outline) => {
+                assert_eq!(layer, 0, "layer out of bounds");
+                (outline, 0xFFFFFFFF)
+            }
         }
     }
 
     fn px_bounds(&self, scale_factor: PxScaleFactor, position: Point) -> Rect {
-        let mut iter = self.group.iter().map(|a| a.0.px_bounds(scale_factor, position));
-        let init = iter.next().unwrap();
-        iter.fold(init,|a,b| {
-            Rect{
-                min: point(a.min.x.min(b.min.x), a.min.y.min(b.min.y)),
-                max: point(a.max.x.max(b.max.x), a.max.y.max(b.max.y)),
-            } 
-        })
+        let mut iter = (0..self.len()).map(|i| self.layer(i).0.px_bounds(scale_factor, position));
+        match iter.next() {
+            Some(init) => iter.fold(init, |a, b| a.union(&b)),
+            None => Rect::default(),
+        }
+    }
+}
+
+/// Single white layer of a non-colored glyph, drawn in the text foreground color.
+#[inline]
+fn uncolored_layer(outline: Outline) -> ColorLayer {
+    ColorLayer {
+        outline,
+        rgba: [0xFF; 4],
+        palette_index: ColorLayer::FOREGROUND,
+        order: 0,
     }
 }
 
@@ -79,6 +594,18 @@ pub struct OutlinedGlyph {
     scale_factor: PxScaleFactor,
     // Raw outline
     outline: OutlineGroup,
+    // Small size simplification
+    lod: Option<Lod>,
+    // Small size coverage darkening
+    darkening: Option<StemDarkening>,
+    // Coverage gamma & contrast
+    gamma: Option<GammaCorrection>,
+    // Custom coverage to alpha mapping
+    coverage_lut: Option<Box<CoverageLut>>,
+    // Pixel width of the outline stroke, drawn instead of the fill
+    stroke: Option<f32>,
+    // Whole pixels of empty space reserved around the bounds
+    padding: u32,
 }
 
 impl OutlinedGlyph {
@@ -93,10 +620,320 @@ impl OutlinedGlyph {
             glyph,
             px_bounds,
             scale_factor,
-            outline
+            outline,
+            lod: None,
+            darkening: None,
+            gamma: None,
+            coverage_lut: None,
+            stroke: None,
+            padding: 0,
         }
     }
 
+    /// Constructs an `OutlinedGlyph` sharing an already extracted outline.
+    ///
+    /// Useful to position the same glyph many times without cloning its curves.
+    /// Requires the `std` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontRef, OutlinedGlyph, ScaleFont};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let scale_factor = font.as_scaled(24.0).scale_factor();
+    ///
+    /// let outline = Arc::new(font.outline(font.glyph_id('s')).unwrap());
+    /// let glyphs: Vec<_> = [point(0.0, 20.0), point(12.0, 20.0)]
+    ///     .iter()
+    ///     .map(|&position| {
+    ///         let glyph = font.glyph_id('s').with_scale_and_position(24.0, position);
+    ///         OutlinedGlyph::new_shared(glyph, Arc::clone(&outline), scale_factor)
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(Arc::strong_count(&outline), 3);
+    /// assert_eq!(glyphs[1].px_bounds().min.x - glyphs[0].px_bounds().min.x, 12.0);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn new_shared(glyph: Glyph, outline: Arc<Outline>, scale_factor: PxScaleFactor) -> Self {
+        Self::new(glyph, OutlineGroup::from_shared(outline), scale_factor)
+    }
+
+    /// Enables level of detail simplification, drawing curves with fewer lines when the
+    /// glyph's pixel scale is below [`Lod::max_px_scale`](struct.Lod.html#structfield.max_px_scale).
+    ///
+    /// Reduces rasterization cost of dense small text, e.g. minimaps & log viewers, where
+    /// the difference isn't visible.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let glyph = font.glyph_id('s').with_scale(10.0);
+    /// let outlined = font.outline_glyph(glyph).unwrap();
+    ///
+    /// let mut exact = vec![];
+    /// outlined.draw(0, |_, _, c| exact.push(c));
+    ///
+    /// let mut simplified = vec![];
+    /// outlined.with_lod(Lod::default()).draw(0, |_, _, c| simplified.push(c));
+    ///
+    /// for (a, b) in exact.iter().zip(&simplified) {
+    ///     assert!((a - b).abs() < 0.5);
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_lod(mut self, lod: Lod) -> Self {
+        self.lod = Some(lod);
+        self
+    }
+
+    /// Enables stem darkening, increasing coverage when the glyph's pixel scale is below
+    /// [`StemDarkening::max_px_scale`](struct.StemDarkening.html#structfield.max_px_scale).
+    ///
+    /// Improves the legibility of light weight fonts at small sizes, similar to FreeType's
+    /// stem darkening. Applies to [`draw`](#method.draw) & [`draw_spans`](#method.draw_spans).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('e').with_scale(11.0)).unwrap();
+    ///
+    /// let mut coverage = vec![];
+    /// outlined.draw(0, |_, _, c| coverage.push(c));
+    ///
+    /// let mut darkened = vec![];
+    /// let outlined = outlined.with_stem_darkening(StemDarkening::default());
+    /// outlined.draw(0, |_, _, c| darkened.push(c));
+    ///
+    /// for (c, dark) in coverage.iter().zip(&darkened) {
+    ///     assert!(dark >= c);
+    /// }
+    /// assert!(darkened.iter().sum::<f32>() > coverage.iter().sum::<f32>());
+    /// ```
+    #[inline]
+    pub fn with_stem_darkening(mut self, darkening: StemDarkening) -> Self {
+        self.darkening = Some(darkening);
+        self
+    }
+
+    /// Enables gamma & contrast correction of coverage, applied after any
+    /// [stem darkening](#method.with_stem_darkening).
+    ///
+    /// Linear coverage blended in a non-linear color space, e.g. sRGB, looks too thin &
+    /// light for dark text on light backgrounds. Applies to [`draw`](#method.draw),
+    /// [`draw_spans`](#method.draw_spans), [`draw_into`](#method.draw_into) &
+    /// [`draw_subpixel`](#method.draw_subpixel).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('e').with_scale(20.0)).unwrap();
+    ///
+    /// let mut coverage = vec![];
+    /// outlined.draw(0, |_, _, c| coverage.push(c));
+    ///
+    /// let mut corrected = vec![];
+    /// let outlined = outlined.with_gamma_correction(GammaCorrection::default());
+    /// outlined.draw(0, |_, _, c| corrected.push(c));
+    ///
+    /// for (c, corrected) in coverage.iter().zip(&corrected) {
+    ///     assert!(corrected >= c);
+    ///     // empty & full coverage is unchanged
+    ///     if *c == 0.0 || *c == 1.0 {
+    ///         assert_eq!(corrected, c);
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_gamma_correction(mut self, gamma: GammaCorrection) -> Self {
+        self.gamma = Some(gamma);
+        self
+    }
+
+    /// Maps 8 bit coverage through a custom lookup table, applied after any
+    /// [stem darkening](#method.with_stem_darkening) &
+    /// [gamma correction](#method.with_gamma_correction). E.g. for custom gamma curves,
+    /// thresholding or stylized rendering.
+    ///
+    /// Applies to [`draw`](#method.draw), [`draw_spans`](#method.draw_spans),
+    /// [`draw_into`](#method.draw_into) & [`draw_subpixel`](#method.draw_subpixel),
+    /// coverage is rounded to 8 bits for the lookup. [`draw_spans`](#method.draw_spans) maps
+    /// each span with a single lookup in a table combined with any darkening & correction,
+    /// spans mapped to zero are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font
+    ///     .outline_glyph(font.glyph_id('e').with_scale(20.0))
+    ///     .unwrap()
+    ///     .with_coverage_lut(CoverageLut::threshold(128));
+    ///
+    /// // aliased, every pixel is either empty or fully covered
+    /// outlined.draw_spans(0, |_, _, _, c| assert_eq!(c, 255));
+    /// outlined.draw(0, |_, _, c| assert!(c == 0.0 || c == 1.0));
+    /// ```
+    #[inline]
+    pub fn with_coverage_lut(mut self, lut: CoverageLut) -> Self {
+        self.coverage_lut = Some(Box::new(lut));
+        self
+    }
+
+    /// Draws just the outline stroke, of the given pixel `width` centred on the outline,
+    /// instead of the fill. Useful for "hollow" text effects & debugging glyph geometry.
+    ///
+    /// Pixel bounds are expanded to fit the stroke.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let glyph = font.glyph_id('T').with_scale_and_position(200.0, point(10.0, 200.0));
+    /// let filled = font.outline_glyph(glyph.clone()).unwrap();
+    /// let hollow = font.outline_glyph(glyph).unwrap().with_stroke(2.0);
+    ///
+    /// let bounds = filled.px_bounds();
+    /// assert_eq!(hollow.px_bounds().min, bounds.min - point(1.0, 1.0));
+    /// assert_eq!(hollow.px_bounds().max, bounds.max + point(1.0, 1.0));
+    ///
+    /// // only the perimeter is drawn
+    /// let (mut fill, mut stroke) = (0.0, 0.0);
+    /// filled.draw(0, |_, _, c| fill += c);
+    /// hollow.draw(0, |_, _, c| stroke += c);
+    /// assert!(stroke < fill / 2.0);
+    /// ```
+    pub fn with_stroke(mut self, width: f32) -> Self {
+        let width = width.max(0.0);
+        if let Some(pad) = self.stroke.map(|w| (w / 2.0).ceil()) {
+            self.px_bounds = expand(self.px_bounds, -pad);
+        }
+        self.stroke = Some(width);
+        self.px_bounds = expand(self.px_bounds, (width / 2.0).ceil());
+        self
+    }
+
+    /// Merges overlapping contours of each layer, e.g. of composite glyph components, so
+    /// overlaps aren't drawn with darker seams. Pixel bounds are unchanged.
+    ///
+    /// See [`Outline::remove_overlaps`](struct.Outline.html#method.remove_overlaps).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('e').with_scale(30.0)).unwrap();
+    /// let merged = outlined.clone().with_overlaps_removed();
+    /// assert_eq!(merged.px_bounds(), outlined.px_bounds());
+    ///
+    /// // no overlaps in this glyph, so the coverage is the same
+    /// let (mut coverage, mut merged_coverage) = (0.0, 0.0);
+    /// outlined.draw(0, |_, _, c| coverage += c);
+    /// merged.draw(0, |_, _, c| merged_coverage += c);
+    /// assert!((coverage - merged_coverage).abs() < 0.5);
+    /// ```
+    pub fn with_overlaps_removed(mut self) -> Self {
+        self.outline = self.outline.map_outlines(Outline::remove_overlaps);
+        self
+    }
+
+    /// Reserves `padding` whole pixels of empty space on all sides of the pixel bounds,
+    /// e.g. room for effects like outlines & drop shadows drawn around the coverage.
+    ///
+    /// Expands [`px_bounds`](#method.px_bounds), [`tight_px_bounds`](#method.tight_px_bounds)
+    /// & [`sdf_px_bounds`](#method.sdf_px_bounds), with [`draw`](#method.draw) coordinates
+    /// relative to the padded bounds. Replaces any previous padding.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('o').with_scale(40.0)).unwrap();
+    /// let bounds = outlined.px_bounds();
+    ///
+    /// let padded = outlined.clone().with_padding(3);
+    /// assert_eq!(padded.px_bounds().min, bounds.min - point(3.0, 3.0));
+    /// assert_eq!(padded.px_bounds().max, bounds.max + point(3.0, 3.0));
+    ///
+    /// // the same coverage is drawn, surrounded by empty padding
+    /// let (mut coverage, mut padded_coverage) = (0.0, 0.0);
+    /// outlined.draw(0, |_, _, c| coverage += c);
+    /// let (w, h) = (bounds.width() as u32, bounds.height() as u32);
+    /// padded.draw(0, |x, y, c| {
+    ///     if x < 3 || y < 3 || x >= w + 3 || y >= h + 3 {
+    ///         assert!(c < 1e-5, "{}", c);
+    ///     }
+    ///     padded_coverage += c;
+    /// });
+    /// assert!((coverage - padded_coverage).abs() < 1e-3);
+    /// ```
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.px_bounds = expand(self.px_bounds, padding as f32 - self.padding as f32);
+        self.padding = padding;
+        self
+    }
+
+    /// Whole pixels of empty space reserved around the bounds, see
+    /// [`with_padding`](#method.with_padding).
+    #[inline]
+    pub fn padding(&self) -> u32 {
+        self.padding
+    }
+
+    /// Returns the coverage exponent of enabled stem darkening at this glyph's scale.
+    #[inline]
+    fn darkening_exponent(&self) -> Option<f32> {
+        let darkening = self.darkening?;
+        let strength = darkening.amount * (1.0 - self.glyph.scale.y / darkening.max_px_scale);
+        if strength > 0.0 {
+            Some((1.0 + strength).recip())
+        } else {
+            None
+        }
+    }
+
+    /// Returns a function applying enabled stem darkening, gamma correction & coverage
+    /// lookup table to coverage.
+    #[inline]
+    pub(crate) fn coverage_adjustment(&self) -> Option<impl Fn(f32) -> f32 + '_> {
+        let exp = self.darkening_exponent();
+        let gamma = self.gamma;
+        let lut = self.coverage_lut.as_deref();
+        if exp.is_none() && gamma.is_none() && lut.is_none() {
+            return None;
+        }
+        Some(move |mut c: f32| {
+            if let Some(exp) = exp {
+                c = c.powf(exp);
+            }
+            if let Some(gamma) = gamma {
+                c = gamma.apply(c);
+            }
+            if let Some(lut) = lut {
+                c = f32::from(lut.get(coverage_u8(c))) / 255.0;
+            }
+            c
+        })
+    }
+
+    /// Returns a table mapping 8 bit coverage through the
+    /// [`coverage_adjustment`](#method.coverage_adjustment), if any.
+    fn coverage_table(&self) -> Option<[u8; 256]> {
+        let adjust = self.coverage_adjustment()?;
+        let mut table = [0; 256];
+        for (c, out) in table.iter_mut().enumerate() {
+            *out = coverage_u8(adjust(c as f32 / 255.0));
+        }
+        Some(table)
+    }
+
     /// Glyph info.
     #[inline]
     pub fn glyph(&self) -> &Glyph {
@@ -110,17 +947,74 @@ impl OutlinedGlyph {
     }
 
     /// Conservative whole number pixel bounding box for this glyph.
+    ///
+    /// Calculated once on construction by transforming the outline's unscaled bounding box,
+    /// without walking the curves. See [`tight_px_bounds`](#method.tight_px_bounds).
     #[inline]
     pub fn px_bounds(&self) -> Rect {
         self.px_bounds
     }
 
+    /// Exact whole number pixel bounding box for this glyph, calculated by walking
+    /// all outline curves. Always within [`px_bounds`](#method.px_bounds) which
+    /// should be preferred unless exact bounds are required.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?;
+    /// let glyph = font.glyph_id('o').with_scale_and_position(60.0, point(10.0, 50.0));
+    /// let outlined = font.outline_glyph(glyph).unwrap();
+    ///
+    /// let (px_bounds, tight) = (outlined.px_bounds(), outlined.tight_px_bounds());
+    /// assert!(tight.min.x >= px_bounds.min.x && tight.min.y >= px_bounds.min.y);
+    /// assert!(tight.max.x <= px_bounds.max.x && tight.max.y <= px_bounds.max.y);
+    /// # Ok(()) }
+    /// ```
+    pub fn tight_px_bounds(&self) -> Rect {
+        let mut iter = (0..self.outline.len()).filter_map(|i| {
+            self.outline
+                .layer(i)
+                .0
+                .tight_px_bounds(self.scale_factor, self.glyph.position)
+        });
+        let init = match iter.next() {
+            Some(bounds) => bounds,
+            None => return self.px_bounds,
+        };
+        let bounds = iter.fold(init, |a, b| a.union(&b));
+        let pad = self.stroke.map_or(0.0, |width| (width / 2.0).ceil());
+        expand(bounds, pad + self.padding as f32)
+    }
+
+    /// Returns the number of color layers, or `None` for non-colored glyphs which have a
+    /// single layer.
     pub fn get_colored_layers(&self) -> Option<usize> {
-        if self.outline.is_colored { Some(self.outline.group.len()) } else { None }
+        if self.outline.is_colored {
+            Some(self.outline.len())
+        } else {
+            None
+        }
     }
 
+    /// Returns the `0xRRGGBBAA` color of a layer, opaque white for non-colored glyphs.
+    ///
+    /// See [`color_layer`](#method.color_layer) for the palette index.
     pub fn get_color(&self, layer: usize) -> u32 {
-        self.outline.group[layer].1
+        self.outline.layer(layer).1
+    }
+
+    /// Returns a layer of a color glyph, including its palette index, e.g. to draw
+    /// [`foreground`](struct.ColorLayer.html#method.is_foreground) layers in the text color.
+    ///
+    /// Returns `None` for non-colored glyphs or layers out of bounds.
+    pub fn color_layer(&self, layer: usize) -> Option<&ColorLayer> {
+        match &self.outline.group {
+            Layers::Owned(group) if self.outline.is_colored => group.get(layer),
+            _ => None,
+        }
     }
 
     /// Draw this glyph outline using a pixel & coverage handling function.
@@ -128,51 +1022,783 @@ impl OutlinedGlyph {
     /// The callback will be called for each `(x, y)` pixel coordinate inside the bounds
     /// with a coverage value in the range `[0.0, 1.0]` indicating how much the glyph covered
     /// that pixel.
-    pub fn draw<O: FnMut(u32, u32, f32)>(&self, layer: usize, o: O) {
-        use ab_glyph_rasterizer::Rasterizer;
-        let h_factor = self.scale_factor.horizontal;
-        let v_factor = -self.scale_factor.vertical;
-        let offset = self.glyph.position - self.px_bounds.min;
+    pub fn draw<O: FnMut(u32, u32, f32)>(&self, layer: usize, mut o: O) {
+        let rasterizer = self.rasterize(layer);
+        match self.coverage_adjustment() {
+            Some(adjust) => rasterizer.for_each_pixel_2d(|x, y, c| o(x, y, adjust(c))),
+            None => rasterizer.for_each_pixel_2d(o),
+        }
+    }
+
+    /// Draw this glyph outline like [`draw`](#method.draw) with a fallible callback,
+    /// stopping at & returning the first error.
+    ///
+    /// Allows aborting early, e.g. once occlusion is detected, saving the work of handling
+    /// the remaining pixels of large glyphs.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('l').with_scale(40.0)).unwrap();
+    ///
+    /// // find the first fully covered pixel
+    /// let mut visited = 0;
+    /// let covered = outlined.try_draw(0, |x, y, c| {
+    ///     visited += 1;
+    ///     if c >= 1.0 { Err((x, y)) } else { Ok(()) }
+    /// });
+    /// assert!(covered.is_err());
+    /// let bounds = outlined.px_bounds();
+    /// assert!(visited < (bounds.width() * bounds.height()) as usize);
+    /// ```
+    pub fn try_draw<E, O: FnMut(u32, u32, f32) -> Result<(), E>>(
+        &self,
+        layer: usize,
+        mut o: O,
+    ) -> Result<(), E> {
+        let rasterizer = self.rasterize(layer);
+        match self.coverage_adjustment() {
+            Some(adjust) => rasterizer.try_for_each_pixel_2d(|x, y, c| o(x, y, adjust(c))),
+            None => rasterizer.try_for_each_pixel_2d(o),
+        }
+    }
+
+    /// Draw this glyph outline as run-length encoded spans of pixels with equal 8 bit
+    /// coverage.
+    ///
+    /// The callback will be called for each span of non-zero coverage with the span's
+    /// `(y, x_start, len, coverage)`, where pixels `x_start..x_start + len` in row `y` all
+    /// have the coverage in the range `[1, 255]`. Typically much faster to blit into a
+    /// framebuffer than handling each pixel with [`draw`](#method.draw).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('l').with_scale(40.0)).unwrap();
+    /// let width = outlined.px_bounds().width() as usize;
+    ///
+    /// let mut pixels = vec![0; width * outlined.px_bounds().height() as usize];
+    /// outlined.draw(0, |x, y, c| {
+    ///     pixels[y as usize * width + x as usize] = (c * 255.0).round() as u8;
+    /// });
+    ///
+    /// let mut spans = vec![0; pixels.len()];
+    /// outlined.draw_spans(0, |y, x, len, c| {
+    ///     let start = y as usize * width + x as usize;
+    ///     spans[start..start + len as usize].iter_mut().for_each(|px| *px = c);
+    /// });
+    /// assert_eq!(spans, pixels);
+    /// ```
+    pub fn draw_spans<O: FnMut(u32, u32, u32, u8)>(&self, layer: usize, mut o: O) {
+        let rasterizer = self.rasterize(layer);
+        match self.coverage_table() {
+            Some(table) => rasterizer.for_each_span(|y, x, len, c| {
+                // a coverage lookup table may map coverage to zero
+                match table[usize::from(c)] {
+                    0 => {}
+                    c => o(y, x, len, c),
+                }
+            }),
+            None => rasterizer.for_each_span(o),
+        }
+    }
+
+    /// Draws this glyph's 8 bit coverage directly into a row-major `buffer`, e.g. a glyph
+    /// atlas, avoiding a callback per pixel.
+    ///
+    /// Pixel `(x, y)` of the [`px_bounds`](#method.px_bounds) is written to
+    /// `buffer[offset + y * stride + x]`, overwriting the existing value. Coverage matches
+    /// [`draw`](#method.draw) scaled to `[0, 255]`.
+    ///
+    /// # Panics
+    /// If `stride` is less than the bounds width or `buffer` is too small.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('w').with_scale(30.0)).unwrap();
+    /// let bounds = outlined.px_bounds();
+    ///
+    /// // draw at (2, 3) in a 64x64 atlas
+    /// let stride = 64;
+    /// let mut atlas = vec![0_u8; stride * 64];
+    /// outlined.draw_into(0, &mut atlas, stride, 3 * stride + 2);
+    ///
+    /// outlined.draw(0, |x, y, c| {
+    ///     let px = atlas[(y as usize + 3) * stride + x as usize + 2];
+    ///     assert_eq!(px, (c * 255.0).round() as u8);
+    /// });
+    /// assert_eq!(atlas[0], 0);
+    /// ```
+    pub fn draw_into(&self, layer: usize, buffer: &mut [u8], stride: usize, offset: usize) {
+        self.draw_into_with(layer, buffer, stride, offset, coverage_u8);
+    }
+
+    /// Draws this glyph's coverage, in the range `[0.0, 1.0]`, directly into a row-major
+    /// `buffer`. See [`draw_into`](#method.draw_into).
+    ///
+    /// # Panics
+    /// If `stride` is less than the bounds width or `buffer` is too small.
+    pub fn draw_into_f32(&self, layer: usize, buffer: &mut [f32], stride: usize, offset: usize) {
+        self.draw_into_with(layer, buffer, stride, offset, |c| c);
+    }
+
+    /// Writes each pixel's coverage, converted by `value`, into the `buffer` rows.
+    fn draw_into_with<T>(
+        &self,
+        layer: usize,
+        buffer: &mut [T],
+        stride: usize,
+        offset: usize,
+        value: impl Fn(f32) -> T,
+    ) {
         let (w, h) = (
             self.px_bounds.width() as usize,
             self.px_bounds.height() as usize,
         );
+        assert!(stride >= w, "stride less than the glyph width");
+        assert!(
+            h == 0 || offset + (h - 1) * stride + w <= buffer.len(),
+            "buffer too small for the glyph"
+        );
+        let rasterizer = self.rasterize(layer);
+        let adjust = self.coverage_adjustment();
+        rasterizer.for_each_pixel(|idx, c| {
+            let c = match &adjust {
+                Some(adjust) => adjust(c),
+                None => c,
+            };
+            buffer[offset + idx / w * stride + idx % w] = value(c);
+        });
+    }
 
-        let scale_up = |&Point { x, y }| point(x * h_factor, y * v_factor);
+    /// Whole number pixel bounding box of the [`draw_sdf`](#method.draw_sdf) field, the
+    /// outline fill bounds expanded by `spread` & any [padding](#method.with_padding) on
+    /// all sides.
+    #[inline]
+    pub fn sdf_px_bounds(&self, spread: f32) -> Rect {
+        let bounds = self
+            .outline
+            .px_bounds(self.scale_factor, self.glyph.position);
+        expand(bounds, spread.max(0.0).ceil() + self.padding as f32)
+    }
 
-        self.outline.group[layer].0
-            .curves
-            .iter()
-            .fold(Rasterizer::new(w, h), |mut rasterizer, curve| match curve {
+    /// Draw a signed distance field of this glyph outline, e.g. to build distance field
+    /// atlases for GPU text rendering.
+    ///
+    /// The callback will be called for each `(x, y)` pixel coordinate inside
+    /// [`sdf_px_bounds(spread)`](#method.sdf_px_bounds) with the pixel distance from the
+    /// pixel centre to the outline, positive inside & negative outside, clamped to
+    /// `[-spread, spread]`.
+    ///
+    /// Stroke, level of detail & stem darkening settings are not applied.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('o').with_scale(40.0)).unwrap();
+    /// let spread = 4.0;
+    /// let bounds = outlined.sdf_px_bounds(spread);
+    /// assert_eq!(bounds.min, outlined.px_bounds().min - point(spread, spread));
+    ///
+    /// let width = bounds.width() as usize;
+    /// let mut sdf = vec![0.0; width * bounds.height() as usize];
+    /// outlined.draw_sdf(0, spread, |x, y, d| sdf[y as usize * width + x as usize] = d);
+    /// assert_eq!(sdf[0], -spread);
+    ///
+    /// // fully covered pixels are inside, uncovered pixels outside
+    /// outlined.draw(0, |x, y, c| {
+    ///     let d = sdf[(y as usize + 4) * width + x as usize + 4];
+    ///     if c > 0.99 {
+    ///         assert!(d > 0.0);
+    ///     } else if c < 0.01 {
+    ///         assert!(d < 0.0);
+    ///     }
+    /// });
+    /// ```
+    pub fn draw_sdf<O: FnMut(u32, u32, f32)>(&self, layer: usize, spread: f32, o: O) {
+        let spread = spread.max(0.0);
+        self.draw_signed_distance(layer, spread, spread, o);
+    }
+
+    /// Draw the exact distance transform of this glyph outline, the unclamped pixel
+    /// distance from each pixel centre to the outline, positive inside & negative outside.
+    ///
+    /// Unlike [`draw_sdf`](#method.draw_sdf) distances aren't clamped, so custom halo or
+    /// glow effects can use true distances. The callback will be called for each `(x, y)`
+    /// pixel coordinate inside [`sdf_px_bounds(spread)`](#method.sdf_px_bounds), `spread`
+    /// only sizing the bounds.
+    ///
+    /// Stroke, level of detail & stem darkening settings are not applied.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('o').with_scale(40.0)).unwrap();
+    /// let spread = 4.0;
+    ///
+    /// let mut distances = vec![];
+    /// outlined.draw_distance(0, spread, |_, _, d| distances.push(d));
+    /// let mut sdf = vec![];
+    /// outlined.draw_sdf(0, spread, |_, _, d| sdf.push(d));
+    ///
+    /// // the sdf is the clamped distance transform
+    /// assert!(distances[0] < -spread);
+    /// for (d, clamped) in distances.iter().zip(&sdf) {
+    ///     assert_eq!(d.max(-spread).min(spread), *clamped);
+    /// }
+    /// ```
+    pub fn draw_distance<O: FnMut(u32, u32, f32)>(&self, layer: usize, spread: f32, o: O) {
+        self.draw_signed_distance(layer, spread.max(0.0), f32::INFINITY, o);
+    }
+
+    /// Draws signed distances, clamped to `max_distance`, within `sdf_px_bounds(spread)`.
+    fn draw_signed_distance<O: FnMut(u32, u32, f32)>(
+        &self,
+        layer: usize,
+        spread: f32,
+        max_distance: f32,
+        mut o: O,
+    ) {
+        let bounds = self.sdf_px_bounds(spread);
+        let transform = PxTransform::new(self.scale_factor, self.glyph.position, bounds.min);
+        let mut lines = Vec::new();
+        for curve in &self.outline.layer(layer).0.curves {
+            flatten(curve, &transform, SDF_TOLERANCE, |p0, p1| {
+                lines.push((p0, p1))
+            });
+        }
+
+        let (w, h) = (bounds.width() as u32, bounds.height() as u32);
+        #[cfg(feature = "counters")]
+        crate::counters::add_pixels_rasterized(w as usize * h as usize);
+        for y in 0..h {
+            for x in 0..w {
+                let p = point(x as f32 + 0.5, y as f32 + 0.5);
+                let mut min_distance_sq = max_distance * max_distance;
+                let mut winding = 0;
+                for &(p0, p1) in &lines {
+                    min_distance_sq = min_distance_sq.min(distance_sq_to_line(p, p0, p1));
+                    // non-zero winding of a ray from `p` towards +x
+                    if (p0.y <= p.y) != (p1.y <= p.y) {
+                        let cross_x = p0.x + (p.y - p0.y) / (p1.y - p0.y) * (p1.x - p0.x);
+                        if cross_x > p.x {
+                            winding += if p1.y > p0.y { 1 } else { -1 };
+                        }
+                    }
+                }
+                let distance = min_distance_sq.sqrt();
+                o(x, y, if winding != 0 { distance } else { -distance });
+            }
+        }
+    }
+
+    /// Whole number pixel bounding box of the [`draw_blurred`](#method.draw_blurred)
+    /// coverage, the pixel bounds expanded by `radius` on all sides.
+    #[inline]
+    pub fn blur_px_bounds(&self, radius: f32) -> Rect {
+        expand(self.px_bounds, radius.max(0.0).ceil())
+    }
+
+    /// Draws a gaussian blurred copy of this glyph's coverage, e.g. for text shadows &
+    /// glows. The blur spreads up to `radius` pixels, a standard deviation of `radius / 3`.
+    ///
+    /// The callback will be called for each `(x, y)` pixel coordinate inside
+    /// [`blur_px_bounds(radius)`](#method.blur_px_bounds) with the blurred coverage in
+    /// the range `[0.0, 1.0]`. Draw it offset from the glyph for a drop shadow.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('l').with_scale(40.0)).unwrap();
+    /// let radius = 6.0;
+    /// let bounds = outlined.blur_px_bounds(radius);
+    /// assert_eq!(bounds.min, outlined.px_bounds().min - point(radius, radius));
+    ///
+    /// // blurring spreads, but preserves, the total coverage
+    /// let (mut coverage, mut blurred, mut max) = (0.0, 0.0, 0.0_f32);
+    /// outlined.draw(0, |_, _, c| coverage += c);
+    /// outlined.draw_blurred(0, radius, |_, _, c| {
+    ///     blurred += c;
+    ///     max = max.max(c);
+    /// });
+    /// assert!((coverage - blurred).abs() < 1e-2);
+    /// assert!(max < 1.0);
+    ///
+    /// // drop shadow 2px right & down
+    /// let shadow_bounds = bounds.translate(point(2.0, 2.0));
+    /// outlined.draw_blurred(0, radius, |x, y, c| {
+    ///     let px = shadow_bounds.min.x as i32 + x as i32;
+    ///     let py = shadow_bounds.min.y as i32 + y as i32;
+    ///     /* blend shadow pixel `(px, py)` with coverage: `c` */
+    /// });
+    /// ```
+    pub fn draw_blurred<O: FnMut(u32, u32, f32)>(&self, layer: usize, radius: f32, mut o: O) {
+        let bounds = self.blur_px_bounds(radius);
+        let pad = radius.max(0.0).ceil() as usize;
+        let (w, h) = (bounds.width() as usize, bounds.height() as usize);
+        let mut coverage = vec![0.0; w * h];
+        self.draw(layer, |x, y, c| {
+            coverage[(y as usize + pad) * w + x as usize + pad] = c;
+        });
+
+        let kernel = gaussian_kernel(radius);
+        let mut blurred = vec![0.0; w * h];
+        // separable blur, horizontally into `blurred` then vertically back
+        for y in 0..h {
+            let row = &coverage[y * w..(y + 1) * w];
+            for x in 0..w {
+                blurred[y * w + x] = convolve(&kernel, x, w, |i| row[i]);
+            }
+        }
+        for x in 0..w {
+            for y in 0..h {
+                coverage[y * w + x] = convolve(&kernel, y, h, |i| blurred[i * w + x]);
+            }
+        }
+
+        for y in 0..h {
+            for x in 0..w {
+                o(x as u32, y as u32, coverage[y * w + x].min(1.0));
+            }
+        }
+    }
+
+    /// Rasterizes a layer's curves.
+    #[inline]
+    fn rasterize(&self, layer: usize) -> ab_glyph_rasterizer::Rasterizer {
+        self.rasterize_h_scaled(layer, 1)
+    }
+
+    /// Rasterizes a layer's curves with `h_samples` horizontal samples per pixel of the
+    /// bounds, e.g. `3` for subpixel rendering.
+    pub(crate) fn rasterize_h_scaled(
+        &self,
+        layer: usize,
+        h_samples: u32,
+    ) -> ab_glyph_rasterizer::Rasterizer {
+        use ab_glyph_rasterizer::Rasterizer;
+        let h_samples = h_samples as f32;
+        let scale_factor = PxScaleFactor {
+            horizontal: self.scale_factor.horizontal * h_samples,
+            vertical: self.scale_factor.vertical,
+        };
+        let position = point(self.glyph.position.x * h_samples, self.glyph.position.y);
+        let origin = point(self.px_bounds.min.x * h_samples, self.px_bounds.min.y);
+        let transform = PxTransform::new(scale_factor, position, origin);
+        let (w, h) = (
+            (self.px_bounds.width() * h_samples) as usize,
+            self.px_bounds.height() as usize,
+        );
+        #[cfg(feature = "counters")]
+        crate::counters::add_pixels_rasterized(w * h);
+        let simplify = self
+            .lod
+            .filter(|lod| self.glyph.scale.y < lod.max_px_scale)
+            .map(|lod| lod.tolerance);
+        // strokes are drawn along lines, so always flatten curves
+        let simplify = match self.stroke {
+            Some(_) => Some(simplify.unwrap_or(STROKE_TOLERANCE)),
+            None => simplify,
+        };
+        let stroke = self.stroke;
+        let line = |rasterizer: &mut Rasterizer, p0: Point, p1: Point| match stroke {
+            Some(width) => draw_stroke_line(rasterizer, p0, p1, width),
+            None => rasterizer.draw_line(p0, p1),
+        };
+
+        self.outline.layer(layer).0.curves.iter().fold(
+            Rasterizer::new(w, h),
+            |mut rasterizer, curve| match curve {
                 OutlineCurve::Line(p0, p1) => {
                     // eprintln!("r.draw_line({:?}, {:?});",
-                    //     scale_up(p0) + offset, scale_up(p1) + offset);
-                    rasterizer.draw_line(scale_up(p0) + offset, scale_up(p1) + offset);
+                    //     transform.apply(p0), transform.apply(p1));
+                    line(&mut rasterizer, transform.apply(p0), transform.apply(p1));
                     rasterizer
                 }
                 OutlineCurve::Quad(p0, p1, p2) => {
                     // eprintln!("r.draw_quad({:?}, {:?}, {:?});",
-                    //     scale_up(p0) + offset, scale_up(p1) + offset, scale_up(p2) + offset);
-                    rasterizer.draw_quad(
-                        scale_up(p0) + offset,
-                        scale_up(p1) + offset,
-                        scale_up(p2) + offset,
+                    //     transform.apply(p0), transform.apply(p1), transform.apply(p2));
+                    let (p0, p1, p2) = (
+                        transform.apply(p0),
+                        transform.apply(p1),
+                        transform.apply(p2),
                     );
+                    match simplify {
+                        Some(tolerance) => {
+                            let max_dd = 2.0 * p0.distance(p1 * 2.0 - p2);
+                            draw_simplified(
+                                max_dd,
+                                tolerance,
+                                |t| p0.lerp(p1, t).lerp(p1.lerp(p2, t), t),
+                                |p0, p1| line(&mut rasterizer, p0, p1),
+                            );
+                        }
+                        None => rasterizer.draw_quad(p0, p1, p2),
+                    }
                     rasterizer
                 }
                 OutlineCurve::Cubic(p0, p1, p2, p3) => {
                     // eprintln!("r.draw_cubic({:?}, {:?}, {:?}, {:?});",
-                    //     scale_up(p0) + offset, scale_up(p1) + offset, scale_up(p2) + offset, scale_up(p3) + offset);
-                    rasterizer.draw_cubic(
-                        scale_up(p0) + offset,
-                        scale_up(p1) + offset,
-                        scale_up(p2) + offset,
-                        scale_up(p3) + offset,
+                    //     transform.apply(p0), transform.apply(p1), transform.apply(p2), transform.apply(p3));
+                    let (p0, p1, p2, p3) = (
+                        transform.apply(p0),
+                        transform.apply(p1),
+                        transform.apply(p2),
+                        transform.apply(p3),
                     );
+                    match simplify {
+                        Some(tolerance) => {
+                            let max_dd =
+                                6.0 * p0.distance(p1 * 2.0 - p2).max(p1.distance(p2 * 2.0 - p3));
+                            draw_simplified(
+                                max_dd,
+                                tolerance,
+                                |t| {
+                                    let (p01, p12) = (p0.lerp(p1, t), p1.lerp(p2, t));
+                                    let p23 = p2.lerp(p3, t);
+                                    p01.lerp(p12, t).lerp(p12.lerp(p23, t), t)
+                                },
+                                |p0, p1| line(&mut rasterizer, p0, p1),
+                            );
+                        }
+                        None => rasterizer.draw_cubic(p0, p1, p2, p3),
+                    }
                     rasterizer
                 }
-            })
-            .for_each_pixel_2d(o);
+            },
+        )
+    }
+}
+
+/// Small size coverage darkening.
+///
+/// Coverage is raised to the power `1 / (1 + strength)`, where strength falls linearly
+/// from `amount` to zero at `max_px_scale`.
+///
+/// See [`OutlinedGlyph::with_stem_darkening`](struct.OutlinedGlyph.html#method.with_stem_darkening).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StemDarkening {
+    /// Glyphs with a vertical pixel scale at or above this aren't darkened. Default `16.0`.
+    pub max_px_scale: f32,
+    /// Darkening strength approached at the smallest scales. Default `1.0`.
+    pub amount: f32,
+}
+
+impl Default for StemDarkening {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_px_scale: 16.0,
+            amount: 1.0,
+        }
+    }
+}
+
+/// Coverage gamma & contrast correction.
+///
+/// Coverage `c` is first boosted by `contrast * c * (1 - c)` then raised to the power
+/// `1 / gamma`. Empty & full coverage are unchanged.
+///
+/// See [`OutlinedGlyph::with_gamma_correction`](struct.OutlinedGlyph.html#method.with_gamma_correction).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaCorrection {
+    /// Values above `1.0` increase partial coverage, darkening dark text on light
+    /// backgrounds. Default `1.8`.
+    pub gamma: f32,
+    /// Increases mid coverage, sharpening edges, in the range `[0.0, 1.0]`. Default `0.0`.
+    pub contrast: f32,
+}
+
+impl GammaCorrection {
+    #[inline]
+    fn apply(self, c: f32) -> f32 {
+        let c = c + self.contrast * c * (1.0 - c);
+        c.powf(self.gamma.recip()).min(1.0)
+    }
+}
+
+impl Default for GammaCorrection {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            gamma: 1.8,
+            contrast: 0.0,
+        }
+    }
+}
+
+/// Custom 8 bit coverage to alpha lookup table, e.g. for custom gamma curves, thresholding
+/// or stylized rendering.
+///
+/// See [`OutlinedGlyph::with_coverage_lut`](struct.OutlinedGlyph.html#method.with_coverage_lut).
+///
+/// # Example
+/// ```
+/// use ab_glyph::CoverageLut;
+///
+/// let lut = CoverageLut::from_fn(|c| 255 - c);
+/// assert_eq!(lut.get(0), 255);
+/// assert_eq!(lut.get(200), 55);
+///
+/// assert_eq!(CoverageLut::default().get(200), 200);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct CoverageLut {
+    table: [u8; 256],
+}
+
+impl CoverageLut {
+    /// Constructs a table mapping coverage `c` to `table[c]`.
+    #[inline]
+    pub fn new(table: [u8; 256]) -> Self {
+        Self { table }
+    }
+
+    /// Constructs a table mapping each coverage value with `f`.
+    pub fn from_fn(f: impl Fn(u8) -> u8) -> Self {
+        let mut table = [0; 256];
+        for (c, out) in table.iter_mut().enumerate() {
+            *out = f(c as u8);
+        }
+        Self { table }
+    }
+
+    /// Constructs a table mapping coverage at or above `min` to `255` & below it to `0`,
+    /// drawing aliased glyphs.
+    #[inline]
+    pub fn threshold(min: u8) -> Self {
+        Self::from_fn(|c| if c >= min { 255 } else { 0 })
+    }
+
+    /// Returns the mapped `coverage`.
+    #[inline]
+    pub fn get(&self, coverage: u8) -> u8 {
+        self.table[usize::from(coverage)]
+    }
+
+    /// Returns the table.
+    #[inline]
+    pub fn table(&self) -> &[u8; 256] {
+        &self.table
+    }
+}
+
+impl Default for CoverageLut {
+    /// Identity mapping.
+    #[inline]
+    fn default() -> Self {
+        Self::from_fn(|c| c)
+    }
+}
+
+impl fmt::Debug for CoverageLut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CoverageLut")
+            .field(&&self.table[..])
+            .finish()
+    }
+}
+
+/// Converts `[0.0, 1.0]` coverage to `[0, 255]`.
+#[inline]
+fn coverage_u8(c: f32) -> u8 {
+    (c * 255.0).round() as u8
+}
+
+/// Outline level of detail simplification for small pixel scales.
+///
+/// See [`OutlinedGlyph::with_lod`](struct.OutlinedGlyph.html#method.with_lod).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lod {
+    /// Glyphs with a vertical pixel scale below this are simplified. Default `12.0`.
+    pub max_px_scale: f32,
+    /// Maximum pixel distance simplified curves may deviate from the outline.
+    /// Default `0.5`.
+    pub tolerance: f32,
+}
+
+impl Default for Lod {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_px_scale: 12.0,
+            tolerance: 0.5,
+        }
+    }
+}
+
+/// Draws a pixel space curve, evaluated by `curve(t)`, as the fewest equal parameter lines
+/// within `tolerance` of the curve, given the maximum magnitude of its second derivative.
+fn draw_simplified(
+    max_dd: f32,
+    tolerance: f32,
+    curve: impl Fn(f32) -> Point,
+    mut draw_line: impl FnMut(Point, Point),
+) {
+    // each line deviates at most `max_dd / (8 * lines^2)` from the curve
+    let lines = ((max_dd / (8.0 * tolerance)).sqrt().ceil() as u32).clamp(1, 64);
+    let step = (lines as f32).recip();
+    let mut p = curve(0.0);
+    for i in 1..lines {
+        let next = curve(i as f32 * step);
+        draw_line(p, next);
+        p = next;
+    }
+    draw_line(p, curve(1.0));
+}
+
+/// Maximum pixel distance signed distance field lines may deviate from curves.
+const SDF_TOLERANCE: f32 = 0.05;
+
+/// Flattens an unscaled curve into pixel space lines within `tolerance` of the curve.
+pub(crate) fn flatten(
+    curve: &OutlineCurve,
+    transform: &PxTransform,
+    tolerance: f32,
+    mut line: impl FnMut(Point, Point),
+) {
+    match curve {
+        OutlineCurve::Line(p0, p1) => line(transform.apply(p0), transform.apply(p1)),
+        OutlineCurve::Quad(p0, p1, p2) => {
+            let (p0, p1, p2) = (
+                transform.apply(p0),
+                transform.apply(p1),
+                transform.apply(p2),
+            );
+            let max_dd = 2.0 * p0.distance(p1 * 2.0 - p2);
+            draw_simplified(
+                max_dd,
+                tolerance,
+                |t| p0.lerp(p1, t).lerp(p1.lerp(p2, t), t),
+                line,
+            );
+        }
+        OutlineCurve::Cubic(p0, p1, p2, p3) => {
+            let (p0, p1, p2, p3) = (
+                transform.apply(p0),
+                transform.apply(p1),
+                transform.apply(p2),
+                transform.apply(p3),
+            );
+            let max_dd = 6.0 * p0.distance(p1 * 2.0 - p2).max(p1.distance(p2 * 2.0 - p3));
+            draw_simplified(
+                max_dd,
+                tolerance,
+                |t| {
+                    let (p01, p12) = (p0.lerp(p1, t), p1.lerp(p2, t));
+                    let p23 = p2.lerp(p3, t);
+                    p01.lerp(p12, t).lerp(p12.lerp(p23, t), t)
+                },
+                line,
+            );
+        }
+    }
+}
+
+/// Returns the normalized gaussian weights of offsets `-radius..=radius`, with a standard
+/// deviation of `radius / 3`.
+fn gaussian_kernel(radius: f32) -> Vec<f32> {
+    let half = radius.max(0.0).ceil() as i32;
+    if half == 0 {
+        return vec![1.0];
+    }
+    let two_variance = 2.0 * (radius / 3.0) * (radius / 3.0);
+    let mut kernel: Vec<f32> = (-half..=half)
+        .map(|i| core::f32::consts::E.powf(-((i * i) as f32) / two_variance))
+        .collect();
+    let sum: f32 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|weight| *weight /= sum);
+    kernel
+}
+
+/// Returns the kernel weighted sum of the `len` values around `center`, values outside
+/// `0..len` being zero.
+#[inline]
+fn convolve(kernel: &[f32], center: usize, len: usize, value: impl Fn(usize) -> f32) -> f32 {
+    let half = kernel.len() / 2;
+    kernel
+        .iter()
+        .enumerate()
+        .filter_map(|(k, weight)| {
+            let i = (center + k).checked_sub(half).filter(|&i| i < len)?;
+            Some(weight * value(i))
+        })
+        .sum()
+}
+
+/// Returns the squared distance from `p` to the line segment `p0`, `p1`.
+#[inline]
+fn distance_sq_to_line(p: Point, p0: Point, p1: Point) -> f32 {
+    let (d, v) = (p1 - p0, p - p0);
+    let len_sq = d.x * d.x + d.y * d.y;
+    let t = if len_sq > 0.0 {
+        ((v.x * d.x + v.y * d.y) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let offset = v - d * t;
+    offset.x * offset.x + offset.y * offset.y
+}
+
+/// Maximum pixel distance stroked lines may deviate from curves.
+const STROKE_TOLERANCE: f32 = 0.1;
+
+/// Unit octagon enclosing the unit circle, used for stroke joins. Wound the same way as
+/// stroked lines.
+const OCTAGON: [(f32, f32); 8] = {
+    // tan(π / 8)
+    const T: f32 = 0.414_213_57;
+    [
+        (1.0, -T),
+        (T, -1.0),
+        (-T, -1.0),
+        (-1.0, -T),
+        (-1.0, T),
+        (-T, 1.0),
+        (T, 1.0),
+        (1.0, T),
+    ]
+};
+
+/// Draws a stroke of `width` centred along a pixel space line with a round-ish join at `p1`.
+///
+/// All shapes are drawn with the same winding so overlaps don't cancel out.
+fn draw_stroke_line(
+    rasterizer: &mut ab_glyph_rasterizer::Rasterizer,
+    p0: Point,
+    p1: Point,
+    width: f32,
+) {
+    let half = width / 2.0;
+    let len = p0.distance(p1);
+    if len > 0.0 {
+        let d = p1 - p0;
+        let n = point(-d.y, d.x) * (half / len);
+        rasterizer.draw_line(p0 + n, p1 + n);
+        rasterizer.draw_line(p1 + n, p1 - n);
+        rasterizer.draw_line(p1 - n, p0 - n);
+        rasterizer.draw_line(p0 - n, p0 + n);
+    }
+
+    let vertex = |(x, y): (f32, f32)| p1 + point(x, y) * half;
+    for (i, v) in OCTAGON.iter().enumerate() {
+        rasterizer.draw_line(vertex(*v), vertex(OCTAGON[(i + 1) % OCTAGON.len()]));
+    }
+}
+
+/// Returns `rect` expanded by `pad` on all sides.
+#[inline]
+fn expand(rect: Rect, pad: f32) -> Rect {
+    Rect {
+        min: rect.min - point(pad, pad),
+        max: rect.max + point(pad, pad),
     }
 }
 
@@ -185,6 +1811,11 @@ impl AsRef<Glyph> for OutlinedGlyph {
 
 /// Glyph outline primitives.
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
 pub enum OutlineCurve {
     /// Straight line from `.0` to `.1`.
     Line(Point, Point),
@@ -195,8 +1826,27 @@ pub enum OutlineCurve {
     Cubic(Point, Point, Point, Point),
 }
 
+impl OutlineCurve {
+    /// Exact unscaled bounds of the curve, including any turning points.
+    ///
+    /// Like [`Outline::bounds`](struct.Outline.html#structfield.bounds) `min.y` is the top
+    /// & `max.y` the bottom.
+    pub fn bounds(&self) -> Rect {
+        let Rect { min, max } = curve_extent(self, |p| *p);
+        Rect {
+            min: point(min.x, max.y),
+            max: point(max.x, min.y),
+        }
+    }
+}
+
 /// A rectangle, with top-left corner at `min`, and bottom-right corner at `max`.
 #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
 pub struct Rect {
     pub min: Point,
     pub max: Point,
@@ -212,4 +1862,180 @@ impl Rect {
     pub fn height(&self) -> f32 {
         self.max.y - self.min.y
     }
+
+    /// Returns the overlapping area of both rects, `None` if they don't overlap.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// let a = Rect { min: point(0.0, 0.0), max: point(4.0, 4.0) };
+    /// let b = Rect { min: point(2.0, 1.0), max: point(6.0, 3.0) };
+    /// assert_eq!(a.intersect(&b), Some(Rect { min: point(2.0, 1.0), max: point(4.0, 3.0) }));
+    ///
+    /// let c = Rect { min: point(4.0, 0.0), max: point(6.0, 4.0) };
+    /// assert_eq!(a.intersect(&c), None);
+    /// ```
+    #[inline]
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect {
+            min: point(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            max: point(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        };
+        if rect.min.x < rect.max.x && rect.min.y < rect.max.y {
+            Some(rect)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rect containing both rects.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// let a = Rect { min: point(0.0, 0.0), max: point(4.0, 4.0) };
+    /// let b = Rect { min: point(2.0, -1.0), max: point(6.0, 3.0) };
+    /// assert_eq!(a.union(&b), Rect { min: point(0.0, -1.0), max: point(6.0, 4.0) });
+    /// ```
+    #[inline]
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: point(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: point(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// Returns `true` if the point is inside the rect, including the `min` edges &
+    /// excluding the `max` edges.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// let rect = Rect { min: point(0.0, 0.0), max: point(4.0, 4.0) };
+    /// assert!(rect.contains(point(0.0, 3.5)));
+    /// assert!(!rect.contains(point(4.0, 2.0)));
+    /// ```
+    #[inline]
+    pub fn contains(&self, p: Point) -> bool {
+        self.min.x <= p.x && p.x < self.max.x && self.min.y <= p.y && p.y < self.max.y
+    }
+
+    /// Returns the rect moved by `offset`.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// let rect = Rect { min: point(0.0, 0.0), max: point(4.0, 4.0) };
+    /// assert_eq!(
+    ///     rect.translate(point(1.0, -2.0)),
+    ///     Rect { min: point(1.0, -2.0), max: point(5.0, 2.0) }
+    /// );
+    /// ```
+    #[inline]
+    pub fn translate(&self, offset: Point) -> Rect {
+        Rect {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// Returns the rect with both corners multiplied by the horizontal & vertical factors.
+    ///
+    /// ```
+    /// # use ab_glyph::*;
+    /// let rect = Rect { min: point(1.0, 2.0), max: point(4.0, 4.0) };
+    /// assert_eq!(
+    ///     rect.scale(2.0, 0.5),
+    ///     Rect { min: point(2.0, 1.0), max: point(8.0, 2.0) }
+    /// );
+    /// ```
+    #[inline]
+    pub fn scale(&self, h_factor: f32, v_factor: f32) -> Rect {
+        Rect {
+            min: point(self.min.x * h_factor, self.min.y * v_factor),
+            max: point(self.max.x * h_factor, self.max.y * v_factor),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<(mint2::Point2<f32>, mint2::Point2<f32>)> for Rect {
+    /// Rect from `(min, max)` mint points.
+    ///
+    /// ```
+    /// # extern crate mint2 as mint;
+    /// # use ab_glyph::*;
+    /// let rect: Rect = (mint::Point2 { x: 1.0, y: 2.0 }, mint::Point2 { x: 5.0, y: 7.0 }).into();
+    /// assert_eq!(rect.min, point(1.0, 2.0));
+    /// assert_eq!(rect.max, point(5.0, 7.0));
+    /// ```
+    #[inline]
+    fn from((min, max): (mint2::Point2<f32>, mint2::Point2<f32>)) -> Self {
+        Self {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Rect> for (mint2::Point2<f32>, mint2::Point2<f32>) {
+    /// `(min, max)` mint points from a rect.
+    #[inline]
+    fn from(Rect { min, max }: Rect) -> Self {
+        (min.into(), max.into())
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<euclid2::Box2D<f32, U>> for Rect {
+    /// ```
+    /// # extern crate euclid2 as euclid;
+    /// # use ab_glyph::*;
+    /// let bounds = euclid::default::Box2D::new(euclid::point2(1.0, 2.0), euclid::point2(5.0, 7.0));
+    /// let rect: Rect = bounds.into();
+    /// assert_eq!(rect.min, point(1.0, 2.0));
+    /// assert_eq!(rect.max, point(5.0, 7.0));
+    /// ```
+    #[inline]
+    fn from(b: euclid2::Box2D<f32, U>) -> Self {
+        Self {
+            min: b.min.into(),
+            max: b.max.into(),
+        }
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<Rect> for euclid2::Box2D<f32, U> {
+    #[inline]
+    fn from(Rect { min, max }: Rect) -> Self {
+        euclid2::Box2D::new(min.into(), max.into())
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<euclid2::Rect<f32, U>> for Rect {
+    /// ```
+    /// # extern crate euclid2 as euclid;
+    /// # use ab_glyph::*;
+    /// let origin_size = euclid::default::Rect::new(euclid::point2(1.0, 2.0), euclid::size2(4.0, 5.0));
+    /// let rect: Rect = origin_size.into();
+    /// assert_eq!(rect.min, point(1.0, 2.0));
+    /// assert_eq!(rect.max, point(5.0, 7.0));
+    /// ```
+    #[inline]
+    fn from(r: euclid2::Rect<f32, U>) -> Self {
+        Self {
+            min: point(r.origin.x, r.origin.y),
+            max: point(r.origin.x + r.size.width, r.origin.y + r.size.height),
+        }
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<Rect> for euclid2::Rect<f32, U> {
+    #[inline]
+    fn from(rect: Rect) -> Self {
+        euclid2::Rect::new(
+            rect.min.into(),
+            euclid2::Size2D::new(rect.width(), rect.height()),
+        )
+    }
 }
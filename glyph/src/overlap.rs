@@ -0,0 +1,698 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{
+    outlined::{flatten, PxTransform},
+    point,
+    synthetic::curves_bounds,
+    Outline, OutlineCurve, Point, PxScaleFactor,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+impl Outline {
+    /// Returns the outline with overlapping contours merged, filling the same area with the
+    /// non-zero fill rule but with no point covered twice.
+    ///
+    /// Overlapping contours, e.g. of composite glyph components, can render with darker
+    /// seams as coverage is accumulated per contour. Curves are split where they intersect
+    /// & only the pieces bounding the filled area are kept, so curves not involved in an
+    /// overlap are unchanged, though zero length curves are dropped. See also
+    /// [`OutlinedGlyph::with_overlaps_removed`](struct.OutlinedGlyph.html#method.with_overlaps_removed).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::*;
+    ///
+    /// let square = |x0: f32, y0: f32, x1: f32, y1: f32| {
+    ///     let (a, b, c, d) = (point(x0, y0), point(x0, y1), point(x1, y1), point(x1, y0));
+    ///     vec![
+    ///         OutlineCurve::Line(a, b),
+    ///         OutlineCurve::Line(b, c),
+    ///         OutlineCurve::Line(c, d),
+    ///         OutlineCurve::Line(d, a),
+    ///     ]
+    /// };
+    /// let coverage = |outline: &Outline| {
+    ///     let glyph = GlyphId(0).with_scale_and_position(10.0, point(0.0, 10.0));
+    ///     let scale = PxScaleFactor { horizontal: 1.0, vertical: 1.0 };
+    ///     let outline = OutlineGroup::from_outline(outline.clone());
+    ///     let mut sum = 0.0;
+    ///     OutlinedGlyph::new(glyph, outline, scale).draw(0, |_, _, c| sum += c);
+    ///     sum
+    /// };
+    /// let bounds = Rect { min: point(0.5, 9.5), max: point(9.5, 0.5) };
+    ///
+    /// // the same square twice, edges half covering pixels
+    /// let mut curves = square(0.5, 0.5, 9.5, 9.5);
+    /// curves.extend(square(0.5, 0.5, 9.5, 9.5));
+    /// let outline = Outline { bounds, curves };
+    /// assert!(coverage(&outline) > 81.5);
+    /// let merged = outline.remove_overlaps();
+    /// assert_eq!(merged.curves.len(), 4);
+    /// assert!((coverage(&merged) - 81.0).abs() < 1e-3);
+    ///
+    /// // overlapping squares merge into a single contour of 8 lines
+    /// let mut curves = square(0.5, 0.5, 6.5, 6.5);
+    /// curves.extend(square(3.5, 3.5, 9.5, 9.5));
+    /// let merged = Outline { bounds, curves }.remove_overlaps();
+    /// assert_eq!(merged.curves.len(), 8);
+    /// assert!((coverage(&merged) - 63.0).abs() < 1e-3);
+    ///
+    /// // outlines without overlaps are unchanged, other than zero length closing lines
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let e = font.outline(font.glyph_id('e')).unwrap();
+    /// let mut curves = e.curves.clone();
+    /// curves.retain(|c| !matches!(c, OutlineCurve::Line(a, b) if a == b));
+    /// assert_eq!(format!("{:?}", e.remove_overlaps().curves), format!("{:?}", curves));
+    /// ```
+    pub fn remove_overlaps(&self) -> Outline {
+        let size = self.bounds.width().max(self.bounds.height().abs());
+        if self.curves.is_empty() || size.is_nan() || size <= 0.0 {
+            return self.clone();
+        }
+        // distance within which points are considered the same
+        let snap = size * 1e-4;
+
+        let segments: Vec<_> = self.curves.iter().map(Segment::from).collect();
+        let mut pieces = Vec::with_capacity(segments.len());
+        for (segment, splits) in segments.iter().zip(intersections(&segments, snap)) {
+            segment.split_at(splits, snap, &mut pieces);
+        }
+
+        // keep pieces with the filled area on one side only
+        let monotonic = y_monotonic(&segments);
+        pieces.retain(|piece| {
+            let (min, max) = piece.hull();
+            if (max.x - min.x).max(max.y - min.y) <= snap {
+                return false;
+            }
+            let (mid, normal) = piece.mid_normal();
+            let offset = (size * 1e-3).min(piece.start().distance(piece.end()) * 0.1);
+            let left = winding(mid + normal * offset, &monotonic);
+            let right = winding(mid - normal * offset, &monotonic);
+            (left == 0) != (right == 0)
+        });
+        // coincident contours leave coincident pieces
+        dedup_pieces(&mut pieces, snap);
+
+        let curves = chain(pieces, snap);
+        let bounds = curves_bounds(&curves);
+        Outline { bounds, curves }
+    }
+}
+
+/// Bézier curve of `len` control points, in outline y up units.
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    points: [Point; 4],
+    len: usize,
+}
+
+impl From<&OutlineCurve> for Segment {
+    fn from(curve: &OutlineCurve) -> Self {
+        match *curve {
+            OutlineCurve::Line(p0, p1) => Self {
+                points: [p0, p1, p1, p1],
+                len: 2,
+            },
+            OutlineCurve::Quad(p0, p1, p2) => Self {
+                points: [p0, p1, p2, p2],
+                len: 3,
+            },
+            OutlineCurve::Cubic(p0, p1, p2, p3) => Self {
+                points: [p0, p1, p2, p3],
+                len: 4,
+            },
+        }
+    }
+}
+
+impl From<Segment> for OutlineCurve {
+    fn from(Segment { points: p, len }: Segment) -> Self {
+        match len {
+            2 => OutlineCurve::Line(p[0], p[1]),
+            3 => OutlineCurve::Quad(p[0], p[1], p[2]),
+            _ => OutlineCurve::Cubic(p[0], p[1], p[2], p[3]),
+        }
+    }
+}
+
+impl Segment {
+    #[inline]
+    fn start(&self) -> Point {
+        self.points[0]
+    }
+
+    #[inline]
+    fn end(&self) -> Point {
+        self.points[self.len - 1]
+    }
+
+    #[inline]
+    fn set_start(&mut self, p: Point) {
+        self.points[0] = p;
+    }
+
+    #[inline]
+    fn set_end(&mut self, p: Point) {
+        self.points[self.len - 1] = p;
+    }
+
+    #[inline]
+    fn is_line(&self) -> bool {
+        self.len == 2
+    }
+
+    /// Splits at `t` using de Casteljau's algorithm.
+    fn split(&self, t: f32) -> (Segment, Segment) {
+        let mut levels = self.points;
+        let (mut head, mut tail) = (*self, *self);
+        for i in 0..self.len {
+            head.points[i] = levels[0];
+            tail.points[self.len - 1 - i] = levels[self.len - 1 - i];
+            for j in 0..self.len - 1 - i {
+                levels[j] = levels[j].lerp(levels[j + 1], t);
+            }
+        }
+        (head, tail)
+    }
+
+    fn eval(&self, t: f32) -> Point {
+        self.split(t).1.start()
+    }
+
+    /// Returns the curve mid point & its unit left normal.
+    fn mid_normal(&self) -> (Point, Point) {
+        let (head, tail) = self.split(0.5);
+        let mut tangent = tail.points[1] - head.points[self.len - 2];
+        if tangent.x == 0.0 && tangent.y == 0.0 {
+            tangent = self.end() - self.start();
+        }
+        let len = (tangent.x * tangent.x + tangent.y * tangent.y).sqrt();
+        let normal = if len > 0.0 {
+            point(-tangent.y / len, tangent.x / len)
+        } else {
+            point(0.0, 0.0)
+        };
+        (tail.start(), normal)
+    }
+
+    /// Control point bounds `(min, max)`, containing the curve.
+    fn hull(&self) -> (Point, Point) {
+        let (mut min, mut max) = (self.start(), self.start());
+        for p in &self.points[1..self.len] {
+            min = point(min.x.min(p.x), min.y.min(p.y));
+            max = point(max.x.max(p.x), max.y.max(p.y));
+        }
+        (min, max)
+    }
+
+    /// Splits at each `(t, point)`, with pieces meeting exactly at the points.
+    fn split_at(&self, mut splits: Vec<(f32, Point)>, snap: f32, pieces: &mut Vec<Segment>) {
+        splits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+        let (mut rest, mut t0) = (*self, 0.0);
+        for (t, p) in splits {
+            if t <= t0 || rest.start().distance(p) <= snap || rest.end().distance(p) <= snap {
+                continue;
+            }
+            let (mut head, mut tail) = rest.split((t - t0) / (1.0 - t0));
+            head.set_end(p);
+            tail.set_start(p);
+            pieces.push(head);
+            rest = tail;
+            t0 = t;
+        }
+        pieces.push(rest);
+    }
+}
+
+/// Returns the `(t, point)` positions each segment crosses or touches another.
+///
+/// Only segments with overlapping bounds are compared, found by sweeping in x.
+fn intersections(segments: &[Segment], snap: f32) -> Vec<Vec<(f32, Point)>> {
+    let hulls: Vec<_> = segments.iter().map(Segment::hull).collect();
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (hulls[a].0.x, hulls[b].0.x);
+        a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+    });
+
+    let mut splits = vec![Vec::new(); segments.len()];
+    let mut found = Vec::new();
+    for (i, &a) in order.iter().enumerate() {
+        for &b in &order[i + 1..] {
+            if hulls[b].0.x > hulls[a].1.x + snap {
+                break;
+            }
+            if !hulls_overlap(hulls[a], hulls[b], snap) {
+                continue;
+            }
+            let (sa, sb) = (&segments[a], &segments[b]);
+            found.clear();
+            if sa.is_line() && sb.is_line() {
+                intersect_lines(sa, sb, snap, &mut found);
+            } else if !same_curve(sa, sb) {
+                let mut budget = MAX_SUBDIVISIONS;
+                intersect_curves(
+                    (*sa, 0.0, 1.0),
+                    (*sb, 0.0, 1.0),
+                    snap / 10.0,
+                    &mut budget,
+                    &mut found,
+                );
+            }
+
+            for &(ta, tb, mut p) in &found {
+                // touching an end, e.g. of the adjacent contour curve, needs no split
+                // of that segment & the other is split exactly at the end point
+                let at_end = |s: &Segment| {
+                    [s.start(), s.end()]
+                        .iter()
+                        .copied()
+                        .find(|e| e.distance(p) <= snap)
+                };
+                let (a_end, b_end) = (at_end(sa), at_end(sb));
+                if let Some(end) = a_end.or(b_end) {
+                    p = end;
+                }
+                if a_end.is_none() {
+                    splits[a].push((ta, p));
+                }
+                if b_end.is_none() {
+                    splits[b].push((tb, p));
+                }
+            }
+        }
+    }
+    splits
+}
+
+/// Limits the work finding the intersections of a pair of curves.
+const MAX_SUBDIVISIONS: u32 = 4096;
+
+#[inline]
+fn hulls_overlap((a_min, a_max): (Point, Point), (b_min, b_max): (Point, Point), eps: f32) -> bool {
+    a_min.x <= b_max.x + eps
+        && b_min.x <= a_max.x + eps
+        && a_min.y <= b_max.y + eps
+        && b_min.y <= a_max.y + eps
+}
+
+/// Returns `true` if the segments have the same points, in either direction.
+fn same_curve(a: &Segment, b: &Segment) -> bool {
+    let n = a.len;
+    n == b.len
+        && ((0..n).all(|i| a.points[i] == b.points[i])
+            || (0..n).all(|i| a.points[i] == b.points[n - 1 - i]))
+}
+
+/// Finds the `(ta, tb, point)` intersections of two lines, including the ends of
+/// collinear overlapping lines.
+fn intersect_lines(a: &Segment, b: &Segment, snap: f32, found: &mut Vec<(f32, f32, Point)>) {
+    let (a0, a1, b0, b1) = (a.start(), a.end(), b.start(), b.end());
+    let (da, db) = (a1 - a0, b1 - b0);
+    let denom = cross(da, db);
+    let (len_a, len_b) = (a0.distance(a1), b0.distance(b1));
+    if denom.abs() > 1e-6 * len_a * len_b {
+        let ta = cross(b0 - a0, db) / denom;
+        let tb = cross(b0 - a0, da) / denom;
+        let (ea, eb) = (snap / len_a, snap / len_b);
+        if (-ea..=1.0 + ea).contains(&ta) && (-eb..=1.0 + eb).contains(&tb) {
+            let (ta, tb) = (ta.clamp(0.0, 1.0), tb.clamp(0.0, 1.0));
+            found.push((ta, tb, a0.lerp(a1, ta)));
+        }
+    } else if len_a > 0.0 && len_b > 0.0 {
+        // parallel, split each at the other's ends lying on it
+        let on = |p: Point, s0: Point, d: Point, len: f32| {
+            let t = ((p.x - s0.x) * d.x + (p.y - s0.y) * d.y) / (len * len);
+            let distance = cross(p - s0, d).abs() / len;
+            Some(t).filter(|t| distance <= snap && (0.0..=1.0).contains(t))
+        };
+        for (p, tb) in [(b0, 0.0), (b1, 1.0)] {
+            if let Some(ta) = on(p, a0, da, len_a) {
+                found.push((ta, tb, p));
+            }
+        }
+        for (p, ta) in [(a0, 0.0), (a1, 1.0)] {
+            if let Some(tb) = on(p, b0, db, len_b) {
+                found.push((ta, tb, p));
+            }
+        }
+    }
+}
+
+/// Finds curve intersections by subdividing both while their hulls overlap, down to
+/// sizes of `eps` where their chords are intersected.
+fn intersect_curves(
+    (a, a0, a1): (Segment, f32, f32),
+    (b, b0, b1): (Segment, f32, f32),
+    eps: f32,
+    budget: &mut u32,
+    found: &mut Vec<(f32, f32, Point)>,
+) {
+    if *budget == 0 {
+        return;
+    }
+    *budget -= 1;
+    let (ha, hb) = (a.hull(), b.hull());
+    if !hulls_overlap(ha, hb, eps) {
+        return;
+    }
+    let size = |(min, max): (Point, Point)| (max.x - min.x).max(max.y - min.y);
+    let (size_a, size_b) = (size(ha), size(hb));
+
+    if size_a <= eps && size_b <= eps {
+        let (ca, cb) = (
+            Segment {
+                points: [a.start(), a.end(), a.end(), a.end()],
+                len: 2,
+            },
+            Segment {
+                points: [b.start(), b.end(), b.end(), b.end()],
+                len: 2,
+            },
+        );
+        let mut chords = Vec::new();
+        intersect_lines(&ca, &cb, eps, &mut chords);
+        for (u, v, p) in chords.into_iter().take(1) {
+            let (ta, tb) = (a0 + (a1 - a0) * u, b0 + (b1 - b0) * v);
+            // adjacent leaves find the same intersection
+            if !found
+                .iter()
+                .any(|&(fa, fb, _)| (fa - ta).abs() < 1e-3 && (fb - tb).abs() < 1e-3)
+            {
+                found.push((ta, tb, p));
+            }
+        }
+        return;
+    }
+
+    if size_a >= size_b {
+        let (head, tail) = a.split(0.5);
+        let mid = (a0 + a1) / 2.0;
+        intersect_curves((head, a0, mid), (b, b0, b1), eps, budget, found);
+        intersect_curves((tail, mid, a1), (b, b0, b1), eps, budget, found);
+    } else {
+        let (head, tail) = b.split(0.5);
+        let mid = (b0 + b1) / 2.0;
+        intersect_curves((a, a0, a1), (head, b0, mid), eps, budget, found);
+        intersect_curves((a, a0, a1), (tail, mid, b1), eps, budget, found);
+    }
+}
+
+#[inline]
+fn cross(a: Point, b: Point) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Splits segments at their y turning points, so each only goes up or down.
+fn y_monotonic(segments: &[Segment]) -> Vec<Segment> {
+    let mut monotonic = Vec::with_capacity(segments.len());
+    for segment in segments {
+        let p = segment.points;
+        // roots of the y derivative, scaled, `a t^2 + b t + c`
+        let (a, b, c) = match segment.len {
+            2 => (0.0, 0.0, 1.0),
+            3 => (0.0, p[0].y - 2.0 * p[1].y + p[2].y, p[1].y - p[0].y),
+            _ => (
+                -p[0].y + 3.0 * p[1].y - 3.0 * p[2].y + p[3].y,
+                2.0 * (p[0].y - 2.0 * p[1].y + p[2].y),
+                p[1].y - p[0].y,
+            ),
+        };
+        let mut roots = [f32::NAN; 2];
+        if a.abs() > 1e-9 {
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant >= 0.0 {
+                let sqrt = discriminant.sqrt();
+                roots = [(-b - sqrt) / (2.0 * a), (-b + sqrt) / (2.0 * a)];
+            }
+        } else if b.abs() > 1e-9 {
+            roots[0] = -c / b;
+        }
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+
+        let (mut rest, mut t0) = (*segment, 0.0);
+        for t in roots
+            .iter()
+            .copied()
+            .filter(|t| *t > 1e-4 && *t < 1.0 - 1e-4)
+        {
+            let (head, tail) = rest.split((t - t0) / (1.0 - t0));
+            monotonic.push(head);
+            rest = tail;
+            t0 = t;
+        }
+        monotonic.push(rest);
+    }
+    monotonic
+}
+
+/// Returns the non-zero rule winding number of `p` for the y monotonic segments.
+fn winding(p: Point, monotonic: &[Segment]) -> i32 {
+    let mut winding = 0;
+    for segment in monotonic {
+        let (y0, y1) = (segment.start().y, segment.end().y);
+        let (low, high) = if y0 < y1 { (y0, y1) } else { (y1, y0) };
+        if p.y < low || p.y >= high {
+            continue;
+        }
+        let (min, max) = segment.hull();
+        let right = if p.x < min.x {
+            true
+        } else if p.x >= max.x {
+            false
+        } else {
+            // bisect for the point at height `p.y`
+            let (mut lo, mut hi) = if y0 < y1 { (0.0, 1.0) } else { (1.0, 0.0) };
+            for _ in 0..32 {
+                let mid = (lo + hi) / 2.0;
+                if segment.eval(mid).y < p.y {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            segment.eval((lo + hi) / 2.0).x > p.x
+        };
+        if right {
+            winding += if y1 > y0 { 1 } else { -1 };
+        }
+    }
+    winding
+}
+
+/// Removes pieces with the same points as an earlier piece.
+fn dedup_pieces(pieces: &mut Vec<Segment>, snap: f32) {
+    let mut order: Vec<usize> = (0..pieces.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (pieces[a].start().x, pieces[b].start().x);
+        a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+    });
+    let mut duplicate = vec![false; pieces.len()];
+    for (i, &a) in order.iter().enumerate() {
+        if duplicate[a] {
+            continue;
+        }
+        for &b in &order[i + 1..] {
+            if pieces[b].start().x > pieces[a].start().x + snap {
+                break;
+            }
+            let (pa, pb) = (&pieces[a], &pieces[b]);
+            if pa.len == pb.len && (0..pa.len).all(|i| pa.points[i].distance(pb.points[i]) <= snap)
+            {
+                // keep the piece earliest in the outline
+                duplicate[a.max(b)] = true;
+                if b < a {
+                    break;
+                }
+            }
+        }
+    }
+    let mut index = 0;
+    pieces.retain(|_| {
+        index += 1;
+        !duplicate[index - 1]
+    });
+}
+
+/// Joins pieces into contours, each piece starting exactly where the previous ended.
+fn chain(pieces: Vec<Segment>, snap: f32) -> Vec<OutlineCurve> {
+    let mut used = vec![false; pieces.len()];
+    let mut curves = Vec::with_capacity(pieces.len());
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let mut piece = pieces[first];
+        let start = piece.start();
+        let mut last = first;
+        loop {
+            if piece.end().distance(start) <= snap {
+                piece.set_end(start);
+                curves.push(piece.into());
+                break;
+            }
+            let end = piece.end();
+            curves.push(piece.into());
+            // usually the following piece of the same original contour
+            let next = (last + 1..pieces.len())
+                .chain(0..last)
+                .find(|&i| !used[i] && pieces[i].start().distance(end) <= snap);
+            match next {
+                Some(i) => {
+                    used[i] = true;
+                    last = i;
+                    piece = pieces[i];
+                    piece.set_start(end);
+                }
+                None => break,
+            }
+        }
+    }
+    curves
+}
+
+/// Decomposes the area filled by the outline, using the non-zero fill rule, into
+/// non-overlapping trapezoids with horizontal top & bottom edges.
+///
+/// Curves are flattened to lines within `tolerance`. Calls `trapezoid` with the bottom &
+/// top y, in outline y up units, and the left & right edge x values at each.
+pub(crate) fn fill_trapezoids(
+    outline: &Outline,
+    tolerance: f32,
+    mut trapezoid: impl FnMut(f32, f32, (f32, f32), (f32, f32)),
+) {
+    let mut edges = Vec::new();
+    flatten_unscaled(outline, tolerance, |p0, p1| {
+        if p0.y < p1.y {
+            edges.push(Edge::new(p0, p1, 1));
+        } else if p0.y > p1.y {
+            edges.push(Edge::new(p1, p0, -1));
+        }
+        // horizontal lines don't affect the winding
+    });
+
+    // split into slabs at every end point & crossing, so edges don't cross within a slab
+    let mut ys = Vec::with_capacity(edges.len() * 2);
+    for e in &edges {
+        ys.push(e.bottom.y);
+        ys.push(e.top.y);
+    }
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            if let Some(y) = a.crossing(b) {
+                ys.push(y);
+            }
+        }
+    }
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+    ys.dedup();
+
+    let mut active: Vec<(f32, f32, f32, i32)> = Vec::new();
+    for slab in ys.windows(2) {
+        let (y0, y1) = (slab[0], slab[1]);
+        active.clear();
+        active.extend(
+            edges
+                .iter()
+                .filter(|e| e.bottom.y <= y0 && e.top.y >= y1)
+                .map(|e| {
+                    let (x0, x1) = (e.x_at(y0), e.x_at(y1));
+                    (x0 + x1, x0, x1, e.winding)
+                }),
+        );
+        active.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+
+        let mut winding = 0;
+        let mut left = (0.0, 0.0);
+        for &(_, x0, x1, dir) in &active {
+            let was_inside = winding != 0;
+            winding += dir;
+            if !was_inside && winding != 0 {
+                left = (x0, x1);
+            } else if was_inside && winding == 0 {
+                trapezoid(y0, y1, left, (x0, x1));
+            }
+        }
+    }
+}
+
+/// Flattens the outline curves into lines within `tolerance`, keeping unscaled y up units.
+pub(crate) fn flatten_unscaled(
+    outline: &Outline,
+    tolerance: f32,
+    mut line: impl FnMut(Point, Point),
+) {
+    let identity = PxTransform::new(
+        PxScaleFactor {
+            horizontal: 1.0,
+            vertical: -1.0,
+        },
+        point(0.0, 0.0),
+        point(0.0, 0.0),
+    );
+    for curve in &outline.curves {
+        flatten(curve, &identity, tolerance, &mut line);
+    }
+}
+
+/// Flattens the outline curves into lines within `tolerance`, keeping unscaled y up units,
+/// returning the points of each contour.
+pub(crate) fn flatten_contours(outline: &Outline, tolerance: f32) -> Vec<Vec<Point>> {
+    let mut contours: Vec<Vec<Point>> = Vec::new();
+    flatten_unscaled(outline, tolerance, |p0, p1| match contours.last_mut() {
+        Some(contour) if contour.last() == Some(&p0) => contour.push(p1),
+        _ => contours.push(vec![p0, p1]),
+    });
+    contours
+}
+
+/// Non-horizontal flattened outline line.
+#[derive(Clone, Copy)]
+struct Edge {
+    /// Lower end point, in y up units.
+    bottom: Point,
+    top: Point,
+    /// `1` if the contour goes up, otherwise `-1`.
+    winding: i32,
+}
+
+impl Edge {
+    #[inline]
+    fn new(bottom: Point, top: Point, winding: i32) -> Self {
+        Self {
+            bottom,
+            top,
+            winding,
+        }
+    }
+
+    #[inline]
+    fn x_at(&self, y: f32) -> f32 {
+        let t = (y - self.bottom.y) / (self.top.y - self.bottom.y);
+        self.bottom.x + (self.top.x - self.bottom.x) * t
+    }
+
+    /// Returns the y of the point the edges cross, if strictly between their end points.
+    fn crossing(&self, other: &Edge) -> Option<f32> {
+        let (y0, y1) = (
+            self.bottom.y.max(other.bottom.y),
+            self.top.y.min(other.top.y),
+        );
+        if y0 >= y1 {
+            return None;
+        }
+        // difference in x is linear in y within the shared range
+        let (d0, d1) = (
+            self.x_at(y0) - other.x_at(y0),
+            self.x_at(y1) - other.x_at(y1),
+        );
+        if (d0 < 0.0 && d1 > 0.0) || (d0 > 0.0 && d1 < 0.0) {
+            Some(y0 + (y1 - y0) * d0 / (d0 - d1))
+        } else {
+            None
+        }
+    }
+}
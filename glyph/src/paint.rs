@@ -0,0 +1,197 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{GlyphId, Point};
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// Color glyph paint graph, as described by COLR version 1 tables.
+///
+/// Colors are `0xRRGGBBAA` with any paint alpha already applied. The text foreground
+/// color, palette index `0xFFFF`, is resolved as opaque black.
+///
+/// Coordinates are unscaled font units, y-axis up, like [`Outline`](struct.Outline.html).
+///
+/// See [`Font::color_paint`](trait.Font.html#method.color_paint).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColorGlyphPaint {
+    /// Paints composited in order, bottom to top.
+    Layers(Vec<ColorGlyphPaint>),
+    /// Solid color fill.
+    Solid(u32),
+    /// Linear gradient through `p0` & `p1`, with `p2` rotating the gradient direction.
+    LinearGradient {
+        p0: Point,
+        p1: Point,
+        p2: Point,
+        color_line: ColorLine,
+    },
+    /// Radial gradient between circle `c0`, `r0` & circle `c1`, `r1`.
+    RadialGradient {
+        c0: Point,
+        r0: f32,
+        c1: Point,
+        r1: f32,
+        color_line: ColorLine,
+    },
+    /// Sweep gradient around `center`, counter-clockwise from `start_angle` to `end_angle`
+    /// degrees.
+    SweepGradient {
+        center: Point,
+        start_angle: f32,
+        end_angle: f32,
+        color_line: ColorLine,
+    },
+    /// Fill of `paint` clipped to a glyph outline.
+    Glyph {
+        id: GlyphId,
+        paint: Box<ColorGlyphPaint>,
+    },
+    /// `paint` transformed by the affine `[xx, yx, xy, yy, dx, dy]` matrix.
+    ///
+    /// Translate, scale, rotate & skew paints are all represented by their matrix.
+    Transform {
+        transform: [f32; 6],
+        paint: Box<ColorGlyphPaint>,
+    },
+    /// `source` composited onto `backdrop` using `mode`.
+    Composite {
+        source: Box<ColorGlyphPaint>,
+        mode: CompositeMode,
+        backdrop: Box<ColorGlyphPaint>,
+    },
+}
+
+/// Gradient color stops.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorLine {
+    /// How the gradient extends outside the stop range.
+    pub extend: GradientExtend,
+    /// Stops in font order, which may not be sorted by offset.
+    pub stops: Vec<ColorStop>,
+}
+
+impl ColorLine {
+    /// Returns the color at gradient position `t`, interpolating between stops &
+    /// applying the `extend` mode outside the stop range.
+    ///
+    /// Returns transparent `0` if there are no stops.
+    pub fn color_at(&self, t: f32) -> u32 {
+        let mut stops: Vec<_> = self.stops.iter().collect();
+        stops.sort_by(|a, b| {
+            a.offset
+                .partial_cmp(&b.offset)
+                .unwrap_or(core::cmp::Ordering::Equal)
+        });
+        let (first, last) = match (stops.first(), stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 0,
+        };
+
+        let range = last.offset - first.offset;
+        let t = if range > 0.0 {
+            let r = (t - first.offset) / range;
+            let r = match self.extend {
+                GradientExtend::Pad => r.clamp(0.0, 1.0),
+                GradientExtend::Repeat => r - r.floor(),
+                GradientExtend::Reflect => {
+                    let r = (r / 2.0 - (r / 2.0).floor()) * 2.0;
+                    if r > 1.0 {
+                        2.0 - r
+                    } else {
+                        r
+                    }
+                }
+            };
+            first.offset + r * range
+        } else {
+            first.offset
+        };
+
+        match stops.windows(2).find(|w| t <= w[1].offset) {
+            Some(w) if w[1].offset > w[0].offset => {
+                let f = (t - w[0].offset) / (w[1].offset - w[0].offset);
+                lerp_color(w[0].color, w[1].color, f.max(0.0))
+            }
+            Some(w) => w[1].color,
+            None => last.color,
+        }
+    }
+}
+
+/// Interpolates each `0xRRGGBBAA` channel.
+#[inline]
+fn lerp_color(a: u32, b: u32, f: f32) -> u32 {
+    let a = a.to_be_bytes();
+    let b = b.to_be_bytes();
+    let mut out = [0; 4];
+    for (o, (a, b)) in out.iter_mut().zip(a.iter().zip(b.iter())) {
+        *o = (f32::from(*a) + (f32::from(*b) - f32::from(*a)) * f + 0.5) as u8;
+    }
+    u32::from_be_bytes(out)
+}
+
+/// A gradient color stop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStop {
+    /// Position along the gradient, usually `0.0..=1.0`.
+    pub offset: f32,
+    /// `0xRRGGBBAA` color, with the stop alpha applied.
+    pub color: u32,
+}
+
+/// How a gradient [`ColorLine`](struct.ColorLine.html) extends outside its stops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GradientExtend {
+    /// Use the nearest stop color.
+    Pad,
+    /// Repeat the color line.
+    Repeat,
+    /// Repeat the color line, mirroring every other repetition.
+    Reflect,
+}
+
+/// Compositing & blending modes of [`ColorGlyphPaint::Composite`](enum.ColorGlyphPaint.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CompositeMode {
+    Clear,
+    Source,
+    Destination,
+    SourceOver,
+    DestinationOver,
+    SourceIn,
+    DestinationIn,
+    SourceOut,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Xor,
+    Plus,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Multiply,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+/// Name & intended background of a font color palette.
+///
+/// See [`Font::color_palette`](trait.Font.html#method.color_palette).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ColorPalette {
+    /// Palette label, if the font names it, e.g. "Dark".
+    pub name: Option<String>,
+    /// Palette is intended for use on light backgrounds.
+    pub light_background: bool,
+    /// Palette is intended for use on dark backgrounds.
+    pub dark_background: bool,
+}
@@ -0,0 +1,131 @@
+use crate::{Font, GlyphId, Outline, OutlinedGlyph};
+use rayon2::prelude::*;
+
+/// Parallel glyph outline extraction using rayon, requires the `rayon` feature.
+///
+/// Implemented for all `Font + Sync` types.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontRef, GlyphId, ParFont};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+///
+/// // pre-bake every outline in the font
+/// let ids: Vec<_> = (0..font.glyph_count() as u16).map(GlyphId).collect();
+/// let outlines = font.par_outlines(&ids);
+///
+/// assert_eq!(outlines.len(), font.glyph_count());
+/// let s = font.glyph_id('s');
+/// assert_eq!(
+///     outlines[s.0 as usize].as_ref().map(|o| o.bounds),
+///     font.outline(s).map(|o| o.bounds),
+/// );
+/// # Ok(()) }
+/// ```
+pub trait ParFont: Font + Sync {
+    /// Compute unscaled glyph outlines for each of `ids` in parallel.
+    ///
+    /// Results are in the same order as `ids`.
+    fn par_outlines(&self, ids: &[GlyphId]) -> Vec<Option<Outline>> {
+        ids.par_iter().map(|id| self.outline(*id)).collect()
+    }
+}
+
+impl<F: Font + Sync> ParFont for F {}
+
+/// Caller-provided 8 bit coverage atlas to [`rasterize_all`](fn.rasterize_all.html) into.
+#[derive(Debug)]
+pub struct AtlasWriter<'a> {
+    /// Row-major atlas pixels.
+    pub pixels: &'a mut [u8],
+    /// Pixels per atlas row.
+    pub stride: usize,
+    /// Top-left atlas pixel of each glyph's [`px_bounds`](struct.OutlinedGlyph.html#method.px_bounds)
+    /// region, in the same order as the glyphs.
+    pub origins: &'a [(u32, u32)],
+}
+
+/// Rasterizes `glyphs` in parallel into their atlas regions, requires the `rayon` feature.
+///
+/// Each glyph's coverage, as [`OutlinedGlyph::draw_into`](struct.OutlinedGlyph.html#method.draw_into)
+/// of the first layer, overwrites its region. Where regions overlap later glyphs are written
+/// over earlier ones. Pixels outside all regions are left unchanged.
+///
+/// # Panics
+/// If the number of `origins` doesn't match `glyphs` or a region doesn't fit in the atlas.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{rasterize_all, AtlasWriter, Font, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let glyphs: Vec<_> = "abcdefgh"
+///     .chars()
+///     .filter_map(|c| font.outline_glyph(font.glyph_id(c).with_scale(24.0)))
+///     .collect();
+///
+/// // pack glyphs into a single row
+/// let mut origins = Vec::new();
+/// let mut x = 0;
+/// for glyph in &glyphs {
+///     origins.push((x, 0));
+///     x += glyph.px_bounds().width() as u32;
+/// }
+///
+/// let stride = 256;
+/// let mut pixels = vec![0; stride * 32];
+/// rasterize_all(&glyphs, AtlasWriter { pixels: &mut pixels, stride, origins: &origins });
+///
+/// let (ox, oy) = origins[3];
+/// glyphs[3].draw(0, |x, y, c| {
+///     let px = pixels[(oy + y) as usize * stride + (ox + x) as usize];
+///     assert_eq!(px, (c * 255.0).round() as u8);
+/// });
+/// # Ok(()) }
+/// ```
+pub fn rasterize_all(glyphs: &[OutlinedGlyph], writer: AtlasWriter<'_>) {
+    let AtlasWriter {
+        pixels,
+        stride,
+        origins,
+    } = writer;
+    assert_eq!(
+        glyphs.len(),
+        origins.len(),
+        "an origin is required for each glyph"
+    );
+    let rows = pixels.len() / stride.max(1);
+
+    // rasterize into glyph buffers in parallel, then copy atlas rows in parallel
+    let rasterized: Vec<_> = glyphs
+        .par_iter()
+        .zip(origins)
+        .map(|(glyph, &(x, y))| {
+            let bounds = glyph.px_bounds();
+            let (w, h) = (bounds.width() as usize, bounds.height() as usize);
+            let (x, y) = (x as usize, y as usize);
+            assert!(
+                w == 0 || h == 0 || (x + w <= stride && y + h <= rows),
+                "glyph region outside the atlas"
+            );
+            let mut coverage = vec![0; w * h];
+            glyph.draw_into(0, &mut coverage, w, 0);
+            (coverage, x, y, w)
+        })
+        .collect();
+
+    pixels
+        .par_chunks_mut(stride.max(1))
+        .enumerate()
+        .for_each(|(row, pixels)| {
+            for (coverage, x, y, w) in &rasterized {
+                if *w > 0 && row >= *y && (row - y + 1) * w <= coverage.len() {
+                    let start = (row - y) * w;
+                    pixels[*x..*x + w].copy_from_slice(&coverage[start..start + w]);
+                }
+            }
+        });
+}
@@ -0,0 +1,182 @@
+use crate::{Outline, OutlineCurve, Point};
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Receives the path commands of an outline, see
+/// [`Outline::for_each_segment`](struct.Outline.html#method.for_each_segment) &
+/// [`Font::outline_with`](trait.Font.html#method.outline_with).
+///
+/// Maps directly to the path builders of vector graphics libraries, e.g. lyon, skia &
+/// tiny-skia. Implemented for `&mut` sinks so they can be reused after visiting.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontRef, OutlineSink, Point};
+///
+/// /// Counts contours.
+/// #[derive(Default)]
+/// struct Contours(usize);
+///
+/// impl OutlineSink for Contours {
+///     fn move_to(&mut self, _: Point) {
+///         self.0 += 1;
+///     }
+///     fn line_to(&mut self, _: Point) {}
+///     fn quad_to(&mut self, _: Point, _: Point) {}
+///     fn curve_to(&mut self, _: Point, _: Point, _: Point) {}
+///     fn close(&mut self) {}
+/// }
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let mut contours = Contours::default();
+/// let outline = font.outline(font.glyph_id('o')).unwrap();
+/// outline.for_each_segment(&mut contours);
+/// assert_eq!(contours.0, 2);
+/// # Ok(()) }
+/// ```
+pub trait OutlineSink {
+    /// Starts a new contour at `p`.
+    fn move_to(&mut self, p: Point);
+    /// Adds a straight line to `p`.
+    fn line_to(&mut self, p: Point);
+    /// Adds a quadratic Bézier curve to `p`.
+    fn quad_to(&mut self, control: Point, p: Point);
+    /// Adds a cubic Bézier curve to `p`.
+    fn curve_to(&mut self, control0: Point, control1: Point, p: Point);
+    /// Closes the current contour.
+    fn close(&mut self);
+}
+
+impl<S: OutlineSink + ?Sized> OutlineSink for &mut S {
+    #[inline]
+    fn move_to(&mut self, p: Point) {
+        (**self).move_to(p)
+    }
+
+    #[inline]
+    fn line_to(&mut self, p: Point) {
+        (**self).line_to(p)
+    }
+
+    #[inline]
+    fn quad_to(&mut self, control: Point, p: Point) {
+        (**self).quad_to(control, p)
+    }
+
+    #[inline]
+    fn curve_to(&mut self, control0: Point, control1: Point, p: Point) {
+        (**self).curve_to(control0, control1, p)
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        (**self).close()
+    }
+}
+
+impl Outline {
+    /// Visits the outline as path commands, unscaled with y pointing up like the curves.
+    ///
+    /// Each contour starts with a `move_to` & ends with a `close`. A curve not continuing
+    /// from the end of the last starts a new contour.
+    pub fn for_each_segment(&self, mut sink: impl OutlineSink) {
+        let mut last_end: Option<Point> = None;
+        for curve in &self.curves {
+            let (start, end) = match *curve {
+                OutlineCurve::Line(p0, p1) => (p0, p1),
+                OutlineCurve::Quad(p0, _, p2) => (p0, p2),
+                OutlineCurve::Cubic(p0, _, _, p3) => (p0, p3),
+            };
+            if last_end != Some(start) {
+                if last_end.is_some() {
+                    sink.close();
+                }
+                sink.move_to(start);
+            }
+            match *curve {
+                OutlineCurve::Line(_, p1) => sink.line_to(p1),
+                OutlineCurve::Quad(_, p1, p2) => sink.quad_to(p1, p2),
+                OutlineCurve::Cubic(_, p1, p2, p3) => sink.curve_to(p1, p2, p3),
+            }
+            last_end = Some(end);
+        }
+        if last_end.is_some() {
+            sink.close();
+        }
+    }
+
+    /// Returns the outline as SVG path data, the `d` attribute of a `<path>`.
+    ///
+    /// Coordinates are unscaled with y negated, so the glyph is upright in SVG's y down
+    /// coordinates with the baseline at `y = 0`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Outline, OutlineCurve, Rect};
+    ///
+    /// let (a, b, c) = (point(0.0, 0.0), point(10.0, 0.0), point(5.0, 10.0));
+    /// let triangle = Outline {
+    ///     bounds: Rect {
+    ///         min: point(0.0, 10.0),
+    ///         max: point(10.0, 0.0),
+    ///     },
+    ///     curves: vec![
+    ///         OutlineCurve::Line(a, b),
+    ///         OutlineCurve::Line(b, c),
+    ///         OutlineCurve::Line(c, a),
+    ///     ],
+    /// };
+    /// assert_eq!(triangle.to_svg_path_string(), "M0 0L10 0L5 -10L0 0Z");
+    /// ```
+    pub fn to_svg_path_string(&self) -> String {
+        let mut path = SvgPath(String::new());
+        self.for_each_segment(&mut path);
+        path.0
+    }
+}
+
+/// Writes SVG path data.
+struct SvgPath(String);
+
+impl SvgPath {
+    #[inline]
+    fn command(&mut self, command: char, points: &[Point]) {
+        self.0.push(command);
+        for (n, p) in points.iter().enumerate() {
+            if n > 0 {
+                self.0.push(' ');
+            }
+            // adding zero avoids writing "-0"
+            let _ = write!(self.0, "{} {}", p.x + 0.0, -p.y + 0.0);
+        }
+    }
+}
+
+impl OutlineSink for SvgPath {
+    #[inline]
+    fn move_to(&mut self, p: Point) {
+        self.command('M', &[p]);
+    }
+
+    #[inline]
+    fn line_to(&mut self, p: Point) {
+        self.command('L', &[p]);
+    }
+
+    #[inline]
+    fn quad_to(&mut self, control: Point, p: Point) {
+        self.command('Q', &[control, p]);
+    }
+
+    #[inline]
+    fn curve_to(&mut self, control0: Point, control1: Point, p: Point) {
+        self.command('C', &[control0, control1, p]);
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.0.push('Z');
+    }
+}
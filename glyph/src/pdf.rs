@@ -0,0 +1,257 @@
+//! Helpers producing the values a PDF writer needs to embed a font as a
+//! `CIDFontType2` (.ttf) or `CIDFontType0` (.otf) descendant font with `Identity-H` encoding.
+//!
+//! PDF glyph space uses 1000 units per em, all returned metrics are scaled accordingly.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::{pdf, Font, FontRef};
+//!
+//! # fn main() -> Result<(), ab_glyph::InvalidFont> {
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+//! let text = "Hello";
+//! let glyphs: Vec<_> = text.chars().map(|c| font.glyph_id(c)).collect();
+//!
+//! let descriptor = pdf::FontDescriptor::new(&font, &glyphs);
+//! assert_eq!(descriptor.ascent, 999.0);
+//! assert_eq!(descriptor.descent, -201.0);
+//! assert_eq!(descriptor.cap_height, 690.0);
+//!
+//! // `/W` array of the descendant font
+//! let widths = pdf::widths(&font, &glyphs);
+//! assert_eq!(widths.len(), 4); // 'l' is used twice
+//! let w_array = pdf::widths_array(&widths);
+//! assert!(w_array.starts_with('['));
+//!
+//! // `/ToUnicode` stream using glyph ids as CIDs
+//! let cid_text: Vec<_> = text.char_indices()
+//!     .map(|(idx, c)| (glyphs[idx].0, &text[idx..idx + c.len_utf8()]))
+//!     .collect();
+//! let cmap = pdf::to_unicode_cmap(&cid_text);
+//! assert!(cmap.contains("beginbfchar"));
+//! # Ok(()) }
+//! ```
+use crate::{Font, GlyphId};
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::Write;
+
+/// Font descriptor flag: all glyphs have the same width.
+pub const FLAG_FIXED_PITCH: u32 = 1 << 0;
+/// Font descriptor flag: glyphs have serifs.
+pub const FLAG_SERIF: u32 = 1 << 1;
+/// Font descriptor flag: font contains glyphs outside the Adobe standard Latin character set.
+pub const FLAG_SYMBOLIC: u32 = 1 << 2;
+/// Font descriptor flag: glyphs resemble cursive handwriting.
+pub const FLAG_SCRIPT: u32 = 1 << 3;
+/// Font descriptor flag: font uses the Adobe standard Latin character set.
+pub const FLAG_NONSYMBOLIC: u32 = 1 << 5;
+/// Font descriptor flag: glyphs have dominant vertical strokes that are slanted.
+pub const FLAG_ITALIC: u32 = 1 << 6;
+
+/// `/FontDescriptor` dictionary values in PDF glyph space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontDescriptor {
+    /// `/FontBBox [llx lly urx ury]`, the union of the bounds of the glyphs used.
+    pub font_bbox: [f32; 4],
+    /// `/ItalicAngle` in counter-clockwise degrees from the vertical.
+    pub italic_angle: f32,
+    /// `/Ascent` above the baseline.
+    pub ascent: f32,
+    /// `/Descent` below the baseline, typically negative.
+    pub descent: f32,
+    /// `/CapHeight` of flat capital letters, measured from the 'H' glyph when available.
+    pub cap_height: f32,
+    /// `/StemV` thickness of dominant vertical stems.
+    ///
+    /// OpenType fonts do not record this so a conventional estimate is used.
+    pub stem_v: f32,
+    /// `/Flags` bit set, see the `FLAG_*` constants.
+    ///
+    /// Embedded CID fonts are always flagged [`FLAG_SYMBOLIC`](constant.FLAG_SYMBOLIC.html).
+    pub flags: u32,
+}
+
+impl FontDescriptor {
+    /// Computes font descriptor values for a font embedding the given `glyphs`.
+    pub fn new<F: Font>(font: &F, glyphs: &[GlyphId]) -> Self {
+        let scale = pdf_scale(font);
+        let ascent = font.ascent_unscaled() * scale;
+        let descent = font.descent_unscaled() * scale;
+
+        let cap_height = font.cap_height_unscaled() * scale;
+
+        // note: outline bounds `min.y` is the top, `max.y` the bottom
+        let font_bbox = glyphs
+            .iter()
+            .filter_map(|id| font.outline(*id))
+            .map(|o| o.bounds)
+            .map(|b| {
+                [
+                    b.min.x * scale,
+                    b.max.y * scale,
+                    b.max.x * scale,
+                    b.min.y * scale,
+                ]
+            })
+            .fold(None, |bbox: Option<[f32; 4]>, b| match bbox {
+                None => Some(b),
+                Some(bbox) => Some([
+                    bbox[0].min(b[0]),
+                    bbox[1].min(b[1]),
+                    bbox[2].max(b[2]),
+                    bbox[3].max(b[3]),
+                ]),
+            })
+            .unwrap_or([0.0, descent, 0.0, ascent]);
+
+        let italic_angle = font.italic_angle();
+        let mut flags = FLAG_SYMBOLIC;
+        if font.is_monospaced() {
+            flags |= FLAG_FIXED_PITCH;
+        }
+        if italic_angle != 0.0 {
+            flags |= FLAG_ITALIC;
+        }
+
+        Self {
+            font_bbox,
+            italic_angle,
+            ascent,
+            descent,
+            cap_height,
+            stem_v: 80.0,
+            flags,
+        }
+    }
+}
+
+/// Returns the sorted & deduplicated horizontal advances of `glyphs` in PDF glyph space.
+pub fn widths<F: Font>(font: &F, glyphs: &[GlyphId]) -> Vec<(GlyphId, f32)> {
+    let scale = pdf_scale(font);
+    let mut ids = glyphs.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+    ids.into_iter()
+        .map(|id| (id, font.h_advance_unscaled(id) * scale))
+        .collect()
+}
+
+/// Formats sorted `(cid, width)` pairs, as returned by [`widths`](fn.widths.html),
+/// into a descendant font `/W` array grouping consecutive CIDs.
+///
+/// ```
+/// use ab_glyph::{pdf, GlyphId};
+///
+/// let widths = [(GlyphId(3), 500.0), (GlyphId(4), 612.5), (GlyphId(9), 250.0)];
+/// assert_eq!(pdf::widths_array(&widths), "[3 [500 612.5] 9 [250]]");
+/// ```
+pub fn widths_array(widths: &[(GlyphId, f32)]) -> String {
+    let mut out = String::from("[");
+    let mut last: Option<u16> = None;
+    for (GlyphId(cid), width) in widths {
+        match last {
+            Some(prev) if prev.checked_add(1) == Some(*cid) => out.push(' '),
+            Some(_) => {
+                let _ = write!(out, "] {} [", cid);
+            }
+            None => {
+                let _ = write!(out, "{} [", cid);
+            }
+        }
+        let _ = write!(out, "{}", width);
+        last = Some(*cid);
+    }
+    if last.is_some() {
+        out.push(']');
+    }
+    out.push(']');
+    out
+}
+
+/// Builds `/CIDToGIDMap` stream data from `(cid, glyph_id)` pairs.
+///
+/// The result holds a big-endian `u16` glyph id for every CID up to the largest given,
+/// unmapped CIDs point to glyph `0`. When CIDs equal glyph ids use `/Identity` instead.
+///
+/// ```
+/// use ab_glyph::{pdf, GlyphId};
+///
+/// let map = pdf::cid_to_gid_map(&[(1, GlyphId(36)), (2, GlyphId(300))]);
+/// assert_eq!(map, vec![0, 0, 0, 36, 1, 44]);
+/// ```
+pub fn cid_to_gid_map(cid_to_gid: &[(u16, GlyphId)]) -> Vec<u8> {
+    let len = cid_to_gid
+        .iter()
+        .map(|(cid, _)| usize::from(*cid) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut map = vec![0; len * 2];
+    for (cid, GlyphId(gid)) in cid_to_gid {
+        let idx = usize::from(*cid) * 2;
+        map[idx..idx + 2].copy_from_slice(&gid.to_be_bytes());
+    }
+    map
+}
+
+/// Builds a `/ToUnicode` CMap stream mapping 2-byte CIDs to the text they represent.
+///
+/// Text may be more than one char, e.g. for ligatures. Duplicate CIDs use the first mapping.
+///
+/// When the source text isn't at hand, [`GlyphTextMap`](../struct.GlyphTextMap.html) resolves
+/// the text of each glyph, including ligatures.
+///
+/// ```
+/// use ab_glyph::{pdf, Font, FontRef, GlyphTextMap};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let texts = GlyphTextMap::new(&font);
+///
+/// let fi = font.glyph_id('\u{FB01}');
+/// let cmap = pdf::to_unicode_cmap(&[(fi.0, texts.get(fi).unwrap())]);
+/// assert!(cmap.contains(&format!("<{:04X}> <00660069>", fi.0)));
+/// # Ok(()) }
+/// ```
+pub fn to_unicode_cmap(cid_to_text: &[(u16, &str)]) -> String {
+    let mut entries: Vec<_> = cid_to_text.to_vec();
+    entries.sort_by_key(|(cid, _)| *cid);
+    entries.dedup_by_key(|(cid, _)| *cid);
+
+    let mut out = String::from(
+        "/CIDInit /ProcSet findresource begin\n\
+         12 dict begin\n\
+         begincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n\
+         /CMapType 2 def\n\
+         1 begincodespacerange\n\
+         <0000> <FFFF>\n\
+         endcodespacerange\n",
+    );
+    // bfchar sections are limited to 100 entries each
+    for chunk in entries.chunks(100) {
+        let _ = writeln!(out, "{} beginbfchar", chunk.len());
+        for (cid, text) in chunk {
+            let _ = write!(out, "<{:04X}> <", cid);
+            for unit in text.encode_utf16() {
+                let _ = write!(out, "{:04X}", unit);
+            }
+            out.push_str(">\n");
+        }
+        out.push_str("endbfchar\n");
+    }
+    out.push_str(
+        "endcmap\n\
+         CMapName currentdict /CMap defineresource pop\n\
+         end\n\
+         end\n",
+    );
+    out
+}
+
+/// Scale factor from font units to PDF glyph space units.
+#[inline]
+fn pdf_scale<F: Font>(font: &F) -> f32 {
+    font.units_per_em().map(|upem| 1000.0 / upem).unwrap_or(1.0)
+}
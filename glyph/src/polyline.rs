@@ -0,0 +1,100 @@
+use crate::{overlap::flatten_contours, Outline, Point};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Direction of closed contours, in unscaled y up coordinates.
+///
+/// Flipping y, e.g. to draw in y down device coordinates, reverses the apparent direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Winding {
+    /// Clockwise, like TrueType outer contours.
+    Clockwise,
+    /// Counter-clockwise, like CFF outer contours.
+    CounterClockwise,
+}
+
+impl Outline {
+    /// Returns each contour as a closed polyline, e.g. toolpaths for pen plotters, laser
+    /// cutters & CNC machines.
+    ///
+    /// Curves are flattened to lines within `tolerance` unscaled units. Polylines are in
+    /// the font's contour order, each starting at the contour's first point & ending with
+    /// it again. Outer contours wind in the `outer` direction & holes, contours nested in
+    /// an odd number of others, the opposite direction, regardless of the font's
+    /// conventions. Contours enclosing no area are skipped.
+    ///
+    /// Points are unscaled, y up.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, Winding};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?;
+    /// let outline = font.outline(font.glyph_id('o')).unwrap();
+    ///
+    /// let polylines = outline.to_polylines(0.5, Winding::CounterClockwise);
+    /// assert_eq!(polylines.len(), 2);
+    /// assert!(polylines.iter().all(|p| p.first() == p.last()));
+    ///
+    /// // shoelace area, positive for counter-clockwise
+    /// let area = |p: &[ab_glyph::Point]| -> f32 {
+    ///     p.windows(2).map(|w| w[0].x * w[1].y - w[1].x * w[0].y).sum::<f32>() / 2.0
+    /// };
+    /// let (outer, hole) = (area(&polylines[0]), area(&polylines[1]));
+    /// assert!(outer > 0.0 && hole < 0.0);
+    /// # Ok(()) }
+    /// ```
+    pub fn to_polylines(&self, tolerance: f32, outer: Winding) -> Vec<Vec<Point>> {
+        let mut contours = flatten_contours(self, tolerance.max(1e-3));
+        for contour in &mut contours {
+            if contour.first() != contour.last() {
+                let first = contour[0];
+                contour.push(first);
+            }
+        }
+        contours.retain(|contour| signed_area(contour) != 0.0);
+
+        let nesting: Vec<_> = contours
+            .iter()
+            .enumerate()
+            .map(|(idx, contour)| {
+                contours
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, polygon)| *other != idx && contains(polygon, contour[0]))
+                    .count()
+            })
+            .collect();
+
+        for (contour, depth) in contours.iter_mut().zip(nesting) {
+            let clockwise = signed_area(contour) < 0.0;
+            let want_clockwise = (outer == Winding::Clockwise) == (depth % 2 == 0);
+            if clockwise != want_clockwise {
+                // reverse keeping the start point
+                contour.reverse();
+            }
+        }
+        contours
+    }
+}
+
+/// Returns twice the signed area of a closed polyline, positive if counter-clockwise.
+fn signed_area(polyline: &[Point]) -> f32 {
+    polyline
+        .windows(2)
+        .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+        .sum()
+}
+
+/// Returns `true` if the point is inside the closed polyline, using the even-odd rule.
+fn contains(polyline: &[Point], p: Point) -> bool {
+    let mut inside = false;
+    for w in polyline.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
@@ -0,0 +1,19 @@
+//! Commonly used traits & types, glob imported with `use ab_glyph::prelude::*`.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::prelude::*;
+//!
+//! # fn main() -> Result<(), ab_glyph::InvalidFont> {
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+//! let scaled = font.as_scaled(24.0);
+//! let glyph: Glyph = scaled.scaled_glyph('a');
+//! assert!(scaled.outline_glyph(glyph).is_some());
+//! # Ok(()) }
+//! ```
+#[cfg(feature = "std")]
+pub use crate::FontArc;
+pub use crate::{
+    point, Font, FontRef, FontVec, Glyph, GlyphId, Layout, OutlinedGlyph, Point, PxScale, Rect,
+    ScaleFont, Text,
+};
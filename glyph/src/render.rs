@@ -0,0 +1,56 @@
+use crate::{GlyphImage, IRect, OutlinedGlyph, Rect};
+
+/// A glyph ready to draw by the route best suited to its font & size, see
+/// [`Font::render_glyph`](trait.Font.html#method.render_glyph).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, Font, FontRef, GlyphRender};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let glyph = font.glyph_id('a').with_scale_and_position(24.0, point(10.0, 20.0));
+///
+/// match font.render_glyph(glyph).unwrap() {
+///     GlyphRender::Outline(outlined) => outlined.draw(0, |x, y, c| { /* draw coverage */ }),
+///     GlyphRender::Color(outlined) => {
+///         for layer in 0..outlined.get_colored_layers().unwrap() {
+///             let _rgba = outlined.get_color(layer);
+///             outlined.draw(layer, |x, y, c| { /* blend color with coverage */ });
+///         }
+///     }
+///     GlyphRender::Image { image, px_bounds } => { /* decode & draw scaled to bounds */ }
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub enum GlyphRender<'a> {
+    /// Monochrome outline, draw the coverage of layer `0`.
+    Outline(OutlinedGlyph),
+    /// Color outline layers, draw the coverage of each layer with its color, bottom first.
+    Color(OutlinedGlyph),
+    /// Embedded raster image, e.g. a color emoji png, to draw scaled to `px_bounds`.
+    Image {
+        /// Image of the strike best matching the glyph's size.
+        image: GlyphImage<'a>,
+        /// Pixel bounds of the image at the glyph's scale & position.
+        px_bounds: Rect,
+    },
+}
+
+impl GlyphRender<'_> {
+    /// Returns the pixel bounds of the glyph.
+    #[inline]
+    pub fn px_bounds(&self) -> Rect {
+        match self {
+            Self::Outline(outlined) | Self::Color(outlined) => outlined.px_bounds(),
+            Self::Image { px_bounds, .. } => *px_bounds,
+        }
+    }
+
+    /// Returns the whole pixels covered by the glyph, rounding image bounds outwards.
+    #[inline]
+    pub fn px_irect(&self) -> IRect {
+        self.px_bounds().into()
+    }
+}
@@ -1,4 +1,11 @@
-use crate::{Font, Glyph, GlyphId, OutlinedGlyph, Rect};
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{
+    point, DecorationMetrics, DeviceVMetrics, Font, Glyph, GlyphId, InvalidScale, OpticalBounds,
+    OutlinedGlyph, Point, Rect,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
 
 /// Pixel scale.
 ///
@@ -14,6 +21,11 @@ use crate::{Font, Glyph, GlyphId, OutlinedGlyph, Rect};
 /// let uniform_scale_24px = PxScale::from(24.0);
 /// ```
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
 pub struct PxScale {
     /// Horizontal scale in pixels.
     pub x: f32,
@@ -23,6 +35,95 @@ pub struct PxScale {
     pub y: f32,
 }
 
+impl PxScale {
+    /// Scale with a pixel `height` & width stretched by `x_stretch`, e.g. `0.8` for
+    /// condensed or `1.2` for extended text.
+    ///
+    /// Stretching applies to horizontal metrics, advances, kerning & outlines, vertical
+    /// metrics & line heights are those of the `height`.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontRef, Layout, PxScale, ScaleFont};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let extended = PxScale::with_aspect(24.0, 1.5);
+    /// assert_eq!(extended, PxScale { x: 36.0, y: 24.0 });
+    /// assert_eq!(extended.aspect(), 1.5);
+    ///
+    /// let layout = |scale: PxScale, glyphs: &mut Vec<_>| {
+    ///     Layout::default().layout(font.as_scaled(scale), point(0.0, 0.0), "Hi", glyphs)[0].clone()
+    /// };
+    /// let (mut glyphs, mut stretched) = (Vec::new(), Vec::new());
+    /// let line = layout(PxScale::from(24.0), &mut glyphs);
+    /// let stretched_line = layout(extended, &mut stretched);
+    ///
+    /// // glyphs keep the stretched scale & are advanced further apart
+    /// assert!(stretched.iter().all(|g| g.scale == extended));
+    /// assert!((stretched_line.width - line.width * 1.5).abs() < 1e-3);
+    /// assert_eq!(stretched_line.ascent, line.ascent);
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn with_aspect(height: f32, x_stretch: f32) -> Self {
+        Self {
+            x: height * x_stretch,
+            y: height,
+        }
+    }
+
+    /// Returns the horizontal stretch, `x / y`, `1.0` for uniform scales.
+    #[inline]
+    pub fn aspect(self) -> f32 {
+        self.x / self.y
+    }
+
+    /// Returns `true` if `x` & `y` are equal, i.e. the font isn't stretched.
+    #[inline]
+    pub fn is_uniform(self) -> bool {
+        self.x == self.y
+    }
+
+    /// Returns `true` if `x` & `y` are finite & positive, so can be used to scale, lay out
+    /// & draw glyphs. Zero scales produce no pixels & negative or NaN scales nonsense.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::PxScale;
+    ///
+    /// assert!(PxScale::from(12.0).is_valid());
+    /// assert!(!PxScale::from(0.0).is_valid());
+    /// assert!(!PxScale::with_aspect(12.0, -1.0).is_valid());
+    /// assert!(!PxScale::from(f32::NAN).is_valid());
+    /// assert!(!PxScale::from(f32::INFINITY).is_valid());
+    /// ```
+    #[inline]
+    pub fn is_valid(self) -> bool {
+        let valid = |s: f32| s.is_finite() && s > 0.0;
+        valid(self.x) && valid(self.y)
+    }
+
+    /// Returns the scale if [valid](#method.is_valid), e.g. to check user provided sizes.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{InvalidScale, PxScale};
+    ///
+    /// assert_eq!(PxScale::from(12.0).validate(), Ok(PxScale::from(12.0)));
+    /// let zero_width = PxScale::with_aspect(12.0, 0.0);
+    /// assert_eq!(zero_width.validate(), Err(InvalidScale(zero_width)));
+    /// ```
+    #[inline]
+    pub fn validate(self) -> Result<Self, InvalidScale> {
+        if self.is_valid() {
+            Ok(self)
+        } else {
+            Err(InvalidScale(self))
+        }
+    }
+}
+
 impl From<f32> for PxScale {
     /// Uniform scaling where x & y are the same.
     #[inline]
@@ -31,13 +132,45 @@ impl From<f32> for PxScale {
     }
 }
 
+impl core::ops::Mul<f32> for PxScale {
+    type Output = Self;
+
+    /// Scales both `x` & `y`, keeping the aspect.
+    #[inline]
+    fn mul(self, factor: f32) -> Self {
+        PxScale {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+
 /// 2D scale factors for use with unscaled metrics.
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde2::Serialize, serde2::Deserialize),
+    serde(crate = "serde2")
+)]
 pub struct PxScaleFactor {
+    /// Factor for unscaled horizontal values.
     pub horizontal: f32,
+    /// Factor for unscaled vertical values.
     pub vertical: f32,
 }
 
+/// Pixel measurements of a line of text, see
+/// [`ScaleFont::measure_str`](trait.ScaleFont.html#method.measure_str).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StrMetrics {
+    /// Total horizontal advance, including kerning.
+    pub width: f32,
+    /// Union of the layout bounds of the glyphs, as
+    /// [`glyph_bounds`](trait.ScaleFont.html#method.glyph_bounds), relative to a baseline
+    /// origin `(0, 0)`.
+    pub bounds: Rect,
+}
+
 /// A [`Font`](trait.Font.html) with an associated pixel scale. This can be used to provide
 /// pixel scale values for glyph advances, heights etc.
 ///
@@ -80,6 +213,7 @@ pub trait ScaleFont<F: Font> {
         self.scale().y / self.font().height_unscaled()
     }
 
+    /// Scale factors for unscaled font horizontal & vertical values.
     #[inline]
     fn scale_factor(&self) -> PxScaleFactor {
         PxScaleFactor {
@@ -88,24 +222,78 @@ pub trait ScaleFont<F: Font> {
         }
     }
 
+    /// Converts unscaled font horizontal units into pixels.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// // Exo2-Light height is 1200 units
+    /// let scaled_font = font.as_scaled(PxScale { x: 48.0, y: 24.0 });
+    ///
+    /// assert_eq!(scaled_font.h_units_to_px(600.0), 24.0);
+    /// assert_eq!(scaled_font.h_px_to_units(24.0), 600.0);
+    /// assert_eq!(scaled_font.v_units_to_px(600.0), 12.0);
+    /// assert_eq!(scaled_font.v_px_to_units(12.0), 600.0);
+    /// ```
+    #[inline]
+    fn h_units_to_px(&self, units: f32) -> f32 {
+        units * self.h_scale_factor()
+    }
+
+    /// Converts unscaled font vertical units into pixels.
+    #[inline]
+    fn v_units_to_px(&self, units: f32) -> f32 {
+        units * self.v_scale_factor()
+    }
+
+    /// Converts horizontal pixels into unscaled font units.
+    #[inline]
+    fn h_px_to_units(&self, px: f32) -> f32 {
+        px / self.h_scale_factor()
+    }
+
+    /// Converts vertical pixels into unscaled font units.
+    #[inline]
+    fn v_px_to_units(&self, px: f32) -> f32 {
+        px / self.v_scale_factor()
+    }
+
     /// Pixel scaled glyph ascent.
+    ///
+    /// At a uniform whole number pixels-per-em the font's device
+    /// [`y_max`](struct.DeviceVMetrics.html#structfield.y_max) is used, if it has one,
+    /// matching legacy rasterizers.
     #[inline]
     fn ascent(&self) -> f32 {
-        self.v_scale_factor() * self.font().ascent_unscaled()
+        match device_v_metrics(self) {
+            Some(metrics) => metrics.y_max,
+            None => self.v_scale_factor() * self.font().ascent_unscaled(),
+        }
     }
 
     /// Pixel scaled glyph descent.
+    ///
+    /// At a uniform whole number pixels-per-em the font's device
+    /// [`y_min`](struct.DeviceVMetrics.html#structfield.y_min) is used, if it has one.
     #[inline]
     fn descent(&self) -> f32 {
-        self.v_scale_factor() * self.font().descent_unscaled()
+        match device_v_metrics(self) {
+            Some(metrics) => metrics.y_min,
+            None => self.v_scale_factor() * self.font().descent_unscaled(),
+        }
     }
 
     /// Pixel scaled height `ascent - descent`.
     ///
-    /// By definition of [`PxScale`], this is `self.scale().y`.
+    /// By definition of [`PxScale`], this is `self.scale().y`, unless using the font's
+    /// device metrics, see [`ascent`](#method.ascent).
     #[inline]
     fn height(&self) -> f32 {
-        self.scale().y
+        match device_v_metrics(self) {
+            Some(metrics) => metrics.height(),
+            None => self.scale().y,
+        }
     }
 
     /// Pixel scaled line gap.
@@ -114,6 +302,66 @@ pub trait ScaleFont<F: Font> {
         self.v_scale_factor() * self.font().line_gap_unscaled()
     }
 
+    /// Pixel scaled height of lowercase letters.
+    ///
+    /// See [`Font::x_height_unscaled`](trait.Font.html#method.x_height_unscaled).
+    #[inline]
+    fn x_height(&self) -> f32 {
+        self.v_scale_factor() * self.font().x_height_unscaled()
+    }
+
+    /// Pixel scaled height of capital letters.
+    #[inline]
+    fn cap_height(&self) -> f32 {
+        self.v_scale_factor() * self.font().cap_height_unscaled()
+    }
+
+    /// Pixel scaled underline position & thickness, if the font defines them.
+    ///
+    /// See [`Font::underline_metrics_unscaled`](trait.Font.html#method.underline_metrics_unscaled).
+    #[inline]
+    fn underline_metrics(&self) -> Option<DecorationMetrics> {
+        let scale = self.v_scale_factor();
+        self.font()
+            .underline_metrics_unscaled()
+            .map(|m| DecorationMetrics {
+                position: m.position * scale,
+                thickness: m.thickness * scale,
+            })
+    }
+
+    /// Pixel scaled strikeout position & thickness, if the font defines them.
+    #[inline]
+    fn strikeout_metrics(&self) -> Option<DecorationMetrics> {
+        let scale = self.v_scale_factor();
+        self.font()
+            .strikeout_metrics_unscaled()
+            .map(|m| DecorationMetrics {
+                position: m.position * scale,
+                thickness: m.thickness * scale,
+            })
+    }
+
+    /// Pixel scaled vertical layout ascent, from a column's center line to its right edge.
+    ///
+    /// See [`Font::vertical_ascent_unscaled`](trait.Font.html#method.vertical_ascent_unscaled).
+    #[inline]
+    fn vertical_ascent(&self) -> f32 {
+        self.h_scale_factor() * self.font().vertical_ascent_unscaled()
+    }
+
+    /// Pixel scaled vertical layout descent, from a column's center line to its left edge.
+    #[inline]
+    fn vertical_descent(&self) -> f32 {
+        self.h_scale_factor() * self.font().vertical_descent_unscaled()
+    }
+
+    /// Pixel scaled vertical layout line gap, the space between columns.
+    #[inline]
+    fn vertical_line_gap(&self) -> f32 {
+        self.h_scale_factor() * self.font().vertical_line_gap_unscaled()
+    }
+
     /// Lookup a `GlyphId` matching a given `char`.
     #[inline]
     fn glyph_id(&self, c: char) -> GlyphId {
@@ -141,22 +389,100 @@ pub trait ScaleFont<F: Font> {
         let font = self.font();
         let glyph_id = font.glyph_id(c);
         let relative = font.relative_scale(glyph_id);
-        let mut scale = self.scale();
-        scale.x *= relative;
-        scale.y *= relative;
-        let mut glyph = glyph_id.with_scale(scale);
+        let mut glyph = glyph_id.with_scale(self.scale() * relative);
         glyph.is_colored = font.has_color(glyph_id);
         glyph
     }
 
     /// Pixel scaled horizontal advance for a given glyph.
+    ///
+    /// At a uniform whole number pixels-per-em the font's
+    /// [device advance](trait.Font.html#method.device_h_advance) is used, if it has one,
+    /// matching legacy rasterizers.
     #[inline]
     fn h_advance(&self, id: GlyphId) -> f32 {
         let font = self.font();
         let relative = font.relative_scale(id);
+        if relative == 1.0 {
+            if let Some(advance) = whole_ppem(self).and_then(|ppem| font.device_h_advance(id, ppem))
+            {
+                return advance;
+            }
+        }
         self.h_scale_factor() * font.h_advance_unscaled(id) * relative
     }
 
+    /// Writes the pixel scaled horizontal advance of each glyph of `ids` into the matching
+    /// element of `out`. Only the first `ids.len().min(out.len())` advances are written.
+    ///
+    /// See [`Font::h_advances_unscaled`](trait.Font.html#method.h_advances_unscaled).
+    #[inline]
+    fn h_advances(&self, ids: &[GlyphId], out: &mut [f32]) {
+        let font = self.font();
+        font.h_advances_unscaled(ids, out);
+        let h_factor = self.h_scale_factor();
+        let ppem = whole_ppem(self);
+        for (id, advance) in ids.iter().zip(out) {
+            let relative = font.relative_scale(*id);
+            *advance = match ppem.filter(|_| relative == 1.0) {
+                Some(ppem) => font
+                    .device_h_advance(*id, ppem)
+                    .unwrap_or(*advance * h_factor),
+                None => *advance * h_factor * relative,
+            };
+        }
+    }
+
+    /// Measures a single line of `text` positioned from the origin with kerning, looking up
+    /// all glyph advances in one batch. Line breaks are not applied.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, ScaleFont};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?;
+    /// let scaled_font = font.as_scaled(24.0);
+    ///
+    /// let metrics = scaled_font.measure_str("AV");
+    /// let (a, v) = (font.glyph_id('A'), font.glyph_id('V'));
+    /// let expected = scaled_font.h_advance(a) + scaled_font.kern(a, v) + scaled_font.h_advance(v);
+    /// assert!((metrics.width - expected).abs() < 1e-4);
+    /// assert_eq!(metrics.bounds.min.y, -scaled_font.ascent());
+    /// assert_eq!(metrics.bounds.max.y, -scaled_font.descent());
+    /// # Ok(()) }
+    /// ```
+    fn measure_str(&self, text: &str) -> StrMetrics {
+        let font = self.font();
+        let mut ids = Vec::new();
+        font.glyph_ids_for_str(text, &mut ids);
+        let mut advances = vec![0.0; ids.len()];
+        self.h_advances(&ids, &mut advances);
+
+        let h_factor = self.h_scale_factor();
+        let (mut x, mut left, mut right) = (0.0, 0.0_f32, 0.0_f32);
+        let mut prev = None;
+        for (&id, &advance) in ids.iter().zip(&advances) {
+            if let Some(prev) = prev {
+                x += h_factor * font.kern_unscaled(prev, id);
+            }
+            let side_bearing =
+                h_factor * font.h_side_bearing_unscaled(id) * font.relative_scale(id);
+            left = left.min(x - side_bearing);
+            x += advance;
+            right = right.max(x);
+            prev = Some(id);
+        }
+
+        StrMetrics {
+            width: x,
+            bounds: Rect {
+                min: point(left, -self.ascent()),
+                max: point(right, -self.descent()),
+            },
+        }
+    }
+
     /// Pixel scaled horizontal side bearing for a given glyph.
     #[inline]
     fn h_side_bearing(&self, id: GlyphId) -> f32 {
@@ -175,12 +501,226 @@ pub trait ScaleFont<F: Font> {
         self.v_scale_factor() * self.font().v_side_bearing_unscaled(id)
     }
 
+    /// Pixel offset from a vertical layout caret, on a column's center line at the top of
+    /// the glyph's vertical advance, to the glyph's position.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let scaled_font = font.as_scaled(24.0);
+    ///
+    /// // a column right of x = 0
+    /// let column_width = scaled_font.vertical_ascent() - scaled_font.vertical_descent();
+    /// let mut caret = point(-scaled_font.vertical_descent(), 0.0);
+    /// let mut last = None;
+    /// let mut glyphs = Vec::new();
+    /// for c in "ab".chars() {
+    ///     let mut glyph = scaled_font.scaled_glyph(c);
+    ///     caret.y += scaled_font.v_pair_advance(last, glyph.id);
+    ///     glyph.position = caret + scaled_font.v_origin(glyph.id);
+    ///     last = Some(glyph.id);
+    ///     glyphs.push(glyph);
+    /// }
+    ///
+    /// // glyphs are centered in the column, stacked top to bottom
+    /// let a = scaled_font.outline_glyph(glyphs[0].clone()).unwrap().px_bounds();
+    /// let b = scaled_font.outline_glyph(glyphs[1].clone()).unwrap().px_bounds();
+    /// assert!(a.min.x > 0.0 && a.max.x < column_width);
+    /// assert!(a.max.y <= b.min.y);
+    /// ```
+    #[inline]
+    fn v_origin(&self, id: GlyphId) -> Point {
+        point(
+            -self.h_advance(id) / 2.0,
+            self.v_scale_factor() * self.font().v_origin_unscaled(id),
+        )
+    }
+
+    /// Pixel scaled left side bearing, from the glyph origin to the left edge of the
+    /// outline bounds.
+    ///
+    /// See [`Font::lsb_unscaled`](trait.Font.html#method.lsb_unscaled).
+    #[inline]
+    fn lsb(&self, id: GlyphId) -> f32 {
+        let font = self.font();
+        self.h_scale_factor() * font.lsb_unscaled(id) * font.relative_scale(id)
+    }
+
+    /// Pixel scaled right side bearing, from the right edge of the outline bounds to the
+    /// advance.
+    ///
+    /// Consistent with [`lsb`](#method.lsb) & [`h_advance`](#method.h_advance), so the
+    /// pixel ink width of a glyph is `h_advance - lsb - rsb`.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let scaled_font = font.as_scaled(24.0);
+    /// let glyph = scaled_font.scaled_glyph('w');
+    /// let id = glyph.id;
+    /// let bounds = font.outline_glyph(glyph).unwrap().px_bounds();
+    ///
+    /// let ink_width = scaled_font.h_advance(id) - scaled_font.lsb(id) - scaled_font.rsb(id);
+    /// assert!((bounds.width() - ink_width).abs() < 2.0);
+    /// ```
+    #[inline]
+    fn rsb(&self, id: GlyphId) -> f32 {
+        let font = self.font();
+        self.h_scale_factor() * font.rsb_unscaled(id) * font.relative_scale(id)
+    }
+
     /// Returns additional pixel scaled kerning to apply for a particular pair of glyphs.
     #[inline]
     fn kern(&self, first: GlyphId, second: GlyphId) -> f32 {
         self.h_scale_factor() * self.font().kern_unscaled(first, second)
     }
 
+    /// Returns additional pixel scaled kerning to apply along the vertical advance for a
+    /// particular pair of glyphs, for vertical layout.
+    #[inline]
+    fn v_kern(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.v_scale_factor() * self.font().v_kern_unscaled(first, second)
+    }
+
+    /// Pixel scaled horizontal offset from a `prev` glyph's position to the `next` glyph,
+    /// i.e. `h_advance(prev) + kern(prev, next)`. Returns `0.0` for `None`, the first glyph
+    /// of a line.
+    ///
+    /// Combines the advance & kerning lookups with a single scale factor calculation for use
+    /// in layout loops.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let scaled_font = font.as_scaled(24.0);
+    ///
+    /// let mut caret = point(0.0, 20.0);
+    /// let mut last = None;
+    /// for c in "AV".chars() {
+    ///     let mut glyph = scaled_font.scaled_glyph(c);
+    ///     caret.x += scaled_font.pair_advance(last, glyph.id);
+    ///     glyph.position = caret;
+    ///     last = Some(glyph.id);
+    /// }
+    ///
+    /// let (a, v) = (font.glyph_id('A'), font.glyph_id('V'));
+    /// assert_eq!(scaled_font.pair_advance(None, a), 0.0);
+    /// assert!((caret.x - (scaled_font.h_advance(a) + scaled_font.kern(a, v))).abs() < 1e-4);
+    /// ```
+    #[inline]
+    fn pair_advance(&self, prev: Option<GlyphId>, next: GlyphId) -> f32 {
+        match prev {
+            Some(prev) => {
+                let font = self.font();
+                let advance = font.h_advance_unscaled(prev) * font.relative_scale(prev);
+                self.h_scale_factor() * (advance + font.kern_unscaled(prev, next))
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Pixel scaled vertical offset from a `prev` glyph's position to the `next` glyph for
+    /// vertical layout, i.e. `v_advance(prev) + v_kern(prev, next)`. Returns `0.0` for
+    /// `None`, the first glyph of a column.
+    ///
+    /// The vertical counterpart of [`pair_advance`](#method.pair_advance).
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf")).unwrap();
+    /// let scaled_font = font.as_scaled(24.0);
+    ///
+    /// let mut caret = point(20.0, 0.0);
+    /// let mut last = None;
+    /// for c in "abc".chars() {
+    ///     let mut glyph = scaled_font.scaled_glyph(c);
+    ///     caret.y += scaled_font.v_pair_advance(last, glyph.id);
+    ///     glyph.position = caret;
+    ///     last = Some(glyph.id);
+    /// }
+    ///
+    /// let (a, b) = (font.glyph_id('a'), font.glyph_id('b'));
+    /// let expected = scaled_font.v_advance(a) + scaled_font.v_advance(b);
+    /// assert!((caret.y - expected).abs() < 1e-4);
+    ///
+    /// // without vertical metrics glyphs advance by the line height
+    /// assert!((scaled_font.v_advance(a) - scaled_font.height()).abs() < 1e-4);
+    /// ```
+    #[inline]
+    fn v_pair_advance(&self, prev: Option<GlyphId>, next: GlyphId) -> f32 {
+        match prev {
+            Some(prev) => {
+                let font = self.font();
+                let advance = font.v_advance_unscaled(prev) + font.v_kern_unscaled(prev, next);
+                self.v_scale_factor() * advance
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Pixel scaled optical bounds for a given glyph, if the font has them.
+    ///
+    /// See [`OpticalBounds`](struct.OpticalBounds.html).
+    #[inline]
+    fn optical_bounds(&self, id: GlyphId) -> Option<OpticalBounds> {
+        let bounds = self.font().optical_bounds_unscaled(id)?;
+        let (h_factor, v_factor) = (self.h_scale_factor(), self.v_scale_factor());
+        Some(OpticalBounds {
+            left: bounds.left * h_factor,
+            top: bounds.top * v_factor,
+            right: bounds.right * h_factor,
+            bottom: bounds.bottom * v_factor,
+        })
+    }
+
+    /// Ratio of a glyph's ink, its rasterized coverage, to its advance box of
+    /// [`h_advance`](#method.h_advance) by [`height`](#method.height) pixels.
+    ///
+    /// Useful to rank characters by darkness, e.g. for ASCII art or density based
+    /// visualizations. Coverage is rasterized at this scale, so includes anti-aliasing.
+    /// Color glyph layers are combined. Returns `0.0` for glyphs without an outline or advance.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, ScaleFont};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let font = font.as_scaled(24.0);
+    /// let ink = |c| font.ink_ratio(font.glyph_id(c));
+    ///
+    /// assert_eq!(ink(' '), 0.0);
+    /// assert!(ink('.') > 0.0 && ink('.') < ink('B'));
+    /// assert!(ink('B') < 1.0);
+    /// # Ok(()) }
+    /// ```
+    fn ink_ratio(&self, id: GlyphId) -> f32 {
+        let box_area = self.h_advance(id) * self.height();
+        if box_area <= 0.0 {
+            return 0.0;
+        }
+        let relative = self.font().relative_scale(id);
+        let outlined = match self.outline_glyph(id.with_scale(self.scale() * relative)) {
+            Some(outlined) => outlined,
+            None => return 0.0,
+        };
+
+        let bounds = outlined.px_bounds();
+        let width = bounds.width() as usize;
+        let mut coverage = vec![0.0_f32; width * bounds.height() as usize];
+        for layer in 0..outlined.get_colored_layers().unwrap_or(1) {
+            outlined.draw(layer, |x, y, c| {
+                let px = &mut coverage[y as usize * width + x as usize];
+                *px = px.max(c.min(1.0));
+            });
+        }
+        coverage.iter().sum::<f32>() / box_area
+    }
+
     /// Returns the layout bounds of this glyph. These are different to the outline `px_bounds()`.
     ///
     /// Horizontally: Glyph position +/- h_advance/h_side_bearing.
@@ -210,6 +750,27 @@ pub trait ScaleFont<F: Font> {
     }
 }
 
+/// Returns the pixels-per-em of a uniform scale if it is a whole number.
+pub(crate) fn whole_ppem<F: Font, SF: ScaleFont<F> + ?Sized>(scaled: &SF) -> Option<u16> {
+    let scale = scaled.scale();
+    if scale.x != scale.y {
+        return None;
+    }
+    let ppem = scale.y * scaled.font().units_per_em()? / scaled.font().height_unscaled();
+    let rounded = ppem.round();
+    if (ppem - rounded).abs() < 1e-3 && rounded >= 1.0 && rounded <= f32::from(u16::MAX) {
+        Some(rounded as u16)
+    } else {
+        None
+    }
+}
+
+/// Returns the font's device vertical metrics at a uniform whole number pixels-per-em.
+#[inline]
+fn device_v_metrics<F: Font, SF: ScaleFont<F> + ?Sized>(scaled: &SF) -> Option<DeviceVMetrics> {
+    scaled.font().device_v_metrics(whole_ppem(scaled)?)
+}
+
 impl<F: Font, SF: ScaleFont<F>> ScaleFont<F> for &SF {
     #[inline]
     fn scale(&self) -> PxScale {
@@ -220,6 +781,46 @@ impl<F: Font, SF: ScaleFont<F>> ScaleFont<F> for &SF {
     fn font(&self) -> &F {
         (*self).font()
     }
+
+    #[inline]
+    fn h_scale_factor(&self) -> f32 {
+        (*self).h_scale_factor()
+    }
+
+    #[inline]
+    fn v_scale_factor(&self) -> f32 {
+        (*self).v_scale_factor()
+    }
+
+    #[inline]
+    fn scale_factor(&self) -> PxScaleFactor {
+        (*self).scale_factor()
+    }
+
+    #[inline]
+    fn ascent(&self) -> f32 {
+        (*self).ascent()
+    }
+
+    #[inline]
+    fn descent(&self) -> f32 {
+        (*self).descent()
+    }
+
+    #[inline]
+    fn line_gap(&self) -> f32 {
+        (*self).line_gap()
+    }
+
+    #[inline]
+    fn pair_advance(&self, prev: Option<GlyphId>, next: GlyphId) -> f32 {
+        (*self).pair_advance(prev, next)
+    }
+
+    #[inline]
+    fn v_pair_advance(&self, prev: Option<GlyphId>, next: GlyphId) -> f32 {
+        (*self).v_pair_advance(prev, next)
+    }
 }
 
 /// A [`Font`](trait.Font.html) and an associated pixel scale.
@@ -237,6 +838,26 @@ impl<F> PxScaleFont<F> {
     }
 }
 
+impl<F: Font> PxScaleFont<F> {
+    /// Compute the scale factor, ascent, descent & line gap once, rather than on each call.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, ScaleFont};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let scaled_font = font.as_scaled(24.0).precompute();
+    ///
+    /// assert_eq!(scaled_font.descent(), -4.02);
+    /// assert_eq!(scaled_font.scale_factor(), font.as_scaled(24.0).scale_factor());
+    /// # Ok(()) }
+    /// ```
+    pub fn precompute(self) -> PrecomputedScaleFont<F> {
+        PrecomputedScaleFont::from(self)
+    }
+}
+
 impl<F: Font> ScaleFont<F> for PxScaleFont<F> {
     #[inline]
     fn scale(&self) -> PxScale {
@@ -248,3 +869,83 @@ impl<F: Font> ScaleFont<F> for PxScaleFont<F> {
         &self.font
     }
 }
+
+/// A [`Font`](trait.Font.html) and an associated pixel scale with the scale factor & vertical
+/// metrics computed once. Constructed with
+/// [`PxScaleFont::precompute`](struct.PxScaleFont.html#method.precompute).
+#[derive(Clone, Copy, Debug)]
+pub struct PrecomputedScaleFont<F> {
+    font: F,
+    scale: PxScale,
+    scale_factor: PxScaleFactor,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+}
+
+impl<F> PrecomputedScaleFont<F> {
+    /// Returns the inner `PxScaleFont`.
+    #[inline]
+    pub fn into_inner(self) -> PxScaleFont<F> {
+        PxScaleFont {
+            font: self.font,
+            scale: self.scale,
+        }
+    }
+}
+
+impl<F: Font> From<PxScaleFont<F>> for PrecomputedScaleFont<F> {
+    fn from(scaled: PxScaleFont<F>) -> Self {
+        let scale_factor = scaled.scale_factor();
+        Self {
+            ascent: scaled.ascent(),
+            descent: scaled.descent(),
+            line_gap: scaled.line_gap(),
+            scale_factor,
+            font: scaled.font,
+            scale: scaled.scale,
+        }
+    }
+}
+
+impl<F: Font> ScaleFont<F> for PrecomputedScaleFont<F> {
+    #[inline]
+    fn scale(&self) -> PxScale {
+        self.scale
+    }
+
+    #[inline]
+    fn font(&self) -> &F {
+        &self.font
+    }
+
+    #[inline]
+    fn h_scale_factor(&self) -> f32 {
+        self.scale_factor.horizontal
+    }
+
+    #[inline]
+    fn v_scale_factor(&self) -> f32 {
+        self.scale_factor.vertical
+    }
+
+    #[inline]
+    fn scale_factor(&self) -> PxScaleFactor {
+        self.scale_factor
+    }
+
+    #[inline]
+    fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    #[inline]
+    fn descent(&self) -> f32 {
+        self.descent
+    }
+
+    #[inline]
+    fn line_gap(&self) -> f32 {
+        self.line_gap
+    }
+}
@@ -0,0 +1,189 @@
+/// OpenType script & language system used to select glyph substitutions.
+///
+/// See [`Font::substitute`](trait.Font.html#method.substitute) &
+/// [`Layout::script`](struct.Layout.html#structfield.script).
+///
+/// # Example
+/// ```
+/// use ab_glyph::Script;
+///
+/// let turkish = Script::new(b"latn").with_language(b"TRK ");
+/// assert_eq!(turkish.script, *b"latn");
+/// assert_eq!(turkish.language, Some(*b"TRK "));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Script {
+    /// OpenType script tag, e.g. `*b"latn"` or `*b"cyrl"`.
+    pub script: [u8; 4],
+    /// OpenType language system tag, e.g. `*b"TRK "`, or `None` for the script's default
+    /// language system.
+    pub language: Option<[u8; 4]>,
+}
+
+impl Script {
+    /// Features applied to all text of any script, glyph composition/decomposition,
+    /// localized forms & required ligatures.
+    ///
+    /// The required feature of the script's language system is also always applied.
+    pub const REQUIRED_FEATURES: [[u8; 4]; 3] = [*b"ccmp", *b"locl", *b"rlig"];
+
+    /// Standard & contextual ligature features, e.g. "fi" & "ffi" ligatures.
+    ///
+    /// See [`Layout::ligatures`](struct.Layout.html#structfield.ligatures).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{point, Font, FontRef, Layout, Script};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// let mut glyphs: Vec<_> = "ffi".chars().map(|c| font.glyph_id(c)).collect();
+    /// font.substitute(Script::default(), &Script::LIGATURE_FEATURES, &mut glyphs);
+    /// assert_eq!(glyphs.len(), 1);
+    ///
+    /// let layout = Layout {
+    ///     ligatures: true,
+    ///     ..Layout::default()
+    /// };
+    /// let mut laid_out = Vec::new();
+    /// layout.layout(font.as_scaled(24.0), point(0.0, 0.0), "office", &mut laid_out);
+    /// assert_eq!(laid_out.len(), 4);
+    /// assert_eq!(laid_out[1].id, glyphs[0]);
+    /// # Ok(()) }
+    /// ```
+    pub const LIGATURE_FEATURES: [[u8; 4]; 2] = [*b"liga", *b"clig"];
+
+    /// Script with the default language system.
+    #[inline]
+    pub const fn new(script: &[u8; 4]) -> Self {
+        Self {
+            script: *script,
+            language: None,
+        }
+    }
+
+    /// Returns this script with the given language system.
+    #[inline]
+    pub const fn with_language(self, language: &[u8; 4]) -> Self {
+        Self {
+            script: self.script,
+            language: Some(*language),
+        }
+    }
+
+    /// Returns the script & language system of a BCP 47 language tag, e.g. `"tr"`,
+    /// `"sr-Latn"` or `"bg-BG"`, selecting language specific glyph forms, i.e. `locl`
+    /// substitutions.
+    ///
+    /// An explicit script subtag overrides the language's usual script. Returns `None` for
+    /// unknown languages.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, Layout, Script};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let turkish = Script::new(b"latn").with_language(b"TRK ");
+    /// assert_eq!(Script::from_language_tag("tr-TR"), Some(turkish));
+    ///
+    /// let serbian_latin = Script::new(b"latn").with_language(b"SRB ");
+    /// assert_eq!(Script::from_language_tag("sr-Latn"), Some(serbian_latin));
+    ///
+    /// // serbian localized cyrillic be
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/DejaVuSansMono.ttf"))?;
+    /// let serbian = Script::from_language_tag("sr").unwrap();
+    /// let mut glyphs = vec![font.glyph_id('б')];
+    /// font.substitute(serbian, &Script::REQUIRED_FEATURES, &mut glyphs);
+    /// assert_ne!(glyphs, [font.glyph_id('б')]);
+    ///
+    /// let bulgarian_layout = Layout {
+    ///     script: Script::from_language_tag("bg"),
+    ///     ..Layout::default()
+    /// };
+    /// # Ok(()) }
+    /// ```
+    pub fn from_language_tag(tag: &str) -> Option<Self> {
+        let mut subtags = tag.split(['-', '_']);
+        let language = subtags.next()?;
+        let &(_, script, language) = LANGUAGES
+            .iter()
+            .find(|(code, ..)| code.eq_ignore_ascii_case(language))?;
+
+        let script = match subtags.next().map(str::as_bytes) {
+            Some(&[a, b, c, d]) if [a, b, c, d].iter().all(u8::is_ascii_alphabetic) => [
+                a.to_ascii_lowercase(),
+                b.to_ascii_lowercase(),
+                c.to_ascii_lowercase(),
+                d.to_ascii_lowercase(),
+            ],
+            _ => script,
+        };
+        Some(Self {
+            script,
+            language: Some(language),
+        })
+    }
+}
+
+/// ISO 639 language codes with their usual OpenType script & language system tags.
+const LANGUAGES: [(&str, [u8; 4], [u8; 4]); 50] = [
+    ("af", *b"latn", *b"AFK "),
+    ("ar", *b"arab", *b"ARA "),
+    ("az", *b"latn", *b"AZE "),
+    ("ba", *b"cyrl", *b"BSH "),
+    ("be", *b"cyrl", *b"BEL "),
+    ("bg", *b"cyrl", *b"BGR "),
+    ("ca", *b"latn", *b"CAT "),
+    ("crh", *b"latn", *b"CRT "),
+    ("cs", *b"latn", *b"CSY "),
+    ("cy", *b"latn", *b"WEL "),
+    ("da", *b"latn", *b"DAN "),
+    ("de", *b"latn", *b"DEU "),
+    ("el", *b"grek", *b"ELL "),
+    ("en", *b"latn", *b"ENG "),
+    ("es", *b"latn", *b"ESP "),
+    ("et", *b"latn", *b"ETI "),
+    ("eu", *b"latn", *b"EUQ "),
+    ("fa", *b"arab", *b"FAR "),
+    ("fi", *b"latn", *b"FIN "),
+    ("fr", *b"latn", *b"FRA "),
+    ("ga", *b"latn", *b"IRI "),
+    ("gl", *b"latn", *b"GAL "),
+    ("he", *b"hebr", *b"IWR "),
+    ("hr", *b"latn", *b"HRV "),
+    ("hu", *b"latn", *b"HUN "),
+    ("is", *b"latn", *b"ISL "),
+    ("it", *b"latn", *b"ITA "),
+    ("kk", *b"cyrl", *b"KAZ "),
+    ("ky", *b"cyrl", *b"KIR "),
+    ("lt", *b"latn", *b"LTH "),
+    ("lv", *b"latn", *b"LVI "),
+    ("mk", *b"cyrl", *b"MKD "),
+    ("mn", *b"cyrl", *b"MNG "),
+    ("mo", *b"latn", *b"MOL "),
+    ("nb", *b"latn", *b"NOR "),
+    ("nl", *b"latn", *b"NLD "),
+    ("nn", *b"latn", *b"NYN "),
+    ("pl", *b"latn", *b"PLK "),
+    ("pt", *b"latn", *b"PTG "),
+    ("ro", *b"latn", *b"ROM "),
+    ("ru", *b"cyrl", *b"RUS "),
+    ("sk", *b"latn", *b"SKY "),
+    ("sl", *b"latn", *b"SLV "),
+    ("sq", *b"latn", *b"SQI "),
+    ("sr", *b"cyrl", *b"SRB "),
+    ("sv", *b"latn", *b"SVE "),
+    ("tr", *b"latn", *b"TRK "),
+    ("tt", *b"cyrl", *b"TAT "),
+    ("uk", *b"cyrl", *b"UKR "),
+    ("vi", *b"latn", *b"VIT "),
+];
+
+impl Default for Script {
+    /// Default script, `"DFLT"`.
+    #[inline]
+    fn default() -> Self {
+        Self::new(b"DFLT")
+    }
+}
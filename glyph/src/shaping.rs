@@ -0,0 +1,369 @@
+//! Text shaping with [rustybuzz](https://docs.rs/rustybuzz), requires the `shaping` feature.
+//!
+//! Shaping applies the font's OpenType layout, e.g. ligatures, mark positioning & the
+//! contextual forms of complex scripts like Arabic & Devanagari, producing positioned
+//! [`Glyph`](../struct.Glyph.html)s ready to outline & draw like any other.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::{point, shaping, Font, FontRef};
+//!
+//! # fn main() -> Result<(), ab_glyph::InvalidFont> {
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+//!
+//! // baseline starting at (10, 30)
+//! let glyphs = shaping::shape(&font, 24.0, point(10.0, 30.0), "Hello");
+//! assert_eq!(glyphs.len(), 5);
+//! assert_eq!(glyphs[0].id, font.glyph_id('H'));
+//! assert_eq!(glyphs[0].position, point(10.0, 30.0));
+//! assert!(glyphs[1].position.x > 10.0);
+//!
+//! for glyph in glyphs {
+//!     if let Some(outlined) = font.outline_glyph(glyph) {
+//!         outlined.draw(0, |x, y, c| { /* draw pixel `(x, y)` with coverage: `c` */ });
+//!     }
+//! }
+//! # Ok(()) }
+//! ```
+use crate::{outlined::Fnv1a, point, Font, FontRef, Glyph, GlyphId, Point, PxScale, ScaleFont};
+use core::fmt;
+use std::collections::HashMap;
+
+/// OpenType feature setting applied to the whole text, e.g. `Feature::new(b"liga", 0)` to
+/// disable standard ligatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Feature {
+    /// Feature tag, e.g. `*b"smcp"`.
+    pub tag: [u8; 4],
+    /// Feature value, `0` disables, `1` enables & higher values select alternates.
+    pub value: u32,
+}
+
+impl Feature {
+    /// Creates a feature setting from its tag & value.
+    #[inline]
+    pub fn new(tag: &[u8; 4], value: u32) -> Self {
+        Self { tag: *tag, value }
+    }
+}
+
+/// Shapes `text` into glyphs at `scale`, with the first glyph origin at `position` on the
+/// baseline, applying the font's default features.
+///
+/// Direction, script & language are guessed from the text. Returns no glyphs if rustybuzz
+/// fails to parse the font, or the font has no [data](../struct.FontRef.html#method.as_slice).
+///
+/// This parses the font for rustybuzz on each call, use a [`Shaper`](struct.Shaper.html)
+/// to shape many texts.
+#[inline]
+pub fn shape<S: Into<PxScale>>(
+    font: &FontRef<'_>,
+    scale: S,
+    position: Point,
+    text: &str,
+) -> Vec<Glyph> {
+    shape_with_features(font, scale, position, text, &[])
+}
+
+/// Shapes `text` into glyphs like [`shape`](fn.shape.html), with `features` enabling,
+/// disabling or selecting alternates of OpenType features.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, shaping::{self, Feature}, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?;
+/// let features = [Feature::new(b"kern", 0)];
+/// let glyphs = shaping::shape_with_features(&font, 18.0, point(0.0, 20.0), "AV", &features);
+/// assert_eq!(glyphs.len(), 2);
+/// # Ok(()) }
+/// ```
+pub fn shape_with_features<S: Into<PxScale>>(
+    font: &FontRef<'_>,
+    scale: S,
+    position: Point,
+    text: &str,
+    features: &[Feature],
+) -> Vec<Glyph> {
+    match Shaper::new(font) {
+        Some(shaper) => shaper.shape_with_features(scale, position, text, features),
+        None => Vec::new(),
+    }
+}
+
+/// A font prepared for shaping, parsing the font for rustybuzz once to shape any number
+/// of texts.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, shaping::Shaper, Font, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let shaper = Shaper::new(&font).expect("rustybuzz face");
+///
+/// let hello = shaper.shape(24.0, point(10.0, 30.0), "Hello");
+/// let world = shaper.shape(24.0, point(10.0, 60.0), "World");
+/// assert_eq!(hello.len(), 5);
+/// assert_eq!(world[0].id, font.glyph_id('W'));
+/// # Ok(()) }
+/// ```
+pub struct Shaper<'font> {
+    font: FontRef<'font>,
+    face: rustybuzz::Face<'font>,
+}
+
+impl fmt::Debug for Shaper<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shaper").field("font", &self.font).finish()
+    }
+}
+
+impl<'font> Shaper<'font> {
+    /// Prepares `font` for shaping.
+    ///
+    /// Returns `None` if rustybuzz fails to parse the font, or the font has no
+    /// [data](../struct.FontRef.html#method.as_slice).
+    pub fn new(font: &FontRef<'font>) -> Option<Self> {
+        let face = rustybuzz::Face::from_slice(font.as_slice()?, font.index())?;
+        Some(Self {
+            font: font.clone(),
+            face,
+        })
+    }
+
+    /// Returns the font being shaped.
+    #[inline]
+    pub fn font(&self) -> &FontRef<'font> {
+        &self.font
+    }
+
+    /// Shapes `text` into glyphs at `scale`, with the first glyph origin at `position` on
+    /// the baseline, applying the font's default features.
+    ///
+    /// Direction, script & language are guessed from the text.
+    #[inline]
+    pub fn shape<S: Into<PxScale>>(&self, scale: S, position: Point, text: &str) -> Vec<Glyph> {
+        self.shape_with_features(scale, position, text, &[])
+    }
+
+    /// Shapes `text` into glyphs like [`Shaper::shape`](#method.shape), with `features`
+    /// enabling, disabling or selecting alternates of OpenType features.
+    pub fn shape_with_features<S: Into<PxScale>>(
+        &self,
+        scale: S,
+        position: Point,
+        text: &str,
+        features: &[Feature],
+    ) -> Vec<Glyph> {
+        let features: Vec<_> = features
+            .iter()
+            .map(|f| rustybuzz::Feature::new(rustybuzz::Tag::from_bytes(&f.tag), f.value, ..))
+            .collect();
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.guess_segment_properties();
+        let shaped = rustybuzz::shape(&self.face, &features, buffer);
+
+        // positions are in unscaled font units, y up
+        let scale = scale.into();
+        let scaled = self.font.as_scaled(scale);
+        let (h_factor, v_factor) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+
+        let mut caret = position;
+        shaped
+            .glyph_infos()
+            .iter()
+            .zip(shaped.glyph_positions())
+            .map(|(info, pos)| {
+                let offset = point(
+                    pos.x_offset as f32 * h_factor,
+                    -pos.y_offset as f32 * v_factor,
+                );
+                let glyph =
+                    GlyphId(info.codepoint as u16).with_scale_and_position(scale, caret + offset);
+                caret.x += pos.x_advance as f32 * h_factor;
+                caret.y -= pos.y_advance as f32 * v_factor;
+                glyph
+            })
+            .collect()
+    }
+}
+
+/// Cache of shaped text, evicting the least recently used when over capacity.
+///
+/// UI frameworks often lay out the same strings every frame, where shaping would dominate.
+/// Results are keyed by a hash of the text, [font fingerprint](../trait.Font.html#method.fingerprint),
+/// scale & features, with glyphs positioned relative to a `(0, 0)` origin on the baseline.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, shaping::{ShapeCache, Shaper}, FontRef};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let shaper = Shaper::new(&font).expect("rustybuzz face");
+/// let mut cache = ShapeCache::new(2);
+///
+/// let glyphs = cache.get_or_shape(&shaper, 24.0, "Hello", &[]);
+/// assert_eq!(glyphs.len(), 5);
+///
+/// // draw at (10, 30)
+/// let position = point(10.0, 30.0);
+/// for glyph in glyphs {
+///     let glyph = glyph.id.with_scale_and_position(glyph.scale, glyph.position + position);
+///     /* outline & draw */
+/// }
+///
+/// cache.get_or_shape(&shaper, 24.0, "World", &[]);
+/// cache.get_or_shape(&shaper, 24.0, "Hello", &[]);
+/// // "World" is least recently used, so is evicted
+/// cache.get_or_shape(&shaper, 24.0, "!", &[]);
+/// assert_eq!(cache.len(), 2);
+/// assert!(cache.is_cached(&shaper, 24.0, "Hello", &[]));
+/// assert!(!cache.is_cached(&shaper, 24.0, "World", &[]));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ShapeCache {
+    capacity: usize,
+    /// Entries by key hash.
+    entries: HashMap<u64, ShapeEntry>,
+    tick: u64,
+}
+
+#[derive(Clone, Debug)]
+struct ShapeEntry {
+    fingerprint: u64,
+    scale: PxScale,
+    features: Vec<Feature>,
+    text: Box<str>,
+    glyphs: Vec<Glyph>,
+    /// Tick of the last use.
+    used: u64,
+}
+
+impl ShapeEntry {
+    #[inline]
+    fn matches(&self, fingerprint: u64, scale: PxScale, text: &str, features: &[Feature]) -> bool {
+        self.fingerprint == fingerprint
+            && self.scale == scale
+            && &*self.text == text
+            && self.features == features
+    }
+}
+
+impl ShapeCache {
+    /// Creates an empty cache holding the shaped glyphs of up to `capacity` texts, at
+    /// least `1`.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the cached glyphs of `text`, otherwise shapes & caches them, see
+    /// [`Shaper::shape_with_features`](struct.Shaper.html#method.shape_with_features).
+    ///
+    /// Glyphs are positioned relative to a `(0, 0)` origin on the baseline.
+    pub fn get_or_shape<S: Into<PxScale>>(
+        &mut self,
+        shaper: &Shaper<'_>,
+        scale: S,
+        text: &str,
+        features: &[Feature],
+    ) -> &[Glyph] {
+        let (fingerprint, scale) = (shaper.font.fingerprint(), scale.into());
+        let key = key(fingerprint, scale, text, features);
+        self.tick += 1;
+
+        let cached = self
+            .entries
+            .get(&key)
+            .is_some_and(|e| e.matches(fingerprint, scale, text, features));
+        if !cached {
+            if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.used)
+                    .map(|(key, _)| *key);
+                if let Some(oldest) = oldest {
+                    self.entries.remove(&oldest);
+                }
+            }
+            let glyphs = shaper.shape_with_features(scale, point(0.0, 0.0), text, features);
+            let entry = ShapeEntry {
+                fingerprint,
+                scale,
+                features: features.to_vec(),
+                text: text.into(),
+                glyphs,
+                used: 0,
+            };
+            // hash collisions replace the colliding entry
+            self.entries.insert(key, entry);
+        }
+
+        let entry = self.entries.get_mut(&key).expect("inserted");
+        entry.used = self.tick;
+        &entry.glyphs
+    }
+
+    /// Returns `true` if the shaped glyphs of `text` are cached.
+    pub fn is_cached<S: Into<PxScale>>(
+        &self,
+        shaper: &Shaper<'_>,
+        scale: S,
+        text: &str,
+        features: &[Feature],
+    ) -> bool {
+        let (fingerprint, scale) = (shaper.font.fingerprint(), scale.into());
+        self.entries
+            .get(&key(fingerprint, scale, text, features))
+            .is_some_and(|e| e.matches(fingerprint, scale, text, features))
+    }
+
+    /// Returns the maximum number of texts cached.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of texts cached.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all cached texts.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Hashes the shaping inputs.
+fn key(fingerprint: u64, scale: PxScale, text: &str, features: &[Feature]) -> u64 {
+    let mut hash = Fnv1a::default();
+    hash.bytes(&fingerprint.to_le_bytes());
+    hash.bytes(&scale.x.to_bits().to_le_bytes());
+    hash.bytes(&scale.y.to_bits().to_le_bytes());
+    for feature in features {
+        hash.bytes(&feature.tag);
+        hash.bytes(&feature.value.to_le_bytes());
+    }
+    hash.bytes(text.as_bytes());
+    hash.0
+}
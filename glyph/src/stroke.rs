@@ -0,0 +1,260 @@
+use crate::{
+    outlined::{flatten, PxTransform},
+    point, Outline, OutlineCurve, Point, PxScaleFactor, Rect,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Shape of the ends of stroked open contours, see
+/// [`Outline::stroke`](struct.Outline.html#method.stroke).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /// Ends flat at the contour end points.
+    Butt,
+    /// Ends flat, extended past the end points by half the stroke width.
+    Square,
+    /// Ends with a half circle.
+    Round,
+}
+
+impl Default for LineCap {
+    #[inline]
+    fn default() -> Self {
+        Self::Butt
+    }
+}
+
+/// Shape of stroked corners, see [`Outline::stroke`](struct.Outline.html#method.stroke).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /// Sharp corners, beveled when longer than 4 times the stroke width.
+    Miter,
+    /// Rounded corners.
+    Round,
+    /// Corners cut straight across.
+    Bevel,
+}
+
+impl Default for LineJoin {
+    #[inline]
+    fn default() -> Self {
+        Self::Miter
+    }
+}
+
+/// Maximum miter length, relative to the stroke width, before miter joins are beveled.
+const MITER_LIMIT: f32 = 4.0;
+
+/// Cosine & sine of the angle between round join & cap points, π / 16.
+const ARC_STEP: (f32, f32) = (0.980_785_3, 0.195_090_32);
+
+impl Outline {
+    /// Returns the outline of a stroke of `width` centred along the contours of this outline,
+    /// e.g. to draw text borders.
+    ///
+    /// `width` is unscaled, like the outline. Curves are flattened to lines within 1/1000 of
+    /// the outline size. Closed contours are stroked to a pair of contours, the outer & inner
+    /// edges, and open contours to a single contour around the stroke including `cap`s.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, LineCap, LineJoin};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let outline = font.outline(font.glyph_id('o')).unwrap();
+    ///
+    /// let stroked = outline.stroke(20.0, LineCap::Butt, LineJoin::Round);
+    /// assert!((stroked.bounds.width() - outline.bounds.width() - 20.0).abs() < 1.0);
+    /// # Ok(()) }
+    /// ```
+    pub fn stroke(&self, width: f32, cap: LineCap, join: LineJoin) -> Outline {
+        let size = self.bounds.width().max(self.bounds.height().abs());
+        let tolerance = (size / 1000.0).max(1e-3);
+        let identity = PxTransform::new(
+            PxScaleFactor {
+                horizontal: 1.0,
+                vertical: -1.0,
+            },
+            point(0.0, 0.0),
+            point(0.0, 0.0),
+        );
+
+        let mut stroker = Stroker {
+            half: width / 2.0,
+            cap,
+            join,
+            curves: Vec::new(),
+        };
+        let mut contour: Vec<Point> = Vec::new();
+        for curve in &self.curves {
+            flatten(curve, &identity, tolerance, |p0, p1| {
+                if contour.last() != Some(&p0) {
+                    stroker.contour(&contour);
+                    contour.clear();
+                    contour.push(p0);
+                }
+                contour.push(p1);
+            });
+        }
+        stroker.contour(&contour);
+
+        let bounds = stroker.bounds();
+        Outline {
+            bounds,
+            curves: stroker.curves,
+        }
+    }
+}
+
+/// Builds stroke contours from flattened contours.
+struct Stroker {
+    half: f32,
+    cap: LineCap,
+    join: LineJoin,
+    curves: Vec<OutlineCurve>,
+}
+
+impl Stroker {
+    /// Strokes a contour of connected points, closed if the last point is the first.
+    fn contour(&mut self, points: &[Point]) {
+        let mut points = points.to_vec();
+        points.dedup();
+        if points.len() < 2 {
+            return;
+        }
+
+        let closed = points.len() > 2 && points.first() == points.last();
+        if closed {
+            points.pop();
+            let n = points.len();
+            // left edge forwards & right edge backwards, so the stroke winds one way
+            let mut left = Vec::new();
+            for (i, &p) in points.iter().enumerate() {
+                self.join(&mut left, points[(i + n - 1) % n], p, points[(i + 1) % n]);
+            }
+            let mut right = Vec::new();
+            for (i, &p) in points.iter().enumerate().rev() {
+                self.join(&mut right, points[(i + 1) % n], p, points[(i + n - 1) % n]);
+            }
+            self.polygon(&left);
+            self.polygon(&right);
+        } else {
+            let mut ring = Vec::new();
+            self.side(&mut ring, &points);
+            points.reverse();
+            self.side(&mut ring, &points);
+            self.polygon(&ring);
+        }
+    }
+
+    /// Adds the left edge of an open contour followed by the cap at its end.
+    fn side(&self, out: &mut Vec<Point>, points: &[Point]) {
+        let n = points.len();
+        out.push(points[0] + left_normal(unit(points[1] - points[0])) * self.half);
+        for w in points.windows(3) {
+            self.join(out, w[0], w[1], w[2]);
+        }
+
+        let (end, u) = (points[n - 1], unit(points[n - 1] - points[n - 2]));
+        let normal = left_normal(u) * self.half;
+        out.push(end + normal);
+        match self.cap {
+            LineCap::Butt => {}
+            LineCap::Square => {
+                out.push(end + normal + u * self.half);
+                out.push(end - normal + u * self.half);
+            }
+            LineCap::Round => {
+                arc(out, end, normal, u * self.half);
+                out.push(end + u * self.half);
+                arc(out, end, u * self.half, -normal);
+            }
+        }
+        out.push(end - normal);
+    }
+
+    /// Adds the left edge points around `p`, joining the lines from `prev` & to `next`.
+    fn join(&self, out: &mut Vec<Point>, prev: Point, p: Point, next: Point) {
+        let (u_in, u_out) = (unit(p - prev), unit(next - p));
+        let (n_in, n_out) = (
+            left_normal(u_in) * self.half,
+            left_normal(u_out) * self.half,
+        );
+        let cross = u_in.x * u_out.y - u_in.y * u_out.x;
+        let dot = u_in.x * u_out.x + u_in.y * u_out.y;
+
+        if cross.abs() < 1e-6 && dot > 0.0 {
+            // straight
+            out.push(p + n_in);
+            return;
+        }
+        if cross > 0.0 {
+            // inner side of a left turn, pivot around `p` so the edges overlap
+            out.push(p + n_in);
+            out.push(p);
+            out.push(p + n_out);
+            return;
+        }
+
+        out.push(p + n_in);
+        match self.join {
+            LineJoin::Bevel => {}
+            LineJoin::Miter => {
+                if (1.0 + dot) * MITER_LIMIT * MITER_LIMIT >= 2.0 {
+                    out.push(p + (n_in + n_out) / (1.0 + dot));
+                }
+            }
+            LineJoin::Round => arc(out, p, n_in, n_out),
+        }
+        out.push(p + n_out);
+    }
+
+    /// Adds a closed contour of lines.
+    fn polygon(&mut self, points: &[Point]) {
+        let n = points.len();
+        for (i, &p0) in points.iter().enumerate() {
+            let p1 = points[(i + 1) % n];
+            if p0 != p1 {
+                self.curves.push(OutlineCurve::Line(p0, p1));
+            }
+        }
+    }
+
+    /// Returns the bounds of the stroke, `min.y` being the top like `Outline::bounds`.
+    fn bounds(&self) -> Rect {
+        let mut points = self.curves.iter().map(|curve| match curve {
+            OutlineCurve::Line(p, _) | OutlineCurve::Quad(p, ..) | OutlineCurve::Cubic(p, ..) => *p,
+        });
+        let init = match points.next() {
+            Some(p) => Rect { min: p, max: p },
+            None => return Rect::default(),
+        };
+        points.fold(init, |r, p| Rect {
+            min: point(r.min.x.min(p.x), r.min.y.max(p.y)),
+            max: point(r.max.x.max(p.x), r.max.y.min(p.y)),
+        })
+    }
+}
+
+/// Adds the points of a clockwise arc around `center`, excluding the ends `from` & `to`
+/// offsets, which must be less than a half turn apart.
+fn arc(out: &mut Vec<Point>, center: Point, from: Point, to: Point) {
+    let (cos, sin) = ARC_STEP;
+    let rotate = |v: Point| point(v.x * cos + v.y * sin, v.y * cos - v.x * sin);
+    let mut v = rotate(from);
+    while v.x * to.y - v.y * to.x < 0.0 {
+        out.push(center + v);
+        v = rotate(v);
+    }
+}
+
+#[inline]
+fn unit(v: Point) -> Point {
+    v / point(0.0, 0.0).distance(v)
+}
+
+#[inline]
+fn left_normal(u: Point) -> Point {
+    point(-u.y, u.x)
+}
@@ -0,0 +1,126 @@
+use crate::{point, OutlinedGlyph, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec;
+
+/// Horizontal filter spreading subpixel coverage onto neighbouring subpixels, reducing the
+/// color fringes of subpixel rendering at the cost of some sharpness.
+///
+/// See [`OutlinedGlyph::draw_subpixel`](struct.OutlinedGlyph.html#method.draw_subpixel).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LcdFilter {
+    /// Weights of the subpixels 2 left to 2 right of each subpixel, summing to `1.0` to
+    /// preserve the total coverage. Default [`LcdFilter::DEFAULT`](#associatedconstant.DEFAULT).
+    pub weights: [f32; 5],
+}
+
+impl LcdFilter {
+    /// FreeType's default FIR filter, a good balance of sharpness & color fringing.
+    pub const DEFAULT: LcdFilter = LcdFilter {
+        weights: [
+            8.0 / 256.0,
+            77.0 / 256.0,
+            86.0 / 256.0,
+            77.0 / 256.0,
+            8.0 / 256.0,
+        ],
+    };
+
+    /// FreeType's light filter, sharper with more color fringing.
+    pub const LIGHT: LcdFilter = LcdFilter {
+        weights: [0.0, 85.0 / 256.0, 86.0 / 256.0, 85.0 / 256.0, 0.0],
+    };
+
+    /// No filtering, each subpixel has just its own coverage.
+    pub const NONE: LcdFilter = LcdFilter {
+        weights: [0.0, 0.0, 1.0, 0.0, 0.0],
+    };
+}
+
+impl Default for LcdFilter {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl OutlinedGlyph {
+    /// Whole number pixel bounding box of the [`draw_subpixel`](#method.draw_subpixel)
+    /// coverage, the pixel bounds expanded by 1 pixel left & right for the filter spread.
+    #[inline]
+    pub fn subpixel_px_bounds(&self) -> Rect {
+        let bounds = self.px_bounds();
+        Rect {
+            min: bounds.min - point(1.0, 0.0),
+            max: bounds.max + point(1.0, 0.0),
+        }
+    }
+
+    /// Draws this glyph outline with RGB subpixel coverage, for LCD screens with horizontal
+    /// red, green, blue subpixel stripes, e.g. ClearType-style terminal & editor text.
+    ///
+    /// The outline is rasterized at 3x horizontal resolution then `filter`ed. The callback
+    /// will be called for each `(x, y)` pixel coordinate inside
+    /// [`subpixel_px_bounds`](#method.subpixel_px_bounds) with the `[r, g, b]` coverage of
+    /// each subpixel in the range `[0.0, 1.0]`. Blend each channel of the text color with
+    /// its coverage. For BGR screens blend blue with `r` & red with `b`, as `r` is the
+    /// coverage of the left third of each pixel.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf")).unwrap();
+    /// let outlined = font.outline_glyph(font.glyph_id('l').with_scale(20.0)).unwrap();
+    /// let bounds = outlined.subpixel_px_bounds();
+    /// assert_eq!(bounds.width(), outlined.px_bounds().width() + 2.0);
+    ///
+    /// // each channel has about the same total coverage as grayscale rendering
+    /// let (mut coverage, mut rgb) = (0.0, [0.0; 3]);
+    /// outlined.draw(0, |_, _, c| coverage += c);
+    /// outlined.draw_subpixel(0, LcdFilter::default(), |_, _, [r, g, b]| {
+    ///     rgb[0] += r;
+    ///     rgb[1] += g;
+    ///     rgb[2] += b;
+    /// });
+    /// for channel in &rgb {
+    ///     assert!((channel - coverage).abs() < coverage * 0.1);
+    /// }
+    /// ```
+    pub fn draw_subpixel<O: FnMut(u32, u32, [f32; 3])>(
+        &self,
+        layer: usize,
+        filter: LcdFilter,
+        mut o: O,
+    ) {
+        let bounds = self.px_bounds();
+        let (w, h) = (bounds.width() as usize, bounds.height() as usize);
+        let sub_w = w * 3;
+        let mut coverage = vec![0.0; sub_w * h];
+        self.rasterize_h_scaled(layer, 3)
+            .for_each_pixel(|idx, c| coverage[idx] = c);
+
+        let adjust = self.coverage_adjustment();
+        for y in 0..h {
+            let row = &coverage[y * sub_w..(y + 1) * sub_w];
+            // output pixels start 1 pixel, 3 subpixels, left of the rasterized row
+            for x in 0..w + 2 {
+                let mut rgb = [0.0; 3];
+                for (channel, value) in rgb.iter_mut().enumerate() {
+                    let center = (x * 3 + channel) as isize - 3;
+                    let mut c = 0.0;
+                    for (tap, weight) in filter.weights.iter().enumerate() {
+                        let i = center + tap as isize - 2;
+                        if i >= 0 && (i as usize) < sub_w {
+                            c += weight * row[i as usize];
+                        }
+                    }
+                    let c = c.clamp(0.0, 1.0);
+                    *value = match &adjust {
+                        Some(adjust) => adjust(c),
+                        None => c,
+                    };
+                }
+                o(x as u32, y as u32, rgb);
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::GlyphId;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Font data containing a subset of another font's glyphs.
+///
+/// See [`Font::subset`](trait.Font.html#method.subset).
+#[derive(Clone, Debug)]
+pub struct FontSubset {
+    pub(crate) data: Vec<u8>,
+    pub(crate) glyphs: Vec<GlyphId>,
+}
+
+impl FontSubset {
+    /// Subset font file data.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the subset font file data.
+    #[inline]
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// The original glyph ids included in the subset, indexed by subset glyph id.
+    ///
+    /// Includes `.notdef` & any composite glyph components, in ascending order.
+    #[inline]
+    pub fn glyphs(&self) -> &[GlyphId] {
+        &self.glyphs
+    }
+
+    /// Returns the subset glyph id of an original font glyph id, if included.
+    #[inline]
+    pub fn new_id(&self, original: GlyphId) -> Option<GlyphId> {
+        self.glyphs
+            .binary_search(&original)
+            .ok()
+            .map(|idx| GlyphId(idx as u16))
+    }
+}
@@ -0,0 +1,824 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+#[cfg(feature = "bumpalo")]
+use crate::ArenaOutline;
+use crate::{
+    point, CodepointIdIter, ColorGlyphPaint, ColorOutline, ColorPalette, CoverageSet,
+    DecorationMetrics, DeviceVMetrics, Font, FontStyle, FontSubset, GlyphComplexity, GlyphId,
+    GlyphImage, NameId, OpticalBounds, Outline, OutlineCurve, OutlineSink, Point, Rect, Script,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+use core::ops::RangeInclusive;
+
+/// Synthetic emboldening & slanting of glyph outlines, faking bold & oblique styles
+/// missing from a font family.
+///
+/// Applied to all glyphs by wrapping a font in a [`SyntheticFont`](struct.SyntheticFont.html),
+/// or to a single outline with [`transform`](#method.transform).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SyntheticStyle {
+    /// Unscaled amount outlines are widened by, also added to horizontal advances.
+    ///
+    /// Each contour is offset outwards by half, then outlines are moved right by half so
+    /// they keep their left side bearing.
+    pub embolden: f32,
+    /// Horizontal shear of outlines, moving points right by `slant` units per unit above
+    /// the baseline. Advances are unchanged.
+    pub slant: f32,
+    /// Synthesize a hollow rectangle outline for an empty `.notdef` glyph, `GlyphId(0)`,
+    /// so missing chars are visible. See
+    /// [`notdef_box_outline`](#method.notdef_box_outline).
+    pub notdef_box: bool,
+}
+
+impl SyntheticStyle {
+    /// Conventional oblique slant of about 12°.
+    pub const OBLIQUE_SLANT: f32 = 0.2126;
+
+    /// Conventional synthetic bold, emboldening by 1/24 of the font's em.
+    #[inline]
+    pub fn bold<F: Font>(font: &F) -> Self {
+        let units_per_em = font
+            .units_per_em()
+            .unwrap_or_else(|| font.height_unscaled());
+        Self {
+            embolden: units_per_em / 24.0,
+            slant: 0.0,
+            notdef_box: false,
+        }
+    }
+
+    /// Conventional synthetic oblique, slanting by
+    /// [`OBLIQUE_SLANT`](#associatedconstant.OBLIQUE_SLANT).
+    #[inline]
+    pub fn oblique() -> Self {
+        Self {
+            embolden: 0.0,
+            slant: Self::OBLIQUE_SLANT,
+            notdef_box: false,
+        }
+    }
+
+    /// Returns `true` if outlines are neither emboldened nor slanted.
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        self.embolden == 0.0 && self.slant == 0.0
+    }
+
+    /// Returns the outline emboldened then slanted, with bounds of the transformed curves.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, SyntheticStyle};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    /// let outline = font.outline(font.glyph_id('l')).unwrap();
+    ///
+    /// let bold = SyntheticStyle::bold(&font).transform(&outline);
+    /// assert!(bold.bounds.width() > outline.bounds.width());
+    /// assert!((bold.bounds.min.x - outline.bounds.min.x).abs() < 1.0);
+    ///
+    /// // points move right in proportion to their height, so the top right of the
+    /// // stem at (148, 700) becomes the rightmost point
+    /// let oblique = SyntheticStyle::oblique().transform(&outline);
+    /// let top_right = 148.0 + 700.0 * SyntheticStyle::OBLIQUE_SLANT;
+    /// assert!((oblique.bounds.max.x - top_right).abs() < 1e-3);
+    /// # Ok(()) }
+    /// ```
+    pub fn transform(&self, outline: &Outline) -> Outline {
+        let mut curves = outline.curves.clone();
+        let bounds = self.transform_curves(&mut curves).unwrap_or(outline.bounds);
+        Outline { bounds, curves }
+    }
+
+    /// Returns the conventional hollow rectangle `.notdef` outline for a font's em.
+    ///
+    /// The box spans `advance`, less side bearings of 1/20 em, from the baseline to 7/10 em
+    /// with sides 1/20 em thick.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontBuilder, GlyphId, SyntheticFont, SyntheticStyle};
+    ///
+    /// // fonts made with `FontBuilder` have an empty .notdef
+    /// let font = FontBuilder::new(1000.0).build();
+    /// assert!(font.outline(font.glyph_id('x')).is_none());
+    ///
+    /// let style = SyntheticStyle {
+    ///     notdef_box: true,
+    ///     ..SyntheticStyle::default()
+    /// };
+    /// let font = SyntheticFont::new(font, style);
+    /// let notdef = font.outline(font.glyph_id('x')).unwrap();
+    /// assert_eq!(notdef.bounds, SyntheticStyle::notdef_box_outline(1000.0, 600.0).bounds);
+    /// assert_eq!((notdef.bounds.min.x, notdef.bounds.max.x), (50.0, 550.0));
+    /// assert_eq!(font.h_advance_unscaled(GlyphId(0)), 600.0);
+    /// assert_eq!(font.glyph_complexity(GlyphId(0)).unwrap().contours, 2);
+    /// ```
+    pub fn notdef_box_outline(units_per_em: f32, advance: f32) -> Outline {
+        let t = units_per_em / 20.0;
+        let (x0, h) = (t, units_per_em * 0.7);
+        let x1 = (advance - t).max(x0 + 3.0 * t);
+
+        let mut curves = Vec::with_capacity(8);
+        // anti-clockwise outer & clockwise inner contours
+        let outer = [point(x0, 0.0), point(x1, 0.0), point(x1, h), point(x0, h)];
+        let inner = [
+            point(x0 + t, t),
+            point(x0 + t, h - t),
+            point(x1 - t, h - t),
+            point(x1 - t, t),
+        ];
+        for contour in &[outer, inner] {
+            for (i, p) in contour.iter().enumerate() {
+                curves.push(OutlineCurve::Line(*p, contour[(i + 1) % contour.len()]));
+            }
+        }
+        Outline {
+            bounds: Rect {
+                min: point(x0, h),
+                max: point(x1, 0.0),
+            },
+            curves,
+        }
+    }
+
+    /// Transforms curves in place, returning their new bounds or `None` if unchanged.
+    fn transform_curves(&self, curves: &mut [OutlineCurve]) -> Option<Rect> {
+        if self.is_identity() || curves.is_empty() {
+            return None;
+        }
+        if self.embolden != 0.0 {
+            embolden(curves, self.embolden / 2.0);
+        }
+        if self.slant != 0.0 {
+            for curve in curves.iter_mut() {
+                for_each_point(curve, |p| p.x += self.slant * p.y);
+            }
+        }
+        Some(curves_bounds(curves))
+    }
+}
+
+/// `Font` wrapper synthesizing bold & oblique glyphs, see
+/// [`SyntheticStyle`](struct.SyntheticStyle.html).
+///
+/// Outlines, including color layers, are transformed & horizontal advances widened by the
+/// emboldening. An empty `.notdef` may be replaced by a box, see
+/// [`SyntheticStyle::notdef_box`](struct.SyntheticStyle.html#structfield.notdef_box).
+/// Other metrics, raster images & [`subset`](trait.Font.html#method.subset) are those of
+/// the wrapped font. Implements `Font` so it can be scaled & laid out like any other font.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontRef, ScaleFont, SyntheticFont, SyntheticStyle};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let style = SyntheticStyle {
+///     slant: SyntheticStyle::OBLIQUE_SLANT,
+///     ..SyntheticStyle::bold(&font)
+/// };
+/// let bold_italic = SyntheticFont::new(&font, style);
+///
+/// let a = font.glyph_id('a');
+/// assert!(bold_italic.as_scaled(24.0).h_advance(a) > font.as_scaled(24.0).h_advance(a));
+/// // slanting moves the leftmost ink, above the baseline, right
+/// assert!(bold_italic.lsb_unscaled(a) > font.lsb_unscaled(a));
+/// assert!(bold_italic.italic_angle() < -11.0);
+/// assert_ne!(bold_italic.fingerprint(), font.fingerprint());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SyntheticFont<F> {
+    font: F,
+    style: SyntheticStyle,
+}
+
+impl<F: Font> SyntheticFont<F> {
+    /// Wraps a font applying `style` to all glyphs.
+    #[inline]
+    pub fn new(font: F, style: SyntheticStyle) -> Self {
+        Self { font, style }
+    }
+
+    /// Returns the wrapped font.
+    #[inline]
+    pub fn font(&self) -> &F {
+        &self.font
+    }
+
+    /// Returns the synthetic style applied.
+    #[inline]
+    pub fn style(&self) -> SyntheticStyle {
+        self.style
+    }
+
+    /// Unwraps the font.
+    #[inline]
+    pub fn into_inner(self) -> F {
+        self.font
+    }
+
+    /// Returns `true` if `id` is an empty `.notdef` to synthesize a box outline for.
+    #[inline]
+    fn synthesizes_notdef(&self, id: GlyphId) -> bool {
+        self.style.notdef_box && id == GlyphId(0) && self.font.outline(id).is_none()
+    }
+
+    /// Returns the advance of the synthesized `.notdef`, the font's if non-zero.
+    #[inline]
+    fn notdef_box_advance(&self) -> f32 {
+        let advance = self.font.h_advance_unscaled(GlyphId(0));
+        if advance > 0.0 {
+            advance
+        } else {
+            self.notdef_units_per_em() * 0.6
+        }
+    }
+
+    #[inline]
+    fn notdef_units_per_em(&self) -> f32 {
+        self.font
+            .units_per_em()
+            .unwrap_or_else(|| self.font.height_unscaled())
+    }
+
+    #[inline]
+    fn notdef_box(&self) -> Outline {
+        let outline = SyntheticStyle::notdef_box_outline(
+            self.notdef_units_per_em(),
+            self.notdef_box_advance(),
+        );
+        self.style.transform(&outline)
+    }
+
+    #[inline]
+    fn transform_layers(&self, mut outline: ColorOutline) -> ColorOutline {
+        for layer in &mut outline.layers {
+            layer.outline = self.style.transform(&layer.outline);
+        }
+        outline
+    }
+}
+
+impl<F: Font> Font for SyntheticFont<F> {
+    #[inline]
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    #[inline]
+    fn ascent_unscaled(&self) -> f32 {
+        self.font.ascent_unscaled()
+    }
+
+    #[inline]
+    fn descent_unscaled(&self) -> f32 {
+        self.font.descent_unscaled()
+    }
+
+    #[inline]
+    fn line_gap_unscaled(&self) -> f32 {
+        self.font.line_gap_unscaled()
+    }
+
+    #[inline]
+    fn vertical_ascent_unscaled(&self) -> f32 {
+        self.font.vertical_ascent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_descent_unscaled(&self) -> f32 {
+        self.font.vertical_descent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_line_gap_unscaled(&self) -> f32 {
+        self.font.vertical_line_gap_unscaled()
+    }
+
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        let angle = self.font.italic_angle();
+        if self.style.slant == 0.0 {
+            return angle;
+        }
+        // combine the font's own slant, counter-clockwise angles lean left
+        let slant = self.style.slant - angle.to_radians().tan();
+        -slant.atan().to_degrees()
+    }
+
+    #[inline]
+    fn underline_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.font.underline_metrics_unscaled()
+    }
+
+    #[inline]
+    fn strikeout_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.font.strikeout_metrics_unscaled()
+    }
+
+    #[inline]
+    fn x_height_unscaled(&self) -> f32 {
+        self.font.x_height_unscaled()
+    }
+
+    #[inline]
+    fn cap_height_unscaled(&self) -> f32 {
+        self.font.cap_height_unscaled()
+    }
+
+    #[inline]
+    fn is_monospaced(&self) -> bool {
+        self.font.is_monospaced()
+    }
+
+    #[inline]
+    fn weight(&self) -> u16 {
+        let weight = self.font.weight();
+        if self.style.embolden > 0.0 {
+            weight.max(700)
+        } else {
+            weight
+        }
+    }
+
+    #[inline]
+    fn stretch(&self) -> u16 {
+        self.font.stretch()
+    }
+
+    #[inline]
+    fn style(&self) -> FontStyle {
+        match self.font.style() {
+            FontStyle::Normal if self.style.slant != 0.0 => FontStyle::Oblique,
+            style => style,
+        }
+    }
+
+    #[inline]
+    fn is_bold(&self) -> bool {
+        self.font.is_bold() || self.style.embolden > 0.0
+    }
+
+    #[inline]
+    fn font_name(&self, id: NameId) -> Option<String> {
+        self.font.font_name(id)
+    }
+
+    #[inline]
+    fn font_name_for_language(&self, id: NameId, language_id: u16) -> Option<String> {
+        self.font.font_name_for_language(id, language_id)
+    }
+
+    #[inline]
+    fn glyph_id(&self, c: char) -> GlyphId {
+        self.font.glyph_id(c)
+    }
+
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        self.font.glyph_id_checked(c)
+    }
+
+    #[inline]
+    fn glyph_id_variation(&self, c: char, selector: char) -> Option<GlyphId> {
+        self.font.glyph_id_variation(c, selector)
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    #[inline]
+    fn coverage_set(&self) -> CoverageSet {
+        self.font.coverage_set()
+    }
+
+    #[inline]
+    fn glyph_ids_for_str(&self, text: &str, ids: &mut Vec<GlyphId>) {
+        self.font.glyph_ids_for_str(text, ids)
+    }
+
+    #[inline]
+    fn substitute(&self, script: Script, features: &[[u8; 4]], glyphs: &mut Vec<GlyphId>) {
+        self.font.substitute(script, features, glyphs)
+    }
+
+    #[inline]
+    fn features(&self, script: Script) -> Vec<[u8; 4]> {
+        self.font.features(script)
+    }
+
+    #[inline]
+    fn ligatures(&self) -> Vec<(GlyphId, Vec<GlyphId>)> {
+        self.font.ligatures()
+    }
+
+    #[inline]
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        if self.synthesizes_notdef(id) {
+            return self.notdef_box_advance() + self.style.embolden;
+        }
+        self.font.h_advance_unscaled(id) + self.style.embolden
+    }
+
+    #[inline]
+    fn try_h_advance_unscaled(&self, id: GlyphId) -> Option<f32> {
+        if self.synthesizes_notdef(id) {
+            return Some(self.h_advance_unscaled(id));
+        }
+        self.font
+            .try_h_advance_unscaled(id)
+            .map(|advance| advance + self.style.embolden)
+    }
+
+    #[inline]
+    fn h_advances_unscaled(&self, ids: &[GlyphId], out: &mut [f32]) {
+        self.font.h_advances_unscaled(ids, out);
+        for (id, advance) in ids.iter().zip(out) {
+            if self.synthesizes_notdef(*id) {
+                *advance = self.notdef_box_advance();
+            }
+            *advance += self.style.embolden;
+        }
+    }
+
+    #[inline]
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        if self.synthesizes_notdef(id) {
+            return self.notdef_units_per_em() / 20.0;
+        }
+        self.font.h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn try_h_side_bearing_unscaled(&self, id: GlyphId) -> Option<f32> {
+        if self.synthesizes_notdef(id) {
+            return Some(self.h_side_bearing_unscaled(id));
+        }
+        self.font.try_h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_origin_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_origin_unscaled(id)
+    }
+
+    #[inline]
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn v_kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.v_kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn kern_pairs(&self) -> Vec<(GlyphId, GlyphId, f32)> {
+        self.font.kern_pairs()
+    }
+
+    #[inline]
+    fn optical_bounds_unscaled(&self, id: GlyphId) -> Option<OpticalBounds> {
+        self.font.optical_bounds_unscaled(id)
+    }
+
+    #[inline]
+    fn glyph_complexity(&self, id: GlyphId) -> Option<GlyphComplexity> {
+        if self.synthesizes_notdef(id) {
+            return Some(GlyphComplexity::of_curves(&self.notdef_box().curves));
+        }
+        self.font.glyph_complexity(id)
+    }
+
+    #[inline]
+    fn many_to_one_ranges(&self) -> Vec<(RangeInclusive<char>, GlyphId)> {
+        self.font.many_to_one_ranges()
+    }
+
+    #[inline]
+    fn device_h_advance(&self, id: GlyphId, ppem: u16) -> Option<f32> {
+        let advance = self.font.device_h_advance(id, ppem)?;
+        let units_per_em = self
+            .units_per_em()
+            .unwrap_or_else(|| self.height_unscaled());
+        Some(advance + self.style.embolden * f32::from(ppem) / units_per_em)
+    }
+
+    #[inline]
+    fn device_v_metrics(&self, ppem: u16) -> Option<DeviceVMetrics> {
+        self.font.device_v_metrics(ppem)
+    }
+
+    #[inline]
+    fn relative_scale(&self, glyph: GlyphId) -> f32 {
+        self.font.relative_scale(glyph)
+    }
+
+    #[inline]
+    fn has_color(&self, glyph: GlyphId) -> bool {
+        self.font.has_color(glyph)
+    }
+
+    #[inline]
+    fn color_outlines(&self, glyph: GlyphId) -> Option<ColorOutline> {
+        let layers = self.font.color_outlines(glyph)?;
+        Some(self.transform_layers(layers))
+    }
+
+    #[inline]
+    fn color_outlines_with_palette(&self, glyph: GlyphId, palette: usize) -> Option<ColorOutline> {
+        let layers = self.font.color_outlines_with_palette(glyph, palette)?;
+        Some(self.transform_layers(layers))
+    }
+
+    #[inline]
+    fn color_palettes(&self) -> usize {
+        self.font.color_palettes()
+    }
+
+    #[inline]
+    fn color_palette(&self, palette: usize) -> Option<ColorPalette> {
+        self.font.color_palette(palette)
+    }
+
+    #[inline]
+    fn color_paint(&self, glyph: GlyphId) -> Option<ColorGlyphPaint> {
+        self.font.color_paint(glyph)
+    }
+
+    #[inline]
+    fn glyph_raster_image(&self, glyph: GlyphId, pixels_per_em: u16) -> Option<GlyphImage<'_>> {
+        self.font.glyph_raster_image(glyph, pixels_per_em)
+    }
+
+    #[inline]
+    fn outline(&self, glyph: GlyphId) -> Option<Outline> {
+        match self.font.outline(glyph) {
+            Some(outline) => Some(self.style.transform(&outline)),
+            None if self.style.notdef_box && glyph == GlyphId(0) => Some(self.notdef_box()),
+            None => None,
+        }
+    }
+
+    #[inline]
+    fn glyph_bounds_unscaled(&self, glyph: GlyphId) -> Option<Rect> {
+        if self.style.is_identity() && !(self.style.notdef_box && glyph == GlyphId(0)) {
+            self.font.glyph_bounds_unscaled(glyph)
+        } else {
+            // transformed bounds depend on the curves
+            self.outline(glyph).map(|outline| outline.bounds)
+        }
+    }
+
+    #[inline]
+    fn outline_into(&self, glyph: GlyphId, curves: &mut Vec<OutlineCurve>) -> Option<Rect> {
+        match self.font.outline_into(glyph, curves) {
+            Some(bounds) => Some(self.style.transform_curves(curves).unwrap_or(bounds)),
+            None if self.style.notdef_box && glyph == GlyphId(0) => {
+                let outline = self.notdef_box();
+                *curves = outline.curves;
+                Some(outline.bounds)
+            }
+            None => None,
+        }
+    }
+
+    #[inline]
+    fn outline_with(&self, glyph: GlyphId, sink: &mut dyn OutlineSink) -> Option<Rect> {
+        if self.style.is_identity() && !(self.style.notdef_box && glyph == GlyphId(0)) {
+            return self.font.outline_with(glyph, sink);
+        }
+        // transformed curves aren't streamed
+        let outline = self.outline(glyph)?;
+        outline.for_each_segment(sink);
+        Some(outline.bounds)
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[inline]
+    fn outline_in<'bump>(
+        &self,
+        glyph: GlyphId,
+        arena: &'bump bumpalo2::Bump,
+    ) -> Option<ArenaOutline<'bump>> {
+        let outline = self.outline(glyph)?;
+        Some(ArenaOutline {
+            bounds: outline.bounds,
+            curves: arena.alloc_slice_clone(&outline.curves),
+        })
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    #[inline]
+    fn glyph_name(&self, id: GlyphId) -> Option<&str> {
+        self.font.glyph_name(id)
+    }
+
+    #[inline]
+    fn subset(&self, glyphs: &[GlyphId]) -> Option<FontSubset> {
+        self.font.subset(glyphs)
+    }
+
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        let mut hash = crate::outlined::Fnv1a::default();
+        hash.bytes(&self.font.fingerprint().to_le_bytes());
+        hash.bytes(&self.style.embolden.to_bits().to_le_bytes());
+        hash.bytes(&self.style.slant.to_bits().to_le_bytes());
+        hash.write(u8::from(self.style.notdef_box));
+        hash.0
+    }
+}
+
+/// Offsets each contour outwards by `strength` then moves all points right by `strength`,
+/// like FreeType's `FT_Outline_EmboldenXY`.
+///
+/// Points move along the bisector of their adjacent control polygon edges, limited where
+/// short edges would otherwise collapse.
+fn embolden(curves: &mut [OutlineCurve], strength: f32) {
+    // outer contours are clockwise in TrueType fonts, anti-clockwise in CFF fonts
+    let area = signed_area(curves);
+    if area == 0.0 {
+        return;
+    }
+    let clockwise = area < 0.0;
+
+    let mut start = 0;
+    while start < curves.len() {
+        let mut end = start + 1;
+        while end < curves.len() && start_point(&curves[end]) == end_point(&curves[end - 1]) {
+            end += 1;
+        }
+        embolden_contour(&mut curves[start..end], strength, clockwise);
+        start = end;
+    }
+}
+
+/// Emboldens a single contour, which is closed if it wasn't already.
+fn embolden_contour(contour: &mut [OutlineCurve], strength: f32, clockwise: bool) {
+    // closed control polygon, the end of each curve is the start of the next
+    let mut points = Vec::new();
+    for curve in contour.iter() {
+        match curve {
+            OutlineCurve::Line(p0, _) => points.push(*p0),
+            OutlineCurve::Quad(p0, p1, _) => points.extend_from_slice(&[*p0, *p1]),
+            OutlineCurve::Cubic(p0, p1, p2, _) => points.extend_from_slice(&[*p0, *p1, *p2]),
+        }
+    }
+    let n = points.len();
+    let moved: Vec<Point> = (0..n)
+        .map(|i| {
+            let current = points[i];
+            // nearest distinct neighbours, skipping zero length edges
+            let prev = (1..n)
+                .map(|k| points[(i + n - k) % n])
+                .find(|p| *p != current);
+            let next = (1..n).map(|k| points[(i + k) % n]).find(|p| *p != current);
+            let shift = match (prev, next) {
+                (Some(prev), Some(next)) => {
+                    bisector_shift(prev, current, next, strength, clockwise)
+                }
+                _ => point(0.0, 0.0),
+            };
+            current + shift + point(strength, 0.0)
+        })
+        .collect();
+
+    let at = |k: usize| moved[k % n];
+    let mut i = 0;
+    for curve in contour.iter_mut() {
+        let (transformed, len) = match curve {
+            OutlineCurve::Line(..) => (OutlineCurve::Line(at(i), at(i + 1)), 1),
+            OutlineCurve::Quad(..) => (OutlineCurve::Quad(at(i), at(i + 1), at(i + 2)), 2),
+            OutlineCurve::Cubic(..) => (
+                OutlineCurve::Cubic(at(i), at(i + 1), at(i + 2), at(i + 3)),
+                3,
+            ),
+        };
+        *curve = transformed;
+        i += len;
+    }
+}
+
+/// Returns the outward shift of `current` for a `strength` offset of its adjacent edges.
+fn bisector_shift(
+    prev: Point,
+    current: Point,
+    next: Point,
+    strength: f32,
+    clockwise: bool,
+) -> Point {
+    let (d_in, d_out) = (current - prev, next - current);
+    let (l_in, l_out) = (prev.distance(current), current.distance(next));
+    let (d_in, d_out) = (d_in / l_in, d_out / l_out);
+
+    let cos = d_in.x * d_out.x + d_in.y * d_out.y;
+    if cos <= -0.9375 {
+        // very sharp corner, avoid spikes
+        return point(0.0, 0.0);
+    }
+    let d = cos + 1.0;
+
+    let mut shift = point(d_in.y + d_out.y, d_in.x + d_out.x);
+    let mut sin = d_out.x * d_in.y - d_out.y * d_in.x;
+    if clockwise {
+        shift.x = -shift.x;
+        sin = -sin;
+    } else {
+        shift.y = -shift.y;
+    }
+
+    let l = l_in.min(l_out);
+    if strength * sin <= l * d {
+        shift * (strength / d)
+    } else {
+        shift * (l / sin)
+    }
+}
+
+/// Shoelace area of the control polygons of all contours, positive if anti-clockwise.
+fn signed_area(curves: &[OutlineCurve]) -> f32 {
+    let mut area = 0.0;
+    let mut edge = |a: Point, b: Point| area += a.x * b.y - b.x * a.y;
+    for curve in curves {
+        match *curve {
+            OutlineCurve::Line(p0, p1) => edge(p0, p1),
+            OutlineCurve::Quad(p0, p1, p2) => {
+                edge(p0, p1);
+                edge(p1, p2);
+            }
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                edge(p0, p1);
+                edge(p1, p2);
+                edge(p2, p3);
+            }
+        }
+    }
+    area / 2.0
+}
+
+#[inline]
+fn start_point(curve: &OutlineCurve) -> Point {
+    match *curve {
+        OutlineCurve::Line(p0, _) | OutlineCurve::Quad(p0, ..) | OutlineCurve::Cubic(p0, ..) => p0,
+    }
+}
+
+#[inline]
+fn end_point(curve: &OutlineCurve) -> Point {
+    match *curve {
+        OutlineCurve::Line(_, p) | OutlineCurve::Quad(.., p) | OutlineCurve::Cubic(.., p) => p,
+    }
+}
+
+#[inline]
+fn for_each_point(curve: &mut OutlineCurve, mut f: impl FnMut(&mut Point)) {
+    match curve {
+        OutlineCurve::Line(p0, p1) => {
+            f(p0);
+            f(p1);
+        }
+        OutlineCurve::Quad(p0, p1, p2) => {
+            f(p0);
+            f(p1);
+            f(p2);
+        }
+        OutlineCurve::Cubic(p0, p1, p2, p3) => {
+            f(p0);
+            f(p1);
+            f(p2);
+            f(p3);
+        }
+    }
+}
+
+/// Union of curve bounds, `min.y` being the top like `Outline::bounds`.
+pub(crate) fn curves_bounds(curves: &[OutlineCurve]) -> Rect {
+    let mut bounds = curves.iter().map(OutlineCurve::bounds);
+    let init = bounds.next().unwrap_or_default();
+    bounds.fold(init, |a, b| Rect {
+        min: point(a.min.x.min(b.min.x), a.min.y.max(b.min.y)),
+        max: point(a.max.x.max(b.max.x), a.max.y.min(b.max.y)),
+    })
+}
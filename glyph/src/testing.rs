@@ -0,0 +1,243 @@
+//! Golden image regression testing of text rendering, requires the `testing` feature.
+//!
+//! [`render_str`](fn.render_str.html) draws text into an [`AlphaImage`](struct.AlphaImage.html)
+//! deterministically. Images can be stored as reference binary PGM files, then later renders
+//! compared to them with a [`Tolerance`](struct.Tolerance.html) allowing for small
+//! floating point differences across platforms.
+//!
+//! # Example
+//! ```
+//! use ab_glyph::{
+//!     testing::{self, AlphaImage, Tolerance},
+//!     FontRef,
+//! };
+//!
+//! # fn main() -> Result<(), ab_glyph::InvalidFont> {
+//! let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+//! let image = testing::render_str(&font, 24.0, "Hello\nworld");
+//!
+//! // references are usually stored as files, e.g. loaded with `include_bytes!`
+//! let reference = AlphaImage::from_pgm(&image.to_pgm()).unwrap();
+//! assert_eq!(image.compare(&reference, Tolerance::default()), Ok(()));
+//!
+//! let other = testing::render_str(&font, 24.0, "Hello\nWorld");
+//! assert!(other.compare(&reference, Tolerance::default()).is_err());
+//! # Ok(()) }
+//! ```
+use crate::{point, Font, Layout, PxScale};
+#[cfg(not(feature = "std"))]
+use alloc::{format, vec, vec::Vec};
+use core::fmt;
+
+/// 8-bit coverage image, `0` uncovered to `255` fully covered.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AlphaImage {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// Row-major coverage of each pixel, `width * height` long.
+    pub data: Vec<u8>,
+}
+
+impl AlphaImage {
+    /// Returns an uncovered image.
+    #[inline]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; width as usize * height as usize],
+        }
+    }
+
+    /// Compares the image with an `expected` reference, returning why they don't match
+    /// within the `tolerance`.
+    pub fn compare(&self, expected: &AlphaImage, tolerance: Tolerance) -> Result<(), Mismatch> {
+        if (self.width, self.height) != (expected.width, expected.height) {
+            return Err(Mismatch::Size {
+                actual: (self.width, self.height),
+                expected: (expected.width, expected.height),
+            });
+        }
+
+        let (mut count, mut max_difference) = (0, 0);
+        for (a, b) in self.data.iter().zip(&expected.data) {
+            let difference = a.abs_diff(*b);
+            if difference > tolerance.pixel {
+                count += 1;
+                max_difference = max_difference.max(difference);
+            }
+        }
+        if count > tolerance.max_pixels {
+            return Err(Mismatch::Pixels {
+                count,
+                max_difference,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns an image of the per pixel differences to `other`, e.g. to save alongside a
+    /// failing test. Returns `None` if the images have different dimensions.
+    pub fn difference(&self, other: &AlphaImage) -> Option<AlphaImage> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return None;
+        }
+        let data = self
+            .data
+            .iter()
+            .zip(&other.data)
+            .map(|(a, b)| a.abs_diff(*b))
+            .collect();
+        Some(Self { data, ..*self })
+    }
+
+    /// Encodes the image as a binary PGM (P5) file.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut pgm = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        pgm.extend_from_slice(&self.data);
+        pgm
+    }
+
+    /// Decodes a binary PGM (P5) file with a maximum value of `255`, e.g. one written
+    /// by [`to_pgm`](#method.to_pgm). Returns `None` for other or malformed files.
+    pub fn from_pgm(data: &[u8]) -> Option<Self> {
+        if data.get(..2)? != b"P5" {
+            return None;
+        }
+        let mut pos = 2;
+        let mut fields = [0_u32; 3];
+        for field in &mut fields {
+            // skip whitespace & comments
+            loop {
+                match *data.get(pos)? {
+                    b'#' => {
+                        while *data.get(pos)? != b'\n' {
+                            pos += 1;
+                        }
+                    }
+                    c if c.is_ascii_whitespace() => pos += 1,
+                    _ => break,
+                }
+            }
+            let start = pos;
+            while data.get(pos).is_some_and(u8::is_ascii_digit) {
+                pos += 1;
+            }
+            *field = core::str::from_utf8(&data[start..pos]).ok()?.parse().ok()?;
+        }
+
+        let [width, height, max] = fields;
+        // a single whitespace char separates the header & pixels
+        if max != 255 || !data.get(pos)?.is_ascii_whitespace() {
+            return None;
+        }
+        let len = (width as usize).checked_mul(height as usize)?;
+        let pixels = data.get(pos + 1..(pos + 1).checked_add(len)?)?;
+        Some(Self {
+            width,
+            height,
+            data: pixels.to_vec(),
+        })
+    }
+}
+
+/// Allowed difference between a render & its reference image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tolerance {
+    /// Largest coverage difference of a pixel treated as matching. Default `2`.
+    pub pixel: u8,
+    /// Number of pixels allowed to differ by more than `pixel`. Default `0`.
+    pub max_pixels: usize,
+}
+
+impl Default for Tolerance {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pixel: 2,
+            max_pixels: 0,
+        }
+    }
+}
+
+/// Why a render doesn't match its reference image, see
+/// [`AlphaImage::compare`](struct.AlphaImage.html#method.compare).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mismatch {
+    /// The images have different dimensions.
+    Size {
+        /// Render `(width, height)`.
+        actual: (u32, u32),
+        /// Reference `(width, height)`.
+        expected: (u32, u32),
+    },
+    /// More pixels than allowed differ by more than the tolerance.
+    Pixels {
+        /// Number of differing pixels.
+        count: usize,
+        /// Largest coverage difference of a pixel.
+        max_difference: u8,
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Size { actual, expected } => write!(
+                f,
+                "image size {}x{} differs from expected {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            Self::Pixels {
+                count,
+                max_difference,
+            } => write!(
+                f,
+                "{} pixels differ from expected, by up to {}",
+                count, max_difference
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Mismatch {}
+
+/// Lays out & draws `text` with the default [`Layout`](../struct.Layout.html) into an image
+/// just large enough to contain it. Color glyph layers are drawn as coverage.
+///
+/// Rendering only depends on the font, scale & text, so is suitable for comparing against
+/// reference images.
+pub fn render_str<F: Font, S: Into<PxScale>>(font: &F, scale: S, text: &str) -> AlphaImage {
+    let scaled = font.as_scaled(scale);
+    let mut glyphs = Vec::new();
+    Layout::default().layout(scaled, point(0.0, 0.0), text, &mut glyphs);
+    let outlined: Vec<_> = glyphs
+        .into_iter()
+        .filter_map(|glyph| font.outline_glyph(glyph))
+        .collect();
+
+    let mut px_bounds = outlined.iter().map(|glyph| glyph.px_bounds());
+    let bounds = match px_bounds.next() {
+        Some(init) => px_bounds.fold(init, |a, b| a.union(&b)),
+        None => return AlphaImage::default(),
+    };
+
+    let mut image = AlphaImage::new(bounds.width() as u32, bounds.height() as u32);
+    for glyph in &outlined {
+        let offset = glyph.px_bounds().min - bounds.min;
+        let (offset_x, offset_y) = (offset.x as u32, offset.y as u32);
+        for layer in 0..glyph.get_colored_layers().unwrap_or(1) {
+            glyph.draw(layer, |x, y, c| {
+                let (x, y) = (x + offset_x, y + offset_y);
+                if x < image.width && y < image.height {
+                    let pixel = &mut image.data[(y * image.width + x) as usize];
+                    *pixel = (*pixel).max((c.min(1.0) * 255.0 + 0.5) as u8);
+                }
+            });
+        }
+    }
+    image
+}
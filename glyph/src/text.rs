@@ -0,0 +1,132 @@
+use crate::{point, Font, Glyph, Layout, OutlinedGlyph, Point, PxScale, Rect};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// High level builder laying out & drawing a string with a font, scale & position.
+///
+/// Covers the common case in a few lines, use [`Layout`](struct.Layout.html),
+/// [`ScaleFont`](trait.ScaleFont.html) & [`OutlinedGlyph`](struct.OutlinedGlyph.html)
+/// directly for more control.
+///
+/// # Example
+/// ```
+/// use ab_glyph::prelude::*;
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let text = Text::new("Hello").font(&font).scale(24.0).position(point(10.0, 10.0));
+///
+/// assert_eq!(text.glyphs().len(), 5);
+/// text.draw(|x, y, c| { /* draw pixel `(x, y)` of `text.px_bounds()` with coverage `c` */ });
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Text<'a, F = ()> {
+    text: &'a str,
+    font: &'a F,
+    scale: PxScale,
+    position: Point,
+    layout: Layout,
+}
+
+impl<'a> Text<'a, ()> {
+    /// Starts building `text` at the default scale of `16.0` pixels, with the top left of
+    /// the first line at `(0, 0)`. Set a [`font`](#method.font) to lay it out.
+    #[inline]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            font: &(),
+            scale: PxScale::from(16.0),
+            position: point(0.0, 0.0),
+            layout: Layout::default(),
+        }
+    }
+}
+
+impl<'a, F> Text<'a, F> {
+    /// Sets the font.
+    #[inline]
+    pub fn font<G: Font>(self, font: &'a G) -> Text<'a, G> {
+        Text {
+            text: self.text,
+            font,
+            scale: self.scale,
+            position: self.position,
+            layout: self.layout,
+        }
+    }
+
+    /// Sets the pixel scale.
+    #[inline]
+    pub fn scale<S: Into<PxScale>>(mut self, scale: S) -> Self {
+        self.scale = scale.into();
+        self
+    }
+
+    /// Sets the top left of the first line.
+    #[inline]
+    pub fn position<P: Into<Point>>(mut self, position: P) -> Self {
+        self.position = position.into();
+        self
+    }
+
+    /// Sets the layout options, e.g. a maximum line width or text direction.
+    #[inline]
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+}
+
+impl<F: Font> Text<'_, F> {
+    /// Returns the positioned glyphs.
+    pub fn glyphs(&self) -> Vec<Glyph> {
+        let mut glyphs = Vec::new();
+        self.layout.layout(
+            self.font.as_scaled(self.scale),
+            self.position,
+            self.text,
+            &mut glyphs,
+        );
+        glyphs
+    }
+
+    /// Returns the outlined glyphs, skipping those without an outline, e.g. spaces.
+    pub fn outline_glyphs(&self) -> Vec<OutlinedGlyph> {
+        self.glyphs()
+            .into_iter()
+            .filter_map(|glyph| self.font.outline_glyph(glyph))
+            .collect()
+    }
+
+    /// Returns the union of the outlined glyph pixel bounds, or `None` if nothing is drawn.
+    pub fn px_bounds(&self) -> Option<Rect> {
+        union_px_bounds(&self.outline_glyphs())
+    }
+
+    /// Draws the text, calling `o` with each pixel `(x, y)` relative to the
+    /// [`px_bounds`](#method.px_bounds) & its coverage in the range `[0.0, 1.0]`.
+    ///
+    /// Pixels of overlapping glyphs, or color glyph layers, are visited once per glyph
+    /// or layer. Returns the drawn bounds.
+    pub fn draw<O: FnMut(u32, u32, f32)>(&self, mut o: O) -> Option<Rect> {
+        let outlined = self.outline_glyphs();
+        let bounds = union_px_bounds(&outlined)?;
+        for glyph in &outlined {
+            let offset = glyph.px_bounds().min - bounds.min;
+            let (offset_x, offset_y) = (offset.x as u32, offset.y as u32);
+            for layer in 0..glyph.get_colored_layers().unwrap_or(1) {
+                glyph.draw(layer, |x, y, c| o(x + offset_x, y + offset_y, c));
+            }
+        }
+        Some(bounds)
+    }
+}
+
+/// Returns the union of the glyph pixel bounds.
+fn union_px_bounds(outlined: &[OutlinedGlyph]) -> Option<Rect> {
+    let mut px_bounds = outlined.iter().map(|glyph| glyph.px_bounds());
+    let init = px_bounds.next()?;
+    Some(px_bounds.fold(init, |a, b| a.union(&b)))
+}
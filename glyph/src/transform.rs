@@ -0,0 +1,161 @@
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, synthetic::curves_bounds, Outline, OutlineCurve, Point, PxScaleFactor};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// 2D affine transform of pixel coordinates, e.g. to draw rotated, skewed or mirrored
+/// glyphs.
+///
+/// Maps `(x, y)` to `(xx * x + xy * y + dx, yx * x + yy * y + dy)`. Pixel y is down, so
+/// positive rotations are clockwise on screen.
+///
+/// See [`Font::outline_glyph_transformed`](trait.Font.html#method.outline_glyph_transformed).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{point, Transform};
+///
+/// let t = Transform::scale(2.0, 2.0).then(Transform::translate(1.0, 0.0));
+/// assert_eq!(t.transform_point(point(1.0, 1.0)), point(3.0, 2.0));
+///
+/// // right becomes down
+/// let p = Transform::rotate(std::f32::consts::FRAC_PI_2).transform_point(point(1.0, 0.0));
+/// assert!(p.x.abs() < 1e-6 && (p.y - 1.0).abs() < 1e-6);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    /// Scale of x into x.
+    pub xx: f32,
+    /// Shear of x into y.
+    pub yx: f32,
+    /// Shear of y into x.
+    pub xy: f32,
+    /// Scale of y into y.
+    pub yy: f32,
+    /// Horizontal translation.
+    pub dx: f32,
+    /// Vertical translation.
+    pub dy: f32,
+}
+
+impl Transform {
+    /// Transform leaving points unchanged.
+    pub const IDENTITY: Self = Self {
+        xx: 1.0,
+        yx: 0.0,
+        xy: 0.0,
+        yy: 1.0,
+        dx: 0.0,
+        dy: 0.0,
+    };
+
+    /// Rotation by `radians` about the origin, clockwise on screen.
+    #[inline]
+    pub fn rotate(radians: f32) -> Self {
+        let (sin, cos) = (radians.sin(), radians.cos());
+        Self {
+            xx: cos,
+            yx: sin,
+            xy: -sin,
+            yy: cos,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Horizontal skew by `radians`, shifting each row right by `tan(radians)` per pixel
+    /// above the origin, so positive angles slant glyphs like italics.
+    #[inline]
+    pub fn skew(radians: f32) -> Self {
+        Self {
+            xy: -radians.tan(),
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Scale about the origin. Negative factors mirror, e.g. `scale(-1.0, 1.0)` flips
+    /// horizontally.
+    #[inline]
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self {
+            xx: x,
+            yy: y,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Translation by `(x, y)` pixels.
+    #[inline]
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self {
+            dx: x,
+            dy: y,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Returns the transform applying this transform then `next`.
+    #[inline]
+    pub fn then(self, next: Transform) -> Self {
+        Self {
+            xx: next.xx * self.xx + next.xy * self.yx,
+            yx: next.yx * self.xx + next.yy * self.yx,
+            xy: next.xx * self.xy + next.xy * self.yy,
+            yy: next.yx * self.xy + next.yy * self.yy,
+            dx: next.xx * self.dx + next.xy * self.dy + next.dx,
+            dy: next.yx * self.dx + next.yy * self.dy + next.dy,
+        }
+    }
+
+    /// Returns the transformed point.
+    #[inline]
+    pub fn transform_point(&self, p: Point) -> Point {
+        point(
+            self.xx * p.x + self.xy * p.y + self.dx,
+            self.yx * p.x + self.yy * p.y + self.dy,
+        )
+    }
+
+    /// Returns `true` if points are unchanged.
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Returns the unscaled `outline` scaled into pixels & transformed relative to the
+    /// glyph origin, keeping outline y up.
+    pub(crate) fn px_outline(&self, outline: &Outline, scale_factor: PxScaleFactor) -> Outline {
+        let (h_factor, v_factor) = (scale_factor.horizontal, scale_factor.vertical);
+        // transform in pixel y down, then flip back to outline y up
+        let apply = |p: Point| {
+            let p = self.transform_point(point(p.x * h_factor, -p.y * v_factor));
+            point(p.x, -p.y)
+        };
+
+        let curves: Vec<_> = outline
+            .curves
+            .iter()
+            .map(|curve| match *curve {
+                OutlineCurve::Line(p0, p1) => OutlineCurve::Line(apply(p0), apply(p1)),
+                OutlineCurve::Quad(p0, p1, p2) => {
+                    OutlineCurve::Quad(apply(p0), apply(p1), apply(p2))
+                }
+                OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                    OutlineCurve::Cubic(apply(p0), apply(p1), apply(p2), apply(p3))
+                }
+            })
+            .collect();
+
+        Outline {
+            bounds: curves_bounds(&curves),
+            curves,
+        }
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
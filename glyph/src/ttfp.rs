@@ -1,11 +1,46 @@
 //! ttf-parser crate specific code. ttf-parser types should not be leaked publicly.
+mod bitmap;
+mod cmap;
+mod collection;
+mod colr;
+mod cpal;
+mod device;
+mod fvar;
+mod glyf;
+mod gpos;
+mod gsub;
+#[cfg(feature = "std")]
+mod incremental;
+mod kern;
+#[cfg(feature = "memmap2")]
+mod mmap;
+mod opbd;
 mod outliner;
+mod subset;
+#[cfg(any(feature = "woff", feature = "woff2"))]
+mod woff;
 
-use crate::{point, Font, GlyphId, InvalidFont, Outline, Rect};
+#[cfg(feature = "bumpalo")]
+use crate::ArenaOutline;
+use crate::{
+    font::glyph_top_unscaled, point, CodepointIdIter, ColorGlyphPaint, ColorLayer, ColorOutline,
+    ColorPalette, DecorationMetrics, DeviceVMetrics, Font, FontError, FontStyle, FontSubset,
+    GlyphComplexity, GlyphId, GlyphImage, NameId, NamedInstance, OpticalBounds, Outline,
+    OutlineCurve, OutlineSink, Rect, Script, VariationAxis,
+};
+use alloc::sync::Arc;
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
-use core::fmt;
-use owned_ttf_parser::AsFaceRef;
+use alloc::{string::String, vec::Vec};
+use cmap::CmapSubtable;
+use core::{fmt, ops::RangeInclusive};
+use kern::KernSubtable;
+use owned_ttf_parser::{AsFaceRef, Tag};
+
+pub use collection::{FontCollectionBuilder, FontVecCollection};
+#[cfg(feature = "std")]
+pub use incremental::IncrementalFont;
+#[cfg(feature = "memmap2")]
+pub use mmap::FontMmap;
 
 impl From<GlyphId> for owned_ttf_parser::GlyphId {
     #[inline]
@@ -27,14 +62,25 @@ impl From<GlyphId> for owned_ttf_parser::GlyphId {
 /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
 ///
 /// assert_eq!(font.glyph_id('s'), ab_glyph::GlyphId(56));
+/// assert_eq!(
+///     format!("{:?}", font),
+///     r#"FontRef { family: "Exo 2 Light", glyphs: 1032, units_per_em: Some(1000), index: 0 }"#
+/// );
 /// # Ok(()) }
 /// ```
 #[derive(Clone)]
-pub struct FontRef<'font>(owned_ttf_parser::Face<'font>);
+pub struct FontRef<'font>(
+    owned_ttf_parser::Face<'font>,
+    Subtables,
+    /// Font collection index.
+    u32,
+    /// Font, or font collection, data, `None` for faces borrowed from a `FontVec`.
+    Option<&'font [u8]>,
+);
 
 impl fmt::Debug for FontRef<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "FontRef")
+        debug_face(f, "FontRef", &self.0, self.2)
     }
 }
 
@@ -52,7 +98,7 @@ impl<'font> FontRef<'font> {
     /// # Ok(()) }
     /// ```
     #[inline]
-    pub fn try_from_slice(data: &'font [u8]) -> Result<Self, InvalidFont> {
+    pub fn try_from_slice(data: &'font [u8]) -> Result<Self, FontError> {
         Self::try_from_slice_and_index(data, 0)
     }
 
@@ -70,10 +116,110 @@ impl<'font> FontRef<'font> {
     /// # Ok(()) }
     /// ```
     #[inline]
-    pub fn try_from_slice_and_index(data: &'font [u8], index: u32) -> Result<Self, InvalidFont> {
-        Ok(Self(
-            owned_ttf_parser::Face::from_slice(data, index).map_err(|_| InvalidFont)?,
-        ))
+    pub fn try_from_slice_and_index(data: &'font [u8], index: u32) -> Result<Self, FontError> {
+        let face = owned_ttf_parser::Face::from_slice(data, index).map_err(font_error)?;
+        let subtables = Subtables::new(&face);
+        Ok(Self(face, subtables, index, Some(data)))
+    }
+
+    /// Returns the number of faces in font collection (e.g. ttc) data, `1` for
+    /// non-collection data.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// let data = include_bytes!("../../dev/fonts/Exo2-Light.otf");
+    /// assert_eq!(FontRef::fonts_in_collection(data), 1);
+    /// ```
+    #[inline]
+    pub fn fonts_in_collection(data: &[u8]) -> u32 {
+        owned_ttf_parser::fonts_in_collection(data).unwrap_or(1)
+    }
+
+    /// Returns an iterator loading each face of font collection (e.g. ttc) data, or the
+    /// single face of non-collection data.
+    ///
+    /// Together with the [`font_name`](trait.Font.html#method.font_name) of each face,
+    /// this allows presenting a face picker rather than guessing indices for
+    /// [`FontRef::try_from_slice_and_index`](#method.try_from_slice_and_index).
+    /// See also [`FontVecCollection`](struct.FontVecCollection.html).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef, NameId};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let data = include_bytes!("../../dev/fonts/Exo2-Light.otf");
+    ///
+    /// let mut names = Vec::new();
+    /// for font in FontRef::try_from_collection(data) {
+    ///     let font = font?;
+    ///     names.push((font.index(), font.font_name(NameId::FAMILY).unwrap()));
+    /// }
+    /// assert_eq!(names, vec![(0, "Exo 2 Light".to_owned())]);
+    /// # Ok(()) }
+    /// ```
+    pub fn try_from_collection(
+        data: &'font [u8],
+    ) -> impl Iterator<Item = Result<Self, FontError>> + 'font {
+        (0..Self::fonts_in_collection(data))
+            .map(move |index| Self::try_from_slice_and_index(data, index))
+    }
+
+    /// Returns the font collection index of this face, `0` for non-collection fonts.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.2
+    }
+
+    /// Returns the font data, for font collections all faces' data, e.g. to pass to other
+    /// font libraries along with the [`index`](#method.index).
+    ///
+    /// `None` for a [`FontVec::as_font_ref`](struct.FontVec.html#method.as_font_ref) face,
+    /// as the owned face doesn't expose its data.
+    #[inline]
+    pub fn as_slice(&self) -> Option<&'font [u8]> {
+        self.3
+    }
+
+    /// Sets the value of a variable font design axis, in user space units, e.g. weight
+    /// `*b"wght"` to `700.0`. Values are clamped to the axis range.
+    ///
+    /// Outlines & metrics, like `outline` & `h_advance_unscaled`, then use the varied
+    /// glyphs. Returns `false` if the font is not variable or has no such axis.
+    ///
+    /// For `FontVec` variations use [`FontVec::as_font_ref`](struct.FontVec.html#method.as_font_ref).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let mut font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+    ///
+    /// // not a variable font
+    /// assert!(font.variation_axes().is_empty());
+    /// assert!(!font.set_variation(b"wght", 700.0));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn set_variation(&mut self, axis: &[u8; 4], value: f32) -> bool {
+        self.0.set_variation(Tag::from_bytes(axis), value).is_some()
+    }
+
+    /// Returns the design axes of a variable font, empty for non-variable fonts.
+    #[inline]
+    pub fn variation_axes(&self) -> Vec<VariationAxis> {
+        fvar::variation_axes(&self.0)
+    }
+
+    /// Returns the named instances, e.g. "Bold", of a variable font.
+    ///
+    /// Each instance may be applied by setting each axis value with
+    /// [`set_variation`](#method.set_variation).
+    #[inline]
+    pub fn named_instances(&self) -> Vec<NamedInstance> {
+        fvar::named_instances(&self.0)
     }
 }
 
@@ -82,6 +228,8 @@ impl<'font> FontRef<'font> {
 ///
 /// Also see [`FontRef`](struct.FontRef.html).
 ///
+/// The parsed data is reference counted so clones are cheap & share the same font data.
+///
 /// # Example
 /// ```
 /// use ab_glyph::{Font, FontVec};
@@ -91,13 +239,69 @@ impl<'font> FontRef<'font> {
 /// let font = FontVec::try_from_vec_and_index(owned_font_data, 0)?;
 ///
 /// assert_eq!(font.glyph_id('s'), ab_glyph::GlyphId(56));
+///
+/// let font2 = font.clone();
+/// assert_eq!(font2.glyph_id('s'), ab_glyph::GlyphId(56));
 /// # Ok(()) }
 /// ```
-pub struct FontVec(owned_ttf_parser::OwnedFace);
+#[derive(Clone)]
+pub struct FontVec(
+    Arc<owned_ttf_parser::OwnedFace>,
+    Subtables,
+    /// Font data `Vec` capacity.
+    usize,
+    /// Font collection index.
+    u32,
+);
 
 impl fmt::Debug for FontVec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "FontVec")
+        debug_face(f, "FontVec", self.0.as_face_ref(), self.3)
+    }
+}
+
+/// Debug formats a face as `name` with its family name, glyph count, units per em &
+/// font collection index.
+fn debug_face(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    face: &owned_ttf_parser::Face<'_>,
+    index: u32,
+) -> fmt::Result {
+    f.debug_struct(name)
+        .field("family", &FamilyName(face))
+        .field("glyphs", &face.number_of_glyphs())
+        .field("units_per_em", &face.units_per_em())
+        .field("index", &index)
+        .finish()
+}
+
+/// Debug formats the first unicode family name of a face, as a quoted string, or `None`.
+struct FamilyName<'a, 'font>(&'a owned_ttf_parser::Face<'font>);
+
+impl fmt::Debug for FamilyName<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        let family = self
+            .0
+            .names()
+            .find(|name| name.name_id() == owned_ttf_parser::name_id::FAMILY && name.is_unicode());
+        match family {
+            Some(family) => {
+                // unicode names are stored as UTF-16BE
+                let utf16 = family
+                    .name()
+                    .chunks_exact(2)
+                    .map(|b| u16::from_be_bytes([b[0], b[1]]));
+                f.write_char('"')?;
+                for c in core::char::decode_utf16(utf16) {
+                    f.write_char(c.unwrap_or(core::char::REPLACEMENT_CHARACTER))?;
+                }
+                f.write_char('"')
+            }
+            None => f.write_str("None"),
+        }
     }
 }
 
@@ -116,7 +320,7 @@ impl FontVec {
     /// # Ok(()) }
     /// ```
     #[inline]
-    pub fn try_from_vec(data: Vec<u8>) -> Result<Self, InvalidFont> {
+    pub fn try_from_vec(data: Vec<u8>) -> Result<Self, FontError> {
         Self::try_from_vec_and_index(data, 0)
     }
 
@@ -134,14 +338,373 @@ impl FontVec {
     /// # Ok(()) }
     /// ```
     #[inline]
-    pub fn try_from_vec_and_index(data: Vec<u8>, index: u32) -> Result<Self, InvalidFont> {
-        Ok(Self(
-            owned_ttf_parser::OwnedFace::from_vec(data, index).map_err(|_| InvalidFont)?,
-        ))
+    pub fn try_from_vec_and_index(data: Vec<u8>, index: u32) -> Result<Self, FontError> {
+        let capacity = data.capacity();
+        let face = owned_ttf_parser::OwnedFace::from_vec(data, index).map_err(font_error)?;
+        let subtables = Subtables::new(face.as_face_ref());
+        Ok(Self(Arc::new(face), subtables, capacity, index))
+    }
+
+    /// Returns a `FontRef` borrowing this font's data, e.g. to
+    /// [set variations](struct.FontRef.html#method.set_variation) without re-parsing the font.
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{Font, FontVec};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// # let owned_font_data = include_bytes!("../../dev/fonts/Exo2-Light.otf").to_vec();
+    /// let font = FontVec::try_from_vec(owned_font_data)?;
+    /// let mut font_ref = font.as_font_ref();
+    /// for axis in font.variation_axes() {
+    ///     font_ref.set_variation(&axis.tag, axis.max_value);
+    /// }
+    ///
+    /// assert_eq!(font_ref.glyph_id('s'), font.glyph_id('s'));
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn as_font_ref(&self) -> FontRef<'_> {
+        FontRef(self.0.as_face_ref().clone(), self.1.clone(), self.3, None)
     }
+
+    /// Returns the font collection index of this face, `0` for non-collection fonts.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.3
+    }
+
+    /// Returns the design axes of a variable font, empty for non-variable fonts.
+    #[inline]
+    pub fn variation_axes(&self) -> Vec<VariationAxis> {
+        fvar::variation_axes(self.0.as_face_ref())
+    }
+
+    /// Returns the named instances, e.g. "Bold", of a variable font.
+    #[inline]
+    pub fn named_instances(&self) -> Vec<NamedInstance> {
+        fvar::named_instances(self.0.as_face_ref())
+    }
+
+    /// Returns the approximate memory in bytes held by this font, including the font data
+    /// & parsed structures.
+    ///
+    /// Clones share the font data, which is included in the usage of each.
+    ///
+    /// # Example
+    /// ```
+    /// # use ab_glyph::*;
+    /// # fn main() -> Result<(), InvalidFont> {
+    /// let owned_font_data = include_bytes!("../../dev/fonts/Exo2-Light.otf").to_vec();
+    /// let data_len = owned_font_data.len();
+    /// let font = FontVec::try_from_vec(owned_font_data)?;
+    ///
+    /// assert!(font.memory_usage() > data_len);
+    /// # Ok(()) }
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        // data & parsed face are stored in a single heap allocation
+        let boxed_face = core::mem::size_of::<Vec<u8>>()
+            + core::mem::size_of::<Option<owned_ttf_parser::Face<'static>>>();
+        // reference counts & the owned face pointer
+        let shared =
+            2 * core::mem::size_of::<usize>() + core::mem::size_of::<owned_ttf_parser::OwnedFace>();
+        core::mem::size_of::<Self>() + shared + boxed_face + self.2
+    }
+}
+
+/// Subtable selections made once on load, avoiding re-walking & filtering subtables
+/// on every `glyph_id`, `kern_unscaled` & `v_kern_unscaled` call.
+#[derive(Clone, Debug)]
+struct Subtables {
+    /// Preferred unicode cmap subtable.
+    cmap: Option<CmapSubtable>,
+    /// Other unicode cmap subtables, checked in order if the preferred subtable misses.
+    cmap_fallbacks: Vec<CmapSubtable>,
+    /// Unicode many-to-one (format 13) cmap subtable, used as a last resort.
+    many_to_one: Option<CmapSubtable>,
+    /// Horizontal, non-variable kern subtables.
+    kern: Vec<KernSubtable>,
+    /// Vertical, non-variable kern subtables.
+    v_kern: Vec<KernSubtable>,
+    /// Fallback to GPOS "kern" feature lookups for horizontal kerning, if there are no
+    /// horizontal kern subtables.
+    gpos_kern: bool,
+    /// Fallback to GPOS "vkrn" feature lookups for vertical kerning, if there are no
+    /// vertical kern subtables.
+    gpos_v_kern: bool,
+}
+
+impl Subtables {
+    fn new(face: &owned_ttf_parser::Face<'_>) -> Self {
+        // prefer full unicode coverage (format 12) subtables, otherwise the first unicode subtable
+        let unicode: Vec<(usize, owned_ttf_parser::cmap::Format)> = face
+            .character_mapping_subtables()
+            .enumerate()
+            .filter(|(_, st)| st.is_unicode())
+            .map(|(idx, st)| (idx, st.format()))
+            .collect();
+        let preferred = unicode
+            .iter()
+            .find(|(_, format)| *format == owned_ttf_parser::cmap::Format::SegmentedCoverage)
+            .or_else(|| unicode.first())
+            .map(|&(idx, _)| idx);
+        let cmap = preferred.and_then(|idx| CmapSubtable::new(face, idx));
+        let cmap_fallbacks = unicode
+            .iter()
+            .filter(|&&(idx, _)| Some(idx) != preferred)
+            .filter_map(|&(idx, _)| CmapSubtable::new(face, idx))
+            .collect();
+        let many_to_one = face
+            .character_mapping_subtables()
+            .position(|st| cmap::is_unicode_many_to_one(&st))
+            .and_then(|idx| CmapSubtable::new(face, idx));
+
+        let (kern, v_kern) = kern::subtables(face);
+        let gpos = face.table_data(Tag::from_bytes(b"GPOS")).is_some();
+        Self {
+            cmap,
+            cmap_fallbacks,
+            many_to_one,
+            gpos_kern: kern.is_empty() && gpos,
+            gpos_v_kern: v_kern.is_empty() && gpos,
+            kern,
+            v_kern,
+        }
+    }
+
+    #[inline]
+    fn glyph_index(
+        &self,
+        face: &owned_ttf_parser::Face<'_>,
+        c: char,
+    ) -> Option<owned_ttf_parser::GlyphId> {
+        let c = u32::from(c);
+        self.cmap
+            .iter()
+            .chain(&self.cmap_fallbacks)
+            .chain(&self.many_to_one)
+            .find_map(|st| st.glyph_index(face, c))
+            .map(owned_ttf_parser::GlyphId)
+    }
+
+    /// Returns the glyph id & char of each char mapped by the preferred unicode subtable, or
+    /// otherwise by the many-to-one subtable, sorted by char.
+    fn codepoint_ids(&self, face: &owned_ttf_parser::Face<'_>) -> Vec<(GlyphId, char)> {
+        let mut ids = Vec::new();
+        for cmap in self.cmap.iter().chain(&self.many_to_one) {
+            if let Some(subtable) = face.character_mapping_subtables().nth(cmap.index()) {
+                subtable.codepoints(|codepoint| {
+                    let mapped = core::char::from_u32(codepoint)
+                        .and_then(|c| Some((GlyphId(cmap.glyph_index(face, codepoint)?), c)));
+                    if let Some((id, c)) = mapped {
+                        ids.push((id, c));
+                    }
+                });
+            }
+        }
+        // stable sort keeps the preferred subtable mapping of duplicates first
+        ids.sort_by_key(|&(_, c)| c);
+        ids.dedup_by_key(|&mut (_, c)| c);
+        ids
+    }
+
+    /// Appends the glyph index of each char.
+    fn glyph_indices(&self, face: &owned_ttf_parser::Face<'_>, text: &str, ids: &mut Vec<GlyphId>) {
+        ids.extend(
+            text.chars()
+                .map(|c| GlyphId(self.glyph_index(face, c).map_or(0, |id| id.0))),
+        );
+    }
+
+    /// Returns kerning from the first of `subtables` containing the pair.
+    #[inline]
+    fn kerning(
+        subtables: &[KernSubtable],
+        face: &owned_ttf_parser::Face<'_>,
+        first: GlyphId,
+        second: GlyphId,
+    ) -> Option<i16> {
+        subtables
+            .iter()
+            .find_map(|st| st.glyphs_kerning(face, first.0, second.0))
+    }
+}
+
+/// Maps a face parsing error to the crate's error, keeping the dependency type private.
+fn font_error(err: owned_ttf_parser::FaceParsingError) -> FontError {
+    use owned_ttf_parser::FaceParsingError as E;
+    match err {
+        E::UnknownMagic => FontError::UnknownMagic,
+        E::FaceIndexOutOfBounds => FontError::IndexOutOfBounds,
+        E::NoHeadTable => FontError::MissingTable(*b"head"),
+        E::NoHheaTable => FontError::MissingTable(*b"hhea"),
+        E::NoMaxpTable => FontError::MissingTable(*b"maxp"),
+        E::MalformedFont => FontError::Malformed,
+    }
+}
+
+/// Reads the `hmtx` advances of `ids` into `out`, finding the tables once rather than
+/// per glyph. Invalid glyphs use the .notdef advance.
+///
+/// Returns `None` for variable fonts with non-default coordinates, whose advances may differ
+/// from `hmtx`, or malformed metrics.
+fn hmtx_advances(
+    face: &owned_ttf_parser::Face<'_>,
+    ids: &[GlyphId],
+    out: &mut [f32],
+) -> Option<()> {
+    if face
+        .variation_coordinates()
+        .iter()
+        .any(|coord| coord.get() != 0)
+    {
+        return None;
+    }
+    let hhea = face.table_data(Tag::from_bytes(b"hhea"))?;
+    let hmtx = face.table_data(Tag::from_bytes(b"hmtx"))?;
+    // glyphs after the last long metric share its advance
+    let last_metric = usize::from(read_u16(hhea, 34)?).checked_sub(1)?;
+    let glyph_count = face.number_of_glyphs();
+    for (id, advance) in ids.iter().zip(out) {
+        let index = if id.0 < glyph_count {
+            usize::from(id.0)
+        } else {
+            0
+        };
+        *advance = f32::from(read_u16(hmtx, index.min(last_metric) * 4)?);
+    }
+    Some(())
+}
+
+/// Reads a big endian `u16` from font table data.
+#[inline]
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// Reads a big endian `u32` from font table data.
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Returns the coverage index of `glyph` in the coverage table at `offset`.
+fn coverage_index(data: &[u8], offset: usize, glyph: u16) -> Option<usize> {
+    let format = read_u16(data, offset)?;
+    let count = usize::from(read_u16(data, offset + 2)?);
+    let record_size = match format {
+        1 => 2,
+        2 => 6,
+        _ => return None,
+    };
+
+    // glyphs & ranges are sorted
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let record = offset + 4 + mid * record_size;
+        let start = read_u16(data, record)?;
+        let end = match format {
+            1 => start,
+            _ => read_u16(data, record + 2)?,
+        };
+        if glyph < start {
+            hi = mid;
+        } else if glyph > end {
+            lo = mid + 1;
+        } else if format == 1 {
+            return Some(mid);
+        } else {
+            let start_index = usize::from(read_u16(data, record + 4)?);
+            return Some(start_index + usize::from(glyph - start));
+        }
+    }
+    None
+}
+
+/// Returns the first unicode name with the given id.
+fn name(face: &owned_ttf_parser::Face<'_>, name_id: u16) -> Option<String> {
+    let name = face
+        .names()
+        .find(|name| name.name_id() == name_id && name.is_unicode())?;
+    Some(decode_name(&name))
 }
 
-/// Implement `Font` for `Self(AsFontRef)` types.
+/// Returns the unicode name with the given id in a Windows language, falling back to
+/// US English then the first unicode name.
+fn localized_name(
+    face: &owned_ttf_parser::Face<'_>,
+    name_id: u16,
+    language_id: u16,
+) -> Option<String> {
+    let names = || {
+        face.names()
+            .filter(move |name| name.name_id() == name_id && name.is_unicode())
+    };
+    let windows = |language_id| {
+        names().find(|name| {
+            name.platform_id() == owned_ttf_parser::PlatformId::Windows
+                && name.language_id() == language_id
+        })
+    };
+    let name = windows(language_id)
+        .or_else(|| windows(0x0409))
+        .or_else(|| names().next())?;
+    Some(decode_name(&name))
+}
+
+/// Decodes a unicode, UTF-16BE, name.
+fn decode_name(name: &owned_ttf_parser::Name<'_>) -> String {
+    let utf16 = name
+        .name()
+        .chunks_exact(2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]));
+    core::char::decode_utf16(utf16)
+        .map(|c| c.unwrap_or(core::char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Outlines a glyph into `buffer` returning it along with the unscaled bounds.
+fn outline_glyph<E: Extend<OutlineCurve>>(
+    face: &owned_ttf_parser::Face<'_>,
+    id: GlyphId,
+    buffer: E,
+) -> (E, Option<Rect>) {
+    let mut outliner = outliner::OutlineCurveBuilder::with_buffer(buffer);
+    let bounds = face
+        .outline_glyph(id.into(), &mut outliner)
+        .map(unscaled_rect);
+    (outliner.take_outline(), bounds)
+}
+
+/// Converts font unit bounds to a `Rect` with `min.y` the top, like `Outline::bounds`.
+#[inline]
+fn unscaled_rect(rect: owned_ttf_parser::Rect) -> Rect {
+    Rect {
+        min: point(f32::from(rect.x_min), f32::from(rect.y_max)),
+        max: point(f32::from(rect.x_max), f32::from(rect.y_min)),
+    }
+}
+
+/// Hashes the `head` table, including the whole font checksum & timestamps, the start of
+/// `OS/2`, distinguishing faces of a collection by weight & width, the glyph count &
+/// variation coordinates.
+fn face_fingerprint(face: &owned_ttf_parser::Face<'_>) -> u64 {
+    let mut hash = crate::outlined::Fnv1a::default();
+    let table = |tag: &[u8; 4]| face.table_data(Tag::from_bytes(tag)).unwrap_or_default();
+    hash.bytes(table(b"head"));
+    hash.bytes(table(b"OS/2").get(..8).unwrap_or_default());
+    hash.bytes(&face.number_of_glyphs().to_le_bytes());
+    for coord in face.variation_coordinates() {
+        hash.bytes(&coord.get().to_le_bytes());
+    }
+    hash.0
+}
+
+/// Implement `Font` for `Self(AsFontRef, Subtables)` types.
 macro_rules! impl_font {
     ($font:ty) => {
         impl Font for $font {
@@ -165,12 +728,112 @@ macro_rules! impl_font {
                 f32::from(self.0.as_face_ref().line_gap())
             }
 
+            #[inline]
+            fn vertical_ascent_unscaled(&self) -> f32 {
+                let face = self.0.as_face_ref();
+                match face.vertical_ascender() {
+                    Some(ascent) => f32::from(ascent),
+                    // no vertical metrics, center the em on the column
+                    None => f32::from(face.units_per_em().unwrap_or_default()) / 2.0,
+                }
+            }
+
+            #[inline]
+            fn vertical_descent_unscaled(&self) -> f32 {
+                let face = self.0.as_face_ref();
+                match face.vertical_descender() {
+                    Some(descent) => f32::from(descent),
+                    None => -f32::from(face.units_per_em().unwrap_or_default()) / 2.0,
+                }
+            }
+
+            #[inline]
+            fn vertical_line_gap_unscaled(&self) -> f32 {
+                self.0
+                    .as_face_ref()
+                    .vertical_line_gap()
+                    .map(f32::from)
+                    .unwrap_or_default()
+            }
+
+            #[inline]
+            fn italic_angle(&self) -> f32 {
+                self.0.as_face_ref().italic_angle().unwrap_or(0.0)
+            }
+
+            #[inline]
+            fn underline_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+                self.0
+                    .as_face_ref()
+                    .underline_metrics()
+                    .map(|m| DecorationMetrics {
+                        position: f32::from(m.position),
+                        thickness: f32::from(m.thickness),
+                    })
+            }
+
+            #[inline]
+            fn strikeout_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+                self.0
+                    .as_face_ref()
+                    .strikeout_metrics()
+                    .map(|m| DecorationMetrics {
+                        position: f32::from(m.position),
+                        thickness: f32::from(m.thickness),
+                    })
+            }
+
+            #[inline]
+            fn x_height_unscaled(&self) -> f32 {
+                match self.0.as_face_ref().x_height() {
+                    Some(x_height) if x_height > 0 => f32::from(x_height),
+                    _ => glyph_top_unscaled(self, 'x')
+                        .unwrap_or_else(|| self.ascent_unscaled() / 2.0),
+                }
+            }
+
+            #[inline]
+            fn is_monospaced(&self) -> bool {
+                self.0.as_face_ref().is_monospaced()
+            }
+
+            #[inline]
+            fn weight(&self) -> u16 {
+                self.0.as_face_ref().weight().to_number()
+            }
+
+            #[inline]
+            fn stretch(&self) -> u16 {
+                self.0.as_face_ref().width().to_number()
+            }
+
+            #[inline]
+            fn style(&self) -> FontStyle {
+                let face = self.0.as_face_ref();
+                if face.is_italic() {
+                    FontStyle::Italic
+                } else if face.is_oblique() {
+                    FontStyle::Oblique
+                } else {
+                    FontStyle::Normal
+                }
+            }
+
+            #[inline]
+            fn is_bold(&self) -> bool {
+                self.0.as_face_ref().is_bold()
+            }
+
+            #[inline]
+            fn font_name_for_language(&self, id: NameId, language_id: u16) -> Option<String> {
+                localized_name(self.0.as_face_ref(), id.0, language_id)
+            }
+
             #[inline]
             fn glyph_id(&self, c: char) -> GlyphId {
                 let index = self
-                    .0
-                    .as_face_ref()
-                    .glyph_index(c)
+                    .1
+                    .glyph_index(self.0.as_face_ref(), c)
                     .map(|id| id.0)
                     .unwrap_or(0);
 
@@ -183,55 +846,170 @@ macro_rules! impl_font {
                 id
             }
 
+            #[inline]
+            fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+                self.1
+                    .glyph_index(self.0.as_face_ref(), c)
+                    .map(|id| GlyphId(id.0))
+            }
+
+            #[inline]
+            fn glyph_id_variation(&self, c: char, selector: char) -> Option<GlyphId> {
+                self.0
+                    .as_face_ref()
+                    .glyph_variation_index(c, selector)
+                    .map(|id| GlyphId(id.0))
+            }
+
+            #[inline]
+            fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+                CodepointIdIter::new(self.1.codepoint_ids(self.0.as_face_ref()).into_iter())
+            }
+
+            #[inline]
+            fn glyph_ids_for_str(&self, text: &str, ids: &mut Vec<GlyphId>) {
+                self.1.glyph_indices(self.0.as_face_ref(), text, ids)
+            }
+
+            #[inline]
+            fn substitute(&self, script: Script, features: &[[u8; 4]], glyphs: &mut Vec<GlyphId>) {
+                gsub::substitute(self.0.as_face_ref(), script, features, glyphs)
+            }
+
+            #[inline]
+            fn features(&self, script: Script) -> Vec<[u8; 4]> {
+                gsub::features(self.0.as_face_ref(), script)
+            }
+
+            #[inline]
+            fn ligatures(&self) -> Vec<(GlyphId, Vec<GlyphId>)> {
+                gsub::ligatures(self.0.as_face_ref())
+            }
+
             #[inline]
             fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
-                let advance = self
-                    .0
+                // invalid glyphs fall back to the .notdef advance
+                self.try_h_advance_unscaled(id)
+                    .or_else(|| self.try_h_advance_unscaled(GlyphId(0)))
+                    .unwrap_or_default()
+            }
+
+            #[inline]
+            fn try_h_advance_unscaled(&self, id: GlyphId) -> Option<f32> {
+                self.0
                     .as_face_ref()
                     .glyph_hor_advance(id.into())
-                    .expect("Invalid glyph_hor_advance");
-                f32::from(advance)
+                    .map(f32::from)
+            }
+
+            #[inline]
+            fn h_advances_unscaled(&self, ids: &[GlyphId], out: &mut [f32]) {
+                if hmtx_advances(self.0.as_face_ref(), ids, out).is_none() {
+                    for (id, advance) in ids.iter().zip(out) {
+                        *advance = self.h_advance_unscaled(*id);
+                    }
+                }
             }
 
             #[inline]
             fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
-                let advance = self
-                    .0
+                self.try_h_side_bearing_unscaled(id).unwrap_or_default()
+            }
+
+            #[inline]
+            fn try_h_side_bearing_unscaled(&self, id: GlyphId) -> Option<f32> {
+                self.0
                     .as_face_ref()
                     .glyph_hor_side_bearing(id.into())
-                    .expect("Invalid glyph_hor_side_bearing");
-                f32::from(advance)
+                    .map(f32::from)
             }
 
             #[inline]
             fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
-                let advance = self
-                    .0
-                    .as_face_ref()
-                    .glyph_ver_advance(id.into())
-                    .expect("Invalid glyph_ver_advance");
-                f32::from(advance)
+                let face = self.0.as_face_ref();
+                match face.glyph_ver_advance(id.into()) {
+                    Some(advance) => f32::from(advance),
+                    // no vertical metrics, default to the ascent - descent line height
+                    None => f32::from(face.ascender()) - f32::from(face.descender()),
+                }
             }
 
             #[inline]
             fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
-                let advance = self
-                    .0
-                    .as_face_ref()
-                    .glyph_ver_side_bearing(id.into())
-                    .expect("Invalid glyph_ver_side_bearing");
-                f32::from(advance)
+                let face = self.0.as_face_ref();
+                match face.glyph_ver_side_bearing(id.into()) {
+                    Some(bearing) => f32::from(bearing),
+                    // no vertical metrics, default to the distance from ascent to glyph top
+                    None => face
+                        .glyph_bounding_box(id.into())
+                        .map(|bounds| f32::from(face.ascender()) - f32::from(bounds.y_max))
+                        .unwrap_or_default(),
+                }
+            }
+
+            #[inline]
+            fn v_origin_unscaled(&self, id: GlyphId) -> f32 {
+                let face = self.0.as_face_ref();
+                if let Some(origin) = face.glyph_y_origin(id.into()) {
+                    // CFF fonts specify the vertical origin in VORG
+                    return f32::from(origin);
+                }
+                match face.glyph_bounding_box(id.into()) {
+                    Some(bounds) => self.v_side_bearing_unscaled(id) + f32::from(bounds.y_max),
+                    None => f32::from(face.ascender()),
+                }
             }
 
             #[inline]
             fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
-                self.0
-                    .as_face_ref()
-                    .kerning_subtables()
-                    .filter(|st| st.is_horizontal() && !st.is_variable())
-                    .find_map(|st| st.glyphs_kerning(first.into(), second.into()))
-                    .map(f32::from)
-                    .unwrap_or_default()
+                let face = self.0.as_face_ref();
+                match Subtables::kerning(&self.1.kern, face, first, second) {
+                    Some(kerning) => f32::from(kerning),
+                    None if self.1.gpos_kern => {
+                        gpos::pair_kerning(face, false, first.0, second.0).unwrap_or_default()
+                    }
+                    None => 0.0,
+                }
+            }
+
+            #[inline]
+            fn v_kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+                let face = self.0.as_face_ref();
+                match Subtables::kerning(&self.1.v_kern, face, first, second) {
+                    Some(kerning) => f32::from(kerning),
+                    None if self.1.gpos_v_kern => {
+                        gpos::pair_kerning(face, true, first.0, second.0).unwrap_or_default()
+                    }
+                    None => 0.0,
+                }
+            }
+
+            fn kern_pairs(&self) -> Vec<(GlyphId, GlyphId, f32)> {
+                kern::kern_pairs(self.0.as_face_ref())
+            }
+
+            #[inline]
+            fn optical_bounds_unscaled(&self, id: GlyphId) -> Option<OpticalBounds> {
+                opbd::optical_bounds(self.0.as_face_ref(), id)
+            }
+
+            #[inline]
+            fn glyph_complexity(&self, id: GlyphId) -> Option<GlyphComplexity> {
+                glyf::glyph_complexity(self.0.as_face_ref(), id)
+            }
+
+            fn many_to_one_ranges(&self) -> Vec<(RangeInclusive<char>, GlyphId)> {
+                cmap::many_to_one_ranges(self.0.as_face_ref())
+            }
+
+            #[inline]
+            fn device_h_advance(&self, id: GlyphId, ppem: u16) -> Option<f32> {
+                device::h_advance(self.0.as_face_ref(), id, ppem).map(f32::from)
+            }
+
+            #[inline]
+            fn device_v_metrics(&self, ppem: u16) -> Option<DeviceVMetrics> {
+                device::v_metrics(self.0.as_face_ref(), ppem)
             }
 
             #[inline]
@@ -245,50 +1023,127 @@ macro_rules! impl_font {
                 face.colr_layers(id.into()).is_some()
             }
 
-            fn color_outlines(&self, id: GlyphId) -> Option<Vec<(Outline,u32)>> {
+            #[inline]
+            fn color_outlines(&self, id: GlyphId) -> Option<ColorOutline> {
+                self.color_outlines_with_palette(id, 0)
+            }
+
+            fn color_outlines_with_palette(
+                &self,
+                id: GlyphId,
+                palette: usize,
+            ) -> Option<ColorOutline> {
                 let face = self.0.as_face_ref();
-                face
-                    .colr_layers(id.into())
-                    .map(|iter| iter.map(|layer| {
-                        let color = face.cpal_color(0, layer.palette_index).unwrap();
-                        let color_int = u32::from_be_bytes([color.r,color.g,color.b,color.a]);
-                        let outline = self.outline(GlyphId(layer.glyph_id)).unwrap();
+                // layers without outlines, e.g. space-like glyphs, draw nothing so are skipped
+                let layers = face
+                    .colr_layers(id.into())?
+                    .enumerate()
+                    .filter_map(|(order, layer)| {
+                        let palette_index = layer.palette_index;
+                        // colors missing from the palette are transparent
+                        let rgba = cpal::color(face, palette, palette_index).unwrap_or(0);
+                        Some(ColorLayer {
+                            outline: self.outline(GlyphId(layer.glyph_id))?,
+                            rgba: rgba.to_be_bytes(),
+                            palette_index,
+                            order: order as u16,
+                        })
+                    })
+                    .collect();
+                Some(ColorOutline { layers })
+            }
 
-                        (outline,color_int)
-                    }).collect())
+            #[inline]
+            fn color_palettes(&self) -> usize {
+                cpal::palette_count(self.0.as_face_ref())
+            }
+
+            #[inline]
+            fn color_palette(&self, palette: usize) -> Option<ColorPalette> {
+                cpal::palette(self.0.as_face_ref(), palette)
+            }
+
+            fn color_paint(&self, id: GlyphId) -> Option<ColorGlyphPaint> {
+                colr::color_paint(self.0.as_face_ref(), id)
+            }
+
+            #[inline]
+            fn glyph_raster_image(
+                &self,
+                id: GlyphId,
+                pixels_per_em: u16,
+            ) -> Option<GlyphImage<'_>> {
+                bitmap::raster_image(self.0.as_face_ref(), id, pixels_per_em)
             }
 
             fn outline(&self, id: GlyphId) -> Option<Outline> {
-                let mut outliner = outliner::OutlineCurveBuilder::default();
-
-                let owned_ttf_parser::Rect {
-                    x_min,
-                    y_min,
-                    x_max,
-                    y_max,
-                } = self
-                    .0
+                let mut curves = Vec::new();
+                let bounds = self.outline_into(id, &mut curves)?;
+                Some(Outline { bounds, curves })
+            }
+
+            #[inline]
+            fn glyph_bounds_unscaled(&self, id: GlyphId) -> Option<Rect> {
+                self.0
                     .as_face_ref()
-                    .outline_glyph(id.into(), &mut outliner)?;
+                    .glyph_bounding_box(id.into())
+                    .map(unscaled_rect)
+            }
 
-                let bounds = Rect {
-                    min: point(x_min as f32, y_max as f32),
-                    max: point(x_max as f32, y_min as f32),
-                };
+            fn outline_into(&self, id: GlyphId, curves: &mut Vec<OutlineCurve>) -> Option<Rect> {
+                let mut buffer = core::mem::take(curves);
+                buffer.clear();
+                let (buffer, bounds) = outline_glyph(self.0.as_face_ref(), id, buffer);
+                *curves = buffer;
+                bounds
+            }
 
-                Some(Outline {
-                    bounds,
-                    curves: outliner.take_outline(),
+            #[cfg(feature = "bumpalo")]
+            fn outline_in<'bump>(
+                &self,
+                id: GlyphId,
+                arena: &'bump bumpalo2::Bump,
+            ) -> Option<ArenaOutline<'bump>> {
+                let buffer = bumpalo2::collections::Vec::new_in(arena);
+                let (buffer, bounds) = outline_glyph(self.0.as_face_ref(), id, buffer);
+                Some(ArenaOutline {
+                    bounds: bounds?,
+                    curves: buffer.into_bump_slice(),
                 })
             }
 
+            #[inline]
+            fn outline_with(&self, id: GlyphId, sink: &mut dyn OutlineSink) -> Option<Rect> {
+                let mut builder = outliner::SinkBuilder(sink);
+                self.0
+                    .as_face_ref()
+                    .outline_glyph(id.into(), &mut builder)
+                    .map(unscaled_rect)
+            }
+
             #[inline]
             fn glyph_count(&self) -> usize {
                 self.0.as_face_ref().number_of_glyphs() as _
             }
+
+            #[inline]
+            fn glyph_name(&self, id: GlyphId) -> Option<&str> {
+                self.0.as_face_ref().glyph_name(id.into())
+            }
+
+            #[inline]
+            fn subset(&self, glyphs: &[GlyphId]) -> Option<FontSubset> {
+                subset::subset(self.0.as_face_ref(), glyphs)
+            }
+
+            fn fingerprint(&self) -> u64 {
+                face_fingerprint(self.0.as_face_ref())
+            }
         }
     };
 }
 
 impl_font!(FontRef<'_>);
 impl_font!(FontVec);
+#[cfg(feature = "memmap2")]
+impl_font!(FontMmap);
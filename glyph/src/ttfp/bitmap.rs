@@ -0,0 +1,206 @@
+//! Embedded bitmap glyphs, sbix, CBLC/CBDT & EBLC/EBDT tables.
+use super::{read_u16, read_u32};
+use crate::{point, GlyphId, GlyphImage, GlyphImageFormat};
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use owned_ttf_parser::{Face, RasterImageFormat, Tag};
+
+/// Returns the embedded image of a glyph from the strike best matching `pixels_per_em`.
+///
+/// Color png images, from sbix or CBDT, are preferred over EBDT monochrome & grayscale
+/// bitmaps.
+pub(crate) fn raster_image<'a>(
+    face: &'a Face<'_>,
+    id: GlyphId,
+    pixels_per_em: u16,
+) -> Option<GlyphImage<'a>> {
+    if let Some(image) = face.glyph_raster_image(id.into(), pixels_per_em) {
+        let format = match image.format {
+            RasterImageFormat::PNG => GlyphImageFormat::Png,
+        };
+        return Some(GlyphImage {
+            data: Cow::Borrowed(image.data),
+            format,
+            width: image.width,
+            height: image.height,
+            // x, y offset the bottom-left of the image, y up
+            bearing: point(
+                f32::from(image.x),
+                -(f32::from(image.y) + f32::from(image.height)),
+            ),
+            pixels_per_em: image.pixels_per_em,
+        });
+    }
+
+    let eblc = face.table_data(Tag::from_bytes(b"EBLC"))?;
+    let ebdt = face.table_data(Tag::from_bytes(b"EBDT"))?;
+    ebdt_image(eblc, ebdt, id.0, pixels_per_em)
+}
+
+/// Bitmap glyph metrics, common to big & small metrics records.
+#[derive(Clone, Copy)]
+struct Metrics {
+    width: u8,
+    height: u8,
+    bearing_x: i8,
+    bearing_y: i8,
+}
+
+impl Metrics {
+    /// Reads the leading height, width, bearing x & bearing y shared by big & small metrics.
+    #[inline]
+    fn read(data: &[u8], offset: usize) -> Option<Self> {
+        let bytes = data.get(offset..offset + 4)?;
+        Some(Self {
+            height: bytes[0],
+            width: bytes[1],
+            bearing_x: bytes[2] as i8,
+            bearing_y: bytes[3] as i8,
+        })
+    }
+}
+
+/// Decodes an EBDT glyph bitmap into an 8 bit coverage mask.
+fn ebdt_image(
+    eblc: &[u8],
+    ebdt: &[u8],
+    glyph: u16,
+    pixels_per_em: u16,
+) -> Option<GlyphImage<'static>> {
+    let strike = best_strike(eblc, glyph, pixels_per_em)?;
+    let bit_depth = *eblc.get(strike + 46)?;
+    let ppem = u16::from(*eblc.get(strike + 45)?);
+
+    let subtables = read_u32(eblc, strike)? as usize;
+    let subtable_count = read_u32(eblc, strike + 8)? as usize;
+    let (first, subtable) = (0..subtable_count)
+        .map(|idx| subtables + idx * 8)
+        .find_map(|record| {
+            let first = read_u16(eblc, record)?;
+            let last = read_u16(eblc, record + 2)?;
+            if first <= glyph && glyph <= last {
+                Some((first, subtables + read_u32(eblc, record + 4)? as usize))
+            } else {
+                None
+            }
+        })?;
+    let (offset, index_metrics) = glyph_location(eblc, subtable, first, glyph)?;
+
+    let (metrics, data, bit_aligned) = match read_u16(eblc, subtable + 2)? {
+        // small metrics
+        1 => (Metrics::read(ebdt, offset)?, offset + 5, false),
+        2 => (Metrics::read(ebdt, offset)?, offset + 5, true),
+        // metrics in the index subtable
+        5 => (index_metrics?, offset, true),
+        // big metrics
+        6 => (Metrics::read(ebdt, offset)?, offset + 8, false),
+        7 => (Metrics::read(ebdt, offset)?, offset + 8, true),
+        _ => return None,
+    };
+
+    let (width, height) = (usize::from(metrics.width), usize::from(metrics.height));
+    let depth = usize::from(bit_depth);
+    if ![1, 2, 4, 8].contains(&depth) {
+        return None;
+    }
+    let max = (1 << depth) - 1;
+    let row_bits = if bit_aligned {
+        width * depth
+    } else {
+        (width * depth).div_ceil(8) * 8
+    };
+    let bits = ebdt.get(data..data + (row_bits * height).div_ceil(8))?;
+
+    let mut mask = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let bit = y * row_bits + x * depth;
+            let byte = bits[bit / 8];
+            let value = (usize::from(byte) >> (8 - depth - bit % 8)) & max;
+            mask.push((value * 255 / max) as u8);
+        }
+    }
+
+    Some(GlyphImage {
+        data: Cow::Owned(mask),
+        format: GlyphImageFormat::Mask,
+        width: u16::from(metrics.width),
+        height: u16::from(metrics.height),
+        bearing: point(f32::from(metrics.bearing_x), -f32::from(metrics.bearing_y)),
+        pixels_per_em: ppem,
+    })
+}
+
+/// Returns the offset of the strike containing `glyph` with the smallest ppem at least
+/// `pixels_per_em`, otherwise the largest.
+fn best_strike(eblc: &[u8], glyph: u16, pixels_per_em: u16) -> Option<usize> {
+    let count = read_u32(eblc, 4)? as usize;
+    (0..count)
+        .map(|idx| 8 + idx * 48)
+        .filter_map(|strike| {
+            let start = read_u16(eblc, strike + 40)?;
+            let end = read_u16(eblc, strike + 42)?;
+            let ppem = u16::from(*eblc.get(strike + 45)?);
+            if start <= glyph && glyph <= end {
+                Some((ppem, strike))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|&(ppem, _)| {
+            if ppem >= pixels_per_em {
+                (0, ppem)
+            } else {
+                (1, u16::MAX - ppem)
+            }
+        })
+        .map(|(_, strike)| strike)
+}
+
+/// Returns the EBDT offset of `glyph` in an index subtable starting at glyph `first`,
+/// along with the metrics shared by all glyphs in the subtable, for index formats that
+/// have them.
+fn glyph_location(
+    eblc: &[u8],
+    subtable: usize,
+    first: u16,
+    glyph: u16,
+) -> Option<(usize, Option<Metrics>)> {
+    let index = usize::from(glyph - first);
+    let image_data = read_u32(eblc, subtable + 4)? as usize;
+    match read_u16(eblc, subtable)? {
+        1 => {
+            let offset = read_u32(eblc, subtable + 8 + index * 4)? as usize;
+            Some((image_data + offset, None))
+        }
+        2 => {
+            let size = read_u32(eblc, subtable + 8)? as usize;
+            let metrics = Metrics::read(eblc, subtable + 12)?;
+            Some((image_data + size * index, Some(metrics)))
+        }
+        3 => {
+            let offset = usize::from(read_u16(eblc, subtable + 8 + index * 2)?);
+            Some((image_data + offset, None))
+        }
+        // sparse glyph id & offset pairs
+        4 => {
+            let count = read_u32(eblc, subtable + 8)? as usize;
+            (0..count)
+                .map(|idx| subtable + 12 + idx * 4)
+                .find(|&pair| read_u16(eblc, pair) == Some(glyph))
+                .and_then(|pair| read_u16(eblc, pair + 2))
+                .map(|offset| (image_data + usize::from(offset), None))
+        }
+        // sparse glyph ids with constant image size
+        5 => {
+            let size = read_u32(eblc, subtable + 8)? as usize;
+            let metrics = Metrics::read(eblc, subtable + 12)?;
+            let count = read_u32(eblc, subtable + 20)? as usize;
+            (0..count)
+                .find(|&idx| read_u16(eblc, subtable + 24 + idx * 2) == Some(glyph))
+                .map(|idx| (image_data + size * idx, Some(metrics)))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,195 @@
+//! cmap subtable lookups & format 13 (many-to-one range mappings) support.
+use super::{read_u16, read_u32};
+use crate::GlyphId;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::{convert::TryFrom, ops::RangeInclusive};
+use owned_ttf_parser::{cmap, Face, PlatformId, Tag};
+
+const WINDOWS_UNICODE_FULL_REPERTOIRE_ENCODING_ID: u16 = 10;
+
+/// A cmap subtable selected on load, located once so lookups read it directly rather
+/// than re-walking the encoding records.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CmapSubtable {
+    /// Encoding record index.
+    index: u16,
+    /// Subtable offset in the cmap table.
+    offset: u32,
+    format: u16,
+}
+
+impl CmapSubtable {
+    /// Locates the subtable of encoding record `index`.
+    pub(crate) fn new(face: &Face<'_>, index: usize) -> Option<Self> {
+        let cmap = face.table_data(Tag::from_bytes(b"cmap"))?;
+        let offset = read_u32(cmap, 4 + index * 8 + 4)?;
+        Some(Self {
+            index: index as u16,
+            offset,
+            format: read_u16(cmap, offset as usize)?,
+        })
+    }
+
+    /// Encoding record index.
+    #[inline]
+    pub(crate) fn index(&self) -> usize {
+        usize::from(self.index)
+    }
+
+    /// Maps a codepoint to a glyph, `None` if unmapped or mapped to `.notdef`.
+    ///
+    /// Common formats are read directly, others fall back to `cmap::Subtable::glyph_index`.
+    pub(crate) fn glyph_index(&self, face: &Face<'_>, codepoint: u32) -> Option<u16> {
+        let cmap = face.table_data(Tag::from_bytes(b"cmap"))?;
+        let data = cmap.get(self.offset as usize..)?;
+        let id = match self.format {
+            0 if codepoint < 256 => data.get(6 + codepoint as usize).map(|&id| u16::from(id)),
+            0 => None,
+            4 => format4_glyph_index(data, codepoint),
+            6 => {
+                let first = u32::from(read_u16(data, 6)?);
+                let count = u32::from(read_u16(data, 8)?);
+                let idx = codepoint.checked_sub(first).filter(|&idx| idx < count)?;
+                read_u16(data, 10 + idx as usize * 2)
+            }
+            12 | 13 => {
+                let count = read_u32(data, 12)? as usize;
+                let group = |idx: usize| -> Option<(u32, u32, u32)> {
+                    let group = 16 + idx * 12;
+                    Some((
+                        read_u32(data, group)?,
+                        read_u32(data, group + 4)?,
+                        read_u32(data, group + 8)?,
+                    ))
+                };
+                // groups are sorted by start char
+                let (mut lo, mut hi) = (0, count);
+                let mut found = None;
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    let (start, end, glyph) = group(mid)?;
+                    if codepoint < start {
+                        hi = mid;
+                    } else if codepoint > end {
+                        lo = mid + 1;
+                    } else {
+                        found = Some((start, glyph));
+                        break;
+                    }
+                }
+                let (start, glyph) = found?;
+                let id = match self.format {
+                    12 => glyph.checked_add(codepoint - start)?,
+                    _ => glyph,
+                };
+                u16::try_from(id).ok()
+            }
+            // mixed coverage & variation sequences don't map single codepoints
+            8 | 14 => None,
+            _ => face
+                .character_mapping_subtables()
+                .nth(usize::from(self.index))?
+                .glyph_index(codepoint)
+                .map(|id| id.0),
+        };
+        id.filter(|&id| id != 0)
+    }
+}
+
+/// Maps a codepoint using format 4 (segment mapping to delta values) subtable data.
+fn format4_glyph_index(data: &[u8], codepoint: u32) -> Option<u16> {
+    let c = u16::try_from(codepoint).ok()?;
+    let seg_count = usize::from(read_u16(data, 6)? / 2);
+    let end_codes = 14;
+    let start_codes = end_codes + seg_count * 2 + 2;
+    let id_deltas = start_codes + seg_count * 2;
+    let id_range_offsets = id_deltas + seg_count * 2;
+
+    // first segment ending at or after `c`
+    let (mut lo, mut hi) = (0, seg_count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if read_u16(data, end_codes + mid * 2)? < c {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    let seg = lo;
+    if seg == seg_count {
+        return None;
+    }
+    let start = read_u16(data, start_codes + seg * 2)?;
+    if start > c {
+        return None;
+    }
+    let id_delta = read_u16(data, id_deltas + seg * 2)?;
+    let id_range_offset = usize::from(read_u16(data, id_range_offsets + seg * 2)?);
+    if id_range_offset == 0 {
+        return Some(c.wrapping_add(id_delta));
+    }
+    let glyph_offset = id_range_offsets + seg * 2 + id_range_offset + usize::from(c - start) * 2;
+    match read_u16(data, glyph_offset)? {
+        0 => None,
+        id => Some(id.wrapping_add(id_delta)),
+    }
+}
+
+/// Returns `true` for format 13 subtables with a unicode encoding.
+///
+/// `cmap::Subtable::is_unicode` only accepts Windows full repertoire encodings for format 12.
+#[inline]
+pub(crate) fn is_unicode_many_to_one(subtable: &cmap::Subtable<'_>) -> bool {
+    subtable.format() == cmap::Format::ManyToOneRangeMappings
+        && match subtable.platform_id() {
+            PlatformId::Unicode => true,
+            PlatformId::Windows => {
+                subtable.encoding_id() == WINDOWS_UNICODE_FULL_REPERTOIRE_ENCODING_ID
+            }
+            _ => false,
+        }
+}
+
+/// Returns the char ranges of the first unicode format 13 subtable, each mapped to a
+/// single glyph.
+pub(crate) fn many_to_one_ranges(face: &Face<'_>) -> Vec<(RangeInclusive<char>, GlyphId)> {
+    let mut ranges = Vec::new();
+    let cmap = match face.table_data(Tag::from_bytes(b"cmap")) {
+        Some(data) => data,
+        None => return ranges,
+    };
+
+    let num_tables = read_u16(cmap, 2).unwrap_or(0);
+    let subtable = (0..usize::from(num_tables))
+        .map(|idx| 4 + idx * 8)
+        .filter_map(|record| {
+            let platform = read_u16(cmap, record)?;
+            let encoding = read_u16(cmap, record + 2)?;
+            let offset = read_u32(cmap, record + 4)? as usize;
+            let unicode = platform == 0
+                || (platform == 3 && encoding == WINDOWS_UNICODE_FULL_REPERTOIRE_ENCODING_ID);
+            Some(offset).filter(|_| unicode)
+        })
+        .find(|&offset| read_u16(cmap, offset) == Some(13));
+
+    if let Some(offset) = subtable {
+        let count = read_u32(cmap, offset + 12).unwrap_or(0) as usize;
+        for group in (0..count).map(|idx| offset + 16 + idx * 12) {
+            let (start, end, glyph) = match (
+                read_u32(cmap, group),
+                read_u32(cmap, group + 4),
+                read_u32(cmap, group + 8),
+            ) {
+                (Some(start), Some(end), Some(glyph)) => (start, end, glyph),
+                _ => break,
+            };
+            if let (Some(start), Some(end)) =
+                (core::char::from_u32(start), core::char::from_u32(end))
+            {
+                ranges.push((start..=end, GlyphId(glyph as u16)));
+            }
+        }
+    }
+    ranges
+}
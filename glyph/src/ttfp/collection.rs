@@ -0,0 +1,259 @@
+use super::{
+    font_error, read_u16, read_u32,
+    subset::{checksum, pad4, write_offset_table},
+    FontRef, Subtables,
+};
+use crate::FontError;
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+use owned_ttf_parser::Face;
+
+/// Font collection (e.g. ttc) data stored once in a `Vec<u8>`, exposing each face as a
+/// [`FontRef`](struct.FontRef.html).
+///
+/// All faces are validated on load, subsequent access only re-reads each face's table
+/// directory. Non-collection font data is treated as a collection of one face.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontVecCollection};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// # let owned_font_data = include_bytes!("../../../dev/fonts/Exo2-Light.otf").to_vec();
+/// let collection = FontVecCollection::try_from_vec(owned_font_data)?;
+/// assert_eq!(collection.len(), 1);
+///
+/// let font = collection.get(0).unwrap();
+/// assert_eq!(font.glyph_id('s'), ab_glyph::GlyphId(56));
+///
+/// for font in collection.iter() {
+///     assert!(font.glyph_count() > 0);
+/// }
+/// # Ok(()) }
+/// ```
+pub struct FontVecCollection {
+    data: Vec<u8>,
+    /// Subtable selections for each face.
+    faces: Vec<Subtables>,
+}
+
+impl fmt::Debug for FontVecCollection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FontVecCollection({} faces)", self.faces.len())
+    }
+}
+
+impl FontVecCollection {
+    /// Creates a `FontVecCollection` from owned font collection, or single font, data.
+    ///
+    /// Errors if any of the faces are invalid.
+    pub fn try_from_vec(data: Vec<u8>) -> Result<Self, FontError> {
+        let count = FontRef::fonts_in_collection(&data);
+        let faces = (0..count)
+            .map(|index| {
+                let face = Face::from_slice(&data, index).map_err(font_error)?;
+                Ok(Subtables::new(&face))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { data, faces })
+    }
+
+    /// Returns the number of faces.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Returns `true` if there are no faces.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// Returns the face at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<FontRef<'_>> {
+        let subtables = self.faces.get(index)?.clone();
+        let face = Face::from_slice(&self.data, index as u32).ok()?;
+        Some(FontRef(face, subtables, index as u32, Some(&self.data)))
+    }
+
+    /// Returns an iterator over each face.
+    pub fn iter(&self) -> impl Iterator<Item = FontRef<'_>> + '_ {
+        (0..self.len()).filter_map(move |index| self.get(index))
+    }
+
+    /// Returns the font collection data.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the owned font collection data.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// Builds font collection (ttc) data from multiple faces, e.g. to bundle
+/// [subset](trait.Font.html#method.subset) faces together.
+///
+/// Identical tables, e.g. a shared `name` or `cmap`, are stored once & referenced by
+/// each face. Tables are copied unchanged.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontCollectionBuilder, FontRef, FontVecCollection};
+///
+/// # fn main() -> Result<(), ab_glyph::FontError> {
+/// let exo = FontRef::try_from_slice(include_bytes!("../../../dev/fonts/Exo2-Light.ttf"))?;
+/// let open_sans = include_bytes!("../../../dev/fonts/OpenSans-Italic.ttf");
+///
+/// let mut builder = FontCollectionBuilder::new();
+/// builder.add_font(&exo)?.add_data(open_sans, 0)?;
+/// let data = builder.build();
+///
+/// let collection = FontVecCollection::try_from_vec(data)?;
+/// assert_eq!(collection.len(), 2);
+/// let face = collection.get(1).unwrap();
+/// assert_eq!(face.glyph_count(), FontRef::try_from_slice(open_sans)?.glyph_count());
+///
+/// // a face added twice shares all its tables
+/// let single = builder.build().len();
+/// builder.add_font(&exo)?;
+/// assert!(builder.build().len() < single + exo.as_slice().unwrap().len() / 2);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FontCollectionBuilder {
+    /// Each face's sfnt version & tables.
+    faces: Vec<(u32, Tables)>,
+}
+
+/// Face `(tag, data)` tables sorted by tag.
+type Tables = Vec<([u8; 4], Vec<u8>)>;
+
+impl FontCollectionBuilder {
+    /// Creates a builder with no faces.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a loaded face.
+    ///
+    /// Errors with `FontError::Malformed` for faces without
+    /// [data](struct.FontRef.html#method.as_slice).
+    #[inline]
+    pub fn add_font(&mut self, font: &FontRef<'_>) -> Result<&mut Self, FontError> {
+        let data = font.as_slice().ok_or(FontError::Malformed)?;
+        self.add_data(data, font.index())
+    }
+
+    /// Adds the face at `index` of font, or font collection, data. For simple fonts use `0`.
+    ///
+    /// Errors if the table directory is invalid, the face's tables are not validated.
+    pub fn add_data(&mut self, data: &[u8], index: u32) -> Result<&mut Self, FontError> {
+        let offset = if data.get(..4) == Some(&b"ttcf"[..]) {
+            let count = read_u32(data, 8).ok_or(FontError::Malformed)?;
+            if index >= count {
+                return Err(FontError::IndexOutOfBounds);
+            }
+            read_u32(data, 12 + 4 * index as usize).ok_or(FontError::Malformed)? as usize
+        } else if index == 0 {
+            0
+        } else {
+            return Err(FontError::IndexOutOfBounds);
+        };
+
+        let sfnt_version = read_u32(data, offset).ok_or(FontError::Malformed)?;
+        if !matches!(
+            &sfnt_version.to_be_bytes(),
+            b"\0\x01\0\0" | b"OTTO" | b"true"
+        ) {
+            return Err(FontError::UnknownMagic);
+        }
+        let num_tables = read_u16(data, offset + 4).ok_or(FontError::Malformed)?;
+        if num_tables == 0 {
+            return Err(FontError::Malformed);
+        }
+
+        let mut tables = Vec::with_capacity(usize::from(num_tables));
+        for record in (0..usize::from(num_tables)).map(|idx| offset + 12 + idx * 16) {
+            let mut tag = [0; 4];
+            tag.copy_from_slice(data.get(record..record + 4).ok_or(FontError::Malformed)?);
+            let start = read_u32(data, record + 8).ok_or(FontError::Malformed)? as usize;
+            let len = read_u32(data, record + 12).ok_or(FontError::Malformed)? as usize;
+            let table = data.get(start..start + len).ok_or(FontError::Malformed)?;
+            tables.push((tag, table.to_vec()));
+        }
+        tables.sort_by_key(|(tag, _)| *tag);
+
+        self.faces.push((sfnt_version, tables));
+        Ok(self)
+    }
+
+    /// Returns the number of faces added.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Returns `true` if no faces have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// Writes the font collection data.
+    pub fn build(&self) -> Vec<u8> {
+        // header, offset tables & table records precede the table data
+        let mut offset = 12
+            + self
+                .faces
+                .iter()
+                .map(|(_, tables)| 4 + 12 + 16 * tables.len())
+                .sum::<usize>();
+
+        // offsets of unique table data, in order of first use
+        let mut offsets = BTreeMap::new();
+        let mut unique = Vec::new();
+        for (_, tables) in &self.faces {
+            for (_, data) in tables {
+                offsets.entry(&data[..]).or_insert_with(|| {
+                    unique.push(&data[..]);
+                    let table_offset = offset;
+                    offset += (data.len() + 3) & !3;
+                    table_offset
+                });
+            }
+        }
+
+        let mut font = Vec::with_capacity(offset);
+        font.extend_from_slice(b"ttcf");
+        font.extend_from_slice(&0x0001_0000_u32.to_be_bytes());
+        font.extend_from_slice(&(self.faces.len() as u32).to_be_bytes());
+        let mut face_offset = 12 + 4 * self.faces.len();
+        for (_, tables) in &self.faces {
+            font.extend_from_slice(&(face_offset as u32).to_be_bytes());
+            face_offset += 12 + 16 * tables.len();
+        }
+
+        for (sfnt_version, tables) in &self.faces {
+            write_offset_table(&mut font, *sfnt_version, tables.len() as u16);
+            for (tag, data) in tables {
+                font.extend_from_slice(tag);
+                font.extend_from_slice(&checksum(data).to_be_bytes());
+                font.extend_from_slice(&(offsets[&data[..]] as u32).to_be_bytes());
+                font.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            }
+        }
+        for data in unique {
+            font.extend_from_slice(data);
+            pad4(&mut font);
+        }
+        font
+    }
+}
@@ -0,0 +1,320 @@
+//! Color (COLR) version 1 paint graphs.
+use super::{cpal, read_u16, read_u32};
+#[cfg(all(feature = "libm", not(feature = "std")))]
+use crate::nostd_float::FloatExt;
+use crate::{point, ColorGlyphPaint, ColorLine, ColorStop, CompositeMode, GlyphId, GradientExtend};
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+use core::cell::{Cell, RefCell};
+use owned_ttf_parser::{Face, Tag};
+
+/// Maximum paint graph nesting.
+const MAX_DEPTH: u8 = 64;
+/// Maximum paints parsed for a glyph, guarding against shared sub-graphs being
+/// expanded exponentially.
+const MAX_PAINTS: u32 = 8192;
+
+/// Returns the paint graph of a color glyph.
+///
+/// COLR version 1 base glyph paints are preferred, falling back to version 0 layers
+/// represented as solid filled glyphs.
+pub(crate) fn color_paint(face: &Face<'_>, id: GlyphId) -> Option<ColorGlyphPaint> {
+    let colr = Colr::new(face)?;
+    colr.base_paint(id.0, 0).or_else(|| colr.v0_layers(id.0))
+}
+
+struct Colr<'a, 'font> {
+    face: &'a Face<'font>,
+    data: &'a [u8],
+    /// Remaining paints that may be parsed.
+    budget: Cell<u32>,
+    /// Offsets of the paints currently being parsed, a repeat is a cycle.
+    visiting: RefCell<Vec<usize>>,
+}
+
+impl<'a, 'font> Colr<'a, 'font> {
+    fn new(face: &'a Face<'font>) -> Option<Self> {
+        let data = face.table_data(Tag::from_bytes(b"COLR"))?;
+        Some(Self {
+            face,
+            data,
+            budget: Cell::new(MAX_PAINTS),
+            visiting: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Version 1 `BaseGlyphList` paint of a glyph.
+    fn base_paint(&self, glyph: u16, depth: u8) -> Option<ColorGlyphPaint> {
+        if read_u16(self.data, 0)? < 1 {
+            return None;
+        }
+        let list = offset32(self.data, 14)?;
+        let count = read_u32(self.data, list)? as usize;
+        let record = self.find_record(list + 4, count, glyph)?;
+        let paint = list + read_u32(self.data, record + 2)? as usize;
+        self.paint(paint, depth)
+    }
+
+    /// Version 0 layers as solid filled glyphs.
+    fn v0_layers(&self, glyph: u16) -> Option<ColorGlyphPaint> {
+        let count = usize::from(read_u16(self.data, 2)?);
+        let base_records = read_u32(self.data, 4)? as usize;
+        let layer_records = read_u32(self.data, 8)? as usize;
+        let record = self.find_record(base_records, count, glyph)?;
+        let first = usize::from(read_u16(self.data, record + 2)?);
+        let layers = usize::from(read_u16(self.data, record + 4)?);
+        let layers = (first..first + layers)
+            .map(|idx| {
+                let layer = layer_records + idx * 4;
+                Some(ColorGlyphPaint::Glyph {
+                    id: GlyphId(read_u16(self.data, layer)?),
+                    paint: Box::new(ColorGlyphPaint::Solid(
+                        self.color(read_u16(self.data, layer + 2)?, 1.0),
+                    )),
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(ColorGlyphPaint::Layers(layers))
+    }
+
+    /// Binary searches `count` 6 byte records from `start`, sorted by a leading glyph id.
+    fn find_record(&self, start: usize, count: usize, glyph: u16) -> Option<usize> {
+        let (mut lo, mut hi) = (0, count);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let record = start + mid * 6;
+            let id = read_u16(self.data, record)?;
+            if id == glyph {
+                return Some(record);
+            } else if id < glyph {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        None
+    }
+
+    /// Palette 0 color of `index`, with `alpha` applied.
+    fn color(&self, index: u16, alpha: f32) -> u32 {
+        let [r, g, b, a] = cpal::color(self.face, 0, index).unwrap_or(0).to_be_bytes();
+        let a = (f32::from(a) * alpha.clamp(0.0, 1.0) + 0.5) as u8;
+        u32::from_be_bytes([r, g, b, a])
+    }
+
+    /// Parses the paint table at `offset`, failing on cycles or if the paint budget
+    /// is exhausted.
+    fn paint(&self, offset: usize, depth: u8) -> Option<ColorGlyphPaint> {
+        if depth >= MAX_DEPTH || self.visiting.borrow().contains(&offset) {
+            return None;
+        }
+        let budget = self.budget.get().checked_sub(1)?;
+        self.budget.set(budget);
+
+        self.visiting.borrow_mut().push(offset);
+        let paint = self.parse_paint(offset, depth);
+        self.visiting.borrow_mut().pop();
+        paint
+    }
+
+    fn parse_paint(&self, offset: usize, depth: u8) -> Option<ColorGlyphPaint> {
+        let data = self.data;
+        let depth = depth + 1;
+        let format = *data.get(offset)?;
+        let child = |at: usize| self.paint(offset + offset24(data, offset + at)?, depth);
+        let fword = |at: usize| read_u16(data, offset + at).map(|v| f32::from(v as i16));
+        let f2dot14 = |at: usize| read_u16(data, offset + at).map(f2dot14_to_f32);
+        // var formats are odd & follow the non-var layout, variation deltas are ignored
+        let var = format % 2 == 1;
+
+        Some(match format {
+            1 => {
+                let count = usize::from(*data.get(offset + 1)?);
+                let first = read_u32(data, offset + 2)? as usize;
+                let list = offset32(data, 18)?;
+                let layers = (first..first + count)
+                    .map(|idx| {
+                        let paint = list + read_u32(data, list + 4 + idx * 4)? as usize;
+                        self.paint(paint, depth)
+                    })
+                    .collect::<Option<_>>()?;
+                ColorGlyphPaint::Layers(layers)
+            }
+            2 | 3 => ColorGlyphPaint::Solid(self.color(read_u16(data, offset + 1)?, f2dot14(3)?)),
+            4 | 5 => ColorGlyphPaint::LinearGradient {
+                color_line: self.color_line(offset + offset24(data, offset + 1)?, var)?,
+                p0: point(fword(4)?, fword(6)?),
+                p1: point(fword(8)?, fword(10)?),
+                p2: point(fword(12)?, fword(14)?),
+            },
+            6 | 7 => ColorGlyphPaint::RadialGradient {
+                color_line: self.color_line(offset + offset24(data, offset + 1)?, var)?,
+                c0: point(fword(4)?, fword(6)?),
+                r0: f32::from(read_u16(data, offset + 8)?),
+                c1: point(fword(10)?, fword(12)?),
+                r1: f32::from(read_u16(data, offset + 14)?),
+            },
+            8 | 9 => ColorGlyphPaint::SweepGradient {
+                color_line: self.color_line(offset + offset24(data, offset + 1)?, var)?,
+                center: point(fword(4)?, fword(6)?),
+                // angles are in units of 180 degrees
+                start_angle: f2dot14(8)? * 180.0,
+                end_angle: f2dot14(10)? * 180.0,
+            },
+            10 => ColorGlyphPaint::Glyph {
+                paint: Box::new(child(1)?),
+                id: GlyphId(read_u16(data, offset + 4)?),
+            },
+            11 => self.base_paint(read_u16(data, offset + 1)?, depth)?,
+            12 | 13 => {
+                let affine = offset + offset24(data, offset + 4)?;
+                let fixed =
+                    |n: usize| read_u32(data, affine + n * 4).map(|v| v as i32 as f32 / 65536.0);
+                transform(
+                    [
+                        fixed(0)?,
+                        fixed(1)?,
+                        fixed(2)?,
+                        fixed(3)?,
+                        fixed(4)?,
+                        fixed(5)?,
+                    ],
+                    child(1)?,
+                )
+            }
+            14 | 15 => transform([1.0, 0.0, 0.0, 1.0, fword(4)?, fword(6)?], child(1)?),
+            16..=23 => {
+                let uniform = format >= 20;
+                let (sx, sy, center) = if uniform {
+                    (f2dot14(4)?, f2dot14(4)?, 6)
+                } else {
+                    (f2dot14(4)?, f2dot14(6)?, 8)
+                };
+                let scale = [sx, 0.0, 0.0, sy, 0.0, 0.0];
+                let around = matches!(format, 18 | 19 | 22 | 23);
+                around_center(scale, around, fword(center), fword(center + 2), child(1)?)?
+            }
+            24..=27 => {
+                let angle = f2dot14(4)? * core::f32::consts::PI;
+                let (sin, cos) = (angle.sin(), angle.cos());
+                let rotate = [cos, sin, -sin, cos, 0.0, 0.0];
+                around_center(rotate, format >= 26, fword(6), fword(8), child(1)?)?
+            }
+            28..=31 => {
+                let x_skew = f2dot14(4)? * core::f32::consts::PI;
+                let y_skew = f2dot14(6)? * core::f32::consts::PI;
+                let skew = [1.0, y_skew.tan(), -x_skew.tan(), 1.0, 0.0, 0.0];
+                around_center(skew, format >= 30, fword(8), fword(10), child(1)?)?
+            }
+            32 => ColorGlyphPaint::Composite {
+                source: Box::new(child(1)?),
+                mode: composite_mode(*data.get(offset + 4)?)?,
+                backdrop: Box::new(child(5)?),
+            },
+            _ => return None,
+        })
+    }
+
+    /// Parses a `ColorLine`, or `VarColorLine` if `var`.
+    fn color_line(&self, offset: usize, var: bool) -> Option<ColorLine> {
+        let extend = match *self.data.get(offset)? {
+            1 => GradientExtend::Repeat,
+            2 => GradientExtend::Reflect,
+            _ => GradientExtend::Pad,
+        };
+        let count = usize::from(read_u16(self.data, offset + 1)?);
+        let stride = if var { 10 } else { 6 };
+        let stops = (0..count)
+            .map(|idx| {
+                let stop = offset + 3 + idx * stride;
+                Some(ColorStop {
+                    offset: f2dot14_to_f32(read_u16(self.data, stop)?),
+                    color: self.color(
+                        read_u16(self.data, stop + 2)?,
+                        f2dot14_to_f32(read_u16(self.data, stop + 4)?),
+                    ),
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(ColorLine { extend, stops })
+    }
+}
+
+#[inline]
+fn transform(transform: [f32; 6], paint: ColorGlyphPaint) -> ColorGlyphPaint {
+    ColorGlyphPaint::Transform {
+        transform,
+        paint: Box::new(paint),
+    }
+}
+
+/// Applies `m` around `(cx, cy)` if `around`, i.e. translate(c) * m * translate(-c).
+fn around_center(
+    mut m: [f32; 6],
+    around: bool,
+    cx: Option<f32>,
+    cy: Option<f32>,
+    paint: ColorGlyphPaint,
+) -> Option<ColorGlyphPaint> {
+    if around {
+        let (cx, cy) = (cx?, cy?);
+        m[4] = cx - (m[0] * cx + m[2] * cy);
+        m[5] = cy - (m[1] * cx + m[3] * cy);
+    }
+    Some(transform(m, paint))
+}
+
+fn composite_mode(mode: u8) -> Option<CompositeMode> {
+    use CompositeMode::*;
+    const MODES: [CompositeMode; 28] = [
+        Clear,
+        Source,
+        Destination,
+        SourceOver,
+        DestinationOver,
+        SourceIn,
+        DestinationIn,
+        SourceOut,
+        DestinationOut,
+        SourceAtop,
+        DestinationAtop,
+        Xor,
+        Plus,
+        Screen,
+        Overlay,
+        Darken,
+        Lighten,
+        ColorDodge,
+        ColorBurn,
+        HardLight,
+        SoftLight,
+        Difference,
+        Exclusion,
+        Multiply,
+        Hue,
+        Saturation,
+        Color,
+        Luminosity,
+    ];
+    MODES.get(usize::from(mode)).copied()
+}
+
+/// Reads a non-zero 32 bit offset.
+#[inline]
+fn offset32(data: &[u8], offset: usize) -> Option<usize> {
+    read_u32(data, offset)
+        .filter(|v| *v != 0)
+        .map(|v| v as usize)
+}
+
+/// Reads a big endian 24 bit offset.
+#[inline]
+fn offset24(data: &[u8], offset: usize) -> Option<usize> {
+    let bytes = data.get(offset..offset + 3)?;
+    Some(usize::from(bytes[0]) << 16 | usize::from(bytes[1]) << 8 | usize::from(bytes[2]))
+}
+
+#[inline]
+fn f2dot14_to_f32(v: u16) -> f32 {
+    f32::from(v as i16) / 16384.0
+}
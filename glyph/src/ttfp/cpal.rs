@@ -0,0 +1,61 @@
+//! Color palette (CPAL) table.
+use super::{name, read_u16, read_u32};
+use crate::ColorPalette;
+use owned_ttf_parser::{Face, Tag};
+
+/// Text foreground palette entry index.
+const FOREGROUND: u16 = 0xFFFF;
+
+/// Returns the number of color palettes.
+pub(crate) fn palette_count(face: &Face<'_>) -> usize {
+    face.table_data(Tag::from_bytes(b"CPAL"))
+        .and_then(|cpal| read_u16(cpal, 4))
+        .map_or(0, usize::from)
+}
+
+/// Returns the `0xRRGGBBAA` color of a palette entry. The text foreground entry is
+/// resolved as opaque black.
+pub(crate) fn color(face: &Face<'_>, palette: usize, index: u16) -> Option<u32> {
+    if index == FOREGROUND {
+        return Some(0x0000_00FF);
+    }
+    let cpal = face.table_data(Tag::from_bytes(b"CPAL"))?;
+    let entries = read_u16(cpal, 2)?;
+    if index >= entries || palette >= usize::from(read_u16(cpal, 4)?) {
+        return None;
+    }
+    let records = read_u32(cpal, 8)? as usize;
+    let first = usize::from(read_u16(cpal, 12 + palette * 2)?);
+    let record = records + (first + usize::from(index)) * 4;
+    // color records are BGRA
+    let bgra = cpal.get(record..record + 4)?;
+    Some(u32::from_be_bytes([bgra[2], bgra[1], bgra[0], bgra[3]]))
+}
+
+/// Returns the label & background usage of a palette, only present in version 1 tables.
+pub(crate) fn palette(face: &Face<'_>, palette: usize) -> Option<ColorPalette> {
+    let cpal = face.table_data(Tag::from_bytes(b"CPAL"))?;
+    let count = usize::from(read_u16(cpal, 4)?);
+    if palette >= count {
+        return None;
+    }
+
+    let mut info = ColorPalette::default();
+    if read_u16(cpal, 0)? >= 1 {
+        // version 1 offsets follow the palette color record indices
+        let header = 12 + count * 2;
+        let types = read_u32(cpal, header)? as usize;
+        let labels = read_u32(cpal, header + 4)? as usize;
+        if types != 0 {
+            let flags = read_u32(cpal, types + palette * 4)?;
+            info.light_background = flags & 1 != 0;
+            info.dark_background = flags & 2 != 0;
+        }
+        if labels != 0 {
+            info.name = read_u16(cpal, labels + palette * 2)
+                .filter(|&name_id| name_id != 0xFFFF)
+                .and_then(|name_id| name(face, name_id));
+        }
+    }
+    Some(info)
+}
@@ -0,0 +1,46 @@
+//! hdmx & VDMX device metrics tables.
+use super::{read_u16, read_u32};
+use crate::{DeviceVMetrics, GlyphId};
+use owned_ttf_parser::{Face, Tag};
+
+/// Returns the hdmx pixel advance of a glyph at `ppem`, if present.
+pub(crate) fn h_advance(face: &Face<'_>, id: GlyphId, ppem: u16) -> Option<u8> {
+    let hdmx = face.table_data(Tag::from_bytes(b"hdmx"))?;
+    if read_u16(hdmx, 0)? != 0 || id.0 >= face.number_of_glyphs() {
+        return None;
+    }
+    let num_records = read_u16(hdmx, 2)?;
+    let record_size = read_u32(hdmx, 4)? as usize;
+    (0..usize::from(num_records))
+        .map(|idx| 8 + idx * record_size)
+        .find(|&record| hdmx.get(record).map(|&size| u16::from(size)) == Some(ppem))
+        .and_then(|record| hdmx.get(record + 2 + usize::from(id.0)).copied())
+}
+
+/// Returns the VDMX pixel y-max & y-min for a 1:1 aspect ratio at `ppem`, if present.
+pub(crate) fn v_metrics(face: &Face<'_>, ppem: u16) -> Option<DeviceVMetrics> {
+    let vdmx = face.table_data(Tag::from_bytes(b"VDMX"))?;
+    let num_ratios = usize::from(read_u16(vdmx, 4)?);
+
+    // find the first ratio range including 1:1, a zero x ratio matches any ratio
+    let ratio = (0..num_ratios).find(|&idx| {
+        let range = 6 + idx * 4;
+        match vdmx.get(range + 1..range + 4) {
+            Some(&[0, _, _]) => true,
+            Some(&[x, y_start, y_end]) => y_start <= x && x <= y_end,
+            _ => false,
+        }
+    })?;
+    let group = usize::from(read_u16(vdmx, 6 + num_ratios * 4 + ratio * 2)?);
+
+    let recs = usize::from(read_u16(vdmx, group)?);
+    (0..recs)
+        .map(|idx| group + 4 + idx * 6)
+        .find(|&entry| read_u16(vdmx, entry) == Some(ppem))
+        .and_then(|entry| {
+            Some(DeviceVMetrics {
+                y_max: f32::from(read_u16(vdmx, entry + 2)? as i16),
+                y_min: f32::from(read_u16(vdmx, entry + 4)? as i16),
+            })
+        })
+}
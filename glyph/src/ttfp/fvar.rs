@@ -0,0 +1,51 @@
+//! Font variations (fvar) table.
+use super::{name, read_u16, read_u32};
+use crate::{NamedInstance, VariationAxis};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use owned_ttf_parser::{Face, Tag};
+
+/// Returns the variation axes of a face, empty for non-variable fonts.
+pub(crate) fn variation_axes(face: &Face<'_>) -> Vec<VariationAxis> {
+    face.variation_axes()
+        .map(|axis| VariationAxis {
+            tag: axis.tag.to_bytes(),
+            min_value: axis.min_value,
+            default_value: axis.def_value,
+            max_value: axis.max_value,
+            hidden: axis.hidden,
+        })
+        .collect()
+}
+
+/// Returns the named instances of a face's fvar table.
+pub(crate) fn named_instances(face: &Face<'_>) -> Vec<NamedInstance> {
+    let fvar = match face.table_data(Tag::from_bytes(b"fvar")) {
+        Some(data) => data,
+        None => return Vec::new(),
+    };
+    let header = |offset| read_u16(fvar, offset).map(usize::from);
+    let (axes_offset, axis_count, axis_size, instance_count, instance_size) =
+        match (header(4), header(8), header(10), header(12), header(14)) {
+            (Some(a), Some(b), Some(c), Some(d), Some(e)) => (a, b, c, d, e),
+            _ => return Vec::new(),
+        };
+    let instances = axes_offset + axis_count * axis_size;
+
+    (0..instance_count)
+        .map(|idx| instances + idx * instance_size)
+        .filter_map(|record| {
+            let name_id = read_u16(fvar, record)?;
+            let coordinates = (0..axis_count)
+                // 16.16 fixed point
+                .map(|axis| {
+                    read_u32(fvar, record + 4 + axis * 4).map(|v| v as i32 as f32 / 65536.0)
+                })
+                .collect::<Option<_>>()?;
+            Some(NamedInstance {
+                name: name(face, name_id),
+                coordinates,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+//! TrueType glyf table glyph headers.
+use super::{outline_glyph, read_u16, read_u32, subset::component_id_offsets};
+use crate::{complexity::CurveCounter, GlyphComplexity, GlyphId};
+use owned_ttf_parser::{Face, Tag};
+
+/// Maximum depth of nested composite glyphs.
+const MAX_COMPONENT_DEPTH: u8 = 8;
+
+/// Returns the contour & point counts of a glyph, read from glyf headers without outlining
+/// or, for CFF fonts, counted while outlining without storing curves.
+pub(crate) fn glyph_complexity(face: &Face<'_>, id: GlyphId) -> Option<GlyphComplexity> {
+    if id.0 >= face.number_of_glyphs() {
+        return None;
+    }
+    match face.table_data(Tag::from_bytes(b"glyf")) {
+        Some(glyf) => {
+            let complexity = glyf_complexity(face, glyf, id.0, 0)?;
+            Some(complexity).filter(|c| c.contours > 0)
+        }
+        None => {
+            let (counter, bounds) = outline_glyph(face, id, CurveCounter::default());
+            bounds.map(|_| counter.complexity)
+        }
+    }
+}
+
+fn glyf_complexity(face: &Face<'_>, glyf: &[u8], id: u16, depth: u8) -> Option<GlyphComplexity> {
+    let data = glyph_data(face, glyf, id)?;
+    if data.is_empty() {
+        return Some(GlyphComplexity::default());
+    }
+    let contours = read_u16(data, 0)? as i16;
+    if contours >= 0 {
+        let contours = contours as usize;
+        let points = match contours {
+            0 => 0,
+            _ => usize::from(read_u16(data, 10 + (contours - 1) * 2)?) + 1,
+        };
+        return Some(GlyphComplexity { contours, points });
+    }
+    if depth >= MAX_COMPONENT_DEPTH {
+        return None;
+    }
+    component_id_offsets(data)
+        .into_iter()
+        .try_fold(GlyphComplexity::default(), |sum, offset| {
+            let component = glyf_complexity(face, glyf, read_u16(data, offset)?, depth + 1)?;
+            Some(GlyphComplexity {
+                contours: sum.contours + component.contours,
+                points: sum.points + component.points,
+            })
+        })
+}
+
+/// Returns a glyph's glyf data using the loca table.
+fn glyph_data<'a>(face: &Face<'_>, glyf: &'a [u8], id: u16) -> Option<&'a [u8]> {
+    let head = face.table_data(Tag::from_bytes(b"head"))?;
+    let loca = face.table_data(Tag::from_bytes(b"loca"))?;
+    let idx = usize::from(id);
+    let (start, end) = if read_u16(head, 50)? == 1 {
+        (
+            read_u32(loca, idx * 4)? as usize,
+            read_u32(loca, idx * 4 + 4)? as usize,
+        )
+    } else {
+        (
+            usize::from(read_u16(loca, idx * 2)?) * 2,
+            usize::from(read_u16(loca, idx * 2 + 2)?) * 2,
+        )
+    };
+    glyf.get(start..end)
+}
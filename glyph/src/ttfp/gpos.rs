@@ -0,0 +1,323 @@
+//! GPOS pair adjustment (kerning) lookups.
+use super::{coverage_index, read_u16, read_u32};
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+use owned_ttf_parser::{Face, Tag};
+
+const PAIR_ADJUSTMENT: u16 = 2;
+const EXTENSION: u16 = 9;
+const X_ADVANCE: u16 = 0x0004;
+const Y_ADVANCE: u16 = 0x0008;
+
+/// Adds the horizontal kerning of every glyph pair in the GPOS pair adjustment lookups of
+/// "kern" features.
+///
+/// Within a lookup the first subtable applying to a pair wins, values of separate lookups
+/// are summed.
+pub(crate) fn kern_pairs(face: &Face<'_>, pairs: &mut BTreeMap<(u16, u16), f32>) {
+    let gpos = match face.table_data(Tag::from_bytes(b"GPOS")) {
+        Some(data) => data,
+        None => return,
+    };
+    let num_glyphs = face.number_of_glyphs();
+    let (lookup_list, lookups) = match kern_lookups(gpos, b"kern") {
+        Some(lookups) => lookups,
+        None => return,
+    };
+
+    for lookup in lookups {
+        let mut lookup_pairs = BTreeMap::new();
+        // first glyphs entirely handled by class pair subtables
+        let mut claimed = BTreeSet::new();
+        for subtable in pair_subtables(lookup_list, lookup) {
+            subtable_pairs(subtable, num_glyphs, &mut lookup_pairs, &mut claimed);
+        }
+        for (pair, value) in lookup_pairs {
+            *pairs.entry(pair).or_insert(0.0) += f32::from(value);
+        }
+    }
+}
+
+/// Returns the horizontal kerning of a glyph pair from the GPOS pair adjustment lookups of
+/// "kern" features, consistent with [`kern_pairs`], or if `vertical` the y-advance
+/// adjustment of "vkrn" features.
+pub(crate) fn pair_kerning(
+    face: &Face<'_>,
+    vertical: bool,
+    first: u16,
+    second: u16,
+) -> Option<f32> {
+    let gpos = face.table_data(Tag::from_bytes(b"GPOS"))?;
+    let (feature, advance) = match vertical {
+        false => (b"kern", X_ADVANCE),
+        true => (b"vkrn", Y_ADVANCE),
+    };
+    let (lookup_list, lookups) = kern_lookups(gpos, feature)?;
+
+    let mut kerning = None;
+    for lookup in lookups {
+        let value = pair_subtables(lookup_list, lookup)
+            .into_iter()
+            .find_map(|subtable| subtable_pair(subtable, advance, first, second));
+        if let Some(value) = value {
+            *kerning.get_or_insert(0.0) += f32::from(value);
+        }
+    }
+    kerning
+}
+
+/// Returns the lookup list & sorted indices of lookups referenced by any `feature`.
+fn kern_lookups<'a>(gpos: &'a [u8], feature: &[u8; 4]) -> Option<(&'a [u8], Vec<u16>)> {
+    let feature_list = usize::from(read_u16(gpos, 6)?);
+    let lookup_list = usize::from(read_u16(gpos, 8)?);
+
+    let mut lookups = Vec::new();
+    let feature_count = usize::from(read_u16(gpos, feature_list)?);
+    for record in (0..feature_count).map(|idx| feature_list + 2 + idx * 6) {
+        if gpos.get(record..record + 4) != Some(&feature[..]) {
+            continue;
+        }
+        let feature = feature_list + usize::from(read_u16(gpos, record + 4)?);
+        let count = usize::from(read_u16(gpos, feature + 2)?);
+        for idx in 0..count {
+            lookups.push(read_u16(gpos, feature + 4 + idx * 2)?);
+        }
+    }
+    lookups.sort_unstable();
+    lookups.dedup();
+
+    Some((gpos.get(lookup_list..)?, lookups))
+}
+
+/// Returns the pair adjustment subtables of a lookup, resolving extension subtables.
+fn pair_subtables(lookup_list: &[u8], index: u16) -> Vec<&[u8]> {
+    let subtable = |lookup: usize, kind: u16, idx: usize| -> Option<&[u8]> {
+        let offset = lookup + usize::from(read_u16(lookup_list, lookup + 6 + idx * 2)?);
+        match kind {
+            PAIR_ADJUSTMENT => lookup_list.get(offset..),
+            EXTENSION if read_u16(lookup_list, offset + 2)? == PAIR_ADJUSTMENT => {
+                let extension = read_u32(lookup_list, offset + 4)? as usize;
+                lookup_list.get(offset + extension..)
+            }
+            _ => None,
+        }
+    };
+
+    let lookup = match read_u16(lookup_list, 2 + usize::from(index) * 2) {
+        Some(offset) => usize::from(offset),
+        None => return Vec::new(),
+    };
+    match (
+        read_u16(lookup_list, lookup),
+        read_u16(lookup_list, lookup + 4),
+    ) {
+        (Some(kind), Some(count)) => (0..usize::from(count))
+            .filter_map(|idx| subtable(lookup, kind, idx))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Adds the first value record x-advance of each pair in a pair adjustment subtable,
+/// if not already set by a previous subtable.
+fn subtable_pairs(
+    data: &[u8],
+    num_glyphs: u16,
+    pairs: &mut BTreeMap<(u16, u16), i16>,
+    claimed: &mut BTreeSet<u16>,
+) -> Option<()> {
+    let format = read_u16(data, 0)?;
+    let first_glyphs = coverage(data, usize::from(read_u16(data, 2)?))?;
+    let (format1, format2) = (read_u16(data, 4)?, read_u16(data, 6)?);
+    if format1 & X_ADVANCE == 0 {
+        return Some(());
+    }
+    let x_advance = value_size(format1 & (X_ADVANCE - 1));
+    let record_size = value_size(format1) + value_size(format2);
+
+    match format {
+        // individual pairs
+        1 => {
+            for (idx, &first) in first_glyphs.iter().enumerate() {
+                if claimed.contains(&first) {
+                    continue;
+                }
+                let set = usize::from(read_u16(data, 10 + idx * 2)?);
+                let count = usize::from(read_u16(data, set)?);
+                for record in (0..count).map(|idx| set + 2 + idx * (2 + record_size)) {
+                    let second = read_u16(data, record)?;
+                    let value = read_u16(data, record + 2 + x_advance)? as i16;
+                    pairs.entry((first, second)).or_insert(value);
+                }
+            }
+        }
+        // class pairs
+        2 => {
+            let class_def1 = usize::from(read_u16(data, 8)?);
+            let class_def2 = usize::from(read_u16(data, 10)?);
+            let class2_count = usize::from(read_u16(data, 14)?);
+
+            let first_classes = classes(data, class_def1, num_glyphs)?;
+            let mut second_glyphs = vec![Vec::new(); class2_count];
+            for (glyph, &class) in classes(data, class_def2, num_glyphs)?.iter().enumerate() {
+                if let Some(glyphs) = second_glyphs.get_mut(usize::from(class)) {
+                    glyphs.push(glyph as u16);
+                }
+            }
+
+            for first in first_glyphs {
+                if !claimed.insert(first) {
+                    continue;
+                }
+                let class1 = usize::from(*first_classes.get(usize::from(first))?);
+                for (class2, seconds) in second_glyphs.iter().enumerate() {
+                    let record = 16 + (class1 * class2_count + class2) * record_size;
+                    let value = read_u16(data, record + x_advance)? as i16;
+                    for &second in seconds {
+                        pairs.entry((first, second)).or_insert(value);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    Some(())
+}
+
+/// Returns the first value record `advance` (`X_ADVANCE` or `Y_ADVANCE`) of a pair in a
+/// pair adjustment subtable.
+///
+/// Class pair subtables apply to every pair with a covered first glyph.
+fn subtable_pair(data: &[u8], advance: u16, first: u16, second: u16) -> Option<i16> {
+    let format = read_u16(data, 0)?;
+    let index = coverage_index(data, usize::from(read_u16(data, 2)?), first)?;
+    let (format1, format2) = (read_u16(data, 4)?, read_u16(data, 6)?);
+    if format1 & advance == 0 {
+        return None;
+    }
+    let value_offset = value_size(format1 & (advance - 1));
+    let record_size = value_size(format1) + value_size(format2);
+
+    match format {
+        // individual pairs, sorted by second glyph
+        1 => {
+            let set = usize::from(read_u16(data, 10 + index * 2)?);
+            let count = usize::from(read_u16(data, set)?);
+            let (mut lo, mut hi) = (0, count);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let record = set + 2 + mid * (2 + record_size);
+                match read_u16(data, record)?.cmp(&second) {
+                    Ordering::Less => lo = mid + 1,
+                    Ordering::Greater => hi = mid,
+                    Ordering::Equal => {
+                        return read_u16(data, record + 2 + value_offset).map(|v| v as i16)
+                    }
+                }
+            }
+            None
+        }
+        // class pairs
+        2 => {
+            let class1 = glyph_class(data, usize::from(read_u16(data, 8)?), first)?;
+            let class2 = glyph_class(data, usize::from(read_u16(data, 10)?), second)?;
+            let (class1_count, class2_count) = (read_u16(data, 12)?, read_u16(data, 14)?);
+            if class1 >= class1_count || class2 >= class2_count {
+                return Some(0);
+            }
+            let class_pair = usize::from(class1) * usize::from(class2_count) + usize::from(class2);
+            let record = 16 + class_pair * record_size;
+            read_u16(data, record + value_offset).map(|v| v as i16)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the byte size of a value record with the given value format.
+#[inline]
+fn value_size(format: u16) -> usize {
+    (format & 0xFF).count_ones() as usize * 2
+}
+
+/// Returns the glyphs of a coverage table in coverage index order.
+fn coverage(data: &[u8], offset: usize) -> Option<Vec<u16>> {
+    let count = usize::from(read_u16(data, offset + 2)?);
+    match read_u16(data, offset)? {
+        1 => (0..count)
+            .map(|idx| read_u16(data, offset + 4 + idx * 2))
+            .collect(),
+        2 => {
+            let mut glyphs = Vec::new();
+            for range in (0..count).map(|idx| offset + 4 + idx * 6) {
+                glyphs.extend(read_u16(data, range)?..=read_u16(data, range + 2)?);
+            }
+            Some(glyphs)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the class of every glyph from a class definition table.
+fn classes(data: &[u8], offset: usize, num_glyphs: u16) -> Option<Vec<u16>> {
+    let mut classes = vec![0; usize::from(num_glyphs)];
+    let mut set = |glyph: u16, class: u16| {
+        if let Some(c) = classes.get_mut(usize::from(glyph)) {
+            *c = class;
+        }
+    };
+    match read_u16(data, offset)? {
+        1 => {
+            let start = read_u16(data, offset + 2)?;
+            let count = read_u16(data, offset + 4)?;
+            for idx in 0..count {
+                let class = read_u16(data, offset + 6 + usize::from(idx) * 2)?;
+                set(start.checked_add(idx)?, class);
+            }
+        }
+        2 => {
+            let count = usize::from(read_u16(data, offset + 2)?);
+            for range in (0..count).map(|idx| offset + 4 + idx * 6) {
+                let class = read_u16(data, range + 4)?;
+                for glyph in read_u16(data, range)?..=read_u16(data, range + 2)? {
+                    set(glyph, class);
+                }
+            }
+        }
+        _ => return None,
+    }
+    Some(classes)
+}
+
+/// Returns the class of a glyph from a class definition table, `0` if not listed.
+fn glyph_class(data: &[u8], offset: usize, glyph: u16) -> Option<u16> {
+    match read_u16(data, offset)? {
+        1 => {
+            let start = read_u16(data, offset + 2)?;
+            let count = read_u16(data, offset + 4)?;
+            match glyph.checked_sub(start) {
+                Some(idx) if idx < count => read_u16(data, offset + 6 + usize::from(idx) * 2),
+                _ => Some(0),
+            }
+        }
+        // ranges sorted by start glyph
+        2 => {
+            let count = usize::from(read_u16(data, offset + 2)?);
+            let (mut lo, mut hi) = (0, count);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let range = offset + 4 + mid * 6;
+                if glyph < read_u16(data, range)? {
+                    hi = mid;
+                } else if glyph > read_u16(data, range + 2)? {
+                    lo = mid + 1;
+                } else {
+                    return read_u16(data, range + 4);
+                }
+            }
+            Some(0)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,404 @@
+//! Glyph substitution (GSUB) lookups.
+//!
+//! Supports single, multiple, alternate & ligature substitutions, plus coverage based
+//! (format 3) contextual & chained contextual substitutions. Lookup flags, e.g. ignoring
+//! marks, are not applied.
+use super::{coverage_index, read_u16, read_u32};
+use crate::{GlyphId, Script};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use owned_ttf_parser::{Face, Tag};
+
+const SINGLE: u16 = 1;
+const MULTIPLE: u16 = 2;
+const ALTERNATE: u16 = 3;
+const LIGATURE: u16 = 4;
+const CONTEXT: u16 = 5;
+const CHAINED_CONTEXT: u16 = 6;
+const EXTENSION: u16 = 7;
+
+/// Maximum contextual lookup nesting, guarding against cyclic lookups.
+const MAX_DEPTH: u8 = 8;
+
+/// Applies the lookups of `features`, & the required feature, of the script's language
+/// system to `glyphs` in lookup order.
+pub(crate) fn substitute(
+    face: &Face<'_>,
+    script: Script,
+    features: &[[u8; 4]],
+    glyphs: &mut Vec<GlyphId>,
+) {
+    let gsub = match face.table_data(Tag::from_bytes(b"GSUB")) {
+        Some(data) => data,
+        None => return,
+    };
+    let (lookup_list, lookups) = match feature_lookups(gsub, script, features) {
+        Some(lookups) => lookups,
+        None => return,
+    };
+
+    for lookup in lookups {
+        let subtables = subtables(lookup_list, lookup);
+        let mut pos = 0;
+        while pos < glyphs.len() {
+            pos += apply_at(lookup_list, &subtables, glyphs, pos, 0).unwrap_or(1);
+        }
+    }
+}
+
+/// Returns every ligature glyph of the font's ligature substitutions with the component
+/// glyphs it replaces, regardless of script or feature.
+pub(crate) fn ligatures(face: &Face<'_>) -> Vec<(GlyphId, Vec<GlyphId>)> {
+    let lookup_list = match face
+        .table_data(Tag::from_bytes(b"GSUB"))
+        .and_then(|gsub| gsub.get(usize::from(read_u16(gsub, 8)?)..))
+    {
+        Some(data) => data,
+        None => return Vec::new(),
+    };
+
+    let mut ligatures = Vec::new();
+    for lookup in 0..read_u16(lookup_list, 0).unwrap_or(0) {
+        for (kind, data) in subtables(lookup_list, lookup) {
+            if kind == LIGATURE {
+                ligature_components(data, &mut ligatures);
+            }
+        }
+    }
+    ligatures
+}
+
+/// Returns the feature tags of the script's language system, including the required feature.
+///
+/// Uses the same script & language system fallbacks as [`substitute`].
+pub(crate) fn features(face: &Face<'_>, script: Script) -> Vec<[u8; 4]> {
+    let gsub = match face.table_data(Tag::from_bytes(b"GSUB")) {
+        Some(data) => data,
+        None => return Vec::new(),
+    };
+    let (feature_list, lang_sys) = match lang_sys(gsub, script) {
+        Some(offsets) => offsets,
+        None => return Vec::new(),
+    };
+    let required = read_u16(gsub, lang_sys + 2).filter(|&index| index != 0xFFFF);
+    let count = read_u16(gsub, lang_sys + 4).map_or(0, usize::from);
+
+    let mut tags: Vec<[u8; 4]> = (0..count)
+        .filter_map(|idx| read_u16(gsub, lang_sys + 6 + idx * 2))
+        .chain(required)
+        .filter_map(|index| {
+            let record = feature_list + 2 + usize::from(index) * 6;
+            match gsub.get(record..record + 4)? {
+                &[a, b, c, d] => Some([a, b, c, d]),
+                _ => None,
+            }
+        })
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    tags
+}
+
+/// Returns the offsets of the feature list & the language system table of `script`.
+///
+/// Falls back to the "DFLT", then "latn", script if the font doesn't support the script &
+/// to the script's default language system if it doesn't support the language.
+fn lang_sys(gsub: &[u8], script: Script) -> Option<(usize, usize)> {
+    let script_list = usize::from(read_u16(gsub, 4)?);
+    let feature_list = usize::from(read_u16(gsub, 6)?);
+
+    let script_count = usize::from(read_u16(gsub, script_list)?);
+    let script_table = [&script.script, b"DFLT", b"latn"]
+        .iter()
+        .find_map(|tag| {
+            (0..script_count)
+                .map(|idx| script_list + 2 + idx * 6)
+                .find(|&record| gsub.get(record..record + 4) == Some(&tag[..]))
+        })
+        .and_then(|record| read_u16(gsub, record + 4))
+        .map(|offset| script_list + usize::from(offset))?;
+
+    let lang_count = usize::from(read_u16(gsub, script_table + 2)?);
+    let lang_sys = script
+        .language
+        .and_then(|lang| {
+            (0..lang_count)
+                .map(|idx| script_table + 4 + idx * 6)
+                .find(|&record| gsub.get(record..record + 4) == Some(&lang[..]))
+        })
+        .and_then(|record| read_u16(gsub, record + 4))
+        .or_else(|| read_u16(gsub, script_table))
+        .filter(|&offset| offset != 0)
+        .map(|offset| script_table + usize::from(offset))?;
+    Some((feature_list, lang_sys))
+}
+
+/// Returns the lookup list & sorted indices of lookups referenced by `features` or the
+/// required feature of the script's language system.
+fn feature_lookups<'a>(
+    gsub: &'a [u8],
+    script: Script,
+    features: &[[u8; 4]],
+) -> Option<(&'a [u8], Vec<u16>)> {
+    let lookup_list = usize::from(read_u16(gsub, 8)?);
+    let (feature_list, lang_sys) = lang_sys(gsub, script)?;
+
+    let feature_tag = |index: u16| {
+        let record = feature_list + 2 + usize::from(index) * 6;
+        gsub.get(record..record + 4)
+    };
+    let mut feature_indices: Vec<u16> = (0..usize::from(read_u16(gsub, lang_sys + 4)?))
+        .filter_map(|idx| read_u16(gsub, lang_sys + 6 + idx * 2))
+        .filter(|&index| match feature_tag(index) {
+            Some(tag) => features.iter().any(|feature| feature[..] == *tag),
+            None => false,
+        })
+        .collect();
+    let required = read_u16(gsub, lang_sys + 2)?;
+    if required != 0xFFFF {
+        feature_indices.push(required);
+    }
+
+    let mut lookups = Vec::new();
+    for index in feature_indices {
+        let record = feature_list + 2 + usize::from(index) * 6;
+        let feature = feature_list + usize::from(read_u16(gsub, record + 4)?);
+        let count = usize::from(read_u16(gsub, feature + 2)?);
+        for idx in 0..count {
+            lookups.push(read_u16(gsub, feature + 4 + idx * 2)?);
+        }
+    }
+    lookups.sort_unstable();
+    lookups.dedup();
+
+    Some((gsub.get(lookup_list..)?, lookups))
+}
+
+/// Returns the subtables of a lookup with their lookup type, resolving extension subtables.
+fn subtables(lookup_list: &[u8], index: u16) -> Vec<(u16, &[u8])> {
+    let lookup = match read_u16(lookup_list, 2 + usize::from(index) * 2) {
+        Some(offset) => usize::from(offset),
+        None => return Vec::new(),
+    };
+    let subtable = |kind: u16, idx: usize| -> Option<(u16, &[u8])> {
+        let offset = lookup + usize::from(read_u16(lookup_list, lookup + 6 + idx * 2)?);
+        match kind {
+            EXTENSION => {
+                let kind = read_u16(lookup_list, offset + 2)?;
+                let extension = read_u32(lookup_list, offset + 4)? as usize;
+                Some((kind, lookup_list.get(offset + extension..)?))
+            }
+            _ => Some((kind, lookup_list.get(offset..)?)),
+        }
+    };
+
+    match (
+        read_u16(lookup_list, lookup),
+        read_u16(lookup_list, lookup + 4),
+    ) {
+        (Some(kind), Some(count)) => (0..usize::from(count))
+            .filter_map(|idx| subtable(kind, idx))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Applies the first subtable matching at `pos`, returning the number of resulting glyphs
+/// to move past.
+fn apply_at(
+    lookup_list: &[u8],
+    subtables: &[(u16, &[u8])],
+    glyphs: &mut Vec<GlyphId>,
+    pos: usize,
+    depth: u8,
+) -> Option<usize> {
+    let glyph = glyphs.get(pos)?.0;
+    subtables.iter().find_map(|&(kind, data)| match kind {
+        SINGLE => {
+            let id = single(data, glyph)?;
+            glyphs[pos] = GlyphId(id);
+            Some(1)
+        }
+        MULTIPLE => {
+            let sequence = sequence(data, glyph)?;
+            let len = sequence.len();
+            glyphs.splice(pos..=pos, sequence);
+            Some(len)
+        }
+        ALTERNATE => {
+            glyphs[pos] = *sequence(data, glyph)?.first()?;
+            Some(1)
+        }
+        LIGATURE => {
+            let (id, components) = ligature(data, glyphs, pos)?;
+            glyphs[pos] = id;
+            glyphs.drain(pos + 1..pos + components);
+            Some(1)
+        }
+        CONTEXT | CHAINED_CONTEXT => contextual(
+            lookup_list,
+            data,
+            kind == CHAINED_CONTEXT,
+            glyphs,
+            pos,
+            depth,
+        ),
+        _ => None,
+    })
+}
+
+/// Returns the single substitution of `glyph`.
+fn single(data: &[u8], glyph: u16) -> Option<u16> {
+    let index = coverage_index(data, usize::from(read_u16(data, 2)?), glyph)?;
+    match read_u16(data, 0)? {
+        1 => Some(glyph.wrapping_add(read_u16(data, 4)?)),
+        2 => read_u16(data, 6 + index * 2),
+        _ => None,
+    }
+}
+
+/// Returns the multiple substitution sequence, or alternate substitution set, of `glyph`.
+fn sequence(data: &[u8], glyph: u16) -> Option<Vec<GlyphId>> {
+    let index = coverage_index(data, usize::from(read_u16(data, 2)?), glyph)?;
+    let sequence = usize::from(read_u16(data, 6 + index * 2)?);
+    let count = usize::from(read_u16(data, sequence)?);
+    (0..count)
+        .map(|idx| read_u16(data, sequence + 2 + idx * 2).map(GlyphId))
+        .collect()
+}
+
+/// Returns the first ligature, & its component count, matching the glyphs from `pos`.
+fn ligature(data: &[u8], glyphs: &[GlyphId], pos: usize) -> Option<(GlyphId, usize)> {
+    let index = coverage_index(data, usize::from(read_u16(data, 2)?), glyphs[pos].0)?;
+    let set = usize::from(read_u16(data, 6 + index * 2)?);
+    let count = usize::from(read_u16(data, set)?);
+    (0..count).find_map(|idx| {
+        let ligature = set + usize::from(read_u16(data, set + 2 + idx * 2)?);
+        let components = usize::from(read_u16(data, ligature + 2)?).max(1);
+        let rest = glyphs.get(pos + 1..pos + components)?;
+        let matches = rest
+            .iter()
+            .enumerate()
+            .all(|(n, glyph)| read_u16(data, ligature + 4 + n * 2) == Some(glyph.0));
+        if matches {
+            Some((GlyphId(read_u16(data, ligature)?), components))
+        } else {
+            None
+        }
+    })
+}
+
+/// Appends each ligature of a ligature substitution subtable with its component glyphs.
+fn ligature_components(data: &[u8], out: &mut Vec<(GlyphId, Vec<GlyphId>)>) -> Option<()> {
+    for (index, first) in coverage_glyphs(data, usize::from(read_u16(data, 2)?)) {
+        let set = usize::from(read_u16(data, 6 + index * 2)?);
+        let count = usize::from(read_u16(data, set)?);
+        for idx in 0..count {
+            let ligature = set + usize::from(read_u16(data, set + 2 + idx * 2)?);
+            let components = usize::from(read_u16(data, ligature + 2)?).max(1);
+            let rest =
+                (0..components - 1).map(|n| read_u16(data, ligature + 4 + n * 2).map(GlyphId));
+            let glyphs = core::iter::once(Some(GlyphId(first)))
+                .chain(rest)
+                .collect::<Option<Vec<_>>>()?;
+            out.push((GlyphId(read_u16(data, ligature)?), glyphs));
+        }
+    }
+    Some(())
+}
+
+/// Returns the `(coverage index, glyph)` pairs of the coverage table at `offset`.
+fn coverage_glyphs(data: &[u8], offset: usize) -> Vec<(usize, u16)> {
+    let count = read_u16(data, offset + 2).map_or(0, usize::from);
+    match read_u16(data, offset) {
+        Some(1) => (0..count)
+            .filter_map(|idx| Some((idx, read_u16(data, offset + 4 + idx * 2)?)))
+            .collect(),
+        Some(2) => (0..count)
+            .filter_map(|idx| {
+                let record = offset + 4 + idx * 6;
+                let start = read_u16(data, record)?;
+                let end = read_u16(data, record + 2)?;
+                let start_index = usize::from(read_u16(data, record + 4)?);
+                Some((start, end, start_index))
+            })
+            .flat_map(|(start, end, start_index)| {
+                (start..=end).map(move |glyph| (start_index + usize::from(glyph - start), glyph))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Applies a coverage based (format 3) contextual, or chained contextual, substitution
+/// matching the glyphs from `pos`.
+fn contextual(
+    lookup_list: &[u8],
+    data: &[u8],
+    chained: bool,
+    glyphs: &mut Vec<GlyphId>,
+    pos: usize,
+    depth: u8,
+) -> Option<usize> {
+    if depth >= MAX_DEPTH || read_u16(data, 0)? != 3 {
+        return None;
+    }
+    let covers = |coverage_offset: usize, glyph: Option<&GlyphId>| match (
+        glyph,
+        read_u16(data, coverage_offset),
+    ) {
+        (Some(glyph), Some(coverage)) => {
+            coverage_index(data, usize::from(coverage), glyph.0).is_some()
+        }
+        _ => false,
+    };
+
+    let (input_count, inputs, lookup_count, records) = if chained {
+        let backtrack_count = usize::from(read_u16(data, 2)?);
+        let input = 4 + backtrack_count * 2;
+        let input_count = usize::from(read_u16(data, input)?);
+        let lookahead = input + 2 + input_count * 2;
+        let lookahead_count = usize::from(read_u16(data, lookahead)?);
+        let lookups = lookahead + 2 + lookahead_count * 2;
+
+        // backtrack coverages are in reverse glyph order
+        let backtrack_matches = pos >= backtrack_count
+            && (0..backtrack_count).all(|idx| covers(4 + idx * 2, glyphs.get(pos - 1 - idx)));
+        let lookahead_matches = (0..lookahead_count)
+            .all(|idx| covers(lookahead + 2 + idx * 2, glyphs.get(pos + input_count + idx)));
+        if !backtrack_matches || !lookahead_matches {
+            return None;
+        }
+        let lookup_count = usize::from(read_u16(data, lookups)?);
+        (input_count, input + 2, lookup_count, lookups + 2)
+    } else {
+        let input_count = usize::from(read_u16(data, 2)?);
+        let lookup_count = usize::from(read_u16(data, 4)?);
+        (input_count, 6, lookup_count, 6 + input_count * 2)
+    };
+
+    let input_matches = (0..input_count).all(|idx| covers(inputs + idx * 2, glyphs.get(pos + idx)));
+    if input_count == 0 || !input_matches {
+        return None;
+    }
+
+    let mut len = input_count;
+    for record in (0..lookup_count).map(|idx| records + idx * 4) {
+        let sequence_index = usize::from(read_u16(data, record)?);
+        let lookup = read_u16(data, record + 2)?;
+        if sequence_index >= len {
+            continue;
+        }
+        let before = glyphs.len();
+        let subtables = subtables(lookup_list, lookup);
+        apply_at(
+            lookup_list,
+            &subtables,
+            glyphs,
+            pos + sequence_index,
+            depth + 1,
+        );
+        len = (len + glyphs.len()).saturating_sub(before);
+    }
+    Some(len)
+}
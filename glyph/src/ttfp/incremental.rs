@@ -0,0 +1,485 @@
+//! Incremental font loading, fetching glyf table data on demand.
+use super::{
+    outline_glyph,
+    outliner::SinkBuilder,
+    read_u16, read_u32,
+    subset::{subset_glyf, write_sfnt, write_u16},
+    unscaled_rect,
+};
+use crate::{
+    point, CodepointIdIter, ColorOutline, CoverageSet, DecorationMetrics, DeviceVMetrics, Font,
+    FontError, FontStyle, FontVec, GlyphId, GlyphImage, NameId, OpticalBounds, Outline,
+    OutlineSink, Rect, Script,
+};
+use core::{fmt, ops::Range, ops::RangeInclusive};
+use owned_ttf_parser::{AsFaceRef, Face, Tag};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+/// A TrueType font loaded from partially available data, fetching glyph outline
+/// data on demand.
+///
+/// Useful for progressively rendering text with huge fonts, like CJK fonts, that are
+/// range-requested over a network. The initial data must contain the font's table
+/// directory & every table needed up front, like head, hhea, maxp, hmtx, cmap & loca.
+/// The glyf table itself may be missing; glyph data is requested through the `loader`
+/// callback as each glyph is outlined.
+///
+/// The loader is called with the byte range, in the original font file, of a glyph's data
+/// & should return exactly those bytes, or `None` if they're not available yet.
+/// Loaded glyph data is kept, so each glyph is only requested once. Failed requests are
+/// not remembered & will be retried on the next outline.
+///
+/// With a `Send + Sync` loader the font is too, so it can be shared in a
+/// [`FontArc`](crate::FontArc).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontArc, FontRef, IncrementalFont};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// # let full_font_bytes = include_bytes!("../../../dev/fonts/Exo2-Light.ttf");
+/// // initial data must contain all tables except glyf
+/// let initial = full_font_bytes.to_vec();
+///
+/// // fetch glyph data as needed, e.g. using HTTP range requests
+/// let font = IncrementalFont::try_from_vec(initial, move |range| {
+///     full_font_bytes.get(range).map(|bytes| bytes.to_vec())
+/// })?;
+///
+/// let q = font.glyph_id('q');
+/// assert!(!font.is_glyph_loaded(q));
+/// let outline = font.outline(q).unwrap();
+/// assert!(font.is_glyph_loaded(q));
+///
+/// let full_font = FontRef::try_from_slice(full_font_bytes)?;
+/// assert_eq!(outline.bounds, full_font.outline(q).unwrap().bounds);
+///
+/// let font = FontArc::new(font);
+/// assert!(font.outline(font.glyph_id('Å')).is_some());
+/// # Ok(()) }
+/// ```
+pub struct IncrementalFont<L> {
+    font: FontVec,
+    /// glyf table offset in the original font file.
+    glyf_offset: usize,
+    long_loca: bool,
+    loader: L,
+    loaded: RwLock<BTreeMap<u16, Arc<[u8]>>>,
+}
+
+impl<L> fmt::Debug for IncrementalFont<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IncrementalFont")
+    }
+}
+
+impl<L> IncrementalFont<L>
+where
+    L: Fn(Range<usize>) -> Option<Vec<u8>>,
+{
+    /// Creates an `IncrementalFont` from initial font data & a glyph data loader.
+    ///
+    /// Errors if the data is not a TrueType (glyf) font or is missing required tables.
+    pub fn try_from_vec(data: Vec<u8>, loader: L) -> Result<Self, FontError> {
+        let glyf_offset = table_offset(&data, b"glyf").ok_or(FontError::MissingTable(*b"glyf"))?;
+        let font = FontVec::try_from_vec(data)?;
+        let face = font.0.as_face_ref();
+        let head = face
+            .table_data(Tag::from_bytes(b"head"))
+            .ok_or(FontError::MissingTable(*b"head"))?;
+        let long_loca = read_u16(head, 50).ok_or(FontError::Malformed)? == 1;
+        face.table_data(Tag::from_bytes(b"loca"))
+            .ok_or(FontError::MissingTable(*b"loca"))?;
+
+        Ok(Self {
+            font,
+            glyf_offset,
+            long_loca,
+            loader,
+            loaded: RwLock::default(),
+        })
+    }
+
+    /// Returns `true` if the glyph's data has been loaded.
+    #[inline]
+    pub fn is_glyph_loaded(&self, id: GlyphId) -> bool {
+        self.read_loaded().contains_key(&id.0)
+    }
+
+    /// Locks loaded glyph data for reading, ignoring poisoning as entries are always complete.
+    #[inline]
+    fn read_loaded(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<u16, Arc<[u8]>>> {
+        self.loaded.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the glyph's byte range in the original font file, empty for empty glyphs.
+    fn glyph_range(&self, id: u16) -> Option<Range<usize>> {
+        let loca = self
+            .font
+            .0
+            .as_face_ref()
+            .table_data(Tag::from_bytes(b"loca"))?;
+        let idx = usize::from(id);
+        let (start, end) = if self.long_loca {
+            (
+                read_u32(loca, idx * 4)? as usize,
+                read_u32(loca, idx * 4 + 4)? as usize,
+            )
+        } else {
+            (
+                usize::from(read_u16(loca, idx * 2)?) * 2,
+                usize::from(read_u16(loca, idx * 2 + 2)?) * 2,
+            )
+        };
+        if start > end {
+            return None;
+        }
+        Some(self.glyf_offset + start..self.glyf_offset + end)
+    }
+
+    /// Returns the glyph's data, loading it if required. Empty glyphs have empty data.
+    fn glyph_data(&self, id: u16) -> Option<Arc<[u8]>> {
+        if let Some(data) = self.read_loaded().get(&id) {
+            return Some(Arc::clone(data));
+        }
+        let range = self.glyph_range(id)?;
+        if range.is_empty() {
+            return Some(Arc::from(&[][..]));
+        }
+        let len = range.len();
+        let data: Arc<[u8]> = (self.loader)(range)
+            .filter(|data| data.len() == len)?
+            .into();
+        self.loaded
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(id, Arc::clone(&data));
+        Some(data)
+    }
+
+    /// Calls `f` with a glyf font holding only the glyph & its composite components,
+    /// loading their data if required, & the glyph's id in that font.
+    fn with_glyph_face<T>(
+        &self,
+        id: GlyphId,
+        f: impl FnOnce(&Face<'_>, GlyphId) -> T,
+    ) -> Option<T> {
+        let face = self.font.0.as_face_ref();
+        let (ids, glyf, loca) = subset_glyf(vec![id.0], face.number_of_glyphs(), |id| {
+            self.glyph_data(id)
+        })?;
+        let new_id = GlyphId(ids.binary_search(&id.0).ok()? as u16);
+
+        let table = |tag: &[u8; 4]| face.table_data(Tag::from_bytes(tag));
+        let mut head = table(b"head")?.to_vec();
+        write_u16(&mut head, 50, 1); // long loca
+        let mut maxp = table(b"maxp")?.to_vec();
+        write_u16(&mut maxp, 4, ids.len() as u16); // numGlyphs
+        let data = write_sfnt(
+            0x0001_0000,
+            vec![
+                (*b"glyf", glyf),
+                (*b"head", head),
+                (*b"hhea", table(b"hhea")?.to_vec()),
+                (*b"loca", loca),
+                (*b"maxp", maxp),
+            ],
+        );
+        let face = Face::from_slice(&data, 0).ok()?;
+        Some(f(&face, new_id))
+    }
+}
+
+impl<L> Font for IncrementalFont<L>
+where
+    L: Fn(Range<usize>) -> Option<Vec<u8>>,
+{
+    #[inline]
+    fn units_per_em(&self) -> Option<f32> {
+        self.font.units_per_em()
+    }
+
+    #[inline]
+    fn ascent_unscaled(&self) -> f32 {
+        self.font.ascent_unscaled()
+    }
+
+    #[inline]
+    fn descent_unscaled(&self) -> f32 {
+        self.font.descent_unscaled()
+    }
+
+    #[inline]
+    fn line_gap_unscaled(&self) -> f32 {
+        self.font.line_gap_unscaled()
+    }
+
+    #[inline]
+    fn vertical_ascent_unscaled(&self) -> f32 {
+        self.font.vertical_ascent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_descent_unscaled(&self) -> f32 {
+        self.font.vertical_descent_unscaled()
+    }
+
+    #[inline]
+    fn vertical_line_gap_unscaled(&self) -> f32 {
+        self.font.vertical_line_gap_unscaled()
+    }
+
+    #[inline]
+    fn italic_angle(&self) -> f32 {
+        self.font.italic_angle()
+    }
+
+    #[inline]
+    fn underline_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.font.underline_metrics_unscaled()
+    }
+
+    #[inline]
+    fn strikeout_metrics_unscaled(&self) -> Option<DecorationMetrics> {
+        self.font.strikeout_metrics_unscaled()
+    }
+
+    #[inline]
+    fn x_height_unscaled(&self) -> f32 {
+        self.font.x_height_unscaled()
+    }
+
+    #[inline]
+    fn cap_height_unscaled(&self) -> f32 {
+        self.font.cap_height_unscaled()
+    }
+
+    #[inline]
+    fn is_monospaced(&self) -> bool {
+        self.font.is_monospaced()
+    }
+
+    #[inline]
+    fn weight(&self) -> u16 {
+        self.font.weight()
+    }
+
+    #[inline]
+    fn stretch(&self) -> u16 {
+        self.font.stretch()
+    }
+
+    #[inline]
+    fn style(&self) -> FontStyle {
+        self.font.style()
+    }
+
+    #[inline]
+    fn is_bold(&self) -> bool {
+        self.font.is_bold()
+    }
+
+    #[inline]
+    fn font_name(&self, id: NameId) -> Option<String> {
+        self.font.font_name(id)
+    }
+
+    #[inline]
+    fn font_name_for_language(&self, id: NameId, language_id: u16) -> Option<String> {
+        self.font.font_name_for_language(id, language_id)
+    }
+
+    #[inline]
+    fn glyph_id(&self, c: char) -> GlyphId {
+        self.font.glyph_id(c)
+    }
+
+    #[inline]
+    fn glyph_id_checked(&self, c: char) -> Option<GlyphId> {
+        self.font.glyph_id_checked(c)
+    }
+
+    #[inline]
+    fn glyph_id_variation(&self, c: char, selector: char) -> Option<GlyphId> {
+        self.font.glyph_id_variation(c, selector)
+    }
+
+    #[inline]
+    fn codepoint_ids(&self) -> CodepointIdIter<'_> {
+        self.font.codepoint_ids()
+    }
+
+    #[inline]
+    fn coverage_set(&self) -> CoverageSet {
+        self.font.coverage_set()
+    }
+
+    #[inline]
+    fn glyph_ids_for_str(&self, text: &str, ids: &mut Vec<GlyphId>) {
+        self.font.glyph_ids_for_str(text, ids)
+    }
+
+    #[inline]
+    fn substitute(&self, script: Script, features: &[[u8; 4]], glyphs: &mut Vec<GlyphId>) {
+        self.font.substitute(script, features, glyphs)
+    }
+
+    #[inline]
+    fn features(&self, script: Script) -> Vec<[u8; 4]> {
+        self.font.features(script)
+    }
+
+    #[inline]
+    fn ligatures(&self) -> Vec<(GlyphId, Vec<GlyphId>)> {
+        self.font.ligatures()
+    }
+
+    #[inline]
+    fn h_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn try_h_advance_unscaled(&self, id: GlyphId) -> Option<f32> {
+        self.font.try_h_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn h_advances_unscaled(&self, ids: &[GlyphId], out: &mut [f32]) {
+        self.font.h_advances_unscaled(ids, out)
+    }
+
+    #[inline]
+    fn h_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn try_h_side_bearing_unscaled(&self, id: GlyphId) -> Option<f32> {
+        self.font.try_h_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_advance_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_advance_unscaled(id)
+    }
+
+    #[inline]
+    fn v_side_bearing_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_side_bearing_unscaled(id)
+    }
+
+    #[inline]
+    fn v_origin_unscaled(&self, id: GlyphId) -> f32 {
+        self.font.v_origin_unscaled(id)
+    }
+
+    #[inline]
+    fn kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn v_kern_unscaled(&self, first: GlyphId, second: GlyphId) -> f32 {
+        self.font.v_kern_unscaled(first, second)
+    }
+
+    #[inline]
+    fn kern_pairs(&self) -> Vec<(GlyphId, GlyphId, f32)> {
+        self.font.kern_pairs()
+    }
+
+    #[inline]
+    fn optical_bounds_unscaled(&self, id: GlyphId) -> Option<OpticalBounds> {
+        self.font.optical_bounds_unscaled(id)
+    }
+
+    #[inline]
+    fn many_to_one_ranges(&self) -> Vec<(RangeInclusive<char>, GlyphId)> {
+        self.font.many_to_one_ranges()
+    }
+
+    #[inline]
+    fn device_h_advance(&self, id: GlyphId, ppem: u16) -> Option<f32> {
+        self.font.device_h_advance(id, ppem)
+    }
+
+    #[inline]
+    fn device_v_metrics(&self, ppem: u16) -> Option<DeviceVMetrics> {
+        self.font.device_v_metrics(ppem)
+    }
+
+    #[inline]
+    fn relative_scale(&self, glyph: GlyphId) -> f32 {
+        self.font.relative_scale(glyph)
+    }
+
+    /// Color layers are not loaded incrementally, always `false`.
+    #[inline]
+    fn has_color(&self, _glyph: GlyphId) -> bool {
+        false
+    }
+
+    #[inline]
+    fn color_outlines(&self, _glyph: GlyphId) -> Option<ColorOutline> {
+        None
+    }
+
+    #[inline]
+    fn glyph_raster_image(&self, id: GlyphId, pixels_per_em: u16) -> Option<GlyphImage<'_>> {
+        self.font.glyph_raster_image(id, pixels_per_em)
+    }
+
+    fn glyph_bounds_unscaled(&self, id: GlyphId) -> Option<Rect> {
+        // the glyph header bounds, loading the glyph but not its components
+        let data = self.glyph_data(id.0)?;
+        let mut bounds = [0; 4];
+        for (idx, b) in bounds.iter_mut().enumerate() {
+            *b = read_u16(&data, 2 + idx * 2)? as i16;
+        }
+        let [x_min, y_min, x_max, y_max] = bounds;
+        Some(Rect {
+            min: point(f32::from(x_min), f32::from(y_max)),
+            max: point(f32::from(x_max), f32::from(y_min)),
+        })
+    }
+
+    fn outline(&self, id: GlyphId) -> Option<Outline> {
+        let (curves, bounds) =
+            self.with_glyph_face(id, |face, id| outline_glyph(face, id, Vec::new()))?;
+        Some(Outline {
+            bounds: bounds?,
+            curves,
+        })
+    }
+
+    fn outline_with(&self, id: GlyphId, sink: &mut dyn OutlineSink) -> Option<Rect> {
+        self.with_glyph_face(id, |face, id| {
+            face.outline_glyph(id.into(), &mut SinkBuilder(sink))
+                .map(unscaled_rect)
+        })?
+    }
+
+    #[inline]
+    fn glyph_count(&self) -> usize {
+        self.font.glyph_count()
+    }
+
+    #[inline]
+    fn fingerprint(&self) -> u64 {
+        self.font.fingerprint()
+    }
+}
+
+/// Returns the offset of a table in sfnt font data.
+fn table_offset(data: &[u8], tag: &[u8; 4]) -> Option<usize> {
+    let num_tables = read_u16(data, 4)?;
+    (0..usize::from(num_tables))
+        .map(|idx| 12 + idx * 16)
+        .find(|&record| data.get(record..record + 4) == Some(&tag[..]))
+        .and_then(|record| read_u32(data, record + 8))
+        .map(|offset| offset as usize)
+}
@@ -0,0 +1,231 @@
+//! Kerning pair lookups & enumeration.
+use super::{gpos, read_u16, read_u32};
+use crate::GlyphId;
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use owned_ttf_parser::{Face, Tag};
+
+/// Returns all non-zero horizontal kerning pairs, sorted by glyph ids.
+///
+/// Uses the kern table or, if it has no pairs, GPOS kern feature lookups.
+pub(crate) fn kern_pairs(face: &Face<'_>) -> Vec<(GlyphId, GlyphId, f32)> {
+    let mut pairs = BTreeMap::new();
+    kern_table_pairs(face, &mut pairs);
+    if pairs.is_empty() {
+        gpos::kern_pairs(face, &mut pairs);
+    }
+    pairs
+        .into_iter()
+        .filter(|(_, value)| *value != 0.0)
+        .map(|((first, second), value)| (GlyphId(first), GlyphId(second), value))
+        .collect()
+}
+
+/// Adds the pairs of horizontal, non-variable, format 0 kern subtables. The first
+/// subtable containing a pair wins.
+fn kern_table_pairs(face: &Face<'_>, pairs: &mut BTreeMap<(u16, u16), f32>) -> Option<()> {
+    let kern = face.table_data(Tag::from_bytes(b"kern"))?;
+    // Apple kern tables have a 32 bit version 1.0, OpenType a 16 bit version 0
+    let apple = read_u16(kern, 0)? == 1;
+    let (count, mut offset) = if apple {
+        (read_u32(kern, 4)? as usize, 8)
+    } else {
+        (usize::from(read_u16(kern, 2)?), 4)
+    };
+
+    for _ in 0..count {
+        let coverage = read_u16(kern, offset + 4)?;
+        let (length, horizontal, format, header) = if apple {
+            // vertical, cross-stream & variation bits
+            let horizontal = coverage & 0xE000 == 0;
+            (
+                read_u32(kern, offset)? as usize,
+                horizontal,
+                coverage & 0xFF,
+                8,
+            )
+        } else {
+            let horizontal = coverage & 0x0001 != 0 && coverage & 0x0004 == 0;
+            let length = usize::from(read_u16(kern, offset + 2)?);
+            (length, horizontal, coverage >> 8, 6)
+        };
+
+        if horizontal && format == 0 {
+            let subtable = offset + header;
+            let num_pairs = usize::from(read_u16(kern, subtable)?);
+            for pair in (0..num_pairs).map(|idx| subtable + 8 + idx * 6) {
+                let left = read_u16(kern, pair)?;
+                let right = read_u16(kern, pair + 2)?;
+                let value = read_u16(kern, pair + 4)? as i16;
+                pairs
+                    .entry((left, right))
+                    .or_insert_with(|| f32::from(value));
+            }
+        }
+        offset += length;
+    }
+    Some(())
+}
+
+/// A horizontal or vertical, non-variable kern subtable selected on load, located once
+/// so pair lookups read its data directly rather than re-walking the subtable headers.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct KernSubtable {
+    /// Offset of the data following the subtable header in the kern table.
+    offset: u32,
+    /// Length of the data following the subtable header.
+    len: u32,
+    format: u8,
+    /// Header size, format 2 offsets are relative to the subtable start.
+    header: u8,
+}
+
+impl KernSubtable {
+    /// Returns the kerning of a glyph pair, `None` if the subtable doesn't contain it.
+    pub(crate) fn glyphs_kerning(&self, face: &Face<'_>, left: u16, right: u16) -> Option<i16> {
+        let kern = face.table_data(Tag::from_bytes(b"kern"))?;
+        let start = self.offset as usize;
+        let data = kern.get(start..start + self.len as usize)?;
+        match self.format {
+            0 => format0_kerning(data, left, right),
+            2 => format2_kerning(data, usize::from(self.header), left, right),
+            3 => format3_kerning(data, left, right),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the horizontal & vertical non-variable kern subtables, in table order.
+pub(crate) fn subtables(face: &Face<'_>) -> (Vec<KernSubtable>, Vec<KernSubtable>) {
+    let (mut horizontal, mut vertical) = (Vec::new(), Vec::new());
+    if let Some(kern) = face.table_data(Tag::from_bytes(b"kern")) {
+        let _ = kern_subtables(kern, |is_horizontal, subtable| match is_horizontal {
+            true => horizontal.push(subtable),
+            false => vertical.push(subtable),
+        });
+    }
+    (horizontal, vertical)
+}
+
+/// Calls `f` with each non-variable subtable & whether it's horizontal, stopping at the
+/// first malformed or unknown format subtable.
+fn kern_subtables(kern: &[u8], mut f: impl FnMut(bool, KernSubtable)) -> Option<()> {
+    // Apple kern tables have a 32 bit version 1.0, OpenType a 16 bit version 0
+    let apple = read_u16(kern, 0)? != 0;
+    let (count, mut offset) = if apple {
+        (read_u32(kern, 4)? as usize, 8)
+    } else {
+        (usize::from(read_u16(kern, 2)?), 4)
+    };
+
+    for _ in 0..count {
+        let (horizontal, variable, format, header, len) = if apple {
+            let coverage = *kern.get(offset + 4)?;
+            let format = *kern.get(offset + 5)?;
+            if format > 3 {
+                return None;
+            }
+            let len = (read_u32(kern, offset)? as usize).checked_sub(8)?;
+            (coverage & 0x80 == 0, coverage & 0x20 != 0, format, 8, len)
+        } else {
+            let format = *kern.get(offset + 4)?;
+            let coverage = *kern.get(offset + 5)?;
+            if format != 0 && format != 2 {
+                return None;
+            }
+            let len = match count {
+                // a single subtable may exceed the 16 bit length, so extends to the table end
+                1 => kern.len().checked_sub(offset + 6)?,
+                _ => usize::from(read_u16(kern, offset + 2)?).checked_sub(6)?,
+            };
+            (coverage & 0x01 != 0, false, format, 6, len)
+        };
+        let data = offset + usize::from(header);
+        if data + len > kern.len() {
+            return None;
+        }
+        if !variable {
+            let subtable = KernSubtable {
+                offset: data as u32,
+                len: len as u32,
+                format,
+                header,
+            };
+            f(horizontal, subtable);
+        }
+        offset = data + len;
+    }
+    Some(())
+}
+
+/// Binary searches the sorted pairs of a format 0 subtable.
+fn format0_kerning(data: &[u8], left: u16, right: u16) -> Option<i16> {
+    let count = usize::from(read_u16(data, 0)?);
+    let needle = u32::from(left) << 16 | u32::from(right);
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let record = 8 + mid * 6;
+        match read_u32(data, record)?.cmp(&needle) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return read_u16(data, record + 4).map(|value| value as i16),
+        }
+    }
+    None
+}
+
+/// Looks up the class kerning of a format 2 subtable. Class values are premultiplied
+/// offsets from the subtable start.
+fn format2_kerning(data: &[u8], header: usize, left: u16, right: u16) -> Option<i16> {
+    let class_table = |offset: usize| -> Option<usize> {
+        let table = usize::from(read_u16(data, offset)?).checked_sub(header)?;
+        Some(table)
+    };
+    let class = |table: usize, glyph: u16| -> Option<u16> {
+        let index = glyph.checked_sub(read_u16(data, table)?)?;
+        if index >= read_u16(data, table + 2)? {
+            return None;
+        }
+        read_u16(data, table + 4 + usize::from(index) * 2)
+    };
+    let left_class = class(class_table(2)?, left).unwrap_or(0);
+    let right_class = class(class_table(4)?, right).unwrap_or(0);
+    let array = class_table(6)?;
+    if usize::from(left_class) < array {
+        return None;
+    }
+    let value = (usize::from(left_class) + usize::from(right_class)).checked_sub(header)?;
+    read_u16(data, value).map(|value| value as i16)
+}
+
+/// Looks up the class kerning of a format 3 subtable, an array of kerning value indices
+/// by left & right glyph class.
+fn format3_kerning(data: &[u8], left: u16, right: u16) -> Option<i16> {
+    let glyph_count = usize::from(read_u16(data, 0)?);
+    let values = usize::from(*data.get(2)?);
+    let left_classes = *data.get(3)?;
+    let right_classes = *data.get(4)?;
+    let left_class_table = 6 + values * 2;
+    let right_class_table = left_class_table + glyph_count;
+    let indices = right_class_table + glyph_count;
+    if usize::from(left) >= glyph_count || usize::from(right) >= glyph_count {
+        return None;
+    }
+    let left_class = *data.get(left_class_table + usize::from(left))?;
+    let right_class = *data.get(right_class_table + usize::from(right))?;
+    if left_class > left_classes || right_class > right_classes {
+        return None;
+    }
+    let index = usize::from(left_class) * usize::from(right_classes) + usize::from(right_class);
+    if index >= usize::from(left_classes) * usize::from(right_classes) {
+        return None;
+    }
+    let value = usize::from(*data.get(indices + index)?);
+    if value >= values {
+        return None;
+    }
+    read_u16(data, 6 + value * 2).map(|value| value as i16)
+}
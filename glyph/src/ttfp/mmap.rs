@@ -0,0 +1,143 @@
+//! Memory-mapped font loading, requires the `memmap2` feature.
+use super::{debug_face, font_error, FontRef, Subtables};
+use crate::FontArc;
+use memmap::Mmap;
+use std::{fmt, fs::File, io, path::Path, sync::Arc};
+
+/// Font data handle memory-mapped from a file + parsed data, requires the `memmap2` feature.
+/// See [`Font`](trait.Font.html) for more methods.
+///
+/// Unlike [`FontVec`](struct.FontVec.html) the font data isn't read into memory, the OS pages
+/// in the parts used, so holding many large fonts, e.g. CJK or emoji fonts, costs little
+/// memory. Clones are cheap & share the same mapping.
+///
+/// # Example
+/// ```
+/// use ab_glyph::{Font, FontMmap};
+///
+/// # fn main() -> std::io::Result<()> {
+/// let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../dev/fonts/Exo2-Light.otf");
+/// // safety: the font file isn't modified while mapped
+/// let font = unsafe { FontMmap::try_from_path(path)? };
+///
+/// assert_eq!(font.glyph_id('s'), ab_glyph::GlyphId(56));
+/// # Ok(()) }
+/// ```
+#[derive(Clone)]
+pub struct FontMmap(
+    pub(super) Arc<MmapFace>,
+    pub(super) Subtables,
+    /// Font collection index.
+    u32,
+);
+
+/// Parsed face borrowing the memory-mapped data it owns.
+pub(super) struct MmapFace {
+    // note: declared before `mmap` so it's dropped first
+    face: owned_ttf_parser::Face<'static>,
+    mmap: Mmap,
+}
+
+impl MmapFace {
+    #[inline]
+    pub(super) fn as_face_ref(&self) -> &owned_ttf_parser::Face<'_> {
+        &self.face
+    }
+
+    #[inline]
+    pub(super) fn as_slice(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+impl fmt::Debug for FontMmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_face(f, "FontMmap", self.0.as_face_ref(), self.2)
+    }
+}
+
+impl FontMmap {
+    /// Creates an `FontMmap` by memory-mapping the font file at `path`.
+    ///
+    /// For font collections see
+    /// [`FontMmap::try_from_path_and_index`](#method.try_from_path_and_index).
+    ///
+    /// # Errors
+    /// Returns file open & mapping errors, or an
+    /// [`InvalidData`](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData)
+    /// error wrapping a [`FontError`](enum.FontError.html) if the data isn't a valid font.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while the font, or any clone, is alive.
+    /// Changes are visible through the mapping & may be undefined behaviour, e.g. `SIGBUS`
+    /// on truncation. Fonts installed read-only for the process' lifetime are typically safe.
+    #[inline]
+    pub unsafe fn try_from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::try_from_path_and_index(path, 0)
+    }
+
+    /// Creates an `FontMmap` by memory-mapping the font file at `path`.
+    ///
+    /// You can set index for font collections. For simple fonts use `0` or
+    /// [`FontMmap::try_from_path`](#method.try_from_path).
+    ///
+    /// # Safety
+    /// See [`FontMmap::try_from_path`](#method.try_from_path).
+    #[inline]
+    pub unsafe fn try_from_path_and_index<P: AsRef<Path>>(path: P, index: u32) -> io::Result<Self> {
+        Self::try_from_file_and_index(&File::open(path)?, index)
+    }
+
+    /// Creates an `FontMmap` by memory-mapping an open font file.
+    ///
+    /// You can set index for font collections. For simple fonts use `0`.
+    ///
+    /// # Safety
+    /// See [`FontMmap::try_from_path`](#method.try_from_path).
+    pub unsafe fn try_from_file_and_index(file: &File, index: u32) -> io::Result<Self> {
+        let mmap = Mmap::map(file)?;
+        // safety: the mapped data doesn't move when `mmap` does & outlives `face`
+        let data: &'static [u8] = core::slice::from_raw_parts(mmap.as_ptr(), mmap.len());
+        let face = owned_ttf_parser::Face::from_slice(data, index)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, font_error(e)))?;
+        let subtables = Subtables::new(&face);
+        Ok(Self(Arc::new(MmapFace { face, mmap }), subtables, index))
+    }
+
+    /// Returns a `FontRef` borrowing this font's data, e.g. to
+    /// [set variations](struct.FontRef.html#method.set_variation) without re-parsing the font.
+    #[inline]
+    pub fn as_font_ref(&self) -> FontRef<'_> {
+        FontRef(
+            self.0.as_face_ref().clone(),
+            self.1.clone(),
+            self.2,
+            Some(self.0.as_slice()),
+        )
+    }
+
+    /// Returns the font collection index of this face, `0` for non-collection fonts.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.2
+    }
+}
+
+impl FontArc {
+    /// Creates an `FontArc` by memory-mapping the font file at `path`, requires the
+    /// `memmap2` feature.
+    ///
+    /// # Safety
+    /// See [`FontMmap::try_from_path`](struct.FontMmap.html#method.try_from_path).
+    #[inline]
+    pub unsafe fn try_from_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(FontMmap::try_from_path(path)?.into())
+    }
+}
+
+impl From<FontMmap> for FontArc {
+    #[inline]
+    fn from(font: FontMmap) -> Self {
+        Self::new(font)
+    }
+}
@@ -0,0 +1,86 @@
+//! AAT optical bounds (opbd) table.
+use super::read_u16;
+use crate::{GlyphId, OpticalBounds};
+use owned_ttf_parser::{Face, Tag};
+
+/// Returns the optical bounds of a glyph from the face's opbd table, if present.
+///
+/// Only format 0 (distance) tables are supported.
+pub(crate) fn optical_bounds(face: &Face<'_>, id: GlyphId) -> Option<OpticalBounds> {
+    let opbd = face.table_data(Tag::from_bytes(b"opbd"))?;
+    if read_u16(opbd, 0)? != 1 || read_u16(opbd, 4)? != 0 {
+        return None;
+    }
+    let offset = usize::from(lookup(opbd.get(6..)?, id.0)?);
+    let value = |n: usize| read_u16(opbd, offset + n * 2).map(|v| f32::from(v as i16));
+    Some(OpticalBounds {
+        left: value(0)?,
+        top: value(1)?,
+        right: value(2)?,
+        bottom: value(3)?,
+    })
+}
+
+/// Looks up a glyph's 16-bit value in an AAT lookup table.
+fn lookup(table: &[u8], glyph: u16) -> Option<u16> {
+    match read_u16(table, 0)? {
+        // simple array
+        0 => read_u16(table, 2 + usize::from(glyph) * 2),
+        // segment single
+        2 => {
+            let (unit_size, count) = bin_search_header(table)?;
+            (0..count).map(|idx| 12 + idx * unit_size).find_map(|seg| {
+                let last = read_u16(table, seg)?;
+                let first = read_u16(table, seg + 2)?;
+                if first <= glyph && glyph <= last {
+                    read_u16(table, seg + 4)
+                } else {
+                    None
+                }
+            })
+        }
+        // segment array
+        4 => {
+            let (unit_size, count) = bin_search_header(table)?;
+            (0..count)
+                .map(|idx| 12 + idx * unit_size)
+                .find_map(|seg| {
+                    let last = read_u16(table, seg)?;
+                    let first = read_u16(table, seg + 2)?;
+                    if first <= glyph && glyph <= last {
+                        Some((first, read_u16(table, seg + 4)?))
+                    } else {
+                        None
+                    }
+                })
+                .and_then(|(first, offset)| {
+                    read_u16(table, usize::from(offset) + usize::from(glyph - first) * 2)
+                })
+        }
+        // single table
+        6 => {
+            let (unit_size, count) = bin_search_header(table)?;
+            (0..count)
+                .map(|idx| 12 + idx * unit_size)
+                .find(|&entry| read_u16(table, entry) == Some(glyph))
+                .and_then(|entry| read_u16(table, entry + 2))
+        }
+        // trimmed array
+        8 => {
+            let first = read_u16(table, 2)?;
+            let count = read_u16(table, 4)?;
+            let idx = glyph.checked_sub(first).filter(|idx| *idx < count)?;
+            read_u16(table, 6 + usize::from(idx) * 2)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the `(unit_size, unit_count)` of a lookup table binary search header.
+#[inline]
+fn bin_search_header(table: &[u8]) -> Option<(usize, usize)> {
+    Some((
+        usize::from(read_u16(table, 2)?),
+        usize::from(read_u16(table, 4)?),
+    ))
+}
@@ -1,22 +1,38 @@
-use crate::{point, OutlineCurve, Point};
+use crate::{point, OutlineCurve, OutlineSink, Point};
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
+/// Builds outline curves into `E`, usually a `Vec<OutlineCurve>`.
 #[derive(Debug, Default)]
-pub(crate) struct OutlineCurveBuilder {
+pub(crate) struct OutlineCurveBuilder<E = Vec<OutlineCurve>> {
     last: Point,
     last_move: Option<Point>,
-    outline: Vec<OutlineCurve>,
+    outline: E,
 }
 
-impl OutlineCurveBuilder {
+impl<E: Extend<OutlineCurve>> OutlineCurveBuilder<E> {
+    /// Builder appending curves to the given, possibly recycled, collection.
     #[inline]
-    pub(crate) fn take_outline(self) -> Vec<OutlineCurve> {
+    pub(crate) fn with_buffer(outline: E) -> Self {
+        Self {
+            last: Point::default(),
+            last_move: None,
+            outline,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn take_outline(self) -> E {
         self.outline
     }
+
+    #[inline]
+    fn push(&mut self, curve: OutlineCurve) {
+        self.outline.extend(Some(curve));
+    }
 }
 
-impl owned_ttf_parser::OutlineBuilder for OutlineCurveBuilder {
+impl<E: Extend<OutlineCurve>> owned_ttf_parser::OutlineBuilder for OutlineCurveBuilder<E> {
     #[inline]
     fn move_to(&mut self, x: f32, y: f32) {
         self.last = point(x, y);
@@ -26,7 +42,7 @@ impl owned_ttf_parser::OutlineBuilder for OutlineCurveBuilder {
     #[inline]
     fn line_to(&mut self, x1: f32, y1: f32) {
         let p1 = point(x1, y1);
-        self.outline.push(OutlineCurve::Line(self.last, p1));
+        self.push(OutlineCurve::Line(self.last, p1));
         self.last = p1;
     }
 
@@ -34,7 +50,7 @@ impl owned_ttf_parser::OutlineBuilder for OutlineCurveBuilder {
     fn quad_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
         let p1 = point(x1, y1);
         let p2 = point(x2, y2);
-        self.outline.push(OutlineCurve::Quad(self.last, p1, p2));
+        self.push(OutlineCurve::Quad(self.last, p1, p2));
         self.last = p2;
     }
 
@@ -44,15 +60,45 @@ impl owned_ttf_parser::OutlineBuilder for OutlineCurveBuilder {
         let p2 = point(x2, y2);
         let p3 = point(x3, y3);
 
-        self.outline
-            .push(OutlineCurve::Cubic(self.last, p1, p2, p3));
+        self.push(OutlineCurve::Cubic(self.last, p1, p2, p3));
         self.last = p3;
     }
 
     #[inline]
     fn close(&mut self) {
         if let Some(m) = self.last_move {
-            self.outline.push(OutlineCurve::Line(self.last, m));
+            self.push(OutlineCurve::Line(self.last, m));
         }
     }
 }
+
+/// Forwards outline builder calls to an `OutlineSink`, streaming outlines without
+/// collecting curves.
+pub(crate) struct SinkBuilder<'a>(pub(crate) &'a mut dyn OutlineSink);
+
+impl owned_ttf_parser::OutlineBuilder for SinkBuilder<'_> {
+    #[inline]
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(point(x, y));
+    }
+
+    #[inline]
+    fn line_to(&mut self, x1: f32, y1: f32) {
+        self.0.line_to(point(x1, y1));
+    }
+
+    #[inline]
+    fn quad_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.0.quad_to(point(x1, y1), point(x2, y2));
+    }
+
+    #[inline]
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) {
+        self.0.curve_to(point(x1, y1), point(x2, y2), point(x3, y3));
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
@@ -0,0 +1,355 @@
+//! TrueType (glyf) font subsetting.
+use super::{read_u16, read_u32};
+use crate::{FontSubset, GlyphId};
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use owned_ttf_parser::{Face, Tag};
+
+/// Tables copied to the subset unchanged. Layout & variation tables are dropped
+/// as they reference original glyph ids.
+const COPIED_TABLES: [&[u8; 4]; 6] = [b"OS/2", b"cvt ", b"fpgm", b"gasp", b"name", b"prep"];
+
+pub(crate) const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+pub(crate) const WE_HAVE_A_SCALE: u16 = 0x0008;
+pub(crate) const MORE_COMPONENTS: u16 = 0x0020;
+pub(crate) const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+pub(crate) const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+
+pub(crate) fn subset(face: &Face<'_>, glyphs: &[GlyphId]) -> Option<FontSubset> {
+    let table = |tag: &[u8; 4]| face.table_data(Tag::from_bytes(tag));
+    let head = table(b"head")?;
+    let hhea = table(b"hhea")?;
+    let maxp = table(b"maxp")?;
+    let glyf = table(b"glyf")?;
+    let loca = table(b"loca")?;
+    let long_loca = read_u16(head, 50)? == 1;
+    let num_glyphs = face.number_of_glyphs();
+
+    let glyph_data = |id: u16| -> Option<&[u8]> {
+        let idx = usize::from(id);
+        let (start, end) = if long_loca {
+            (
+                read_u32(loca, idx * 4)? as usize,
+                read_u32(loca, idx * 4 + 4)? as usize,
+            )
+        } else {
+            (
+                usize::from(read_u16(loca, idx * 2)?) * 2,
+                usize::from(read_u16(loca, idx * 2 + 2)?) * 2,
+            )
+        };
+        glyf.get(start..end)
+    };
+
+    // always include .notdef
+    let mut roots: Vec<u16> = glyphs.iter().map(|g| g.0).collect();
+    roots.push(0);
+    let (old_ids, new_glyf, new_loca) = subset_glyf(roots, num_glyphs, glyph_data)?;
+    let new_id = |old: u16| old_ids.binary_search(&old).ok().map(|idx| idx as u16);
+    let count = old_ids.len() as u16;
+
+    let mut hmtx = Vec::with_capacity(old_ids.len() * 4);
+    for &old in &old_ids {
+        let advance = face.glyph_hor_advance(owned_ttf_parser::GlyphId(old));
+        let lsb = face.glyph_hor_side_bearing(owned_ttf_parser::GlyphId(old));
+        hmtx.extend_from_slice(&advance.unwrap_or(0).to_be_bytes());
+        hmtx.extend_from_slice(&lsb.unwrap_or(0).to_be_bytes());
+    }
+
+    let mut new_head = head.to_vec();
+    write_u16(&mut new_head, 8, 0); // checkSumAdjustment, set after writing
+    write_u16(&mut new_head, 10, 0);
+    write_u16(&mut new_head, 50, 1); // long loca
+    let mut new_hhea = hhea.to_vec();
+    write_u16(&mut new_hhea, 34, count); // numberOfHMetrics
+    let mut new_maxp = maxp.to_vec();
+    write_u16(&mut new_maxp, 4, count); // numGlyphs
+
+    let mut tables = vec![
+        (*b"cmap", subset_cmap(face, new_id)),
+        (*b"glyf", new_glyf),
+        (*b"head", new_head),
+        (*b"hhea", new_hhea),
+        (*b"hmtx", hmtx),
+        (*b"loca", new_loca),
+        (*b"maxp", new_maxp),
+    ];
+    if let Some(post) = table(b"post").and_then(|post| post.get(..32)) {
+        // version 3.0, no glyph names
+        let mut post = post.to_vec();
+        post[..4].copy_from_slice(&0x0003_0000_u32.to_be_bytes());
+        tables.push((*b"post", post));
+    }
+    for tag in COPIED_TABLES.iter() {
+        if let Some(data) = table(tag) {
+            tables.push((**tag, data.to_vec()));
+        }
+    }
+
+    Some(FontSubset {
+        data: write_sfnt(0x0001_0000, tables),
+        glyphs: old_ids.into_iter().map(GlyphId).collect(),
+    })
+}
+
+/// Copies glyphs, resolving composite components, into new glyf & long format loca
+/// table data with component ids remapped.
+///
+/// Returns the sorted original ids of the new glyphs with the glyf & loca data.
+pub(crate) fn subset_glyf<D: AsRef<[u8]>>(
+    roots: Vec<u16>,
+    num_glyphs: u16,
+    glyph_data: impl Fn(u16) -> Option<D>,
+) -> Option<(Vec<u16>, Vec<u8>, Vec<u8>)> {
+    let mut included = BTreeSet::new();
+    let mut pending = roots;
+    while let Some(id) = pending.pop() {
+        if id < num_glyphs && included.insert(id) {
+            let data = glyph_data(id)?;
+            let data = data.as_ref();
+            for offset in component_id_offsets(data) {
+                pending.push(read_u16(data, offset)?);
+            }
+        }
+    }
+    let old_ids: Vec<u16> = included.into_iter().collect();
+    let new_id = |old: u16| old_ids.binary_search(&old).ok().map(|idx| idx as u16);
+
+    let mut glyf = Vec::new();
+    let mut loca = Vec::with_capacity((old_ids.len() + 1) * 4);
+    for &old in &old_ids {
+        loca.extend_from_slice(&(glyf.len() as u32).to_be_bytes());
+        let data = glyph_data(old)?;
+        let data = data.as_ref();
+        let start = glyf.len();
+        glyf.extend_from_slice(data);
+        for offset in component_id_offsets(data) {
+            let component = new_id(read_u16(data, offset)?)?;
+            write_u16(&mut glyf, start + offset, component);
+        }
+        pad4(&mut glyf);
+    }
+    loca.extend_from_slice(&(glyf.len() as u32).to_be_bytes());
+    Some((old_ids, glyf, loca))
+}
+
+/// Returns the offsets of each component glyph id in composite glyph data.
+pub(crate) fn component_id_offsets(glyph: &[u8]) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    match read_u16(glyph, 0) {
+        Some(contours) if (contours as i16) < 0 => {}
+        _ => return offsets,
+    }
+    let mut offset = 10;
+    while let Some(flags) = read_u16(glyph, offset) {
+        if offset + 4 > glyph.len() {
+            break;
+        }
+        offsets.push(offset + 2);
+        offset += if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+            8
+        } else {
+            6
+        };
+        if flags & WE_HAVE_A_SCALE != 0 {
+            offset += 2;
+        } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+            offset += 4;
+        } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+            offset += 8;
+        }
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+    offsets
+}
+
+/// Builds a cmap with a format 4 BMP subtable & a format 12 subtable if required.
+fn subset_cmap(face: &Face<'_>, new_id: impl Fn(u16) -> Option<u16>) -> Vec<u8> {
+    let mut mapping = BTreeMap::new();
+    for subtable in face.character_mapping_subtables() {
+        if !subtable.is_unicode() {
+            continue;
+        }
+        subtable.codepoints(|cp| {
+            if let Some(id) = subtable.glyph_index(cp).and_then(|id| new_id(id.0)) {
+                mapping.entry(cp).or_insert(id);
+            }
+        });
+    }
+
+    // runs of consecutive chars mapping to consecutive glyphs
+    let mut groups: Vec<(u32, u32, u16)> = Vec::new();
+    for (&cp, &id) in &mapping {
+        let extends = groups.last_mut().filter(|&&mut (start, end, start_id)| {
+            end + 1 == cp && u32::from(start_id) + (cp - start) == u32::from(id)
+        });
+        if let Some(group) = extends {
+            group.1 = cp;
+        } else {
+            groups.push((cp, cp, id));
+        }
+    }
+
+    let format4 = cmap_format4(&groups);
+    let needs_format12 = groups.iter().any(|&(_, end, _)| end > 0xFFFF) || format4.is_none();
+
+    let mut subtables = Vec::new();
+    if let Some(format4) = format4 {
+        subtables.push((1_u16, format4));
+    }
+    if needs_format12 {
+        subtables.push((10, cmap_format12(&groups)));
+    }
+
+    let mut cmap = Vec::new();
+    cmap.extend_from_slice(&0_u16.to_be_bytes());
+    cmap.extend_from_slice(&(subtables.len() as u16).to_be_bytes());
+    let mut offset = 4 + 8 * subtables.len() as u32;
+    for (encoding, data) in &subtables {
+        cmap.extend_from_slice(&3_u16.to_be_bytes()); // windows platform
+        cmap.extend_from_slice(&encoding.to_be_bytes());
+        cmap.extend_from_slice(&offset.to_be_bytes());
+        offset += data.len() as u32;
+    }
+    for (_, data) in subtables {
+        cmap.extend(data);
+    }
+    cmap
+}
+
+/// Segment mapping to delta values, returns `None` if the groups don't fit.
+fn cmap_format4(groups: &[(u32, u32, u16)]) -> Option<Vec<u8>> {
+    let mut segments: Vec<(u16, u16, u16)> = groups
+        .iter()
+        .filter(|&&(start, _, _)| start < 0xFFFF)
+        .map(|&(start, end, id)| (start as u16, end.min(0xFFFE) as u16, id))
+        .collect();
+    segments.push((0xFFFF, 0xFFFF, 0));
+
+    let seg_count = segments.len();
+    let length = 16 + seg_count * 8;
+    if length > usize::from(u16::MAX) {
+        return None;
+    }
+    let entry_selector = 15 - (seg_count as u16).leading_zeros() as u16;
+    let search_range = 2 << entry_selector;
+
+    let mut data = Vec::with_capacity(length);
+    for value in &[
+        4,
+        length as u16,
+        0,
+        seg_count as u16 * 2,
+        search_range,
+        entry_selector,
+        seg_count as u16 * 2 - search_range,
+    ] {
+        data.extend_from_slice(&value.to_be_bytes());
+    }
+    for (_, end, _) in &segments {
+        data.extend_from_slice(&end.to_be_bytes());
+    }
+    data.extend_from_slice(&0_u16.to_be_bytes()); // reservedPad
+    for (start, _, _) in &segments {
+        data.extend_from_slice(&start.to_be_bytes());
+    }
+    for (start, _, id) in &segments {
+        let delta = if *start == 0xFFFF {
+            1
+        } else {
+            id.wrapping_sub(*start)
+        };
+        data.extend_from_slice(&delta.to_be_bytes());
+    }
+    for _ in &segments {
+        data.extend_from_slice(&0_u16.to_be_bytes()); // idRangeOffset
+    }
+    Some(data)
+}
+
+/// Segmented coverage.
+fn cmap_format12(groups: &[(u32, u32, u16)]) -> Vec<u8> {
+    let length = 16 + groups.len() as u32 * 12;
+    let mut data = Vec::with_capacity(length as usize);
+    data.extend_from_slice(&12_u16.to_be_bytes());
+    data.extend_from_slice(&0_u16.to_be_bytes());
+    data.extend_from_slice(&length.to_be_bytes());
+    data.extend_from_slice(&0_u32.to_be_bytes()); // language
+    data.extend_from_slice(&(groups.len() as u32).to_be_bytes());
+    for &(start, end, id) in groups {
+        data.extend_from_slice(&start.to_be_bytes());
+        data.extend_from_slice(&end.to_be_bytes());
+        data.extend_from_slice(&u32::from(id).to_be_bytes());
+    }
+    data
+}
+
+/// Writes a font file from `(tag, data)` tables, e.g. `0x00010000` version for TrueType.
+pub(crate) fn write_sfnt(sfnt_version: u32, mut tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    tables.sort_by_key(|(tag, _)| *tag);
+    let mut font = Vec::new();
+    write_offset_table(&mut font, sfnt_version, tables.len() as u16);
+
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = None;
+    for (tag, data) in &tables {
+        if tag == b"head" {
+            head_offset = Some(offset);
+        }
+        font.extend_from_slice(tag);
+        font.extend_from_slice(&checksum(data).to_be_bytes());
+        font.extend_from_slice(&(offset as u32).to_be_bytes());
+        font.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        offset += (data.len() + 3) & !3;
+    }
+    for (_, data) in &tables {
+        font.extend_from_slice(data);
+        pad4(&mut font);
+    }
+
+    if let Some(head_offset) = head_offset {
+        let adjustment = 0xB1B0_AFBA_u32.wrapping_sub(checksum(&font));
+        font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+    font
+}
+
+/// Writes an sfnt offset table header, the table records follow.
+pub(crate) fn write_offset_table(font: &mut Vec<u8>, sfnt_version: u32, num_tables: u16) {
+    let entry_selector = 15 - num_tables.leading_zeros() as u16;
+    let search_range = 16 << entry_selector;
+
+    font.extend_from_slice(&sfnt_version.to_be_bytes());
+    for value in &[
+        num_tables,
+        search_range,
+        entry_selector,
+        num_tables * 16 - search_range,
+    ] {
+        font.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// Sum of big-endian u32 words, zero padded.
+pub(crate) fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0_u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+#[inline]
+pub(crate) fn pad4(data: &mut Vec<u8>) {
+    data.resize((data.len() + 3) & !3, 0);
+}
+
+#[inline]
+pub(crate) fn write_u16(data: &mut [u8], offset: usize, value: u16) {
+    if let Some(bytes) = data.get_mut(offset..offset + 2) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+}
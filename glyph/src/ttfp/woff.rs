@@ -0,0 +1,586 @@
+//! WOFF & WOFF2 web font decoding into sfnt font data.
+#[cfg(feature = "woff2")]
+use super::subset::{
+    pad4, ARG_1_AND_2_ARE_WORDS, MORE_COMPONENTS, WE_HAVE_AN_X_AND_Y_SCALE, WE_HAVE_A_SCALE,
+    WE_HAVE_A_TWO_BY_TWO,
+};
+use super::{read_u16, read_u32, subset::write_sfnt};
+use crate::{FontError, FontVec};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+impl FontVec {
+    /// Creates a `FontVec` by decompressing WOFF font data, requires the `woff` feature.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ab_glyph::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let data = std::fs::read("Exo2-Light.woff")?;
+    /// let font = FontVec::try_from_woff(&data)?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "woff")]
+    pub fn try_from_woff(data: &[u8]) -> Result<Self, FontError> {
+        Self::try_from_vec(woff_to_sfnt(data)?)
+    }
+
+    /// Creates a `FontVec` by decompressing WOFF2 font data, requires the `woff2` feature.
+    ///
+    /// Transformed glyf, loca & hmtx tables are reconstructed. WOFF2 font collections are
+    /// not supported.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ab_glyph::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let data = std::fs::read("Exo2-Light.woff2")?;
+    /// let font = FontVec::try_from_woff2(&data)?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "woff2")]
+    pub fn try_from_woff2(data: &[u8]) -> Result<Self, FontError> {
+        Self::try_from_vec(woff2_to_sfnt(data)?)
+    }
+}
+
+/// Decompresses WOFF data into sfnt font data.
+#[cfg(feature = "woff")]
+fn woff_to_sfnt(data: &[u8]) -> Result<Vec<u8>, FontError> {
+    if data.get(..4) != Some(&b"wOFF"[..]) {
+        return Err(FontError::UnknownMagic);
+    }
+    let flavor = read_u32(data, 4).ok_or(FontError::Malformed)?;
+    let num_tables = read_u16(data, 12).ok_or(FontError::Malformed)?;
+
+    let mut tables = Vec::with_capacity(usize::from(num_tables));
+    for entry in (0..usize::from(num_tables)).map(|idx| 44 + idx * 20) {
+        let field = |offset| {
+            read_u32(data, entry + offset)
+                .map(|value| value as usize)
+                .ok_or(FontError::Malformed)
+        };
+        let mut tag = [0; 4];
+        tag.copy_from_slice(data.get(entry..entry + 4).ok_or(FontError::Malformed)?);
+        let (offset, comp_len, orig_len) = (field(4)?, field(8)?, field(12)?);
+
+        let stored = data
+            .get(offset..offset + comp_len)
+            .ok_or(FontError::Malformed)?;
+        // tables that don't shrink are stored uncompressed
+        let table = if comp_len < orig_len {
+            miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(stored, orig_len)
+                .map_err(|_| FontError::Malformed)?
+        } else {
+            stored.to_vec()
+        };
+        if table.len() != orig_len {
+            return Err(FontError::Malformed);
+        }
+        tables.push((tag, table));
+    }
+    Ok(write_sfnt(flavor, tables))
+}
+
+/// Table tags by index of the WOFF2 table directory entry flags.
+#[cfg(feature = "woff2")]
+const WOFF2_TAGS: [&[u8; 4]; 63] = [
+    b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"cvt ", b"fpgm",
+    b"glyf", b"loca", b"prep", b"CFF ", b"VORG", b"EBDT", b"EBLC", b"gasp", b"hdmx", b"kern",
+    b"LTSH", b"PCLT", b"VDMX", b"vhea", b"vmtx", b"BASE", b"GDEF", b"GPOS", b"GSUB", b"EBSC",
+    b"JSTF", b"MATH", b"CBDT", b"CBLC", b"COLR", b"CPAL", b"SVG ", b"sbix", b"acnt", b"avar",
+    b"bdat", b"bloc", b"bsln", b"cvar", b"fdsc", b"feat", b"fmtx", b"fvar", b"gvar", b"hsty",
+    b"just", b"lcar", b"mort", b"morx", b"opbd", b"prop", b"trak", b"Zapf", b"Silf", b"Glat",
+    b"Gloc", b"Feat", b"Sill",
+];
+
+#[cfg(feature = "woff2")]
+const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+
+/// WOFF2 table directory entry.
+#[cfg(feature = "woff2")]
+struct Woff2Table {
+    tag: [u8; 4],
+    transformed: bool,
+    /// Length in the decompressed stream.
+    len: usize,
+}
+
+/// Decompresses WOFF2 data into sfnt font data.
+#[cfg(feature = "woff2")]
+fn woff2_to_sfnt(data: &[u8]) -> Result<Vec<u8>, FontError> {
+    use std::io::Read;
+
+    if data.get(..4) != Some(&b"wOF2"[..]) {
+        return Err(FontError::UnknownMagic);
+    }
+    let flavor = read_u32(data, 4).ok_or(FontError::Malformed)?;
+    if flavor.to_be_bytes() == *b"ttcf" {
+        return Err(FontError::Malformed);
+    }
+    let num_tables = read_u16(data, 12).ok_or(FontError::Malformed)?;
+    let compressed_len = read_u32(data, 20).ok_or(FontError::Malformed)? as usize;
+
+    let mut directory = Reader::new(data.get(48..).ok_or(FontError::Malformed)?);
+    let mut entries = Vec::with_capacity(usize::from(num_tables));
+    let mut total_len = 0_usize;
+    for _ in 0..num_tables {
+        let flags = directory.u8()?;
+        let tag = match flags & 0x3F {
+            63 => {
+                let mut tag = [0; 4];
+                tag.copy_from_slice(directory.bytes(4)?);
+                tag
+            }
+            idx => *WOFF2_TAGS[usize::from(idx)],
+        };
+        // glyf & loca transform version 0 is the glyf transform, 3 is none
+        let version = flags >> 6;
+        let transformed = match &tag {
+            b"glyf" | b"loca" => version == 0,
+            _ => version != 0,
+        };
+        let orig_len = directory.base128()? as usize;
+        let len = if transformed {
+            directory.base128()? as usize
+        } else {
+            orig_len
+        };
+        total_len = total_len.checked_add(len).ok_or(FontError::Malformed)?;
+        entries.push(Woff2Table {
+            tag,
+            transformed,
+            len,
+        });
+    }
+
+    let compressed_start = 48 + directory.offset;
+    let compressed = data
+        .get(compressed_start..compressed_start + compressed_len)
+        .ok_or(FontError::Malformed)?;
+    let mut stream = Vec::with_capacity(total_len);
+    brotli_decompressor::Decompressor::new(compressed, 4096)
+        .take(total_len as u64)
+        .read_to_end(&mut stream)
+        .map_err(|_| FontError::Malformed)?;
+    if stream.len() != total_len {
+        return Err(FontError::Malformed);
+    }
+
+    let mut offset = 0;
+    let mut stored = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        stored.push((entry, &stream[offset..offset + entry.len]));
+        offset += entry.len;
+    }
+    let untransformed = |tag: &[u8; 4]| {
+        stored
+            .iter()
+            .find(|(entry, _)| entry.tag == *tag && !entry.transformed)
+            .map(|(_, data)| *data)
+            .ok_or(FontError::MissingTable(*tag))
+    };
+
+    let glyf = stored
+        .iter()
+        .find(|(entry, _)| &entry.tag == b"glyf" && entry.transformed)
+        .map(|(_, data)| reconstruct_glyf(data))
+        .transpose()?;
+
+    let mut tables = Vec::with_capacity(entries.len());
+    for (entry, data) in &stored {
+        let table = match (&entry.tag, &glyf) {
+            _ if !entry.transformed => data.to_vec(),
+            (b"glyf", Some(glyf)) => glyf.glyf.clone(),
+            (b"loca", Some(glyf)) => glyf.loca.clone(),
+            (b"hmtx", Some(glyf)) => {
+                let num_glyphs = read_u16(untransformed(b"maxp")?, 4);
+                let num_h_metrics = read_u16(untransformed(b"hhea")?, 34);
+                match (num_glyphs, num_h_metrics) {
+                    (Some(num_glyphs), Some(num_h_metrics)) => reconstruct_hmtx(
+                        data,
+                        usize::from(num_glyphs),
+                        usize::from(num_h_metrics),
+                        &glyf.x_mins,
+                    )?,
+                    _ => return Err(FontError::Malformed),
+                }
+            }
+            _ => return Err(FontError::Malformed),
+        };
+        tables.push((entry.tag, table));
+    }
+    Ok(write_sfnt(flavor, tables))
+}
+
+/// Reconstructed glyf & loca tables.
+#[cfg(feature = "woff2")]
+struct Glyf {
+    glyf: Vec<u8>,
+    loca: Vec<u8>,
+    /// Bounding box x min of each glyph, `0` for empty glyphs.
+    x_mins: Vec<i16>,
+}
+
+/// Reconstructs the glyf & loca tables from a transformed glyf table.
+#[cfg(feature = "woff2")]
+fn reconstruct_glyf(table: &[u8]) -> Result<Glyf, FontError> {
+    let mut header = Reader::new(table);
+    header.u16()?; // reserved
+    let option_flags = header.u16()?;
+    let num_glyphs = usize::from(header.u16()?);
+    let long_loca = header.u16()? == 1;
+    let mut stream_lens = [0; 7];
+    for len in &mut stream_lens {
+        *len = header.u32()? as usize;
+    }
+
+    let mut start = header.offset;
+    let mut next_stream = |len: usize| {
+        let stream = table.get(start..start + len).map(Reader::new);
+        start += len;
+        stream.ok_or(FontError::Malformed)
+    };
+    let mut n_contours = next_stream(stream_lens[0])?;
+    let mut n_points = next_stream(stream_lens[1])?;
+    let mut flags = next_stream(stream_lens[2])?;
+    let mut glyphs = next_stream(stream_lens[3])?;
+    let mut composites = next_stream(stream_lens[4])?;
+    let mut bboxes = next_stream(stream_lens[5])?;
+    let mut instructions = next_stream(stream_lens[6])?;
+    let overlap_bitmap = if option_flags & 1 != 0 {
+        Some(next_stream(num_glyphs.div_ceil(8))?.data)
+    } else {
+        None
+    };
+    let bbox_bitmap = bboxes.bytes(((num_glyphs + 31) >> 5) << 2)?;
+    let bit = |bitmap: &[u8], idx: usize| bitmap[idx >> 3] & (0x80 >> (idx & 7)) != 0;
+
+    let mut glyf = Vec::new();
+    let mut offsets = Vec::with_capacity(num_glyphs + 1);
+    let mut x_mins = Vec::with_capacity(num_glyphs);
+    for idx in 0..num_glyphs {
+        offsets.push(glyf.len());
+        let explicit_bbox = if bit(bbox_bitmap, idx) {
+            Some([bboxes.i16()?, bboxes.i16()?, bboxes.i16()?, bboxes.i16()?])
+        } else {
+            None
+        };
+
+        match n_contours.i16()? {
+            0 => x_mins.push(0),
+            -1 => {
+                let bbox = explicit_bbox.ok_or(FontError::Malformed)?;
+                let start = composites.offset;
+                let mut have_instructions = false;
+                loop {
+                    let flags = composites.u16()?;
+                    // glyph index & arguments
+                    let mut len = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                        6
+                    } else {
+                        4
+                    };
+                    if flags & WE_HAVE_A_SCALE != 0 {
+                        len += 2;
+                    } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                        len += 4;
+                    } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                        len += 8;
+                    }
+                    composites.bytes(len)?;
+                    have_instructions |= flags & WE_HAVE_INSTRUCTIONS != 0;
+                    if flags & MORE_COMPONENTS == 0 {
+                        break;
+                    }
+                }
+
+                glyf.extend_from_slice(&(-1_i16).to_be_bytes());
+                for value in &bbox {
+                    glyf.extend_from_slice(&value.to_be_bytes());
+                }
+                glyf.extend_from_slice(&composites.data[start..composites.offset]);
+                if have_instructions {
+                    let len = glyphs.u255()?;
+                    glyf.extend_from_slice(&len.to_be_bytes());
+                    glyf.extend_from_slice(instructions.bytes(usize::from(len))?);
+                }
+                x_mins.push(bbox[0]);
+            }
+            contours if contours > 0 => {
+                let mut end_points = Vec::with_capacity(contours as usize);
+                let mut total_points = 0_usize;
+                for _ in 0..contours {
+                    total_points += usize::from(n_points.u255()?);
+                    let end = total_points.checked_sub(1).ok_or(FontError::Malformed)?;
+                    if end > usize::from(u16::MAX) {
+                        return Err(FontError::Malformed);
+                    }
+                    end_points.push(end as u16);
+                }
+
+                let mut points = Vec::with_capacity(total_points);
+                let (mut x, mut y) = (0_i32, 0_i32);
+                for _ in 0..total_points {
+                    let flag = flags.u8()?;
+                    let (dx, dy) = triplet(flag & 0x7F, &mut glyphs)?;
+                    x += dx;
+                    y += dy;
+                    points.push((x, y, flag >> 7 == 0));
+                }
+                let instruction_len = usize::from(glyphs.u255()?);
+                let glyph_instructions = instructions.bytes(instruction_len)?;
+
+                let bbox = explicit_bbox.unwrap_or_else(|| {
+                    let (x, y) = (points[0].0, points[0].1);
+                    let (x_min, y_min, x_max, y_max) = points.iter().fold(
+                        (x, y, x, y),
+                        |(x_min, y_min, x_max, y_max), &(x, y, _)| {
+                            (x_min.min(x), y_min.min(y), x_max.max(x), y_max.max(y))
+                        },
+                    );
+                    [x_min as i16, y_min as i16, x_max as i16, y_max as i16]
+                });
+                let overlap = overlap_bitmap.is_some_and(|bitmap| bit(bitmap, idx));
+
+                glyf.extend_from_slice(&contours.to_be_bytes());
+                for value in &bbox {
+                    glyf.extend_from_slice(&value.to_be_bytes());
+                }
+                for end in &end_points {
+                    glyf.extend_from_slice(&end.to_be_bytes());
+                }
+                glyf.extend_from_slice(&(instruction_len as u16).to_be_bytes());
+                glyf.extend_from_slice(glyph_instructions);
+                write_points(&mut glyf, &points, overlap);
+                x_mins.push(bbox[0]);
+            }
+            _ => return Err(FontError::Malformed),
+        }
+        pad4(&mut glyf);
+    }
+    offsets.push(glyf.len());
+
+    let mut loca = Vec::with_capacity(offsets.len() * 4);
+    for offset in offsets {
+        if long_loca {
+            loca.extend_from_slice(&(offset as u32).to_be_bytes());
+        } else if offset / 2 <= usize::from(u16::MAX) {
+            loca.extend_from_slice(&((offset / 2) as u16).to_be_bytes());
+        } else {
+            return Err(FontError::Malformed);
+        }
+    }
+    Ok(Glyf { glyf, loca, x_mins })
+}
+
+/// Decodes a point's coordinate deltas from its flag & triplet data.
+#[cfg(feature = "woff2")]
+fn triplet(flag: u8, glyphs: &mut Reader<'_>) -> Result<(i32, i32), FontError> {
+    let with_sign = |flag: u8, value: i32| if flag & 1 != 0 { value } else { -value };
+    let flag_i = i32::from(flag);
+    Ok(match flag {
+        0..=9 => {
+            let b0 = i32::from(glyphs.u8()?);
+            (0, with_sign(flag, ((flag_i & 14) << 7) + b0))
+        }
+        10..=19 => {
+            let b0 = i32::from(glyphs.u8()?);
+            (with_sign(flag, (((flag_i - 10) & 14) << 7) + b0), 0)
+        }
+        20..=83 => {
+            let (b0, b1) = (flag_i - 20, i32::from(glyphs.u8()?));
+            (
+                with_sign(flag, 1 + (b0 & 0x30) + (b1 >> 4)),
+                with_sign(flag >> 1, 1 + ((b0 & 0x0C) << 2) + (b1 & 0x0F)),
+            )
+        }
+        84..=119 => {
+            let b0 = flag_i - 84;
+            let (b1, b2) = (i32::from(glyphs.u8()?), i32::from(glyphs.u8()?));
+            (
+                with_sign(flag, 1 + ((b0 / 12) << 8) + b1),
+                with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + b2),
+            )
+        }
+        120..=123 => {
+            let b = glyphs.bytes(3)?;
+            let (b0, b1, b2) = (i32::from(b[0]), i32::from(b[1]), i32::from(b[2]));
+            (
+                with_sign(flag, (b0 << 4) + (b1 >> 4)),
+                with_sign(flag >> 1, ((b1 & 0x0F) << 8) + b2),
+            )
+        }
+        _ => {
+            let b = glyphs.bytes(4)?;
+            (
+                with_sign(flag, i32::from(u16::from_be_bytes([b[0], b[1]]))),
+                with_sign(flag >> 1, i32::from(u16::from_be_bytes([b[2], b[3]]))),
+            )
+        }
+    })
+}
+
+/// Writes simple glyph flags & coordinates of absolute `(x, y, on_curve)` points.
+#[cfg(feature = "woff2")]
+fn write_points(glyf: &mut Vec<u8>, points: &[(i32, i32, bool)], overlap: bool) {
+    const ON_CURVE_POINT: u8 = 0x01;
+    const X_SHORT_VECTOR: u8 = 0x02;
+    const Y_SHORT_VECTOR: u8 = 0x04;
+    const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+    const OVERLAP_SIMPLE: u8 = 0x40;
+
+    // returns the flags of a delta, writing its coordinate bytes
+    fn delta(delta: i32, coords: &mut Vec<u8>, short: u8, same_or_positive: u8) -> u8 {
+        if delta == 0 {
+            same_or_positive
+        } else if delta.unsigned_abs() < 256 {
+            coords.push(delta.unsigned_abs() as u8);
+            if delta > 0 {
+                short | same_or_positive
+            } else {
+                short
+            }
+        } else {
+            coords.extend_from_slice(&(delta as i16).to_be_bytes());
+            0
+        }
+    }
+
+    let (mut flags, mut xs, mut ys) = (Vec::new(), Vec::new(), Vec::new());
+    let (mut last_x, mut last_y) = (0, 0);
+    for (idx, &(x, y, on_curve)) in points.iter().enumerate() {
+        let mut flag = if on_curve { ON_CURVE_POINT } else { 0 };
+        if idx == 0 && overlap {
+            flag |= OVERLAP_SIMPLE;
+        }
+        flag |= delta(x - last_x, &mut xs, X_SHORT_VECTOR, X_IS_SAME_OR_POSITIVE);
+        flag |= delta(y - last_y, &mut ys, Y_SHORT_VECTOR, Y_IS_SAME_OR_POSITIVE);
+        flags.push(flag);
+        last_x = x;
+        last_y = y;
+    }
+    glyf.extend_from_slice(&flags);
+    glyf.extend_from_slice(&xs);
+    glyf.extend_from_slice(&ys);
+}
+
+/// Reconstructs the hmtx table from a transformed hmtx table, using glyph x mins for
+/// omitted left side bearings.
+#[cfg(feature = "woff2")]
+fn reconstruct_hmtx(
+    table: &[u8],
+    num_glyphs: usize,
+    num_h_metrics: usize,
+    x_mins: &[i16],
+) -> Result<Vec<u8>, FontError> {
+    if num_h_metrics == 0 || num_h_metrics > num_glyphs {
+        return Err(FontError::Malformed);
+    }
+    let mut transformed = Reader::new(table);
+    let flags = transformed.u8()?;
+    let mut advances = Vec::with_capacity(num_h_metrics);
+    for _ in 0..num_h_metrics {
+        advances.push(transformed.u16()?);
+    }
+
+    let mut hmtx = Vec::with_capacity(num_h_metrics * 2 + num_glyphs * 2);
+    for idx in 0..num_glyphs {
+        // bit 0: proportional, bit 1: monospaced left side bearings omitted
+        let omitted = if idx < num_h_metrics {
+            flags & 1 != 0
+        } else {
+            flags & 2 != 0
+        };
+        let lsb = if omitted {
+            *x_mins.get(idx).ok_or(FontError::Malformed)?
+        } else {
+            transformed.i16()?
+        };
+        if let Some(advance) = advances.get(idx) {
+            hmtx.extend_from_slice(&advance.to_be_bytes());
+        }
+        hmtx.extend_from_slice(&lsb.to_be_bytes());
+    }
+    Ok(hmtx)
+}
+
+/// Sequential big endian reader of WOFF2 data.
+#[cfg(feature = "woff2")]
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+#[cfg(feature = "woff2")]
+impl<'a> Reader<'a> {
+    #[inline]
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    #[inline]
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], FontError> {
+        let bytes = self
+            .data
+            .get(self.offset..self.offset + len)
+            .ok_or(FontError::Malformed)?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn u8(&mut self) -> Result<u8, FontError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    #[inline]
+    fn u16(&mut self) -> Result<u16, FontError> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    #[inline]
+    fn i16(&mut self) -> Result<i16, FontError> {
+        Ok(self.u16()? as i16)
+    }
+
+    #[inline]
+    fn u32(&mut self) -> Result<u32, FontError> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Reads a `UIntBase128`, 7 bits per byte with the high bit set on all but the last.
+    fn base128(&mut self) -> Result<u32, FontError> {
+        let mut value = 0_u32;
+        for idx in 0..5 {
+            let byte = self.u8()?;
+            // no leading zeros or overflow
+            if (idx == 0 && byte == 0x80) || value & 0xFE00_0000 != 0 {
+                return Err(FontError::Malformed);
+            }
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(FontError::Malformed)
+    }
+
+    /// Reads a `255UInt16`, a variable length encoding of small numbers.
+    fn u255(&mut self) -> Result<u16, FontError> {
+        const ONE_MORE_BYTE_CODE_1: u8 = 255;
+        const ONE_MORE_BYTE_CODE_2: u8 = 254;
+        const WORD_CODE: u8 = 253;
+        const LOWEST_U_CODE: u16 = 253;
+
+        Ok(match self.u8()? {
+            WORD_CODE => self.u16()?,
+            ONE_MORE_BYTE_CODE_1 => u16::from(self.u8()?) + LOWEST_U_CODE,
+            ONE_MORE_BYTE_CODE_2 => u16::from(self.u8()?) + LOWEST_U_CODE * 2,
+            code => u16::from(code),
+        })
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// A variable font design axis, like weight or width.
+///
+/// See [`FontRef::variation_axes`](struct.FontRef.html#method.variation_axes).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VariationAxis {
+    /// Axis tag, e.g. `*b"wght"` or `*b"wdth"`.
+    pub tag: [u8; 4],
+    /// Minimum axis value, in user space units.
+    pub min_value: f32,
+    /// Default axis value, used when no variation is set.
+    pub default_value: f32,
+    /// Maximum axis value, in user space units.
+    pub max_value: f32,
+    /// The font recommends this axis is not exposed in user interfaces.
+    pub hidden: bool,
+}
+
+/// A named instance of a variable font, i.e. a predefined set of axis values like
+/// "Bold" or "Condensed Light".
+///
+/// See [`FontRef::named_instances`](struct.FontRef.html#method.named_instances).
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedInstance {
+    /// Subfamily name of the instance, if available as a unicode name.
+    pub name: Option<String>,
+    /// Value of each axis, in the same order as the font's variation axes.
+    pub coordinates: Vec<f32>,
+}
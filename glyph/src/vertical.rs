@@ -0,0 +1,309 @@
+use crate::{
+    point, CharAdvance, Font, Glyph, GlyphId, Outline, OutlineCurve, OutlineGroup, OutlinedGlyph,
+    Point, PxScaleFactor, Rect, ScaleFont,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Orientation of a character in vertical text, following the Unicode
+/// [Vertical_Orientation](https://www.unicode.org/reports/tr50/) property.
+///
+/// # Example
+/// ```
+/// use ab_glyph::VerticalOrientation;
+///
+/// assert_eq!(VerticalOrientation::of('本'), VerticalOrientation::Upright);
+/// assert_eq!(VerticalOrientation::of('か'), VerticalOrientation::Upright);
+/// assert_eq!(VerticalOrientation::of('Ａ'), VerticalOrientation::Upright);
+/// assert_eq!(VerticalOrientation::of('A'), VerticalOrientation::Rotated);
+/// assert_eq!(VerticalOrientation::of('「'), VerticalOrientation::Rotated);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VerticalOrientation {
+    /// Drawn upright, advancing by the vertical advance, e.g. CJK ideographs & kana.
+    Upright,
+    /// Drawn rotated 90° clockwise, advancing by the horizontal advance, e.g. Latin,
+    /// Cyrillic & Arabic text, or brackets without a vertical form.
+    Rotated,
+}
+
+impl VerticalOrientation {
+    /// Returns the orientation of a character in vertical text.
+    ///
+    /// Transformed characters, e.g. `'、'` or `'（'`, whose vertical forms are usually
+    /// substituted by the font are classified as their fallback orientation.
+    pub fn of(c: char) -> Self {
+        let upright = UPRIGHT
+            .binary_search_by(|&(start, end)| {
+                if end < c {
+                    Ordering::Less
+                } else if start > c {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok();
+        if upright {
+            Self::Upright
+        } else {
+            Self::Rotated
+        }
+    }
+}
+
+/// Inclusive character ranges drawn upright in vertical text, sorted.
+const UPRIGHT: &[(char, char)] = &[
+    ('\u{a7}', '\u{a7}'),
+    ('\u{a9}', '\u{a9}'),
+    ('\u{ae}', '\u{ae}'),
+    ('\u{b1}', '\u{b1}'),
+    ('\u{bc}', '\u{be}'),
+    ('\u{d7}', '\u{d7}'),
+    ('\u{f7}', '\u{f7}'),
+    ('\u{2ea}', '\u{2eb}'),
+    ('\u{1100}', '\u{11ff}'),
+    ('\u{1401}', '\u{167f}'),
+    ('\u{18b0}', '\u{18ff}'),
+    ('\u{2016}', '\u{2016}'),
+    ('\u{2020}', '\u{2021}'),
+    ('\u{2030}', '\u{2031}'),
+    ('\u{203b}', '\u{203c}'),
+    ('\u{2042}', '\u{2042}'),
+    ('\u{2047}', '\u{2049}'),
+    ('\u{2051}', '\u{2051}'),
+    ('\u{2100}', '\u{2101}'),
+    ('\u{2103}', '\u{2109}'),
+    ('\u{210f}', '\u{210f}'),
+    ('\u{2113}', '\u{2114}'),
+    ('\u{2116}', '\u{2117}'),
+    ('\u{211e}', '\u{2123}'),
+    ('\u{2125}', '\u{2125}'),
+    ('\u{2127}', '\u{2127}'),
+    ('\u{2129}', '\u{2129}'),
+    ('\u{212e}', '\u{212e}'),
+    ('\u{2135}', '\u{213f}'),
+    ('\u{2145}', '\u{214a}'),
+    ('\u{214c}', '\u{214d}'),
+    ('\u{214f}', '\u{2189}'),
+    ('\u{2460}', '\u{24ff}'),
+    ('\u{25a0}', '\u{2619}'),
+    ('\u{2620}', '\u{2767}'),
+    ('\u{2776}', '\u{2793}'),
+    // CJK radicals, ideographic description & CJK symbols up to the brackets
+    ('\u{2e80}', '\u{3007}'),
+    ('\u{3012}', '\u{3013}'),
+    ('\u{3020}', '\u{302f}'),
+    // hiragana, except the katakana-hiragana double hyphen
+    ('\u{3031}', '\u{309f}'),
+    // katakana, except the prolonged sound mark
+    ('\u{30a1}', '\u{30fb}'),
+    // bopomofo, hangul compatibility jamo, kanbun, CJK strokes, enclosed & compatibility
+    // CJK, CJK ideographs & yi
+    ('\u{30fd}', '\u{a4cf}'),
+    ('\u{a960}', '\u{a97f}'),
+    // hangul syllables & jamo extended-b
+    ('\u{ac00}', '\u{d7ff}'),
+    // private use & CJK compatibility ideographs
+    ('\u{e000}', '\u{faff}'),
+    ('\u{fe10}', '\u{fe1f}'),
+    ('\u{fe30}', '\u{fe4f}'),
+    ('\u{fe50}', '\u{fe57}'),
+    ('\u{fe5f}', '\u{fe6f}'),
+    // fullwidth forms, except brackets, dashes & colons
+    ('\u{ff01}', '\u{ff07}'),
+    ('\u{ff0a}', '\u{ff0c}'),
+    ('\u{ff0e}', '\u{ff19}'),
+    ('\u{ff1f}', '\u{ff3a}'),
+    ('\u{ff3c}', '\u{ff3c}'),
+    ('\u{ff3e}', '\u{ff3e}'),
+    ('\u{ff40}', '\u{ff5a}'),
+    ('\u{ffe0}', '\u{ffe7}'),
+    // kana supplement & extensions
+    ('\u{1b000}', '\u{1b2ff}'),
+    // mahjong, domino & playing card symbols, enclosed supplements & emoji
+    ('\u{1f000}', '\u{1faff}'),
+    // CJK ideograph extensions
+    ('\u{20000}', '\u{3fffd}'),
+    // supplementary private use
+    ('\u{f0000}', '\u{10fffd}'),
+];
+
+/// A glyph laid out in vertical text, see [`layout_vertical`](fn.layout_vertical.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerticalGlyph {
+    /// Glyph with position. Upright glyphs are positioned on their baseline as usual,
+    /// rotated glyphs at their origin which is drawn rotated 90° clockwise about it.
+    pub glyph: Glyph,
+    /// Orientation of the glyph's character.
+    pub orientation: VerticalOrientation,
+}
+
+impl VerticalGlyph {
+    /// Compute glyph outline ready for drawing, rotated 90° clockwise if the glyph is
+    /// [`Rotated`](enum.VerticalOrientation.html#variant.Rotated).
+    ///
+    /// # Example
+    /// ```
+    /// use ab_glyph::{layout_vertical, point, Font, FontRef};
+    ///
+    /// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+    /// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/OpenSans-Italic.ttf"))?;
+    /// let mut glyphs = Vec::new();
+    /// layout_vertical(font.as_scaled(24.0), "l", point(20.0, 0.0), &mut glyphs);
+    ///
+    /// // the tall 'l' lies on its side
+    /// let rotated = glyphs[0].outline(&font).unwrap().px_bounds();
+    /// assert!(rotated.width() > rotated.height());
+    /// let upright = font.outline_glyph(glyphs[0].glyph.clone()).unwrap().px_bounds();
+    /// assert!(upright.height() > upright.width());
+    /// # Ok(()) }
+    /// ```
+    pub fn outline<F: Font>(&self, font: &F) -> Option<OutlinedGlyph> {
+        if self.orientation == VerticalOrientation::Upright {
+            return font.outline_glyph(self.glyph.clone());
+        }
+
+        #[cfg(feature = "counters")]
+        crate::counters::add_glyph_outlined();
+
+        let scale_factor = font.as_scaled(self.glyph.scale).scale_factor();
+        let group = match font.color_outlines(self.glyph.id) {
+            Some(mut outline) if !outline.is_empty() => {
+                for layer in &mut outline.layers {
+                    layer.outline = rotate_cw(&layer.outline, scale_factor);
+                }
+                OutlineGroup::new(outline)
+            }
+            _ => OutlineGroup::from_outline(rotate_cw(&font.outline(self.glyph.id)?, scale_factor)),
+        };
+        // rotated outlines are already in pixels
+        let scale_factor = PxScaleFactor {
+            horizontal: 1.0,
+            vertical: 1.0,
+        };
+        Some(OutlinedGlyph::new(self.glyph.clone(), group, scale_factor))
+    }
+}
+
+/// Returns the outline scaled into pixels & rotated 90° clockwise, so unscaled y up
+/// becomes pixel x right & unscaled x right becomes pixel y down.
+fn rotate_cw(outline: &Outline, scale_factor: PxScaleFactor) -> Outline {
+    let (h_factor, v_factor) = (scale_factor.horizontal, scale_factor.vertical);
+    // pixel y is drawn down from outline y up
+    let rotate = |p: Point| point(p.y * v_factor, -p.x * h_factor);
+
+    let curves = outline
+        .curves
+        .iter()
+        .map(|curve| match *curve {
+            OutlineCurve::Line(p0, p1) => OutlineCurve::Line(rotate(p0), rotate(p1)),
+            OutlineCurve::Quad(p0, p1, p2) => {
+                OutlineCurve::Quad(rotate(p0), rotate(p1), rotate(p2))
+            }
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                OutlineCurve::Cubic(rotate(p0), rotate(p1), rotate(p2), rotate(p3))
+            }
+        })
+        .collect();
+
+    // bounds min is (left, top) & max (right, bottom) in outline y up
+    let Rect { min, max } = outline.bounds;
+    Outline {
+        bounds: Rect {
+            min: rotate(point(min.x, max.y)),
+            max: rotate(point(max.x, min.y)),
+        },
+        curves,
+    }
+}
+
+/// Lays out `text` top to bottom in vertical columns, starting with the column centred
+/// on `start.x` with its top at `start.y`. Newlines start a new column to the left.
+///
+/// [`Upright`](enum.VerticalOrientation.html#variant.Upright) glyphs are centred in the
+/// column & advance by their vertical advance, see
+/// [`ScaleFont::v_advance`](trait.ScaleFont.html#method.v_advance).
+/// [`Rotated`](enum.VerticalOrientation.html#variant.Rotated) glyphs, e.g. Latin text
+/// embedded in vertical CJK, are rotated 90° clockwise with their ascent to descent
+/// centred in the column & advance by their horizontal advance. Adjacent glyphs of the
+/// same orientation are kerned.
+///
+/// Draw with [`VerticalGlyph::outline`](struct.VerticalGlyph.html#method.outline).
+///
+/// # Example
+/// ```
+/// use ab_glyph::{layout_vertical, point, Font, FontRef, ScaleFont, VerticalOrientation};
+///
+/// # fn main() -> Result<(), ab_glyph::InvalidFont> {
+/// let font = FontRef::try_from_slice(include_bytes!("../../dev/fonts/Exo2-Light.otf"))?;
+/// let scaled_font = font.as_scaled(24.0);
+///
+/// let mut glyphs = Vec::new();
+/// layout_vertical(&scaled_font, "本AV\n本", point(100.0, 0.0), &mut glyphs);
+/// assert_eq!(glyphs.len(), 4);
+/// assert_eq!(glyphs[0].orientation, VerticalOrientation::Upright);
+/// assert_eq!(glyphs[1].orientation, VerticalOrientation::Rotated);
+///
+/// // upright glyphs sit on their baseline
+/// assert_eq!(glyphs[0].glyph.position.y, scaled_font.ascent());
+///
+/// // rotated glyphs advance down by their horizontal advance, kerned
+/// let (a, v) = (glyphs[1].glyph.id, glyphs[2].glyph.id);
+/// let rotated_advance = glyphs[2].glyph.position.y - glyphs[1].glyph.position.y;
+/// let expected = scaled_font.h_advance(a) + scaled_font.kern(a, v);
+/// assert!((rotated_advance - expected).abs() < 1e-4);
+///
+/// // the next column is to the left
+/// assert!(glyphs[3].glyph.position.x < glyphs[0].glyph.position.x);
+/// # Ok(()) }
+/// ```
+pub fn layout_vertical<F, SF>(font: SF, text: &str, start: Point, target: &mut Vec<VerticalGlyph>)
+where
+    F: Font,
+    SF: ScaleFont<F>,
+{
+    let column_advance = font.height() + font.line_gap();
+    let rotated_x_offset = -(font.ascent() + font.descent()) / 2.0;
+
+    let mut caret = start;
+    let mut last: Option<(GlyphId, VerticalOrientation)> = None;
+    for c in text.chars() {
+        match CharAdvance::of(c) {
+            CharAdvance::Newline => {
+                caret = point(caret.x - column_advance, start.y);
+                last = None;
+                continue;
+            }
+            CharAdvance::Zero => continue,
+            _ => {}
+        }
+
+        let orientation = VerticalOrientation::of(c);
+        let mut glyph = font.scaled_glyph(c);
+        match last {
+            Some((last, last_orientation)) if last_orientation == orientation => {
+                caret.y += match orientation {
+                    VerticalOrientation::Upright => font.v_kern(last, glyph.id),
+                    VerticalOrientation::Rotated => font.kern(last, glyph.id),
+                };
+            }
+            _ => {}
+        }
+
+        match orientation {
+            VerticalOrientation::Upright => {
+                let h_advance = font.h_advance(glyph.id);
+                glyph.position = point(caret.x - h_advance / 2.0, caret.y + font.ascent());
+                caret.y += font.v_advance(glyph.id);
+            }
+            VerticalOrientation::Rotated => {
+                glyph.position = point(caret.x + rotated_x_offset, caret.y);
+                caret.y += font.h_advance(glyph.id);
+            }
+        }
+        last = Some((glyph.id, orientation));
+        target.push(VerticalGlyph { glyph, orientation });
+    }
+}
@@ -0,0 +1,88 @@
+//! JavaScript interop for `wasm32-unknown-unknown`, requires the `wasm` feature.
+//!
+//! The crate itself uses no threads or SIMD so works in the browser as-is; these
+//! helpers just remove the boilerplate of copying JS font data into a `Vec<u8>`.
+use crate::{FontArc, FontError, FontVec};
+use js_sys::{ArrayBuffer, Uint8Array};
+
+impl FontVec {
+    /// Creates an `FontVec` by copying the bytes of a JavaScript `Uint8Array`.
+    ///
+    /// For font collections see
+    /// [`FontVec::try_from_uint8_array_and_index`](#method.try_from_uint8_array_and_index).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ab_glyph::*;
+    /// # fn main() -> Result<(), InvalidFont> {
+    /// # let array = js_sys::Uint8Array::new_with_length(0);
+    /// // e.g. `array` from `new Uint8Array(await response.arrayBuffer())`
+    /// let font = FontVec::try_from_uint8_array(&array)?;
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn try_from_uint8_array(array: &Uint8Array) -> Result<Self, FontError> {
+        Self::try_from_uint8_array_and_index(array, 0)
+    }
+
+    /// Creates an `FontVec` by copying the bytes of a JavaScript `Uint8Array`.
+    ///
+    /// You can set index for font collections. For simple fonts use `0` or
+    /// [`FontVec::try_from_uint8_array`](#method.try_from_uint8_array).
+    #[inline]
+    pub fn try_from_uint8_array_and_index(
+        array: &Uint8Array,
+        index: u32,
+    ) -> Result<Self, FontError> {
+        Self::try_from_vec_and_index(array.to_vec(), index)
+    }
+
+    /// Creates an `FontVec` by copying the bytes of a JavaScript `ArrayBuffer`.
+    ///
+    /// For font collections see
+    /// [`FontVec::try_from_array_buffer_and_index`](#method.try_from_array_buffer_and_index).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ab_glyph::*;
+    /// # fn main() -> Result<(), InvalidFont> {
+    /// # let buffer = js_sys::ArrayBuffer::new(0);
+    /// // e.g. `buffer` from `await response.arrayBuffer()`
+    /// let font = FontVec::try_from_array_buffer(&buffer)?;
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn try_from_array_buffer(buffer: &ArrayBuffer) -> Result<Self, FontError> {
+        Self::try_from_array_buffer_and_index(buffer, 0)
+    }
+
+    /// Creates an `FontVec` by copying the bytes of a JavaScript `ArrayBuffer`.
+    ///
+    /// You can set index for font collections. For simple fonts use `0` or
+    /// [`FontVec::try_from_array_buffer`](#method.try_from_array_buffer).
+    #[inline]
+    pub fn try_from_array_buffer_and_index(
+        buffer: &ArrayBuffer,
+        index: u32,
+    ) -> Result<Self, FontError> {
+        Self::try_from_uint8_array_and_index(&Uint8Array::new(buffer), index)
+    }
+}
+
+impl FontArc {
+    /// Creates an `FontArc` by copying the bytes of a JavaScript `Uint8Array`.
+    ///
+    /// See [`FontVec::try_from_uint8_array`](struct.FontVec.html#method.try_from_uint8_array).
+    #[inline]
+    pub fn try_from_uint8_array(array: &Uint8Array) -> Result<Self, FontError> {
+        Ok(FontVec::try_from_uint8_array(array)?.into())
+    }
+
+    /// Creates an `FontArc` by copying the bytes of a JavaScript `ArrayBuffer`.
+    ///
+    /// See [`FontVec::try_from_array_buffer`](struct.FontVec.html#method.try_from_array_buffer).
+    #[inline]
+    pub fn try_from_array_buffer(buffer: &ArrayBuffer) -> Result<Self, FontError> {
+        Ok(FontVec::try_from_array_buffer(buffer)?.into())
+    }
+}
@@ -9,6 +9,7 @@ use crate::nostd_float::FloatExt;
 /// let p: Point = point(0.1, 23.2);
 /// ```
 #[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -21,11 +22,33 @@ impl core::fmt::Debug for Point {
 }
 
 impl Point {
+    /// Euclidean distance to another point.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let d = point(1.0, 1.0).distance(point(4.0, 5.0));
+    /// assert!((d - 5.0).abs() <= core::f32::EPSILON);
+    /// ```
     #[inline]
-    pub(crate) fn distance_to(self, other: Point) -> f32 {
+    pub fn distance(self, other: Point) -> f32 {
         let d = other - self;
         (d.x * d.x + d.y * d.y).sqrt()
     }
+
+    /// Linear interpolation towards `other`, `t = 0.0` returns `self`, `t = 1.0` returns `other`.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let p = point(1.0, 2.0).lerp(point(3.0, -2.0), 0.25);
+    /// assert_eq!(p, point(1.5, 1.0));
+    /// ```
+    #[inline]
+    pub fn lerp(self, other: Point, t: f32) -> Point {
+        point(
+            self.x + t * (other.x - self.x),
+            self.y + t * (other.y - self.y),
+        )
+    }
 }
 
 /// [`Point`](struct.Point.html) constructor.
@@ -43,7 +66,7 @@ pub fn point(x: f32, y: f32) -> Point {
 /// Linear interpolation between points.
 #[inline]
 pub(crate) fn lerp(t: f32, p0: Point, p1: Point) -> Point {
-    point(p0.x + t * (p1.x - p0.x), p0.y + t * (p1.y - p0.y))
+    p0.lerp(p1, t)
 }
 
 impl core::ops::Sub for Point {
@@ -112,6 +135,92 @@ impl core::ops::SubAssign for Point {
     }
 }
 
+impl core::ops::Mul<f32> for Point {
+    type Output = Point;
+    /// Multiply x & y by rhs.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// assert_eq!(point(1.0, -2.5) * 2.0, point(2.0, -5.0));
+    /// ```
+    #[inline]
+    fn mul(self, rhs: f32) -> Point {
+        point(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl core::ops::Mul<Point> for f32 {
+    type Output = Point;
+    /// Multiply rhs.x & rhs.y by self.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// assert_eq!(2.0 * point(1.0, -2.5), point(2.0, -5.0));
+    /// ```
+    #[inline]
+    fn mul(self, rhs: Point) -> Point {
+        rhs * self
+    }
+}
+
+impl core::ops::Div<f32> for Point {
+    type Output = Point;
+    /// Divide x & y by rhs.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// assert_eq!(point(1.0, -2.5) / 2.0, point(0.5, -1.25));
+    /// ```
+    #[inline]
+    fn div(self, rhs: f32) -> Point {
+        point(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl core::ops::MulAssign<f32> for Point {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let mut p1 = point(1.0, -2.5);
+    /// p1 *= 2.0;
+    ///
+    /// assert_eq!(p1, point(2.0, -5.0));
+    /// ```
+    #[inline]
+    fn mul_assign(&mut self, rhs: f32) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl core::ops::DivAssign<f32> for Point {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let mut p1 = point(1.0, -2.5);
+    /// p1 /= 2.0;
+    ///
+    /// assert_eq!(p1, point(0.5, -1.25));
+    /// ```
+    #[inline]
+    fn div_assign(&mut self, rhs: f32) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
+impl core::ops::Neg for Point {
+    type Output = Point;
+    /// Negate x & y.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// assert_eq!(-point(1.0, -2.5), point(-1.0, 2.5));
+    /// ```
+    #[inline]
+    fn neg(self) -> Point {
+        point(-self.x, -self.y)
+    }
+}
+
 impl<F: Into<f32>> From<(F, F)> for Point {
     /// ```
     /// # use ab_glyph_rasterizer::*;
@@ -136,13 +245,142 @@ impl<F: Into<f32>> From<[F; 2]> for Point {
     }
 }
 
+impl From<Point> for (f32, f32) {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let (x, y) = point(23.0, 34.5).into();
+    /// assert_eq!((x, y), (23.0, 34.5));
+    /// ```
+    #[inline]
+    fn from(p: Point) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl From<Point> for [f32; 2] {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let xy: [f32; 2] = point(23.0, 34.5).into();
+    /// assert_eq!(xy, [23.0, 34.5]);
+    /// ```
+    #[inline]
+    fn from(p: Point) -> Self {
+        [p.x, p.y]
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::Point2<f32>> for Point {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let p: Point = mint::Point2 { x: 23.0, y: 34.5 }.into();
+    /// assert_eq!(p, point(23.0, 34.5));
+    /// ```
+    #[inline]
+    fn from(p: mint::Point2<f32>) -> Self {
+        point(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Point> for mint::Point2<f32> {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let p: mint::Point2<f32> = point(23.0, 34.5).into();
+    /// assert_eq!(p, mint::Point2 { x: 23.0, y: 34.5 });
+    /// ```
+    #[inline]
+    fn from(p: Point) -> Self {
+        mint::Point2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::Vector2<f32>> for Point {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let p: Point = mint::Vector2 { x: 23.0, y: 34.5 }.into();
+    /// assert_eq!(p, point(23.0, 34.5));
+    /// ```
+    #[inline]
+    fn from(v: mint::Vector2<f32>) -> Self {
+        point(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Point> for mint::Vector2<f32> {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let v: mint::Vector2<f32> = point(23.0, 34.5).into();
+    /// assert_eq!(v, mint::Vector2 { x: 23.0, y: 34.5 });
+    /// ```
+    #[inline]
+    fn from(p: Point) -> Self {
+        mint::Vector2 { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<euclid::Point2D<f32, U>> for Point {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let p: Point = euclid::Point2D::<f32, euclid::UnknownUnit>::new(23.0, 34.5).into();
+    /// assert_eq!(p, point(23.0, 34.5));
+    /// ```
+    #[inline]
+    fn from(p: euclid::Point2D<f32, U>) -> Self {
+        point(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<Point> for euclid::Point2D<f32, U> {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// struct ScreenPx;
+    /// let p: euclid::Point2D<f32, ScreenPx> = point(23.0, 34.5).into();
+    /// assert_eq!(p, euclid::point2(23.0, 34.5));
+    /// ```
+    #[inline]
+    fn from(p: Point) -> Self {
+        euclid::Point2D::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<euclid::Vector2D<f32, U>> for Point {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let p: Point = euclid::Vector2D::<f32, euclid::UnknownUnit>::new(23.0, 34.5).into();
+    /// assert_eq!(p, point(23.0, 34.5));
+    /// ```
+    #[inline]
+    fn from(v: euclid::Vector2D<f32, U>) -> Self {
+        point(v.x, v.y)
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<Point> for euclid::Vector2D<f32, U> {
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let v: euclid::default::Vector2D<f32> = point(23.0, 34.5).into();
+    /// assert_eq!(v, euclid::vec2(23.0, 34.5));
+    /// ```
+    #[inline]
+    fn from(p: Point) -> Self {
+        euclid::Vector2D::new(p.x, p.y)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     #[test]
-    fn distance_to() {
-        let distance = point(0.0, 0.0).distance_to(point(3.0, 4.0));
-        assert!((distance - 5.0).abs() <= core::f32::EPSILON);
+    fn distance() {
+        let distance = point(0.0, 0.0).distance(point(3.0, 4.0));
+        assert!((distance - 5.0).abs() <= f32::EPSILON);
     }
 }
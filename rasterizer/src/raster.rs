@@ -23,6 +23,40 @@ use alloc::vec::Vec;
 
 use crate::geometry::{lerp, Point};
 
+/// Maximum pixel distance between quadratic curves & the lines they are drawn with,
+/// `1 / (4√3)` matching the previous fixed step count formula.
+const FLATTEN_TOLERANCE: f32 = 0.144_337_57;
+/// Limits the lines drawn for huge quadratic curves.
+const MAX_QUAD_SEGMENTS: f32 = 1024.0;
+
+/// Evaluates `$body`, with the `no-panic` feature & `--cfg no_panic_check` failing to link
+/// optimized builds if it may panic, in the style of the `no-panic` crate's `#[no_panic]`.
+///
+/// Verify with `RUSTFLAGS="--cfg no_panic_check" cargo test --release --features no-panic`.
+macro_rules! no_panic {
+    ($name:literal, $body:block) => {{
+        #[cfg(all(feature = "no-panic", no_panic_check))]
+        let guard = {
+            struct Guard;
+            impl Drop for Guard {
+                fn drop(&mut self) {
+                    extern "C" {
+                        #[link_name = concat!("\n\nERROR: `", $name, "` may panic\n\n")]
+                        fn may_panic() -> !;
+                    }
+                    unsafe { may_panic() }
+                }
+            }
+            Guard
+        };
+        #[allow(clippy::redundant_closure_call)]
+        let result = (|| $body)();
+        #[cfg(all(feature = "no-panic", no_panic_check))]
+        core::mem::forget(guard);
+        result
+    }};
+}
+
 /// Coverage rasterizer for lines, quadratic & cubic beziers.
 pub struct Rasterizer {
     width: usize,
@@ -37,7 +71,30 @@ impl Rasterizer {
     /// use ab_glyph_rasterizer::Rasterizer;
     /// let mut rasterizer = Rasterizer::new(14, 38);
     /// ```
+    ///
+    /// With the `no-panic` feature dimensions wider than `i32::MAX / 2`, exceeding `isize::MAX`
+    /// bytes or failing to allocate produce a `0` x `0` rasterizer rather than panicking.
     pub fn new(width: usize, height: usize) -> Self {
+        #[cfg(feature = "no-panic")]
+        return no_panic!("Rasterizer::new", {
+            let len = width.checked_mul(height).and_then(|n| n.checked_add(4));
+            let mut a = vec![];
+            match len {
+                // `try_reserve_exact` also errors for byte sizes over `isize::MAX`
+                Some(len)
+                    if width <= (i32::MAX / 2) as usize && a.try_reserve_exact(len).is_ok() =>
+                {
+                    a.extend((0..len).map(|_| 0.0));
+                    Self { width, height, a }
+                }
+                _ => Self {
+                    width: 0,
+                    height: 0,
+                    a: vec![],
+                },
+            }
+        });
+        #[cfg(not(feature = "no-panic"))]
         Self {
             width,
             height,
@@ -63,65 +120,83 @@ impl Rasterizer {
     /// # let mut rasterizer = Rasterizer::new(9, 8);
     /// rasterizer.draw_line(point(0.0, 0.48), point(1.22, 0.48));
     /// ```
+    ///
+    /// With the `no-panic` feature lines with non-finite coordinates are ignored &
+    /// coverage outside the rasterizer bounds is clamped or dropped rather than
+    /// panicking or overflowing.
     pub fn draw_line(&mut self, p0: Point, p1: Point) {
-        if (p0.y - p1.y).abs() <= core::f32::EPSILON {
-            return;
-        }
-        let (dir, p0, p1) = if p0.y < p1.y {
-            (1.0, p0, p1)
-        } else {
-            (-1.0, p1, p0)
-        };
-        let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
-        let mut x = p0.x;
-        let y0 = p0.y as usize; // note: implicit max of 0 because usize (TODO: really true?)
-        if p0.y < 0.0 {
-            x -= p0.y * dxdy;
-        }
-        for y in y0..self.height.min(p1.y.ceil() as usize) {
-            let linestart = y * self.width;
-            let dy = ((y + 1) as f32).min(p1.y) - (y as f32).max(p0.y);
-            let xnext = x + dxdy * dy;
-            let d = dy * dir;
-            let (x0, x1) = if x < xnext { (x, xnext) } else { (xnext, x) };
-            let x0floor = x0.floor();
-            let x0i = x0floor as i32;
-            let x1ceil = x1.ceil();
-            let x1i = x1ceil as i32;
-            if x1i <= x0i + 1 {
-                let xmf = 0.5 * (x + xnext) - x0floor;
-                let linestart_x0i = linestart as isize + x0i as isize;
-                if linestart_x0i < 0 {
-                    continue; // oob index
+        no_panic!("Rasterizer::draw_line", {
+            if (p0.y - p1.y).abs() <= f32::EPSILON {
+                return;
+            }
+            #[cfg(feature = "no-panic")]
+            {
+                if !(p0.x.is_finite() && p0.y.is_finite() && p1.x.is_finite() && p1.y.is_finite()) {
+                    return;
                 }
-                self.a[linestart_x0i as usize] += d - d * xmf;
-                self.a[linestart_x0i as usize + 1] += d * xmf;
+            }
+            let (dir, p0, p1) = if p0.y < p1.y {
+                (1.0, p0, p1)
             } else {
-                let s = (x1 - x0).recip();
-                let x0f = x0 - x0floor;
-                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
-                let x1f = x1 - x1ceil + 1.0;
-                let am = 0.5 * s * x1f * x1f;
-                let linestart_x0i = linestart as isize + x0i as isize;
-                if linestart_x0i < 0 {
-                    continue; // oob index
-                }
-                self.a[linestart_x0i as usize] += d * a0;
-                if x1i == x0i + 2 {
-                    self.a[linestart_x0i as usize + 1] += d * (1.0 - a0 - am);
+                (-1.0, p1, p0)
+            };
+            let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+            let mut x = p0.x;
+            let y0 = p0.y as usize; // note: implicit max of 0 because usize (TODO: really true?)
+            if p0.y < 0.0 {
+                x -= p0.y * dxdy;
+            }
+            for y in y0..self.height.min(p1.y.ceil() as usize) {
+                let linestart = y * self.width;
+                let dy = ((y + 1) as f32).min(p1.y) - (y as f32).max(p0.y);
+                let xnext = x + dxdy * dy;
+                let d = dy * dir;
+                let (x0, x1) = if x < xnext { (x, xnext) } else { (xnext, x) };
+                #[cfg(feature = "no-panic")]
+                let (x0, x1) = (clamp_x(x0, self.width), clamp_x(x1, self.width));
+                let x0floor = x0.floor();
+                let x0i = x0floor as i32;
+                let x1ceil = x1.ceil();
+                let x1i = x1ceil as i32;
+                if x1i <= x0i + 1 {
+                    let xmf = 0.5 * (x0 + x1) - x0floor;
+                    let linestart_x0i = linestart as isize + x0i as isize;
+                    if linestart_x0i < 0 {
+                        continue; // oob index
+                    }
+                    accumulate(&mut self.a, linestart_x0i as usize, d - d * xmf);
+                    accumulate(&mut self.a, linestart_x0i as usize + 1, d * xmf);
                 } else {
-                    let a1 = s * (1.5 - x0f);
-                    self.a[linestart_x0i as usize + 1] += d * (a1 - a0);
-                    for xi in x0i + 2..x1i - 1 {
-                        self.a[linestart + xi as usize] += d * s;
+                    let s = (x1 - x0).recip();
+                    let x0f = x0 - x0floor;
+                    let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                    let x1f = x1 - x1ceil + 1.0;
+                    let am = 0.5 * s * x1f * x1f;
+                    let linestart_x0i = linestart as isize + x0i as isize;
+                    if linestart_x0i < 0 {
+                        continue; // oob index
                     }
-                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
-                    self.a[linestart + (x1i - 1) as usize] += d * (1.0 - a2 - am);
+                    accumulate(&mut self.a, linestart_x0i as usize, d * a0);
+                    if x1i == x0i + 2 {
+                        accumulate(&mut self.a, linestart_x0i as usize + 1, d * (1.0 - a0 - am));
+                    } else {
+                        let a1 = s * (1.5 - x0f);
+                        accumulate(&mut self.a, linestart_x0i as usize + 1, d * (a1 - a0));
+                        for xi in x0i + 2..x1i - 1 {
+                            accumulate(&mut self.a, linestart + xi as usize, d * s);
+                        }
+                        let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                        accumulate(
+                            &mut self.a,
+                            linestart + (x1i - 1) as usize,
+                            d * (1.0 - a2 - am),
+                        );
+                    }
+                    accumulate(&mut self.a, linestart + x1i as usize, d * am);
                 }
-                self.a[linestart + x1i as usize] += d * am;
+                x = xnext;
             }
-            x = xnext;
-        }
+        })
     }
 
     /// Adds a quadratic Bézier curve from `p0` to `p2` to the outline using `p1` as the control.
@@ -132,25 +207,32 @@ impl Rasterizer {
     /// rasterizer.draw_quad(point(6.2, 34.5), point(7.2, 34.5), point(9.2, 34.0));
     /// ```
     pub fn draw_quad(&mut self, p0: Point, p1: Point, p2: Point) {
-        let devx = p0.x - 2.0 * p1.x + p2.x;
-        let devy = p0.y - 2.0 * p1.y + p2.y;
-        let devsq = devx * devx + devy * devy;
-        if devsq < 0.333 {
-            self.draw_line(p0, p2);
-            return;
-        }
-        let tol = 3.0;
-        let n = 1 + (tol * devsq).sqrt().sqrt().floor() as usize;
-        let mut p = p0;
-        let nrecip = (n as f32).recip();
-        let mut t = 0.0;
-        for _i in 0..n - 1 {
-            t += nrecip;
-            let pn = lerp(t, lerp(t, p0, p1), lerp(t, p1, p2));
-            self.draw_line(p, pn);
-            p = pn;
-        }
-        self.draw_line(p, p2);
+        no_panic!("Rasterizer::draw_quad", {
+            // the curve deviates from its chord by at most a quarter of its second difference,
+            // which is constant along the curve, so equal parameter steps are optimal with
+            // the deviation reduced by the square of their count
+            let devx = p0.x - 2.0 * p1.x + p2.x;
+            let devy = p0.y - 2.0 * p1.y + p2.y;
+            let dev = (devx * devx + devy * devy).sqrt();
+            if dev <= 4.0 * FLATTEN_TOLERANCE {
+                self.draw_line(p0, p2);
+                return;
+            }
+            let n = (dev / (4.0 * FLATTEN_TOLERANCE))
+                .sqrt()
+                .ceil()
+                .min(MAX_QUAD_SEGMENTS) as usize;
+            let mut p = p0;
+            let nrecip = (n as f32).recip();
+            let mut t = 0.0;
+            for _i in 0..n - 1 {
+                t += nrecip;
+                let pn = lerp(t, lerp(t, p0, p1), lerp(t, p1, p2));
+                self.draw_line(p, pn);
+                p = pn;
+            }
+            self.draw_line(p, p2);
+        })
     }
 
     /// Adds a cubic Bézier curve from `p0` to `p3` to the outline using `p1` as the control
@@ -167,21 +249,23 @@ impl Rasterizer {
     /// );
     /// ```
     pub fn draw_cubic(&mut self, p0: Point, p1: Point, p2: Point, p3: Point) {
-        self.tesselate_cubic(p0, p1, p2, p3, 0);
+        no_panic!("Rasterizer::draw_cubic", {
+            self.tesselate_cubic(p0, p1, p2, p3, 0);
+        })
     }
 
-    // stb_truetype style cubic approximation by lines.
+    // Approximates the cubic by lines, bisecting while its control polygon is notably longer
+    // than its chord, so flat sections use few lines & tight curls many.
     fn tesselate_cubic(&mut self, p0: Point, p1: Point, p2: Point, p3: Point, n: u8) {
-        // ...I'm not sure either ¯\_(ツ)_/¯
-        const OBJSPACE_FLATNESS: f32 = 0.35;
-        const OBJSPACE_FLATNESS_SQUARED: f32 = OBJSPACE_FLATNESS * OBJSPACE_FLATNESS;
         const MAX_RECURSION_DEPTH: u8 = 16;
+        /// Pixel flatness, in the style of stb_truetype's `objspace_flatness`.
+        const FLATNESS_SQUARED: f32 = 0.35 * 0.35;
 
-        let longlen = p0.distance_to(p1) + p1.distance_to(p2) + p2.distance_to(p3);
-        let shortlen = p0.distance_to(p3);
+        let longlen = p0.distance(p1) + p1.distance(p2) + p2.distance(p3);
+        let shortlen = p0.distance(p3);
         let flatness_squared = longlen * longlen - shortlen * shortlen;
 
-        if n < MAX_RECURSION_DEPTH && flatness_squared > OBJSPACE_FLATNESS_SQUARED {
+        if n < MAX_RECURSION_DEPTH && flatness_squared > FLATNESS_SQUARED {
             let p01 = lerp(0.5, p0, p1);
             let p12 = lerp(0.5, p1, p2);
             let p23 = lerp(0.5, p2, p3);
@@ -239,6 +323,98 @@ impl Rasterizer {
         let width32 = self.width as u32;
         self.for_each_pixel(|idx, alpha| px_fn(idx as u32 % width32, idx as u32 / width32, alpha));
     }
+
+    /// Run a fallible callback for each pixel index & alpha, like `for_each_pixel`, stopping
+    /// at & returning the first error.
+    ///
+    /// Allows aborting early, e.g. once a pixel is found to be occluded.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let mut rasterizer = Rasterizer::new(4, 1);
+    /// rasterizer.draw_line(point(1.0, 0.0), point(1.0, 1.0));
+    /// rasterizer.draw_line(point(4.0, 1.0), point(4.0, 0.0));
+    ///
+    /// let mut visited = 0;
+    /// let first_covered = rasterizer.try_for_each_pixel(|index, alpha| {
+    ///     visited += 1;
+    ///     if alpha > 0.0 { Err(index) } else { Ok(()) }
+    /// });
+    /// assert_eq!(first_covered, Err(1));
+    /// assert_eq!(visited, 2);
+    /// ```
+    pub fn try_for_each_pixel<E, O: FnMut(usize, f32) -> Result<(), E>>(
+        &self,
+        mut px_fn: O,
+    ) -> Result<(), E> {
+        let mut acc = 0.0;
+        self.a[..self.width * self.height]
+            .iter()
+            .enumerate()
+            .try_for_each(|(idx, c)| {
+                acc += c;
+                px_fn(idx, acc.abs().min(1.0))
+            })
+    }
+
+    /// Run a fallible callback for each pixel x position, y position & alpha, stopping at
+    /// & returning the first error.
+    ///
+    /// Convenience wrapper for `try_for_each_pixel`.
+    pub fn try_for_each_pixel_2d<E, O: FnMut(u32, u32, f32) -> Result<(), E>>(
+        &self,
+        mut px_fn: O,
+    ) -> Result<(), E> {
+        let width32 = self.width as u32;
+        self.try_for_each_pixel(|idx, alpha| {
+            px_fn(idx as u32 % width32, idx as u32 / width32, alpha)
+        })
+    }
+
+    /// Run a callback for each horizontal span of consecutive pixels with equal, non-zero,
+    /// 8 bit alpha with the span y position, start x position, pixel length & alpha.
+    ///
+    /// A run-length encoding of `for_each_pixel_2d` with alpha quantized as
+    /// `(alpha * 255.0).round() as u8`. Typically much more compact as glyph interiors are
+    /// fully covered & allows blitting spans, e.g. with `fill`, rather than handling each pixel.
+    ///
+    /// ```
+    /// # use ab_glyph_rasterizer::*;
+    /// let mut rasterizer = Rasterizer::new(5, 2);
+    /// // rectangle from x: 1 to 4
+    /// rasterizer.draw_line(point(1.0, 0.0), point(1.0, 2.0));
+    /// rasterizer.draw_line(point(4.0, 2.0), point(4.0, 0.0));
+    ///
+    /// let mut spans = vec![];
+    /// rasterizer.for_each_span(|y, x, len, alpha| spans.push((y, x, len, alpha)));
+    /// assert_eq!(spans, [(0, 1, 3, 255), (1, 1, 3, 255)]);
+    /// ```
+    pub fn for_each_span<O: FnMut(u32, u32, u32, u8)>(&self, mut span_fn: O) {
+        if self.width == 0 {
+            return;
+        }
+        let mut acc = 0.0;
+        self.a[..self.width * self.height]
+            .chunks_exact(self.width)
+            .enumerate()
+            .for_each(|(y, row)| {
+                let (mut start, mut alpha) = (0, 0);
+                for (x, c) in row.iter().enumerate() {
+                    acc += c;
+                    let a = (acc.abs().min(1.0) * 255.0).round() as u8;
+                    if a != alpha {
+                        if alpha != 0 {
+                            span_fn(y as u32, start as u32, (x - start) as u32, alpha);
+                        }
+                        start = x;
+                        alpha = a;
+                    }
+                }
+                if alpha != 0 {
+                    span_fn(y as u32, start as u32, (row.len() - start) as u32, alpha);
+                }
+            });
+    }
 }
 
 /// ```
@@ -253,3 +429,27 @@ impl core::fmt::Debug for Rasterizer {
             .finish()
     }
 }
+
+/// Adds `value` to the accumulation buffer at `index`.
+///
+/// With the `no-panic` feature out of bounds indices are ignored.
+#[inline(always)]
+fn accumulate(a: &mut [f32], index: usize, value: f32) {
+    #[cfg(feature = "no-panic")]
+    {
+        if let Some(a) = a.get_mut(index) {
+            *a += value;
+        }
+    }
+    #[cfg(not(feature = "no-panic"))]
+    {
+        a[index] += value;
+    }
+}
+
+/// Clamps a line x coordinate to `0..=width`, mapping `NaN` to `0`.
+#[cfg(feature = "no-panic")]
+#[inline]
+fn clamp_x(x: f32, width: usize) -> f32 {
+    x.max(0.0).min(width as f32)
+}
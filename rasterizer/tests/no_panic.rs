@@ -0,0 +1,107 @@
+//! Drawing that would otherwise panic, run with `--features no-panic`.
+//!
+//! Also verify the drawing functions have no panic paths with
+//! `RUSTFLAGS="--cfg no_panic_check" cargo test --release --features no-panic`.
+#![cfg(feature = "no-panic")]
+
+use ab_glyph_rasterizer::*;
+
+/// Asserts all pixels have valid coverage.
+fn assert_valid(r: &Rasterizer) {
+    r.for_each_pixel(|_, alpha| assert!((0.0..=1.0).contains(&alpha), "{}", alpha));
+}
+
+#[test]
+fn far_out_of_bounds_lines() {
+    let mut r = Rasterizer::new(8, 8);
+    r.draw_line(point(-1e9, 2.0), point(1e9, 6.0));
+    r.draw_line(point(100.0, 1.0), point(120.0, 7.0));
+    r.draw_line(point(-100.0, 7.0), point(-120.0, 1.0));
+    r.draw_line(point(4.0, -1e30), point(4.0, 1e30));
+    assert_valid(&r);
+}
+
+#[test]
+fn huge_coordinates() {
+    let mut r = Rasterizer::new(8, 8);
+    r.draw_line(point(f32::MAX, 0.0), point(f32::MIN, 8.0));
+    r.draw_quad(point(0.0, 0.0), point(f32::MAX, 4.0), point(0.0, 8.0));
+    r.draw_cubic(
+        point(0.0, f32::MIN),
+        point(f32::MAX, 0.0),
+        point(f32::MIN, 8.0),
+        point(8.0, f32::MAX),
+    );
+    assert_valid(&r);
+}
+
+#[test]
+fn non_finite_coordinates() {
+    let mut r = Rasterizer::new(8, 8);
+    r.draw_line(point(f32::NAN, 0.0), point(4.0, 8.0));
+    r.draw_line(point(4.0, f32::NAN), point(4.0, 8.0));
+    r.draw_line(point(f32::INFINITY, 0.0), point(4.0, 8.0));
+    r.draw_line(point(4.0, 0.0), point(4.0, f32::NEG_INFINITY));
+    r.draw_quad(point(0.0, 0.0), point(f32::NAN, 4.0), point(0.0, 8.0));
+    r.draw_cubic(
+        point(0.0, 0.0),
+        point(f32::INFINITY, 2.0),
+        point(f32::NEG_INFINITY, 6.0),
+        point(0.0, 8.0),
+    );
+    r.for_each_pixel(|_, alpha| assert_eq!(alpha, 0.0));
+}
+
+#[test]
+fn in_bounds_drawing_unaffected() {
+    let mut r = Rasterizer::new(4, 4);
+    r.draw_line(point(1.0, 1.0), point(1.0, 3.0));
+    r.draw_line(point(3.0, 3.0), point(3.0, 1.0));
+    r.draw_line(point(-5.0, 0.5), point(-5.0, 1.0));
+    r.draw_line(point(-5.0, 1.0), point(-5.0, 0.5));
+
+    let mut filled = 0;
+    r.for_each_pixel_2d(|x, y, alpha| {
+        if (1..3).contains(&x) && (1..3).contains(&y) {
+            assert!((alpha - 1.0).abs() < 1e-6, "{} {} {}", x, y, alpha);
+            filled += 1;
+        } else {
+            assert_eq!(alpha, 0.0, "{} {}", x, y);
+        }
+    });
+    assert_eq!(filled, 4);
+}
+
+#[test]
+fn overflowing_dimensions() {
+    let r = Rasterizer::new(usize::MAX, 2);
+    assert_eq!(r.dimensions(), (0, 0));
+
+    let mut r = Rasterizer::new(usize::MAX / 2, 0);
+    assert_eq!(r.dimensions(), (0, 0));
+    r.draw_line(point(0.0, 0.0), point(4.0, 8.0));
+    r.for_each_pixel(|_, _| unreachable!());
+
+    // element count fits in usize but the byte size exceeds isize::MAX
+    let mut r = Rasterizer::new(1 << 20, 1 << 41);
+    assert_eq!(r.dimensions(), (0, 0));
+    r.draw_line(point(0.0, 0.0), point(4.0, 8.0));
+    r.for_each_pixel(|_, _| unreachable!());
+}
+
+#[test]
+fn unallocatable_dimensions() {
+    // 256 TiB
+    let mut r = Rasterizer::new(1 << 16, 1 << 30);
+    assert_eq!(r.dimensions(), (0, 0));
+    r.draw_quad(point(0.0, 0.0), point(8.0, 4.0), point(0.0, 8.0));
+    r.for_each_pixel(|_, _| unreachable!());
+}
+
+#[test]
+fn zero_dimensions() {
+    let mut r = Rasterizer::new(0, 4);
+    r.draw_line(point(0.0, 0.0), point(4.0, 8.0));
+    r.draw_line(point(-4.0, 8.0), point(0.0, 0.0));
+    r.for_each_pixel(|_, _| unreachable!());
+}